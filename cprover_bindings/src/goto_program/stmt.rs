@@ -79,8 +79,13 @@ pub enum StmtBody {
         function: Expr,
         arguments: Vec<Expr>,
     },
-    /// `goto dest;`
-    Goto(InternedString),
+    /// `goto dest;` If the goto is the backedge of a loop, it may carry the
+    /// loop invariant and the locations the loop may assign.
+    Goto {
+        dest: InternedString,
+        loop_invariant: Option<Expr>,
+        loop_assigns: Option<Vec<Expr>>,
+    },
     /// `if (i) { t } else { e }`
     Ifthenelse {
         i: Expr,
@@ -153,6 +158,21 @@ pub fn with_location(mut self, loc: Location) -> Self {
         self.location = loc;
         self
     }
+
+    /// Attach a loop contract to a `goto` that is the backedge of a loop.
+    /// CBMC's `--apply-loop-contracts` reads the contract from the backedge.
+    /// Without `assigns`, CBMC infers the places that the loop modifies.
+    pub fn with_loop_contract(mut self, invariant: Expr, assigns: Option<Vec<Expr>>) -> Self {
+        assert!(invariant.typ().is_bool());
+        match self.body.as_mut() {
+            Goto { loop_invariant, loop_assigns, .. } => {
+                *loop_invariant = Some(invariant);
+                *loop_assigns = assigns;
+            }
+            _ => unreachable!("Loop contracts can only be attached to a goto"),
+        }
+        self
+    }
 }
 
 macro_rules! stmt {
@@ -276,7 +296,7 @@ pub fn function_call(
     pub fn goto<T: Into<InternedString>>(dest: T, loc: Location) -> Self {
         let dest = dest.into();
         assert!(!dest.is_empty());
-        stmt!(Goto(dest), loc)
+        stmt!(Goto { dest, loop_invariant: None, loop_assigns: None }, loc)
     }
 
     /// `if (i) { t } else { e }` or `if (i) { t }`
@@ -95,6 +95,21 @@ impl FunctionContract {
     pub fn new(assigns: Vec<Lambda>) -> Self {
         Self { assigns }
     }
+
+    /// The places the function may assign to.
+    pub fn assigns(&self) -> &[Lambda] {
+        &self.assigns
+    }
+
+    /// Allow the function to also assign to `target`.
+    pub fn push_assigns(&mut self, target: Lambda) {
+        self.assigns.push(target)
+    }
+
+    /// Extend this contract with the clauses of `other`.
+    pub fn merge(&mut self, other: FunctionContract) {
+        self.assigns.extend(other.assigns)
+    }
 }
 
 /// Currently, only C is understood by CBMC.
@@ -166,9 +181,7 @@ fn new<T: Into<InternedString>, U: Into<InternedString>, V: Into<InternedString>
     pub fn attach_contract(&mut self, contract: FunctionContract) {
         assert!(self.typ.is_code());
         match self.contract {
-            Some(ref mut prior) => {
-                prior.assigns.extend(contract.assigns);
-            }
+            Some(ref mut prior) => prior.merge(contract),
             None => self.contract = Some(Box::new(contract)),
         }
     }
@@ -463,8 +463,20 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
                     arguments_irep(arguments.iter(), mm),
                 ],
             ),
-            StmtBody::Goto(dest) => code_irep(IrepId::Goto, vec![])
-                .with_named_sub(IrepId::Destination, Irep::just_string_id(dest.to_string())),
+            StmtBody::Goto { dest, loop_invariant, loop_assigns } => {
+                code_irep(IrepId::Goto, vec![])
+                    .with_named_sub(IrepId::Destination, Irep::just_string_id(dest.to_string()))
+                    .with_named_sub_option(
+                        IrepId::CSpecLoopInvariant,
+                        loop_invariant.as_ref().map(|inv| Irep::just_sub(vec![inv.to_irep(mm)])),
+                    )
+                    .with_named_sub_option(
+                        IrepId::CSpecAssigns,
+                        loop_assigns.as_ref().map(|assigns| {
+                            Irep::just_sub(assigns.iter().map(|a| a.to_irep(mm)).collect())
+                        }),
+                    )
+            }
             StmtBody::Ifthenelse { i, t, e } => code_irep(
                 IrepId::Ifthenelse,
                 vec![
@@ -47,6 +47,31 @@ pub struct Arguments {
     pub reachability_analysis: ReachabilityType,
     #[clap(long = "enable-stubbing")]
     pub stubbing_enabled: bool,
+    /// Option used to stop the compilation after the contracts of this crate have been checked.
+    ///
+    /// No code is generated for the crate when this is set.
+    #[clap(long)]
+    pub check_contracts_only: bool,
+    /// Option used to drop the postcondition checks of the contract checked by a
+    /// `proof_for_contract` harness.
+    #[clap(long)]
+    pub skip_postcondition_checks: bool,
+    /// Option used to add a cover property after each precondition of the contract
+    /// checked by a `proof_for_contract` harness, which tells whether it is satisfiable.
+    #[clap(long)]
+    pub contract_report: bool,
+    /// Option used to replace every function with a contract by its contract in a
+    /// `proof_for_contract` harness, except the functions whose contract it checks.
+    #[clap(long)]
+    pub replace_all_contracts: bool,
+    /// Option used to also verify the replacement client that the contract macros generate for
+    /// every function with a contract that a `proof_for_contract` harness checks.
+    #[clap(long)]
+    pub enforce_and_replace: bool,
+    /// Option used to give the precondition checks of replaced functions their own property
+    /// class, so that the driver can report a violation at the call site.
+    #[clap(long)]
+    pub precondition_call_site: bool,
     /// Option name used to define unstable features.
     #[clap(short = 'Z', long = "unstable")]
     pub unstable_features: Vec<String>,
@@ -72,6 +72,23 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: None TODO: Why should this exist?
     FiniteCheck,
+    /// The precondition of a function with a contract, checked at a call site where the function
+    /// was replaced by its contract (`stub_verified`). Only used with `--precondition-call-site`,
+    /// otherwise these checks are assertions.
+    ///
+    /// SPECIAL BEHAVIOR: A failure means the caller violated the precondition of the callee. The
+    /// driver reports the location of the offending call.
+    Precondition,
+    /// An `assert(false)` added before every return of a `#[kani::expect_unreachable]` harness.
+    ///
+    /// SPECIAL BEHAVIOR: Like `Cover`, a failure means "reachable". The driver reports the
+    /// harness as failed if no return is reachable, i.e., if its assumptions prune every path.
+    HarnessExit,
+    /// Added before every return of a `#[kani::should_panic(mode = "all")]` harness.
+    ///
+    /// SPECIAL BEHAVIOR: A failure means that the harness can return without panicking. The
+    /// driver reports it as a missing panic rather than as an unexpected failure.
+    ShouldPanic,
     /// Checks added by Kani compiler to determine whether a property (e.g.
     /// `PropertyClass::Assertion` or `PropertyClass:Cover`) is reachable
     ReachabilityCheck,
@@ -26,6 +26,7 @@ pub fn codegen_block(&mut self, bb: BasicBlockIdx, bbd: &BasicBlock) {
             0 => {
                 let term = &bbd.terminator;
                 let tcode = self.codegen_terminator(term);
+                let tcode = self.codegen_loop_contract_backedges(bb, tcode);
                 // When checking coverage, the `coverage` check should be
                 // labelled instead.
                 if check_coverage {
@@ -67,6 +68,7 @@ pub fn codegen_block(&mut self, bb: BasicBlockIdx, bbd: &BasicBlock) {
                     self.current_fn_mut().push_onto_block(cover);
                 }
                 let tcode = self.codegen_terminator(term);
+                let tcode = self.codegen_loop_contract_backedges(bb, tcode);
                 self.current_fn_mut().push_onto_block(tcode);
             }
         }
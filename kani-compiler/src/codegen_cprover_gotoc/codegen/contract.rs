@@ -1,15 +1,23 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
-use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::codegen_cprover_gotoc::codegen::ty_stable::{pointee_type_stable, pretty_ty};
+use crate::codegen_cprover_gotoc::{utils, GotocCtx};
 use crate::kani_middle::attributes::KaniAttributes;
+use cbmc::goto_program::Expr;
 use cbmc::goto_program::FunctionContract;
 use cbmc::goto_program::Lambda;
-use kani_metadata::AssignsContract;
+use cbmc::goto_program::Type;
+use kani_metadata::{AssignsContract, AssignsTarget, ContractClauseMetadata};
 use rustc_hir::def_id::DefId as InternalDefId;
 use rustc_smir::rustc_internal;
-use stable_mir::mir::mono::{Instance, MonoItem};
-use stable_mir::mir::Local;
-use stable_mir::CrateDef;
+use rustc_span::Symbol;
+use stable_mir::mir::mono::{Instance, MonoItem, StaticDef};
+use stable_mir::mir::{Local, TerminatorKind};
+use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::{CrateDef, CrateItem};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashSet};
+use std::hash::{Hash, Hasher};
 use tracing::debug;
 
 impl<'tcx> GotocCtx<'tcx> {
@@ -18,21 +26,34 @@ impl<'tcx> GotocCtx<'tcx> {
     /// for which it needs to be enforced.
     ///
     /// 1. Gets the `#[kanitool::inner_check = "..."]` target, then resolves exactly one instance
-    ///    of it. Panics if there are more or less than one instance.
+    ///    of it. The instance may also be reached through a vtable, e.g. if the harness calls the
+    ///    function under contract from a trait object method. Reports an error and returns `None`
+    ///    if the harness never reaches it or reaches more than one instance, e.g. because it calls
+    ///    a const generic function with different values for `N`.
     /// 2. Expects that a `#[kanitool::modifies(...)]` is placed on the `inner_check` function,
     ///    turns it into a CBMC contract and attaches it to the symbol for the previously resolved
     ///    instance.
     /// 3. Returns the mangled name of the symbol it attached the contract to.
     /// 4. Resolves the `#[kanitool::checked_with = "..."]` target from `function_under_contract`
-    ///    which has `static mut REENTRY : bool` declared inside.
-    /// 5. Returns the full path to this constant that `--nondet-static-exclude` expects which is
-    ///    comprised of the file path that `checked_with` is located in, the name of the
-    ///    `checked_with` function and the name of the constant (`REENTRY`).
+    ///    and finds the static marked `#[kanitool::recursion_tracker]` declared inside it, which
+    ///    counts the active checks of the contract.
+    /// 5. Returns the full path to this static that `--nondet-static-exclude` expects, see
+    ///    [`Self::recursion_tracker_static_path`].
+    /// 6. If the `inner_check` function carries `#[kanitool::decreases]`, also returns the full
+    ///    path to the static holding the `decreases` measure, which must not be havocked either.
+    /// 7. Returns the fingerprint of the `harnesses` that check the contract, see
+    ///    [`Self::contract_fingerprint`].
+    /// 8. Returns the `requires` and `ensures` clauses of the check function, which the driver
+    ///    uses for `--contract-report`.
+    /// 9. Returns the targets of the `modifies` clauses as written by the user, numbered in the
+    ///    order of the CBMC contract, so the driver can name them when reporting a failed
+    ///    `assigns` check.
     pub fn handle_check_contract(
         &mut self,
         function_under_contract: InternalDefId,
+        harnesses: &[MonoItem],
         items: &[MonoItem],
-    ) -> AssignsContract {
+    ) -> Option<AssignsContract> {
         let tcx = self.tcx;
         let function_under_contract_attrs = KaniAttributes::for_item(tcx, function_under_contract);
         let wrapped_fn = function_under_contract_attrs.inner_check().unwrap().unwrap();
@@ -45,38 +66,207 @@ pub fn handle_check_contract(
             }
             _ => None,
         });
-        let instance_of_check = instance_under_contract.next().unwrap();
-        assert!(
-            instance_under_contract.next().is_none(),
-            "Only one instance of a checked function may be in scope"
-        );
+        let Some(instance_of_check) = instance_under_contract.next() else {
+            tcx.dcx().span_err(
+                tcx.def_span(function_under_contract),
+                format!(
+                    "the contract of `{}` cannot be checked because the harness never calls it",
+                    tcx.def_path_str(function_under_contract)
+                ),
+            );
+            return None;
+        };
+        let other_instances = instance_under_contract.collect::<Vec<_>>();
+        if !other_instances.is_empty() {
+            let instances = std::iter::once(instance_of_check)
+                .chain(other_instances)
+                .map(|instance| format!("`{}`", instance.name()))
+                .collect::<Vec<_>>();
+            tcx.dcx().span_err(
+                tcx.def_span(function_under_contract),
+                format!(
+                    "the contract of `{}` cannot be checked because the harness calls it with \
+                    more than one instantiation of its generic parameters: {}. Check each \
+                    instantiation in a separate harness.",
+                    tcx.def_path_str(function_under_contract),
+                    instances.join(", ")
+                ),
+            );
+            return None;
+        }
         let attrs_of_wrapped_fn = KaniAttributes::for_item(tcx, wrapped_fn);
         let assigns_contract = attrs_of_wrapped_fn.modifies_contract().unwrap_or_else(|| {
             debug!(?instance_of_check, "had no assigns contract specified");
             vec![]
         });
-        self.attach_modifies_contract(instance_of_check, assigns_contract);
+        let (modified_places, sources): (Vec<_>, Vec<_>) = assigns_contract.into_iter().unzip();
+        self.attach_modifies_contract(instance_of_check, modified_places);
 
         let wrapper_name = self.symbol_name_stable(instance_of_check);
 
         let recursion_wrapper_id =
             function_under_contract_attrs.checked_with_id().unwrap().unwrap();
-        let span_of_recursion_wrapper = tcx.def_span(recursion_wrapper_id);
-        let location_of_recursion_wrapper = self.codegen_span(&span_of_recursion_wrapper);
-
-        let full_name = format!(
-            "{}:{}::REENTRY",
-            location_of_recursion_wrapper
-                .filename()
-                .expect("recursion location wrapper should have a file name"),
-            tcx.item_name(recursion_wrapper_id),
-        );
-
-        AssignsContract { recursion_tracker: full_name, contracted_function_name: wrapper_name }
+        let decreases_measure =
+            attrs_of_wrapped_fn.has_decreases().then(|| self.decreases_measure_name());
+
+        Some(AssignsContract {
+            function: tcx.def_path_str(function_under_contract),
+            recursion_tracker: self.recursion_tracker_static_path(recursion_wrapper_id, items),
+            contracted_function_name: wrapper_name,
+            decreases_measure,
+            fingerprint: self.contract_fingerprint(function_under_contract, harnesses, items),
+            clauses: self.checked_contract_clauses(recursion_wrapper_id, items),
+            targets: sources.into_iter().map(|source| AssignsTarget { source }).collect(),
+        })
+    }
+
+    /// The clauses recorded in [`GotocCtx::contract_clauses`] for the check function that the
+    /// recursion wrapper `checked_with` calls.
+    fn checked_contract_clauses(
+        &self,
+        checked_with: InternalDefId,
+        items: &[MonoItem],
+    ) -> Vec<ContractClauseMetadata> {
+        let callees = items
+            .iter()
+            .filter_map(|item| match item {
+                MonoItem::Fn(instance)
+                    if rustc_internal::internal(instance.def.def_id()) == checked_with =>
+                {
+                    instance.body()
+                }
+                _ => None,
+            })
+            .flat_map(|body| {
+                body.blocks
+                    .iter()
+                    .filter_map(|block| {
+                        let TerminatorKind::Call { func, .. } = &block.terminator.kind else {
+                            return None;
+                        };
+                        let TyKind::RigidTy(RigidTy::FnDef(def, _)) =
+                            func.ty(body.locals()).ok()?.kind()
+                        else {
+                            return None;
+                        };
+                        Some(def.def_id())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<HashSet<_>>();
+        self.contract_clauses
+            .iter()
+            .filter(|(function, _)| callees.contains(function))
+            .map(|(_, clause)| clause.clone())
+            .collect()
+    }
+
+    /// A hash of everything that decides the outcome of checking the contract of
+    /// `function_under_contract` with `harnesses`, which lets `--changed-only` skip the harnesses
+    /// if it did not change since the last run.
+    ///
+    /// It combines the source code of the harnesses and of every local function and static among
+    /// the reachable `items`, so that a change to any helper the harness calls is noticed, even
+    /// if the helper has no contract. It also adds the `#[kanitool::contract_fingerprint]` of the
+    /// function under contract and of every function with a contract among the `items`, which
+    /// covers the contracts of functions that are replaced via `stub_verified` and whose body is
+    /// therefore not reachable. Items of other crates are not covered.
+    fn contract_fingerprint(
+        &self,
+        function_under_contract: InternalDefId,
+        harnesses: &[MonoItem],
+        items: &[MonoItem],
+    ) -> String {
+        let tcx = self.tcx;
+        let def_ids = |items: &[MonoItem]| {
+            items
+                .iter()
+                .filter_map(|item| match item {
+                    MonoItem::Fn(instance) => Some(rustc_internal::internal(instance.def.def_id())),
+                    MonoItem::Static(def) => Some(rustc_internal::internal(def.def_id())),
+                    MonoItem::GlobalAsm(_) => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut hasher = DefaultHasher::new();
+        let sources = def_ids(harnesses)
+            .into_iter()
+            .chain(def_ids(items))
+            .filter_map(|def_id| def_id.as_local())
+            .map(|def_id| {
+                let span = tcx.source_span(def_id);
+                tcx.sess.source_map().span_to_snippet(span).unwrap_or_default()
+            })
+            .collect::<BTreeSet<_>>();
+        sources.hash(&mut hasher);
+        let contracts = std::iter::once(function_under_contract)
+            .chain(def_ids(items))
+            .filter_map(|def_id| KaniAttributes::for_item(tcx, def_id).contract_fingerprint())
+            .filter_map(Result::ok)
+            .map(|fingerprint| fingerprint.to_string())
+            .collect::<BTreeSet<_>>();
+        contracts.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// The full path that `--nondet-static-exclude` expects for the static that counts the
+    /// active checks of a contract.
+    ///
+    /// The static is declared by the macro inside the `recursion_wrapper_id` function and marked
+    /// `#[kanitool::recursion_tracker]`, so it is found among the reachable `items` by that
+    /// attribute rather than by its name.
+    fn recursion_tracker_static_path(
+        &self,
+        recursion_wrapper_id: InternalDefId,
+        items: &[MonoItem],
+    ) -> String {
+        let recursion_tracker = items
+            .iter()
+            .find_map(|item| match item {
+                MonoItem::Static(def) => {
+                    let def_id = rustc_internal::internal(def.def_id());
+                    (self.tcx.parent(def_id) == recursion_wrapper_id
+                        && KaniAttributes::for_item(self.tcx, def_id).is_recursion_tracker())
+                    .then_some(*def)
+                }
+                _ => None,
+            })
+            .expect("the recursion wrapper should declare a recursion tracker");
+        self.nondet_static_exclude_name(recursion_tracker)
+    }
+
+    /// The full path that `--nondet-static-exclude` expects for the static in
+    /// `kani::internal` that holds the `decreases` measure.
+    fn decreases_measure_name(&self) -> String {
+        let measure_id = self
+            .tcx
+            .get_diagnostic_item(Symbol::intern("KaniDecreasesMeasure"))
+            .expect("the `decreases` measure should be declared in the kani library");
+        let measure = StaticDef::try_from(CrateItem(rustc_internal::stable(measure_id))).unwrap();
+        self.nondet_static_exclude_name(measure)
+    }
+
+    /// The full path that `--nondet-static-exclude` expects for `static_def`, i.e. the file it
+    /// is located in followed by its pretty name.
+    fn nondet_static_exclude_name(&self, static_def: StaticDef) -> String {
+        let instance = Instance::from(static_def);
+        let symbol = self.symbol_table.lookup(instance.mangled_name()).unwrap_or_else(|| {
+            panic!("`{}` should be reachable from the contract check", instance.name())
+        });
+        format!(
+            "{}:{}",
+            symbol.location.filename().expect("static variables should have a file name"),
+            symbol.pretty_name.expect("static variables have a pretty name"),
+        )
     }
 
     /// Convert the Kani level contract into a CBMC level contract by creating a
     /// CBMC lambda.
+    ///
+    /// A slice target, e.g. `core::ptr::slice_from_raw_parts_mut(ptr, len)`, is expressed as the
+    /// `len` elements behind its data pointer with `__CPROVER_object_upto`. Other places whose
+    /// type is not `Sized` cannot be expressed as a CBMC assigns target. They are reported as an
+    /// error and left out of the contract.
     fn codegen_modifies_contract(&mut self, modified_places: Vec<Local>) -> FunctionContract {
         let goto_annotated_fn_name = self.current_fn().name();
         let goto_annotated_fn_typ = self
@@ -88,12 +278,38 @@ fn codegen_modifies_contract(&mut self, modified_places: Vec<Local>) -> Function
 
         let assigns = modified_places
             .into_iter()
-            .map(|local| {
-                Lambda::as_contract_for(
-                    &goto_annotated_fn_typ,
-                    None,
-                    self.codegen_place_stable(&local.into()).unwrap().goto_expr.dereference(),
-                )
+            .filter_map(|local| {
+                let decl = &self.current_fn().locals()[local];
+                let (ty, span) = (decl.ty, decl.span);
+                let target_ty = pointee_type_stable(ty).expect("modifies target must be a pointer");
+                let target = self.codegen_place_stable(&local.into()).unwrap().goto_expr;
+                if let TyKind::RigidTy(RigidTy::Slice(elem_ty)) = target_ty.kind() {
+                    let elem_size = self.layout_of_stable(elem_ty).size.bytes_usize();
+                    let size = Expr::int_constant(elem_size, Type::size_t())
+                        .with_size_of_annotation(self.codegen_ty_stable(elem_ty))
+                        .mul(target.clone().member("len", &self.symbol_table));
+                    let data = target
+                        .member("data", &self.symbol_table)
+                        .cast_to(Type::empty().to_pointer());
+                    return Some(Lambda::as_contract_for(
+                        &goto_annotated_fn_typ,
+                        None,
+                        object_upto().call(vec![data, size]),
+                    ));
+                }
+                if self.is_unsized(rustc_internal::internal(target_ty)) {
+                    utils::span_err(
+                        self.tcx,
+                        span,
+                        format!(
+                            "`{}` cannot be used as a `modifies` target because its size is not \
+                            known at compile time. Targets must be `Sized` or slices.",
+                            pretty_ty(target_ty)
+                        ),
+                    );
+                    return None;
+                }
+                Some(Lambda::as_contract_for(&goto_annotated_fn_typ, None, target.dereference()))
             })
             .collect();
 
@@ -117,3 +333,18 @@ pub fn attach_modifies_contract(&mut self, instance: Instance, modified_places:
         self.reset_current_fn()
     }
 }
+
+/// CBMC's `__CPROVER_object_upto(ptr, size)`, which makes the first `size` bytes of the object
+/// behind `ptr` assignable.
+fn object_upto() -> Expr {
+    Expr::symbol_expression(
+        "__CPROVER_object_upto",
+        Type::code(
+            vec![
+                Type::empty().to_pointer().as_parameter(None, Some("ptr".into())),
+                Type::size_t().as_parameter(None, Some("size".into())),
+            ],
+            Type::empty(),
+        ),
+    )
+}
@@ -4,8 +4,10 @@
 //! This file contains functions related to codegenning MIR functions into gotoc
 
 use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::attributes::KaniAttributes;
 use cbmc::goto_program::{Expr, Stmt, Symbol};
 use cbmc::InternString;
+use kani_metadata::ShouldPanicMode;
 use rustc_middle::mir::traversal::reverse_postorder;
 use stable_mir::mir::mono::Instance;
 use stable_mir::mir::{Body, Local};
@@ -32,6 +34,13 @@ fn codegen_declare_variables(&mut self, body: &Body) {
             }
             let base_name = self.codegen_var_base_name(&lc);
             let name = self.codegen_var_name(&lc);
+            if let Some(source_name) = self.current_fn().local_name(lc) {
+                let fn_name = self.current_fn().name().into();
+                self.var_name_map
+                    .entry(fn_name)
+                    .or_default()
+                    .insert(format!("var_{lc}"), source_name.to_string());
+            }
             let var_type = self.codegen_ty_stable(ldata.ty);
             let loc = self.codegen_span_stable(ldata.span);
             // Indices [1, N] represent the function parameters where N is the number of parameters.
@@ -63,6 +72,13 @@ pub fn codegen_function(&mut self, instance: Instance) {
             assert!(old_sym.is_function());
             let body = instance.body().unwrap();
             self.set_current_fn(instance, &body);
+            let loop_contracts = self.loop_contract_headers(&body);
+            self.has_loop_contracts |= !loop_contracts.is_empty();
+            self.current_fn_mut().set_loop_contracts(loop_contracts);
+            let attributes = KaniAttributes::for_instance(self.tcx, instance);
+            let must_panic = attributes.should_panic() == Some(ShouldPanicMode::All);
+            self.current_fn_mut().set_must_panic(must_panic);
+            self.current_fn_mut().set_check_exit_reachable(attributes.expect_unreachable());
             self.print_instance(instance, &body);
             self.codegen_function_prelude(&body);
             self.codegen_declare_variables(&body);
@@ -0,0 +1,184 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Code generation for loop contracts, i.e. `#[kani::loop_contract]` and `#[kani::invariant]`.
+//!
+//! The macro registers the contract of a loop by calling
+//! `kani::internal::loop_contract(invariant, assigns)` first thing in the loop guard, where
+//! `invariant` and `assigns` are pointers to closures created before the loop. The call itself
+//! evaluates to `true` (see the `LoopContract` hook). Instead, the contract is attached to the
+//! backedges of the loop, which is where `goto-instrument --apply-loop-contracts` expects it:
+//!
+//! - The invariant is a call to `kani::internal::loop_contract` itself, which evaluates the
+//!   invariant closure.
+//! - The assigns clause consists of the places captured by reference by the assigns closure,
+//!   i.e. the dereferenced fields of its environment.
+//!
+//! `#[kani::invariant]` calls `kani::internal::loop_invariant(invariant)` instead. The loop then
+//! has no assigns clause, and CBMC infers the places that the loop modifies.
+
+use crate::codegen_cprover_gotoc::codegen::bb_label;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Stmt, StmtBody, SwitchCase, Type};
+use rustc_middle::mir::BasicBlock as InternalBasicBlock;
+use rustc_smir::rustc_internal;
+use rustc_span::Symbol;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{BasicBlockIdx, Body, Operand, TerminatorKind};
+use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::CrateDef;
+use std::collections::{HashMap, HashSet};
+
+/// The call to `kani::internal::loop_contract` that registers the contract of a loop.
+#[derive(Clone, Debug)]
+pub struct LoopContractCall {
+    instance: Instance,
+    args: Vec<Operand>,
+}
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Is `instance` the `kani::internal::loop_contract` or `kani::internal::loop_invariant`
+    /// function?
+    pub fn is_loop_contract(&self, instance: Instance) -> bool {
+        let def_id = rustc_internal::internal(instance.def.def_id());
+        ["KaniLoopContract", "KaniLoopInvariant"]
+            .into_iter()
+            .any(|name| self.tcx.is_diagnostic_item(Symbol::intern(name), def_id))
+    }
+
+    /// Find the headers of the loops in the current function that have a contract, together
+    /// with the call that registers the contract.
+    ///
+    /// A loop header is the target of a backedge. The call that registers the contract is the
+    /// first thing the guard of the loop evaluates, so the header reaches it through a (possibly
+    /// empty) chain of gotos that does not pass through the header of another loop.
+    pub fn loop_contract_headers(&self, body: &Body) -> HashMap<BasicBlockIdx, LoopContractCall> {
+        let calls: HashMap<BasicBlockIdx, LoopContractCall> = body
+            .blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(bb, block)| {
+                let TerminatorKind::Call { func, args, .. } = &block.terminator.kind else {
+                    return None;
+                };
+                let TyKind::RigidTy(RigidTy::FnDef(def, fn_args)) =
+                    self.operand_ty_stable(func).kind()
+                else {
+                    return None;
+                };
+                let instance = Instance::resolve(def, &fn_args).ok()?;
+                self.is_loop_contract(instance)
+                    .then(|| (bb, LoopContractCall { instance, args: args.clone() }))
+            })
+            .collect();
+        if calls.is_empty() {
+            return HashMap::new();
+        }
+
+        let mir = self.current_fn().body_internal();
+        let dominators = mir.basic_blocks.dominators();
+        let headers: HashSet<BasicBlockIdx> = mir
+            .basic_blocks
+            .iter_enumerated()
+            .flat_map(|(bb, data)| {
+                data.terminator().successors().filter(move |succ| dominators.dominates(*succ, bb))
+            })
+            .map(|header| header.index())
+            .collect();
+        headers
+            .iter()
+            .filter_map(|&header| {
+                let mut bb = header;
+                loop {
+                    if let Some(call) = calls.get(&bb) {
+                        return Some((header, call.clone()));
+                    }
+                    match body.blocks[bb].terminator.kind {
+                        TerminatorKind::Goto { target } if !headers.contains(&target) => {
+                            bb = target
+                        }
+                        _ => return None,
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Attach the loop contract to the code `tcode` generated for the terminator of `bb` if it
+    /// jumps back to the header of a loop with a contract.
+    pub fn codegen_loop_contract_backedges(&mut self, bb: BasicBlockIdx, tcode: Stmt) -> Stmt {
+        if !self.current_fn().has_loop_contracts() {
+            return tcode;
+        }
+        let mir = self.current_fn().body_internal();
+        let dominators = mir.basic_blocks.dominators();
+        let from = InternalBasicBlock::from_usize(bb);
+        let headers: Vec<BasicBlockIdx> = mir.basic_blocks[from]
+            .terminator()
+            .successors()
+            .filter(|header| dominators.dominates(*header, from))
+            .map(|header| header.index())
+            .collect();
+        headers.into_iter().fold(tcode, |tcode, header| {
+            match self.current_fn().loop_contract(header).cloned() {
+                Some(call) => {
+                    let (invariant, assigns) = self.codegen_loop_contract(call);
+                    attach_loop_contract(tcode, &bb_label(header), &invariant, &assigns)
+                }
+                None => tcode,
+            }
+        })
+    }
+
+    /// Generate the invariant and the assigns targets registered by `call`. There are no assigns
+    /// targets if the call only registers an invariant.
+    fn codegen_loop_contract(&mut self, call: LoopContractCall) -> (Expr, Option<Vec<Expr>>) {
+        let fargs = self.codegen_funcall_args(&call.args, false);
+        let invariant =
+            self.codegen_func_expr(call.instance, None).call(fargs.clone()).cast_to(Type::bool());
+        let assigns = fargs.get(1).map(|assigns| {
+            let env = assigns.clone().dereference();
+            env.typ()
+                .lookup_components(&self.symbol_table)
+                .into_iter()
+                .flatten()
+                .filter(|component| !component.is_padding())
+                .map(|component| {
+                    env.clone().member(component.name(), &self.symbol_table).dereference()
+                })
+                .collect()
+        });
+        (invariant, assigns)
+    }
+}
+
+/// Attach the loop contract to every `goto` to `header` in `stmt`.
+fn attach_loop_contract(
+    stmt: Stmt,
+    header: &str,
+    invariant: &Expr,
+    assigns: &Option<Vec<Expr>>,
+) -> Stmt {
+    let loc = *stmt.location();
+    let attach = |s: &Stmt| attach_loop_contract(s.clone(), header, invariant, assigns);
+    match stmt.body() {
+        StmtBody::Goto { dest, .. } if *dest == header => {
+            stmt.with_loop_contract(invariant.clone(), assigns.clone())
+        }
+        StmtBody::Block(stmts) => Stmt::block(stmts.iter().map(attach).collect(), loc),
+        StmtBody::Label { label, body } => attach(body).with_label(*label),
+        StmtBody::Ifthenelse { i, t, e } => {
+            Stmt::if_then_else(i.clone(), attach(t), e.as_ref().map(attach), loc)
+        }
+        StmtBody::Switch { control, cases, default } => Stmt::switch(
+            control.clone(),
+            cases
+                .iter()
+                .map(|case| SwitchCase::new(case.case().clone(), attach(case.body())))
+                .collect(),
+            default.as_ref().map(attach),
+            loc,
+        ),
+        _ => stmt,
+    }
+}
@@ -9,6 +9,7 @@
 mod foreign_function;
 mod function;
 mod intrinsic;
+mod loop_contract;
 mod operand;
 mod place;
 mod rvalue;
@@ -23,4 +24,5 @@
 
 pub use assert::PropertyClass;
 pub use block::bb_label;
+pub use loop_contract::LoopContractCall;
 pub use typ::TypeExt;
@@ -135,21 +135,27 @@ pub fn codegen_terminator(&mut self, term: &Terminator) -> Stmt {
                 "https://github.com/model-checking/kani/issues/692",
             ),
             TerminatorKind::Return => {
-                let rty = self.current_fn().sig().output();
-                if rty.kind().is_unit() {
-                    self.codegen_ret_unit()
+                let ret = self.codegen_return(loc);
+                if self.current_fn().must_panic() {
+                    // The harness is expected to panic on every path, so it must not return.
+                    let check = self.codegen_assert_assume_false(
+                        PropertyClass::ShouldPanic,
+                        "harness returned without panicking",
+                        loc,
+                    );
+                    Stmt::block(vec![check, ret], loc)
+                } else if self.current_fn().check_exit_reachable() {
+                    // Fails if the harness can return. The driver reports the harness as failed
+                    // if this holds on every return, i.e., if the assumptions prune every path.
+                    let check = self.codegen_assert(
+                        Expr::bool_false(),
+                        PropertyClass::HarnessExit,
+                        "the end of the harness is reachable",
+                        loc,
+                    );
+                    Stmt::block(vec![check, ret], loc)
                 } else {
-                    let place = Place::from(RETURN_LOCAL);
-                    let place_expr = unwrap_or_return_codegen_unimplemented_stmt!(
-                        self,
-                        self.codegen_place_stable(&place)
-                    )
-                    .goto_expr;
-                    if self.place_ty_stable(&place).kind().is_bool() {
-                        place_expr.cast_to(Type::c_bool()).ret(loc)
-                    } else {
-                        place_expr.ret(loc)
-                    }
+                    ret
                 }
             }
             TerminatorKind::Unreachable => self.codegen_assert_assume_false(
@@ -295,6 +301,26 @@ fn codegen_deinit(&mut self, place: &Place, loc: Location) -> Stmt {
         }
     }
 
+    /// Generate the return of the current function.
+    fn codegen_return(&mut self, loc: Location) -> Stmt {
+        let rty = self.current_fn().sig().output();
+        if rty.kind().is_unit() {
+            self.codegen_ret_unit()
+        } else {
+            let place = Place::from(RETURN_LOCAL);
+            let place_expr = unwrap_or_return_codegen_unimplemented_stmt!(
+                self,
+                self.codegen_place_stable(&place)
+            )
+            .goto_expr;
+            if self.place_ty_stable(&place).kind().is_bool() {
+                place_expr.cast_to(Type::c_bool()).ret(loc)
+            } else {
+                place_expr.ret(loc)
+            }
+        }
+    }
+
     /// A special case handler to codegen `return ();`
     fn codegen_ret_unit(&mut self) -> Stmt {
         let is_file_local = false;
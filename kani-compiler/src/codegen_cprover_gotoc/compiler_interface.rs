@@ -79,15 +79,16 @@ pub fn new(queries: Arc<Mutex<QueryDb>>) -> Self {
 
     /// Generate code that is reachable from the given starting points.
     ///
-    /// Invariant: iff `check_contract.is_some()` then `return.2.is_some()`
+    /// Invariant: if `check_contracts` is empty then so is `return.2`. Otherwise it only misses a
+    /// contract if an error was reported.
     fn codegen_items<'tcx>(
         &self,
         tcx: TyCtxt<'tcx>,
         starting_items: &[MonoItem],
         symtab_goto: &Path,
         machine_model: &MachineModel,
-        check_contract: Option<InternalDefId>,
-    ) -> (GotocCtx<'tcx>, Vec<MonoItem>, Option<AssignsContract>) {
+        check_contracts: &[InternalDefId],
+    ) -> (GotocCtx<'tcx>, Vec<MonoItem>, Vec<AssignsContract>) {
         let items = with_timer(
             || collect_reachable_items(tcx, starting_items),
             "codegen reachability analysis",
@@ -147,7 +148,12 @@ fn codegen_items<'tcx>(
                     }
                 }
 
-                check_contract.map(|check_id| gcx.handle_check_contract(check_id, &items))
+                check_contracts
+                    .iter()
+                    .filter_map(|check_id| {
+                        gcx.handle_check_contract(*check_id, starting_items, &items)
+                    })
+                    .collect()
             },
             "codegen",
         );
@@ -171,6 +177,7 @@ fn codegen_items<'tcx>(
         if !tcx.sess.opts.unstable_opts.no_codegen && tcx.sess.opts.output_types.should_codegen() {
             let pretty = self.queries.lock().unwrap().args().output_pretty_json;
             write_file(&symtab_goto, ArtifactType::PrettyNameMap, &pretty_name_map, pretty);
+            write_file(&symtab_goto, ArtifactType::VarNameMap, &gcx.var_name_map, pretty);
             if gcx.queries.args().write_json_symtab {
                 write_file(&symtab_goto, ArtifactType::SymTab, &gcx.symbol_table, pretty);
                 symbol_table_to_gotoc(&tcx, &symtab_goto);
@@ -248,15 +255,22 @@ fn codegen_crate(
                             &[MonoItem::Fn(harness)],
                             model_path,
                             &results.machine_model,
-                            contract_metadata,
+                            &contract_metadata,
                         );
+                        let has_loop_contracts = gcx.has_loop_contracts;
                         results.extend(gcx, items, None);
-                        if let Some(assigns_contract) = contract_info {
-                            self.queries.lock().unwrap().register_assigns_contract(
+                        if !contract_info.is_empty() {
+                            self.queries.lock().unwrap().register_assigns_contracts(
                                 canonical_mangled_name(harness).intern(),
-                                assigns_contract,
+                                contract_info,
                             );
                         }
+                        if has_loop_contracts {
+                            self.queries
+                                .lock()
+                                .unwrap()
+                                .register_loop_contracts(canonical_mangled_name(harness).intern());
+                        }
                     }
                 }
                 ReachabilityType::Tests => {
@@ -284,15 +298,18 @@ fn codegen_crate(
                         &results.machine_model,
                         Default::default(),
                     );
+                    let has_loop_contracts = gcx.has_loop_contracts;
                     results.extend(gcx, items, None);
 
-                    assert!(contract_info.is_none());
+                    assert!(contract_info.is_empty());
 
                     for (test_fn, test_desc) in harnesses.iter().zip(descriptions.iter()) {
                         let instance =
                             if let MonoItem::Fn(instance) = test_fn { instance } else { continue };
-                        let metadata =
+                        let mut metadata =
                             gen_test_metadata(tcx, *test_desc, *instance, &base_filename);
+                        // All tests share one model, so they all get its loop contracts.
+                        metadata.has_loop_contracts = has_loop_contracts;
                         let test_model_path = &metadata.goto_file.as_ref().unwrap();
                         std::fs::copy(&model_path, test_model_path).expect(&format!(
                             "Failed to copy {} to {}",
@@ -321,7 +338,7 @@ fn codegen_crate(
                         &results.machine_model,
                         Default::default(),
                     );
-                    assert!(contract_info.is_none());
+                    assert!(contract_info.is_empty());
                     results.extend(gcx, items, None);
                 }
             }
@@ -421,9 +438,13 @@ fn link(
 fn contract_metadata_for_harness(
     tcx: TyCtxt,
     def_id: DefId,
-) -> Result<Option<InternalDefId>, ErrorGuaranteed> {
+) -> Result<Vec<InternalDefId>, ErrorGuaranteed> {
     let attrs = KaniAttributes::for_def_id(tcx, def_id);
-    Ok(attrs.interpret_the_for_contract_attribute().transpose()?.map(|(_, id, _)| id))
+    attrs
+        .interpret_the_for_contract_attribute()
+        .into_iter()
+        .map(|target| target.map(|(_, id, _)| id))
+        .collect()
 }
 
 fn check_target(session: &Session) {
@@ -611,6 +632,7 @@ pub fn generate_metadata(&self) -> KaniMetadata {
             proof_harnesses: proofs,
             unsupported_features,
             test_harnesses: tests,
+            contracted_functions: vec![],
         }
     }
 
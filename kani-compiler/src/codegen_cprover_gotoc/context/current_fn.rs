@@ -1,6 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::codegen_cprover_gotoc::codegen::LoopContractCall;
 use crate::codegen_cprover_gotoc::GotocCtx;
 use cbmc::goto_program::Stmt;
 use cbmc::InternedString;
@@ -8,7 +9,7 @@
 use rustc_middle::ty::Instance as InternalInstance;
 use rustc_smir::rustc_internal;
 use stable_mir::mir::mono::Instance;
-use stable_mir::mir::{Body, Local, LocalDecl};
+use stable_mir::mir::{BasicBlockIdx, Body, Local, LocalDecl};
 use stable_mir::ty::FnSig;
 use stable_mir::CrateDef;
 use std::collections::HashMap;
@@ -36,6 +37,13 @@ pub struct CurrentFnCtx<'tcx> {
     readable_name: String,
     /// A counter to enable creating temporary variables
     temp_var_counter: u64,
+    /// The headers of the loops that have a contract, with the call that registers it.
+    loop_contracts: HashMap<BasicBlockIdx, LoopContractCall>,
+    /// Whether this is a harness that must panic on every path, i.e., must not return.
+    must_panic: bool,
+    /// Whether this is a harness whose end must be reachable, i.e., whose assumptions must not
+    /// prune every path.
+    check_exit_reachable: bool,
 }
 
 /// Constructor
@@ -63,6 +71,9 @@ pub fn new(instance: Instance, gcx: &GotocCtx<'tcx>, body: &Body) -> Self {
             name,
             readable_name,
             temp_var_counter: 0,
+            loop_contracts: HashMap::new(),
+            must_panic: false,
+            check_exit_reachable: false,
         }
     }
 }
@@ -83,6 +94,18 @@ pub fn get_and_incr_counter(&mut self) -> u64 {
     pub fn push_onto_block(&mut self, s: Stmt) {
         self.block.push(s)
     }
+
+    pub fn set_loop_contracts(&mut self, loop_contracts: HashMap<BasicBlockIdx, LoopContractCall>) {
+        self.loop_contracts = loop_contracts
+    }
+
+    pub fn set_must_panic(&mut self, must_panic: bool) {
+        self.must_panic = must_panic
+    }
+
+    pub fn set_check_exit_reachable(&mut self, check_exit_reachable: bool) {
+        self.check_exit_reachable = check_exit_reachable
+    }
 }
 
 /// Getters
@@ -123,6 +146,26 @@ pub fn locals(&self) -> &[LocalDecl] {
     pub fn local_name(&self, local: Local) -> Option<InternedString> {
         self.local_names.get(&local).copied()
     }
+
+    /// Whether any loop of this function has a contract.
+    pub fn has_loop_contracts(&self) -> bool {
+        !self.loop_contracts.is_empty()
+    }
+
+    /// The call that registers the contract of the loop with the given header, if any.
+    pub fn loop_contract(&self, header: BasicBlockIdx) -> Option<&LoopContractCall> {
+        self.loop_contracts.get(&header)
+    }
+
+    /// Is the current function a harness that must panic on every path?
+    pub fn must_panic(&self) -> bool {
+        self.must_panic
+    }
+
+    /// Is the current function a harness whose end must be reachable?
+    pub fn check_exit_reachable(&self) -> bool {
+        self.check_exit_reachable
+    }
 }
 
 /// Utility functions
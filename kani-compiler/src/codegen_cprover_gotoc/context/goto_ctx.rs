@@ -22,7 +22,7 @@
 use cbmc::goto_program::{DatatypeComponent, Expr, Location, Stmt, Symbol, SymbolTable, Type};
 use cbmc::utils::aggr_tag;
 use cbmc::{InternedString, MachineModel};
-use kani_metadata::HarnessMetadata;
+use kani_metadata::{ContractClauseMetadata, HarnessMetadata};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_middle::span_bug;
 use rustc_middle::ty::layout::{
@@ -37,6 +37,8 @@
 use stable_mir::mir::mono::Instance;
 use stable_mir::mir::Body;
 use stable_mir::ty::Allocation;
+use stable_mir::DefId;
+use std::collections::BTreeMap;
 
 pub struct GotocCtx<'tcx> {
     /// the typing context
@@ -57,6 +59,8 @@ pub struct GotocCtx<'tcx> {
     pub vtable_ctx: VtableCtx,
     pub current_fn: Option<CurrentFnCtx<'tcx>>,
     pub type_map: FxHashMap<InternedString, Ty<'tcx>>,
+    /// map from function name to the source names of its local variables, by base name
+    pub var_name_map: BTreeMap<InternedString, BTreeMap<String, String>>,
     /// map from symbol identifier to string literal
     /// TODO: consider making the map from Expr to String instead
     pub str_literals: FxHashMap<InternedString, String>,
@@ -70,6 +74,11 @@ pub struct GotocCtx<'tcx> {
     /// We collect them and print one warning at the end if not empty instead of printing one
     /// warning at each occurrence.
     pub concurrent_constructs: UnsupportedConstructs,
+    /// The `requires` and `ensures` clauses of the contract check functions that were
+    /// generated, by the function they appear in.
+    pub contract_clauses: Vec<(DefId, ContractClauseMetadata)>,
+    /// Whether a function with a loop contract was generated.
+    pub has_loop_contracts: bool,
 }
 
 /// Constructor
@@ -93,12 +102,15 @@ pub fn new(
             vtable_ctx: VtableCtx::new(emit_vtable_restrictions),
             current_fn: None,
             type_map: FxHashMap::default(),
+            var_name_map: BTreeMap::default(),
             str_literals: FxHashMap::default(),
             proof_harnesses: vec![],
             test_harnesses: vec![],
             global_checks_count: 0,
             unsupported_constructs: FxHashMap::default(),
             concurrent_constructs: FxHashMap::default(),
+            contract_clauses: vec![],
+            has_loop_contracts: false,
         }
     }
 }
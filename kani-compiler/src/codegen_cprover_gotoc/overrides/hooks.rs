@@ -9,14 +9,16 @@
 //! this module addresses this issue.
 
 use crate::codegen_cprover_gotoc::codegen::{bb_label, PropertyClass};
-use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::codegen_cprover_gotoc::{utils, GotocCtx};
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
-use cbmc::goto_program::{BuiltinFn, Expr, Location, Stmt, Type};
-use rustc_middle::ty::TyCtxt;
+use cbmc::goto_program::{BuiltinFn, Expr, ExprValue, Location, Stmt, Type};
+use kani_metadata::{ContractClauseKind, ContractClauseMetadata};
+use rustc_middle::ty::{ParamEnv, TyCtxt};
 use rustc_smir::rustc_internal;
 use stable_mir::mir::mono::Instance;
 use stable_mir::mir::{BasicBlockIdx, Place};
-use stable_mir::{ty::Span, CrateDef};
+use stable_mir::ty::{RigidTy, Span, TyKind};
+use stable_mir::CrateDef;
 use std::rc::Rc;
 use tracing::debug;
 
@@ -119,6 +121,58 @@ fn hook_applies(&self, tcx: TyCtxt, instance: Instance) -> bool {
         matches_function(tcx, instance, "KaniAssert")
     }
 
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        fargs: Vec<Expr>,
+        _assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        codegen_assert_hook(gcx, PropertyClass::Assertion, fargs, target, span)
+    }
+}
+
+/// A hook for `kani::internal::check_precondition`, which the contract macros
+/// use to check the precondition in the body of a replacement. It is an
+/// assertion. With `--precondition-call-site` it gets its own property class so
+/// that the driver can tell precondition violations apart.
+struct CheckPrecondition;
+impl GotocHook for CheckPrecondition {
+    fn hook_applies(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        matches_function(tcx, instance, "KaniCheckPrecondition")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        fargs: Vec<Expr>,
+        _assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        let class = if gcx.queries.args().precondition_call_site {
+            PropertyClass::Precondition
+        } else {
+            PropertyClass::Assertion
+        };
+        codegen_assert_hook(gcx, class, fargs, target, span)
+    }
+}
+
+/// A hook for `kani::internal::assume_precondition`, which the contract macros
+/// use to assume the precondition in the body of a check function. Besides the
+/// assumption it records the clause for the contract report and, if the driver
+/// asked for that report, adds a cover property that tells whether the
+/// precondition is satisfiable.
+struct AssumePrecondition;
+impl GotocHook for AssumePrecondition {
+    fn hook_applies(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        matches_function(tcx, instance, "KaniAssumePrecondition")
+    }
+
     fn handle(
         &self,
         gcx: &mut GotocCtx,
@@ -129,30 +183,148 @@ fn handle(
         span: Span,
     ) -> Stmt {
         assert_eq!(fargs.len(), 2);
+        let msg = gcx.extract_const_message(&fargs[1]).unwrap();
+        record_contract_clause(gcx, ContractClauseKind::Requires, &fargs[0], &msg, span);
         let cond = fargs.remove(0).cast_to(Type::bool());
-        let msg = fargs.remove(0);
-        let msg = gcx.extract_const_message(&msg).unwrap();
         let target = target.unwrap();
-        let caller_loc = gcx.codegen_caller_span_stable(span);
+        let loc = gcx.codegen_span_stable(span);
 
-        let (msg, reach_stmt) = gcx.codegen_reachability_check(msg, span);
+        let mut stmts = vec![gcx.codegen_assume(cond, loc)];
+        if gcx.queries.args().contract_report {
+            let (msg, reach_stmt) = gcx.codegen_reachability_check(msg, span);
+            stmts.extend([reach_stmt, gcx.codegen_cover(Expr::bool_true(), &msg, span)]);
+        }
+        stmts.push(Stmt::goto(bb_label(target), loc));
+        Stmt::block(stmts, loc)
+    }
+}
+
+/// A hook for `kani::internal::check_postcondition`, which the contract macros
+/// use to check the postcondition in the body of a check function. It is a
+/// regular assertion, unless the driver asked us to skip postcondition checks,
+/// in which case the call is dropped.
+struct CheckPostcondition;
+impl GotocHook for CheckPostcondition {
+    fn hook_applies(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        matches_function(tcx, instance, "KaniCheckPostcondition")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        fargs: Vec<Expr>,
+        _assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        let msg = gcx.extract_const_message(&fargs[1]).unwrap();
+        record_contract_clause(gcx, ContractClauseKind::Ensures, &fargs[0], &msg, span);
+        if gcx.queries.args().skip_postcondition_checks {
+            let caller_loc = gcx.codegen_caller_span_stable(span);
+            Stmt::goto(bb_label(target.unwrap()), caller_loc)
+        } else {
+            codegen_assert_hook(gcx, PropertyClass::Assertion, fargs, target, span)
+        }
+    }
+}
 
-        // Since `cond` might have side effects, assign it to a temporary
-        // variable so that it's evaluated once, then assert and assume it
-        // TODO: I don't think `cond` can have side effects, this is MIR, it's going to be temps
-        let (tmp, decl) = gcx.decl_temp_variable(cond.typ().clone(), Some(cond), caller_loc);
+/// A hook for `kani::internal::loop_contract` and `kani::internal::loop_invariant`, which
+/// `#[kani::loop_contract]` and `#[kani::invariant]` call in the guard of the annotated loop to
+/// register its contract. The call evaluates to `true`; the contract itself is attached to the
+/// backedges of the loop (see the `loop_contract` module).
+struct LoopContract;
+impl GotocHook for LoopContract {
+    fn hook_applies(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        matches_function(tcx, instance, "KaniLoopContract")
+            || matches_function(tcx, instance, "KaniLoopInvariant")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        _fargs: Vec<Expr>,
+        assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        if !gcx.queries.args().unstable_features.iter().any(|feature| feature == "loop-contracts") {
+            utils::span_err(
+                gcx.tcx,
+                span,
+                "Using loop contracts requires activating the unstable `loop-contracts` feature"
+                    .to_string(),
+            );
+        }
+        let loc = gcx.codegen_span_stable(span);
+        let pe =
+            unwrap_or_return_codegen_unimplemented_stmt!(gcx, gcx.codegen_place_stable(assign_to))
+                .goto_expr;
         Stmt::block(
-            vec![
-                reach_stmt,
-                decl,
-                gcx.codegen_assert_assume(tmp, PropertyClass::Assertion, &msg, caller_loc),
-                Stmt::goto(bb_label(target), caller_loc),
-            ],
-            caller_loc,
+            vec![pe.assign(Expr::c_true(), loc), Stmt::goto(bb_label(target.unwrap()), loc)],
+            loc,
         )
     }
 }
 
+/// Remember a `requires` or `ensures` clause of the contract check function
+/// that is being generated, see [`GotocCtx::contract_clauses`]. The location is
+/// the one CBMC reports for the property of the clause.
+fn record_contract_clause(
+    gcx: &mut GotocCtx,
+    kind: ContractClauseKind,
+    cond: &Expr,
+    msg: &str,
+    span: Span,
+) {
+    let loc = gcx.codegen_caller_span_stable(span);
+    let (Some(filename), Some(start_line)) = (loc.filename(), loc.start_line()) else {
+        return;
+    };
+    let clause = ContractClauseMetadata {
+        kind,
+        condition: msg.to_string(),
+        location: kani_metadata::Location { filename, start_line },
+        constant: matches!(cond.value(), ExprValue::BoolConstant(_) | ExprValue::CBoolConstant(_)),
+    };
+    let function = gcx.current_fn().instance_stable().def.def_id();
+    gcx.contract_clauses.push((function, clause));
+}
+
+/// Codegen a call to an assertion function that takes a condition and a
+/// message as an assert-assume of the given `property_class`.
+fn codegen_assert_hook(
+    gcx: &mut GotocCtx,
+    property_class: PropertyClass,
+    mut fargs: Vec<Expr>,
+    target: Option<BasicBlockIdx>,
+    span: Span,
+) -> Stmt {
+    assert_eq!(fargs.len(), 2);
+    let cond = fargs.remove(0).cast_to(Type::bool());
+    let msg = fargs.remove(0);
+    let msg = gcx.extract_const_message(&msg).unwrap();
+    let target = target.unwrap();
+    let caller_loc = gcx.codegen_caller_span_stable(span);
+
+    let (msg, reach_stmt) = gcx.codegen_reachability_check(msg, span);
+
+    // Since `cond` might have side effects, assign it to a temporary
+    // variable so that it's evaluated once, then assert and assume it
+    // TODO: I don't think `cond` can have side effects, this is MIR, it's going to be temps
+    let (tmp, decl) = gcx.decl_temp_variable(cond.typ().clone(), Some(cond), caller_loc);
+    Stmt::block(
+        vec![
+            reach_stmt,
+            decl,
+            gcx.codegen_assert_assume(tmp, property_class, &msg, caller_loc),
+            Stmt::goto(bb_label(target), caller_loc),
+        ],
+        caller_loc,
+    )
+}
+
 struct Nondet;
 
 impl GotocHook for Nondet {
@@ -319,6 +491,97 @@ fn handle(
     }
 }
 
+/// A hook for Kani's `overlaps` function (declared in `library/kani/src/lib.rs`), which
+/// implements `kani::aliases`.
+///
+/// The function takes two pointers with their respective sizes in bytes and returns whether the
+/// two memory ranges overlap. This compiles `overlaps(a, a_len, b, b_len)` to:
+/// ```c
+/// __CPROVER_same_object(a, b) && a_len != 0 && b_len != 0
+///     && __CPROVER_POINTER_OFFSET(a) < __CPROVER_POINTER_OFFSET(b) + b_len
+///     && __CPROVER_POINTER_OFFSET(b) < __CPROVER_POINTER_OFFSET(a) + a_len
+/// ```
+struct Overlaps;
+
+impl GotocHook for Overlaps {
+    fn hook_applies(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        matches_function(tcx, instance, "KaniOverlaps")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        mut fargs: Vec<Expr>,
+        assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 4);
+        let loc = gcx.codegen_span_stable(span);
+        let target = target.unwrap();
+        let a = fargs.remove(0);
+        let a_len = fargs.remove(0);
+        let b = fargs.remove(0);
+        let b_len = fargs.remove(0);
+        let (a, a_decl) = gcx.decl_temp_variable(a.typ().clone(), Some(a), loc);
+        let (a_len, a_len_decl) = gcx.decl_temp_variable(a_len.typ().clone(), Some(a_len), loc);
+        let (b, b_decl) = gcx.decl_temp_variable(b.typ().clone(), Some(b), loc);
+        let (b_len, b_len_decl) = gcx.decl_temp_variable(b_len.typ().clone(), Some(b_len), loc);
+        let size_t = a_len.typ().clone();
+        let a_start = a.clone().pointer_offset().cast_to(size_t.clone());
+        let b_start = b.clone().pointer_offset().cast_to(size_t);
+        let overlaps = a
+            .same_object(b)
+            .and(a_len.clone().is_zero().not())
+            .and(b_len.clone().is_zero().not())
+            .and(a_start.clone().lt(b_start.clone().plus(b_len)))
+            .and(b_start.lt(a_start.plus(a_len)));
+        let place_expr =
+            unwrap_or_return_codegen_unimplemented_stmt!(gcx, gcx.codegen_place_stable(assign_to))
+                .goto_expr;
+        let code = place_expr.clone().assign(overlaps.cast_to(place_expr.typ().clone()), loc);
+        Stmt::block(
+            vec![a_decl, a_len_decl, b_decl, b_len_decl, code, Stmt::goto(bb_label(target), loc)],
+            loc,
+        )
+    }
+}
+
+/// A hook for `kani::internal::shared_modifies_target`, which the contract
+/// macros call for a `modifies` target that is behind a shared reference or
+/// shared pointer. Such a target can only be assigned if its type has interior
+/// mutability, so the message passed to the call is reported as an error if the
+/// type is `Freeze`.
+struct SharedModifiesTarget;
+
+impl GotocHook for SharedModifiesTarget {
+    fn hook_applies(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        matches_function(tcx, instance, "KaniSharedModifiesTarget")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        instance: Instance,
+        fargs: Vec<Expr>,
+        _assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let TyKind::RigidTy(RigidTy::FnDef(_, args)) = instance.ty().kind() else {
+            unreachable!("Expected `shared_modifies_target` to be a function")
+        };
+        let target_ty = rustc_internal::internal(args.0[0].expect_ty());
+        if target_ty.is_freeze(gcx.tcx, ParamEnv::reveal_all()) {
+            let msg = gcx.extract_const_message(&fargs[1]).unwrap();
+            utils::span_err(gcx.tcx, span, msg);
+        }
+        Stmt::goto(bb_label(target.unwrap()), gcx.codegen_span_stable(span))
+    }
+}
+
 /// A builtin that is essentially a C-style dereference operation, creating an
 /// unsafe shallow copy. Importantly either this copy or the original needs to
 /// be `mem::forget`en or a double-free will occur.
@@ -371,11 +634,17 @@ pub fn fn_hooks() -> GotocHooks {
             Rc::new(Panic),
             Rc::new(Assume),
             Rc::new(Assert),
+            Rc::new(CheckPrecondition),
+            Rc::new(AssumePrecondition),
+            Rc::new(CheckPostcondition),
+            Rc::new(LoopContract),
             Rc::new(Cover),
             Rc::new(Nondet),
             Rc::new(RustAlloc),
             Rc::new(MemCmp),
             Rc::new(UntrackedDeref),
+            Rc::new(SharedModifiesTarget),
+            Rc::new(Overlaps),
         ],
     }
 }
@@ -18,16 +18,16 @@
 use crate::args::{Arguments, ReachabilityType};
 #[cfg(feature = "cprover")]
 use crate::codegen_cprover_gotoc::GotocCodegenBackend;
-use crate::kani_middle::attributes::is_proof_harness;
-use crate::kani_middle::check_crate_items;
-use crate::kani_middle::metadata::gen_proof_metadata;
+use crate::kani_middle::attributes::{is_proof_harness, is_replacement_client};
+use crate::kani_middle::metadata::{gen_contracted_functions, gen_proof_metadata};
 use crate::kani_middle::reachability::filter_crate_items;
-use crate::kani_middle::stubbing::{self, harness_stub_map};
+use crate::kani_middle::stubbing::{self, harness_stub_map, StubTarget};
+use crate::kani_middle::{check_contracts, check_crate_items};
 use crate::kani_queries::QueryDb;
 use crate::session::init_session;
 use cbmc::{InternString, InternedString};
 use clap::Parser;
-use kani_metadata::{ArtifactType, HarnessMetadata, KaniMetadata};
+use kani_metadata::{ArtifactType, ContractedFunction, HarnessMetadata, KaniMetadata};
 use rustc_codegen_ssa::traits::CodegenBackend;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_driver::{Callbacks, Compilation, RunCompiler};
@@ -38,7 +38,8 @@
 use rustc_session::config::{ErrorOutputType, OutputType};
 use rustc_smir::rustc_internal;
 use rustc_span::ErrorGuaranteed;
-use std::collections::{BTreeMap, HashMap};
+use stable_mir::mir::mono::Instance;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::BufWriter;
 use std::mem;
@@ -73,7 +74,7 @@ fn backend(queries: Arc<Mutex<QueryDb>>) -> Box<CodegenBackend> {
 type HarnessId = InternedString;
 
 /// A set of stubs.
-type Stubs = BTreeMap<DefPathHash, DefPathHash>;
+type Stubs = BTreeMap<StubTarget, DefPathHash>;
 
 #[derive(Clone, Debug)]
 struct HarnessInfo {
@@ -88,6 +89,8 @@ struct CrateInfo {
     pub name: String,
     /// The metadata output path that shall be generated as part of the crate compilation.
     pub output_path: PathBuf,
+    /// The functions with contracts found in this crate.
+    pub contracted_functions: Vec<ContractedFunction>,
 }
 
 /// Represents the current compilation stage.
@@ -98,8 +101,8 @@ struct CrateInfo {
 /// - We always start in the [CompilationStage::Init].
 /// - After [CompilationStage::Init] we transition to either
 ///   - [CompilationStage::CodegenNoStubs] on a regular crate compilation, this will follow Init.
-///   - [CompilationStage::CompilationSkipped], running the compiler to gather information, such as
-///     `--version` will skip code generation completely, and there is no work to be done.
+///   - [CompilationStage::CompilationSkipped], running the compiler with `--check-contracts-only`
+///     stops right after analysis, and there is no work to be done.
 /// - After the [CompilationStage::CodegenNoStubs], we transition to either
 ///   - [CompilationStage::CodegenWithStubs] when there is at least one harness with stubs.
 ///   - [CompilationStage::Done] where there is no harness left to process.
@@ -129,7 +132,8 @@ enum CompilationStage {
     /// Initial state that the compiler is always instantiated with.
     /// In this stage, we initialize the Query and collect all harnesses.
     Init,
-    /// State where the compiler ran but didn't actually compile anything (e.g.: --version).
+    /// State where the compiler ran but didn't actually compile anything (e.g.:
+    /// `--check-contracts-only`).
     CompilationSkipped,
     /// Stage where the compiler will perform codegen of all harnesses that don't use stub.
     CodegenNoStubs {
@@ -188,21 +192,19 @@ pub fn run(&mut self, orig_args: Vec<String>) -> Result<(), ErrorGuaranteed> {
             // Because this modifies `self.stage` we need to run this before
             // borrowing `&self.stage` immutably
             if let CompilationStage::Done { metadata: Some((metadata, _)), .. } = &mut self.stage {
-                let mut contracts = self
-                    .queries
-                    .lock()
-                    .unwrap()
+                let queries = self.queries.lock().unwrap();
+                let mut contracts = queries
                     .assigns_contracts()
                     .map(|(k, v)| (*k, v.clone()))
                     .collect::<FxHashMap<_, _>>();
                 for harness in
                     metadata.proof_harnesses.iter_mut().chain(metadata.test_harnesses.iter_mut())
                 {
-                    if let Some(modifies_contract) =
-                        contracts.remove(&(&harness.mangled_name).intern())
-                    {
-                        harness.contract = modifies_contract.into();
+                    let name = (&harness.mangled_name).intern();
+                    if let Some(modifies_contracts) = contracts.remove(&name) {
+                        harness.contract = modifies_contracts;
                     }
+                    harness.has_loop_contracts |= queries.has_loop_contracts(name);
                 }
                 assert!(
                     contracts.is_empty(),
@@ -280,9 +282,9 @@ fn next_stage(&mut self) {
                     }
                 }
             }
-            CompilationStage::Done { .. } | CompilationStage::CompilationSkipped => {
-                unreachable!()
-            }
+            // We stopped after checking the contracts, so there is nothing left to do.
+            CompilationStage::CompilationSkipped => CompilationStage::CompilationSkipped,
+            CompilationStage::Done { .. } => unreachable!(),
         };
     }
 
@@ -298,23 +300,41 @@ fn run_compilation_session(&mut self, args: &[String]) -> Result<(), ErrorGuaran
 
     /// Gather and process all harnesses from this crate that shall be compiled.
     fn process_harnesses(&self, tcx: TyCtxt) -> CompilationStage {
-        let crate_info = CrateInfo {
+        let mut crate_info = CrateInfo {
             name: tcx.crate_name(LOCAL_CRATE).as_str().into(),
             output_path: metadata_output_path(tcx),
+            contracted_functions: vec![],
         };
         if self.queries.lock().unwrap().args().reachability_analysis == ReachabilityType::Harnesses
         {
             let base_filename = tcx.output_filenames(()).output_path(OutputType::Object);
+            let replace_all_contracts = self.queries.lock().unwrap().args().replace_all_contracts;
+            let enforce_and_replace = self.queries.lock().unwrap().args().enforce_and_replace;
+            let harness_info = |harness: Instance| {
+                let def_path = harness.mangled_name().intern();
+                let metadata =
+                    gen_proof_metadata(tcx, harness, &base_filename, replace_all_contracts);
+                let stub_map = harness_stub_map(tcx, harness, &metadata);
+                (def_path, HarnessInfo { metadata, stub_map })
+            };
             let harnesses = filter_crate_items(tcx, |_, instance| is_proof_harness(tcx, instance));
-            let all_harnesses = harnesses
-                .into_iter()
-                .map(|harness| {
-                    let def_path = harness.mangled_name().intern();
-                    let metadata = gen_proof_metadata(tcx, harness, &base_filename);
-                    let stub_map = harness_stub_map(tcx, harness, &metadata);
-                    (def_path, HarnessInfo { metadata, stub_map })
-                })
-                .collect::<HashMap<_, _>>();
+            let mut all_harnesses =
+                harnesses.into_iter().map(harness_info).collect::<HashMap<_, _>>();
+            if enforce_and_replace {
+                // The replacement phase of every checked contract is also verified with the
+                // replacement client generated for the function.
+                let checked = all_harnesses
+                    .values()
+                    .flat_map(|info| info.metadata.attributes.proof_for_contract.iter().cloned())
+                    .collect::<HashSet<_>>();
+                let clients =
+                    filter_crate_items(tcx, |_, instance| is_replacement_client(tcx, instance));
+                all_harnesses.extend(clients.into_iter().map(harness_info).filter(|(_, info)| {
+                    info.metadata.attributes.verified_stubs.iter().any(|f| checked.contains(f))
+                }));
+            }
+            crate_info.contracted_functions =
+                gen_contracted_functions(tcx, all_harnesses.values().map(|info| &info.metadata));
 
             let (no_stubs, with_stubs): (Vec<_>, Vec<_>) =
                 if self.queries.lock().unwrap().args().stubbing_enabled {
@@ -426,13 +446,22 @@ fn after_analysis<'tcx>(
         if self.stage.is_init() {
             self.stage = rustc_queries.global_ctxt().unwrap().enter(|tcx| {
                 rustc_internal::run(tcx, || {
-                    check_crate_items(tcx, self.queries.lock().unwrap().args().ignore_global_asm);
-                    self.process_harnesses(tcx)
+                    let args = self.queries.lock().unwrap().args().clone();
+                    check_crate_items(tcx, args.ignore_global_asm);
+                    if args.check_contracts_only {
+                        check_contracts(tcx);
+                        CompilationStage::CompilationSkipped
+                    } else {
+                        self.process_harnesses(tcx)
+                    }
                 })
                 .unwrap()
             })
         }
 
+        if matches!(self.stage, CompilationStage::CompilationSkipped) {
+            return Compilation::Stop;
+        }
         self.prepare_codegen()
     }
 }
@@ -452,6 +481,7 @@ fn generate_metadata(
         proof_harnesses,
         unsupported_features: vec![],
         test_harnesses,
+        contracted_functions: crate_info.contracted_functions.clone(),
     }
 }
 
@@ -495,6 +525,8 @@ fn mock_metadata(name: String, krate: String) -> HarnessMetadata {
             goto_file: None,
             attributes: HarnessAttributes::default(),
             contract: Default::default(),
+            has_loop_contracts: false,
+            verified_with: None,
         }
     }
 
@@ -510,13 +542,13 @@ fn test_group_by_stubs_works() {
         let harness_3 = mock_next_harness_id();
         let harnesses = vec![harness_1, harness_2, harness_3];
 
-        let stub_1 = (mock_next_stub_id(), mock_next_stub_id());
-        let stub_2 = (mock_next_stub_id(), mock_next_stub_id());
-        let stub_3 = (mock_next_stub_id(), mock_next_stub_id());
-        let stub_4 = (stub_3.0, mock_next_stub_id());
+        let stub_1 = ((mock_next_stub_id(), None), mock_next_stub_id());
+        let stub_2 = ((mock_next_stub_id(), Some("u32".to_string())), mock_next_stub_id());
+        let stub_3 = ((mock_next_stub_id(), None), mock_next_stub_id());
+        let stub_4 = (stub_3.0.clone(), mock_next_stub_id());
 
-        let set_1 = Stubs::from([stub_1, stub_2, stub_3]);
-        let set_2 = Stubs::from([stub_1, stub_2, stub_4]);
+        let set_1 = Stubs::from([stub_1.clone(), stub_2.clone(), stub_3.clone()]);
+        let set_2 = Stubs::from([stub_1.clone(), stub_2.clone(), stub_4]);
         let set_3 = Stubs::from([stub_1, stub_3, stub_2]);
         assert_eq!(set_1, set_3);
         assert_ne!(set_1, set_2);
@@ -544,7 +576,11 @@ fn test_group_by_stubs_works() {
     fn test_generate_metadata() {
         // Mock inputs.
         let name = "my_crate".to_string();
-        let crate_info = CrateInfo { name: name.clone(), output_path: PathBuf::default() };
+        let crate_info = CrateInfo {
+            name: name.clone(),
+            output_path: PathBuf::default(),
+            contracted_functions: vec![],
+        };
 
         let mut info = mock_info_with_stubs(Stubs::default());
         info.metadata.attributes.proof = true;
@@ -564,7 +600,11 @@ fn test_generate_metadata() {
     fn test_generate_empty_metadata() {
         // Mock inputs.
         let name = "my_crate".to_string();
-        let crate_info = CrateInfo { name: name.clone(), output_path: PathBuf::default() };
+        let crate_info = CrateInfo {
+            name: name.clone(),
+            output_path: PathBuf::default(),
+            contracted_functions: vec![],
+        };
         let all_harnesses = HashMap::new();
 
         // Call generate metadata.
@@ -579,7 +619,11 @@ fn test_generate_empty_metadata() {
     fn test_generate_metadata_with_multiple_harness() {
         // Mock inputs.
         let krate = "my_crate".to_string();
-        let crate_info = CrateInfo { name: krate.clone(), output_path: PathBuf::default() };
+        let crate_info = CrateInfo {
+            name: krate.clone(),
+            output_path: PathBuf::default(),
+            contracted_functions: vec![],
+        };
 
         let harnesses = ["h1", "h2", "h3"];
         let infos = harnesses.map(|harness| {
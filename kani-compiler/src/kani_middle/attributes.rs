@@ -4,7 +4,10 @@
 
 use std::collections::BTreeMap;
 
-use kani_metadata::{CbmcSolver, HarnessAttributes, Stub};
+use kani_metadata::{
+    CbmcSolver, ContractClauseKind, HarnessAttributes, PointerCheck, ShouldPanicMode, Stub,
+    UnstableFeature,
+};
 use rustc_ast::{
     attr,
     token::Token,
@@ -18,25 +21,44 @@
     def::DefKind,
     def_id::{DefId, LocalDefId},
 };
-use rustc_middle::ty::{Instance, TyCtxt, TyKind};
+use rustc_middle::mir;
+use rustc_middle::ty::{AliasKind, GenericParamDefKind, Instance, TyCtxt, TyKind};
 use rustc_session::Session;
 use rustc_smir::rustc_internal;
-use rustc_span::{Span, Symbol};
+use rustc_span::{source_map::Spanned, Span, Symbol};
 use stable_mir::mir::mono::Instance as InstanceStable;
 use stable_mir::mir::Local;
 use stable_mir::{CrateDef, DefId as StableDefId};
 use std::str::FromStr;
-use strum_macros::{AsRefStr, EnumString};
+use strum::VariantNames;
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
 use tracing::{debug, trace};
 
 use super::resolve::{self, resolve_fn, ResolveError};
 
-#[derive(Debug, Clone, Copy, AsRefStr, EnumString, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord
+)]
 #[strum(serialize_all = "snake_case")]
 enum KaniAttributeKind {
     Proof,
+    /// Enables or disables individual CBMC pointer checks for a harness.
+    PointerChecks,
     ShouldPanic,
+    /// Fails verification of a harness if its assumptions prune every path.
+    ExpectUnreachable,
+    /// Excludes a harness from the default verification run.
+    Slow,
     Solver,
     Stub,
     /// Attribute used to mark unstable APIs.
@@ -49,6 +71,10 @@ enum KaniAttributeKind {
     /// contract, e.g. the contract check is substituted for the target function
     /// before the the verification runs.
     ProofForContract,
+    /// Added to a [`Self::Proof`] harness that checks that the target function
+    /// is idempotent. The check itself is generated by the macro, if the target
+    /// has a contract it is substituted by its contract check.
+    ProofIdempotent,
     /// Attribute on a function with a contract that identifies the code
     /// implementing the check for this contract.
     CheckedWith,
@@ -59,11 +85,49 @@ enum KaniAttributeKind {
     /// Attribute on a function that was auto-generated from expanding a
     /// function contract.
     IsContractGenerated,
+    /// Marks the replacement client generated for a function with a contract,
+    /// which calls the function with nondeterministic arguments. With
+    /// `--enforce-and-replace` the client is verified as a harness that
+    /// replaces the function by its contract, like [`Self::StubVerified`].
+    ReplacementClient,
+    /// Hash of the complete contract and body of a function with a contract.
+    /// Placed on the original function and on its replace function.
+    ///
+    /// Used to compute the fingerprint of a `proof_for_contract` harness for
+    /// `--changed-only`.
+    ContractFingerprint,
+    /// The source text of a `requires` clause of the contract of this function.
+    /// Placed on the original function, once per clause.
+    ///
+    /// Used to export the contracts of a crate, see [`KaniAttributes::contract_clauses`].
+    ContractRequires,
+    /// Like [`Self::ContractRequires`], but for an `ensures` clause.
+    ContractEnsures,
+    /// Like [`Self::ContractRequires`], but for a target of a `modifies` clause.
+    ContractModifies,
     /// Identifies a set of pointer arguments that should be added to the write
     /// set when checking a function contract. Placed on the inner check function.
     ///
     /// Emitted by the expansion of a `modifies` function contract clause.
     Modifies,
+    /// Marks the inner check function of a contract that has a `decreases`
+    /// clause. The measure itself is evaluated by the generated code, the
+    /// compiler only needs to keep the static holding it from being havocked.
+    ///
+    /// Emitted by the expansion of a `decreases` function contract clause.
+    Decreases,
+    /// Marks the static declared in the recursion wrapper of a contract that
+    /// counts the active checks of the contract. The compiler only needs to
+    /// keep it from being havocked.
+    RecursionTracker,
+    /// Marks a side effect free function that may be used in the conditions
+    /// of a contract. Predicates are evaluated like any other function, they
+    /// may not have a contract of their own.
+    Predicate,
+    /// Marks a function with a contract that does not modify any memory
+    /// visible to its caller, i.e. whose write set is empty. The function may
+    /// not have a `modifies` clause.
+    Pure,
     /// A function used as the inner code of a contract check.
     ///
     /// Contains the original body of the contracted function. The signature is
@@ -77,30 +141,77 @@ impl KaniAttributeKind {
     pub fn is_harness_only(self) -> bool {
         match self {
             KaniAttributeKind::Proof
+            | KaniAttributeKind::PointerChecks
             | KaniAttributeKind::ShouldPanic
+            | KaniAttributeKind::ExpectUnreachable
+            | KaniAttributeKind::Slow
             | KaniAttributeKind::Solver
             | KaniAttributeKind::Stub
             | KaniAttributeKind::ProofForContract
+            | KaniAttributeKind::ProofIdempotent
             | KaniAttributeKind::StubVerified
             | KaniAttributeKind::Unwind => true,
             KaniAttributeKind::Unstable
             | KaniAttributeKind::ReplacedWith
             | KaniAttributeKind::CheckedWith
             | KaniAttributeKind::Modifies
+            | KaniAttributeKind::Decreases
+            | KaniAttributeKind::RecursionTracker
+            | KaniAttributeKind::Predicate
+            | KaniAttributeKind::Pure
             | KaniAttributeKind::InnerCheck
-            | KaniAttributeKind::IsContractGenerated => false,
+            | KaniAttributeKind::ContractFingerprint
+            | KaniAttributeKind::ContractRequires
+            | KaniAttributeKind::ContractEnsures
+            | KaniAttributeKind::ContractModifies
+            | KaniAttributeKind::IsContractGenerated
+            | KaniAttributeKind::ReplacementClient => false,
         }
     }
 
-    /// Is this an "active" function contract attribute? This means it is
-    /// part of the function contract interface *and* it implies that a contract
-    /// will be used (stubbed or checked) in some way, thus requiring that the
-    /// user activate the unstable feature.
+    /// The unstable feature the user must activate to use this attribute, if
+    /// any.
     ///
-    /// If we find an "inactive" contract attribute we chose not to error,
-    /// because it wouldn't have any effect anyway.
-    pub fn demands_function_contract_use(self) -> bool {
-        matches!(self, KaniAttributeKind::ProofForContract)
+    /// Only "active" function contract attributes require a feature. This means
+    /// the attribute is part of the function contract interface *and* it implies
+    /// that a contract will be used (stubbed or checked) in some way. If we find
+    /// an "inactive" contract attribute we chose not to error, because it
+    /// wouldn't have any effect anyway.
+    ///
+    /// Of the harness attributes, `proof_for_contract` checks a contract and
+    /// `stub_verified` replaces a function by its contract. `proof_idempotent`
+    /// is a regular harness that only uses the contract check of its target if
+    /// the target has a contract, so it requires no feature.
+    pub fn required_unstable_feature(self) -> Option<UnstableFeature> {
+        match self {
+            KaniAttributeKind::ProofForContract | KaniAttributeKind::StubVerified => {
+                Some(UnstableFeature::FunctionContracts)
+            }
+            KaniAttributeKind::Proof
+            | KaniAttributeKind::PointerChecks
+            | KaniAttributeKind::ShouldPanic
+            | KaniAttributeKind::ExpectUnreachable
+            | KaniAttributeKind::Slow
+            | KaniAttributeKind::Solver
+            | KaniAttributeKind::Stub
+            | KaniAttributeKind::Unstable
+            | KaniAttributeKind::Unwind
+            | KaniAttributeKind::ProofIdempotent
+            | KaniAttributeKind::CheckedWith
+            | KaniAttributeKind::ReplacedWith
+            | KaniAttributeKind::IsContractGenerated
+            | KaniAttributeKind::ReplacementClient
+            | KaniAttributeKind::ContractFingerprint
+            | KaniAttributeKind::ContractRequires
+            | KaniAttributeKind::ContractEnsures
+            | KaniAttributeKind::ContractModifies
+            | KaniAttributeKind::Modifies
+            | KaniAttributeKind::Decreases
+            | KaniAttributeKind::RecursionTracker
+            | KaniAttributeKind::Predicate
+            | KaniAttributeKind::Pure
+            | KaniAttributeKind::InnerCheck => None,
+        }
     }
 
     /// Would this attribute be placed on a function as part of a function
@@ -109,6 +220,41 @@ pub fn is_function_contract(self) -> bool {
         use KaniAttributeKind::*;
         matches!(self, CheckedWith | IsContractGenerated)
     }
+
+    /// Is this an attribute a user is expected to write, as opposed to one that
+    /// is only emitted by the Kani library and macros?
+    ///
+    /// Only these are offered as suggestions for a mistyped attribute.
+    pub fn is_user_facing(self) -> bool {
+        match self {
+            KaniAttributeKind::Proof
+            | KaniAttributeKind::PointerChecks
+            | KaniAttributeKind::ShouldPanic
+            | KaniAttributeKind::ExpectUnreachable
+            | KaniAttributeKind::Slow
+            | KaniAttributeKind::Solver
+            | KaniAttributeKind::Stub
+            | KaniAttributeKind::Unwind
+            | KaniAttributeKind::StubVerified
+            | KaniAttributeKind::ProofForContract
+            | KaniAttributeKind::ProofIdempotent
+            | KaniAttributeKind::Predicate
+            | KaniAttributeKind::Pure => true,
+            KaniAttributeKind::Unstable
+            | KaniAttributeKind::CheckedWith
+            | KaniAttributeKind::ReplacedWith
+            | KaniAttributeKind::IsContractGenerated
+            | KaniAttributeKind::ReplacementClient
+            | KaniAttributeKind::ContractFingerprint
+            | KaniAttributeKind::ContractRequires
+            | KaniAttributeKind::ContractEnsures
+            | KaniAttributeKind::ContractModifies
+            | KaniAttributeKind::Modifies
+            | KaniAttributeKind::Decreases
+            | KaniAttributeKind::RecursionTracker
+            | KaniAttributeKind::InnerCheck => false,
+        }
+    }
 }
 
 /// Bundles together common data used when evaluating the attributes of a given
@@ -180,13 +326,20 @@ fn expect_maybe_one(&self, kind: KaniAttributeKind) -> Option<&'tcx Attribute> {
     /// returned `Symbol` and `DefId` are respectively the name and id of
     /// `TARGET`. The `Span` is that of the contents of the attribute and used
     /// for error reporting.
+    ///
+    /// `TARGET` is resolved relative to the module of the harness, so it may
+    /// also be a path into a dependency, e.g. `::other_crate::func`. The
+    /// contract attributes of such a function are read from the metadata of its
+    /// crate.
+    ///
+    /// The target of the `replacement_client` attribute of a replacement client
+    /// is interpreted the same way.
     fn interpret_stub_verified_attribute(
         &self,
     ) -> Vec<Result<(Symbol, DefId, Span), ErrorGuaranteed>> {
-        self.map
-            .get(&KaniAttributeKind::StubVerified)
-            .map_or([].as_slice(), Vec::as_slice)
+        [KaniAttributeKind::StubVerified, KaniAttributeKind::ReplacementClient]
             .iter()
+            .flat_map(|kind| self.map.get(kind).map_or([].as_slice(), Vec::as_slice))
             .map(|attr| {
                 let name = expect_key_string_value(self.tcx.sess, attr)?;
                 let ok = self.resolve_sibling(name.as_str()).map_err(|e| {
@@ -200,26 +353,32 @@ fn interpret_stub_verified_attribute(
             .collect()
     }
 
-    /// Parse and extract the `proof_for_contract(TARGET)` attribute. The
-    /// returned symbol and DefId are respectively the name and id of `TARGET`,
-    /// the span in the span for the attribute (contents).
+    /// Parse and extract the `proof_for_contract(TARGET, ...)` attribute. Each
+    /// target is stored as a separate attribute, the returned symbols and
+    /// DefIds are respectively the names and ids of the targets, the span is
+    /// the span for the attribute (contents).
     pub(crate) fn interpret_the_for_contract_attribute(
         &self,
-    ) -> Option<Result<(Symbol, DefId, Span), ErrorGuaranteed>> {
-        self.expect_maybe_one(KaniAttributeKind::ProofForContract).map(|target| {
-            let name = expect_key_string_value(self.tcx.sess, target)?;
-            self.resolve_sibling(name.as_str()).map(|ok| (name, ok, target.span)).map_err(
-                |resolve_err| {
-                    self.tcx.dcx().span_err(
-                        target.span,
-                        format!(
-                            "Failed to resolve checking function {} because {resolve_err}",
-                            name.as_str()
-                        ),
-                    )
-                },
-            )
-        })
+    ) -> Vec<Result<(Symbol, DefId, Span), ErrorGuaranteed>> {
+        self.map
+            .get(&KaniAttributeKind::ProofForContract)
+            .map_or([].as_slice(), Vec::as_slice)
+            .iter()
+            .map(|target| {
+                let name = expect_key_string_value(self.tcx.sess, target)?;
+                self.resolve_sibling(name.as_str()).map(|ok| (name, ok, target.span)).map_err(
+                    |resolve_err| {
+                        self.tcx.dcx().span_err(
+                            target.span,
+                            format!(
+                                "Failed to resolve checking function {} because {resolve_err}",
+                                name.as_str()
+                            ),
+                        )
+                    },
+                )
+            })
+            .collect()
     }
 
     /// Extract the name of the sibling function this function's contract is
@@ -236,6 +395,39 @@ pub fn inner_check(&self) -> Option<Result<DefId, ErrorGuaranteed>> {
         self.eval_sibling_attribute(KaniAttributeKind::InnerCheck)
     }
 
+    /// The hash of the complete contract and body of this function, if it has a contract.
+    pub fn contract_fingerprint(&self) -> Option<Result<Symbol, ErrorGuaranteed>> {
+        self.expect_maybe_one(KaniAttributeKind::ContractFingerprint)
+            .map(|target| expect_key_string_value(self.tcx.sess, target))
+    }
+
+    /// The source text of the clauses of the contract of this function of the given kind, in
+    /// the order they were written. Clauses that cannot be read were already reported by
+    /// [`Self::check_attributes`].
+    pub fn contract_clauses(&self, kind: ContractClauseKind) -> Vec<String> {
+        let kind = match kind {
+            ContractClauseKind::Requires => KaniAttributeKind::ContractRequires,
+            ContractClauseKind::Ensures => KaniAttributeKind::ContractEnsures,
+        };
+        self.contract_clause_values(kind)
+    }
+
+    /// The source text of the `modifies` targets of the contract of this function, in the order
+    /// they were written.
+    pub fn contract_modifies(&self) -> Vec<String> {
+        self.contract_clause_values(KaniAttributeKind::ContractModifies)
+    }
+
+    fn contract_clause_values(&self, kind: KaniAttributeKind) -> Vec<String> {
+        self.map.get(&kind).map_or(vec![], |attrs| {
+            attrs
+                .iter()
+                .filter_map(|attr| expect_key_string_value(self.tcx.sess, attr).ok())
+                .map(|value| value.to_string())
+                .collect()
+        })
+    }
+
     pub fn replaced_with(&self) -> Option<Result<Symbol, ErrorGuaranteed>> {
         self.expect_maybe_one(KaniAttributeKind::ReplacedWith)
             .map(|target| expect_key_string_value(self.tcx.sess, target))
@@ -246,9 +438,9 @@ pub fn checked_with_id(&self) -> Option<Result<DefId, ErrorGuaranteed>> {
         self.eval_sibling_attribute(KaniAttributeKind::CheckedWith)
     }
 
-    /// Find the `mod` that `self.item` is defined in, then search in the items defined in this
-    /// `mod` for an item that is named after the `name` in the `#[kanitool::<kind> = "<name>"]`
-    /// annotation on `self.item`.
+    /// Find the `mod`, `impl` or `trait` that `self.item` is defined in, then search in the items
+    /// defined in there for an item that is named after the `name` in the
+    /// `#[kanitool::<kind> = "<name>"]` annotation on `self.item`.
     ///
     /// This is similar to [`resolve_fn`] but more efficient since it only looks inside one `mod`.
     fn eval_sibling_attribute(
@@ -274,6 +466,9 @@ fn eval_sibling_attribute(
                     ItemKind::Impl(imp) => {
                         imp.items.iter().find(|it| it.ident.name == name).unwrap().id.hir_id()
                     }
+                    ItemKind::Trait(.., items) => {
+                        items.iter().find(|it| it.ident.name == name).unwrap().id.hir_id()
+                    }
                     other => panic!("Odd parent item kind {other:?}"),
                 },
                 Node::Crate(m) => find_in_mod(m),
@@ -311,26 +506,43 @@ pub(super) fn check_attributes(&self) {
             }
             match kind {
                 KaniAttributeKind::ShouldPanic => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_should_panic(self.tcx, attr);
+                    })
+                }
+                KaniAttributeKind::ExpectUnreachable => {
+                    if self.map.contains_key(&KaniAttributeKind::ShouldPanic) {
+                        local_error(
+                            "`expect_unreachable` and `should_panic` may not be used on the same function.".to_string(),
+                        );
+                    }
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| {
                         expect_no_args(self.tcx, kind, attr);
                     })
                 }
-                KaniAttributeKind::Solver => {
+                KaniAttributeKind::Slow => {
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| {
-                        parse_solver(self.tcx, attr);
+                        expect_no_args(self.tcx, kind, attr);
                     })
                 }
+                KaniAttributeKind::Solver => {
+                    parse_solvers(self.tcx, attrs);
+                }
                 KaniAttributeKind::Stub => {
                     parse_stubs(self.tcx, self.item, attrs);
                 }
-                KaniAttributeKind::Unwind => {
+                KaniAttributeKind::PointerChecks => {
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| {
-                        parse_unwind(self.tcx, attr);
+                        parse_pointer_checks(self.tcx, attr);
                     })
                 }
+                KaniAttributeKind::Unwind => {
+                    parse_unwinds(self.tcx, attrs);
+                }
                 KaniAttributeKind::Proof => {
                     if self.map.contains_key(&KaniAttributeKind::ProofForContract) {
                         local_error(
@@ -349,15 +561,28 @@ pub(super) fn check_attributes(&self) {
                             "`proof` and `proof_for_contract` may not be used on the same function.".to_string(),
                         );
                     }
+                    self.check_distinct_contract_targets(&attrs);
+                }
+                KaniAttributeKind::ProofIdempotent => {
                     expect_single(self.tcx, kind, &attrs);
                 }
                 KaniAttributeKind::StubVerified => {
                     expect_single(self.tcx, kind, &attrs);
                 }
-                KaniAttributeKind::CheckedWith | KaniAttributeKind::ReplacedWith => {
+                KaniAttributeKind::CheckedWith
+                | KaniAttributeKind::ReplacedWith
+                | KaniAttributeKind::ReplacementClient
+                | KaniAttributeKind::ContractFingerprint => {
                     self.expect_maybe_one(kind)
                         .map(|attr| expect_key_string_value(&self.tcx.sess, attr));
                 }
+                KaniAttributeKind::ContractRequires
+                | KaniAttributeKind::ContractEnsures
+                | KaniAttributeKind::ContractModifies => {
+                    attrs.iter().for_each(|attr| {
+                        let _ = expect_key_string_value(&self.tcx.sess, attr);
+                    });
+                }
                 KaniAttributeKind::IsContractGenerated => {
                     // Ignored here because this is only used by the proc macros
                     // to communicate with one another. So by the time it gets
@@ -366,6 +591,34 @@ pub(super) fn check_attributes(&self) {
                 KaniAttributeKind::Modifies => {
                     self.modifies_contract();
                 }
+                KaniAttributeKind::Decreases => {
+                    if let [_, second, ..] = attrs.as_slice() {
+                        self.tcx.dcx().span_err(
+                            second.span,
+                            "only one `decreases` clause is allowed per function",
+                        );
+                    }
+                    attrs.iter().for_each(|attr| expect_no_args(self.tcx, kind, attr))
+                }
+                KaniAttributeKind::RecursionTracker => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| expect_no_args(self.tcx, kind, attr));
+                }
+                KaniAttributeKind::Predicate => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| expect_no_args(self.tcx, kind, attr));
+                    if self.map.contains_key(&KaniAttributeKind::CheckedWith) {
+                        local_error(format!(
+                            "`{}` is a predicate and may not have a contract",
+                            self.tcx.def_path_str(self.item)
+                        ));
+                    }
+                }
+                KaniAttributeKind::Pure => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| expect_no_args(self.tcx, kind, attr));
+                    self.check_pure(attrs[0].span);
+                }
                 KaniAttributeKind::InnerCheck => {
                     self.inner_check();
                 }
@@ -373,6 +626,58 @@ pub(super) fn check_attributes(&self) {
         }
     }
 
+    /// Check that a function marked `#[kani::pure]` has a contract and that the contract has no
+    /// `modifies` clause. The functions generated for the contract inherit the attribute, they
+    /// are not checked.
+    fn check_pure(&self, span: Span) {
+        if self.map.contains_key(&KaniAttributeKind::IsContractGenerated) {
+            return;
+        }
+        if !self.map.contains_key(&KaniAttributeKind::CheckedWith)
+            && !self.map.contains_key(&KaniAttributeKind::ReplacedWith)
+        {
+            self.tcx.dcx().span_err(
+                span,
+                format!(
+                    "`{}` is marked as `#[kani::pure]` but has no contract",
+                    self.tcx.def_path_str(self.item)
+                ),
+            );
+        } else if let Some(Ok(inner_check)) = self.inner_check() {
+            if KaniAttributes::for_item(self.tcx, inner_check)
+                .map
+                .contains_key(&KaniAttributeKind::Modifies)
+            {
+                self.tcx.dcx().span_err(
+                    span,
+                    format!(
+                        "`{}` is marked as `#[kani::pure]` but has a `modifies` clause",
+                        self.tcx.def_path_str(self.item)
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Check that every target of `proof_for_contract` is only named once.
+    fn check_distinct_contract_targets(&self, attributes: &[&'tcx Attribute]) {
+        let mut targets: Vec<(Symbol, Vec<&Attribute>)> = vec![];
+        for attr in attributes {
+            let Some(target) = attr.value_str() else { continue };
+            match targets.iter_mut().find(|(name, _)| *name == target) {
+                Some((_, attrs)) => attrs.push(*attr),
+                None => targets.push((target, vec![*attr])),
+            }
+        }
+        for (target, attrs) in targets {
+            report_duplicates(
+                self.tcx,
+                &attrs,
+                format!("the contract of `{target}` is checked more than once by this harness"),
+            );
+        }
+    }
+
     /// Check that any unstable API has been enabled. Otherwise, emit an error.
     ///
     /// TODO: Improve error message by printing the span of the harness instead of the definition.
@@ -384,20 +689,23 @@ pub fn check_unstable_features(&self, enabled_features: &[String]) {
             return;
         }
 
-        // If the `function-contracts` unstable feature is not enabled then no
-        // function should use any of those APIs.
-        if !enabled_features.iter().any(|feature| feature == "function-contracts") {
-            for kind in self.map.keys().copied().filter(|a| a.demands_function_contract_use()) {
-                let msg = format!(
-                    "Using the {} attribute requires activating the unstable `function-contracts` feature",
-                    kind.as_ref()
-                );
-                if let Some(attr) = self.map.get(&kind).unwrap().first() {
-                    self.tcx.dcx().span_err(attr.span, msg);
-                } else {
-                    self.tcx.dcx().err(msg);
-                }
+        // Attributes that belong to an unstable API, e.g. function contracts,
+        // may only be used if its feature is enabled.
+        for (kind, attrs) in self.map.iter() {
+            let Some(feature) = kind.required_unstable_feature() else { continue };
+            if enabled_features.iter().any(|enabled| enabled == feature.as_ref()) {
+                continue;
             }
+            let msg = format!(
+                "Using the {} attribute requires activating the unstable `{feature}` feature",
+                kind.as_ref()
+            );
+            let err = if let Some(attr) = attrs.first() {
+                self.tcx.dcx().struct_span_err(attr.span, msg)
+            } else {
+                self.tcx.dcx().struct_err(msg)
+            };
+            err.with_help(format!("use `-Z {feature}` to enable it")).emit();
         }
 
         if let Some(unstable_attrs) = self.map.get(&KaniAttributeKind::Unstable) {
@@ -433,11 +741,28 @@ fn report_unstable_forbidden(&self, unstable_attr: &UnstableAttribute) -> ErrorG
 
     /// Is this item a harness? (either `proof` or `proof_for_contract`
     /// attribute are present)
-    fn is_harness(&self) -> bool {
+    pub fn is_harness(&self) -> bool {
         self.map.contains_key(&KaniAttributeKind::Proof)
             || self.map.contains_key(&KaniAttributeKind::ProofForContract)
     }
 
+    /// Is this item the replacement client generated for a function with a
+    /// contract? It is only verified with `--enforce-and-replace`.
+    pub fn is_replacement_client(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::ReplacementClient)
+    }
+
+    /// The mode of the `#[kani::should_panic]` attribute, if the item has one.
+    pub fn should_panic(&self) -> Option<ShouldPanicMode> {
+        let attr = self.map.get(&KaniAttributeKind::ShouldPanic)?.first()?;
+        parse_should_panic(self.tcx, attr).map(|(mode, _)| mode)
+    }
+
+    /// Whether the item has the `#[kani::expect_unreachable]` attribute.
+    pub fn expect_unreachable(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::ExpectUnreachable)
+    }
+
     /// Extract harness attributes for a given `def_id`.
     ///
     /// We only extract attributes for harnesses that are local to the current crate.
@@ -448,30 +773,66 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
             panic!("Expected a local item, but got: {:?}", self.item);
         };
         trace!(?self, "extract_harness_attributes");
+        if self.is_replacement_client() {
+            return self.replacement_client_attributes();
+        }
         assert!(self.is_harness());
         self.map.iter().fold(HarnessAttributes::default(), |mut harness, (kind, attributes)| {
             match kind {
-                KaniAttributeKind::ShouldPanic => harness.should_panic = true,
+                KaniAttributeKind::ShouldPanic => {
+                    if let Some((mode, expected)) = parse_should_panic(self.tcx, attributes[0]) {
+                        harness.should_panic = Some(mode);
+                        harness.should_panic_expected = expected;
+                    }
+                }
+                KaniAttributeKind::ExpectUnreachable => harness.expect_unreachable = true,
+                KaniAttributeKind::Slow => harness.slow = true,
                 KaniAttributeKind::Solver => {
-                    harness.solver = parse_solver(self.tcx, attributes[0]);
+                    harness.solver = parse_solvers(self.tcx, attributes);
                 }
                 KaniAttributeKind::Stub => {
                     harness.stubs.extend_from_slice(&parse_stubs(self.tcx, self.item, attributes));
                 }
                 KaniAttributeKind::Unwind => {
-                    harness.unwind_value = parse_unwind(self.tcx, attributes[0])
+                    (harness.unwind_value, harness.unwind_loops) =
+                        parse_unwinds(self.tcx, attributes)
+                }
+                KaniAttributeKind::PointerChecks => {
+                    harness.pointer_checks = parse_pointer_checks(self.tcx, attributes[0])
                 }
                 KaniAttributeKind::Proof => harness.proof = true,
                 KaniAttributeKind::ProofForContract => self.handle_proof_for_contract(&mut harness),
+                KaniAttributeKind::ProofIdempotent => {
+                    self.handle_proof_idempotent(attributes[0], &mut harness)
+                }
                 KaniAttributeKind::StubVerified => self.handle_stub_verified(&mut harness),
                 KaniAttributeKind::Unstable => {
                     // Internal attribute which shouldn't exist here.
                     unreachable!()
                 }
+                KaniAttributeKind::Predicate => {
+                    self.tcx.dcx().span_err(
+                        self.tcx.def_span(self.item),
+                        "a harness cannot be used as a contract predicate",
+                    );
+                }
+                KaniAttributeKind::Pure => {
+                    self.tcx.dcx().span_err(
+                        self.tcx.def_span(self.item),
+                        "a harness cannot be marked as `#[kani::pure]`",
+                    );
+                }
                 KaniAttributeKind::CheckedWith
                 | KaniAttributeKind::IsContractGenerated
                 | KaniAttributeKind::Modifies
+                | KaniAttributeKind::Decreases
+                | KaniAttributeKind::RecursionTracker
                 | KaniAttributeKind::InnerCheck
+                | KaniAttributeKind::ContractFingerprint
+                | KaniAttributeKind::ContractRequires
+                | KaniAttributeKind::ContractEnsures
+                | KaniAttributeKind::ContractModifies
+                | KaniAttributeKind::ReplacementClient
                 | KaniAttributeKind::ReplacedWith => {
                     self.tcx.dcx().span_err(self.tcx.def_span(self.item), format!("Contracts are not supported on harnesses. (Found the kani-internal contract attribute `{}`)", kind.as_ref()));
                 }
@@ -480,33 +841,77 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
         })
     }
 
+    /// The attributes of a replacement client, which is verified as a `proof` harness that
+    /// replaces its target by the contract of the target, see
+    /// [`KaniAttributeKind::ReplacementClient`].
+    ///
+    /// The client inherits the attributes of its target, which do not apply to the client and
+    /// are ignored.
+    fn replacement_client_attributes(&self) -> HarnessAttributes {
+        let mut harness = HarnessAttributes { proof: true, ..HarnessAttributes::default() };
+        self.handle_stub_verified(&mut harness);
+        harness
+    }
+
     fn handle_proof_for_contract(&self, harness: &mut HarnessAttributes) {
-        let dcx = self.tcx.dcx();
-        let (name, id, span) = match self.interpret_the_for_contract_attribute() {
-            None => unreachable!(
-                "impossible, was asked to handle `proof_for_contract` but didn't find such an attribute."
-            ),
-            Some(Err(_)) => return, // This error was already emitted
-            Some(Ok(values)) => values,
-        };
-        let Some(Ok(replacement_name)) = KaniAttributes::for_item(self.tcx, id).checked_with()
-        else {
-            dcx.struct_span_err(
-                span,
-                format!(
-                    "Failed to check contract: Function `{}` has no contract.",
-                    self.item_name(),
-                ),
-            )
-            .with_span_note(self.tcx.def_span(id), "Try adding a contract to this function.")
-            .emit();
-            return;
+        for contract in self.interpret_the_for_contract_attribute() {
+            let Ok((name, id, span)) = contract else {
+                // This error was already emitted
+                continue;
+            };
+            let target = KaniAttributes::for_item(self.tcx, id);
+            if target.check_has_contract(span, KaniAttributeKind::ProofForContract).is_err() {
+                continue;
+            }
+            if self.tcx.is_foreign_item(id) {
+                self.tcx
+                    .dcx()
+                    .struct_span_err(
+                        span,
+                        format!(
+                            "Failed to check contract: Function `{}` has no body to check the \
+                            contract against.",
+                            target.item_name()
+                        ),
+                    )
+                    .with_span_note(self.tcx.def_span(id), "The function is declared here.")
+                    .with_help(
+                        "The contract of a function without a body can only be used with \
+                        `stub_verified`.",
+                    )
+                    .emit();
+                continue;
+            }
+            harness.proof_for_contract.push(self.tcx.def_path_str(id));
+            let Some(Ok(replacement_name)) = target.checked_with() else {
+                // Errors in the attribute itself were already emitted.
+                continue;
+            };
+            harness.stubs.push(self.stub_for_relative_item(name, replacement_name));
+        }
+    }
+
+    fn handle_proof_idempotent(&self, target: &Attribute, harness: &mut HarnessAttributes) {
+        let Ok(name) = expect_key_string_value(self.tcx.sess, target) else { return };
+        let id = match self.resolve_sibling(name.as_str()) {
+            Ok(id) => id,
+            Err(resolve_err) => {
+                self.tcx.dcx().span_err(
+                    target.span,
+                    format!("Failed to resolve function {} because {resolve_err}", name.as_str()),
+                );
+                return;
+            }
         };
-        harness.stubs.push(self.stub_for_relative_item(name, replacement_name));
+        harness.proof_idempotent = Some(self.tcx.def_path_str(id));
+        // Substitute the contract check so that the preconditions are assumed
+        // for both applications.
+        if let Some(Ok(replacement_name)) = KaniAttributes::for_item(self.tcx, id).checked_with() {
+            harness.stubs.push(self.stub_for_relative_item(name, replacement_name));
+        }
     }
 
     fn handle_stub_verified(&self, harness: &mut HarnessAttributes) {
-        let dcx = self.tcx.dcx();
         for contract in self.interpret_stub_verified_attribute() {
             let Ok((name, def_id, span)) = contract else {
                 // This error has already been emitted so we can ignore it now.
@@ -514,33 +919,212 @@ fn handle_stub_verified(&self, harness: &mut HarnessAttributes) {
                 // optimistically forge on and try to find more errors.
                 continue;
             };
-            let replacement_name = match KaniAttributes::for_item(self.tcx, def_id).replaced_with()
+            let target = KaniAttributes::for_item(self.tcx, def_id);
+            if target.check_has_contract(span, KaniAttributeKind::StubVerified).is_err() {
+                continue;
+            }
+            if returns_impl_trait(self.tcx, def_id) {
+                self.tcx
+                    .dcx()
+                    .struct_span_err(
+                        span,
+                        format!(
+                            "Failed to generate verified stub: Function `{}` returns \
+                            `impl Trait`, so its result cannot be replaced by its contract.",
+                            target.item_name()
+                        ),
+                    )
+                    .with_span_note(self.tcx.def_span(def_id), "The function is declared here.")
+                    .with_help("The contract can still be checked with `proof_for_contract`.")
+                    .emit();
+                continue;
+            }
+            let Some(Ok(replacement_name)) = target.replaced_with() else {
+                continue;
+            };
+            harness.verified_stubs.push(self.tcx.def_path_str(def_id));
+            harness.stubs.push(self.stub_for_relative_item(name, replacement_name))
+        }
+    }
+
+    /// Replace every function of the local crate that has a contract by its contract in this
+    /// `proof_for_contract` harness, except the functions under contract and the ones that
+    /// `stub_verified` already replaces. Used for `--replace-all-contracts`.
+    ///
+    /// Only the functions that can be named by a path from the crate root are replaced, which
+    /// excludes e.g. methods of trait implementations and functions declared inside other
+    /// functions. Replacing a function that the harness does not reach has no effect.
+    pub fn replace_all_contracts(&self, harness: &mut HarnessAttributes) {
+        if harness.proof_for_contract.is_empty() {
+            return;
+        }
+        let current_module = self.tcx.parent_module_from_def_id(self.item.expect_local());
+        let resolves =
+            |path: &str| resolve_fn(self.tcx, current_module.to_local_def_id(), path).is_ok();
+        for def_id in self.tcx.hir().body_owners().map(|local_def_id| local_def_id.to_def_id()) {
+            if !matches!(self.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn) {
+                continue;
+            }
+            let function = self.tcx.def_path_str(def_id);
+            if harness.proof_for_contract.contains(&function)
+                || harness.verified_stubs.contains(&function)
             {
-                None => {
-                    dcx.struct_span_err(
+                continue;
+            }
+            let Some(Ok(replacement_name)) =
+                KaniAttributes::for_item(self.tcx, def_id).replaced_with()
+            else {
+                continue;
+            };
+            if returns_impl_trait(self.tcx, def_id) {
+                continue;
+            }
+            let original = format!("crate::{function}");
+            let parent = original.rsplit_once("::").unwrap().0;
+            let replacement = format!("{parent}::{replacement_name}");
+            if !resolves(&original) || !resolves(&replacement) {
+                debug!(?function, "replace_all_contracts: cannot name the function");
+                continue;
+            }
+            harness.verified_stubs.push(function);
+            harness.stubs.push(Stub { original, replacement });
+        }
+    }
+
+    /// Check that this item carries a complete contract, i.e. that at least one of the
+    /// `requires`, `ensures` or `modifies` clauses was expanded on it.
+    ///
+    /// Every contract clause generates both the `checked_with` and the `replaced_with`
+    /// attributes, so the absence of both means the function has no clause at all, whereas the
+    /// absence of only one indicates that the contract attributes were not expanded correctly,
+    /// unless the item is a declaration without a body, which has no check function.
+    /// A complete contract is also checked with [`Self::check_contract_targets`].
+    /// `span` is the location of the `requested_by` harness attribute that requested the
+    /// contract, either `proof_for_contract` or `stub_verified`.
+    fn check_has_contract(
+        &self,
+        span: Span,
+        requested_by: KaniAttributeKind,
+    ) -> Result<(), ErrorGuaranteed> {
+        let dcx = self.tcx.dcx();
+        let (action, alternative) = if requested_by == KaniAttributeKind::StubVerified {
+            (
+                "Failed to generate verified stub",
+                format!(
+                    ", or use the unsound `{}` attribute instead",
+                    KaniAttributeKind::Stub.as_ref()
+                ),
+            )
+        } else {
+            ("Failed to check contract", String::new())
+        };
+        let has_check = self.map.contains_key(&KaniAttributeKind::CheckedWith);
+        let has_replace = self.map.contains_key(&KaniAttributeKind::ReplacedWith);
+        match (has_check, has_replace) {
+            (true, true) => self.check_contract_targets(span),
+            // A function without a body only has a replace function.
+            (false, true) if self.tcx.is_foreign_item(self.item) => {
+                self.check_contract_targets(span)
+            }
+            (false, false) => Err(dcx
+                .struct_span_err(
+                    span,
+                    format!("{action}: Function `{}` has no contract.", self.item_name()),
+                )
+                .with_span_note(
+                    self.tcx.def_span(self.item),
+                    format!("`{}` is defined here without a contract.", self.item_name()),
+                )
+                .with_help(format!(
+                    "Add a `#[kani::requires(...)]`, `#[kani::ensures(...)]` or \
+                    `#[kani::modifies(...)]` attribute to `{}` to give it a contract{alternative}.",
+                    self.item_name()
+                ))
+                .emit()),
+            (has_check, _) => {
+                let missing = if has_check {
+                    KaniAttributeKind::ReplacedWith
+                } else {
+                    KaniAttributeKind::CheckedWith
+                };
+                Err(dcx
+                    .struct_span_err(
                         span,
                         format!(
-                            "Failed to generate verified stub: Function `{}` has no contract.",
-                            self.item_name(),
+                            "{action}: The contract of function `{}` is incomplete.",
+                            self.item_name()
                         ),
                     )
                     .with_span_note(
-                        self.tcx.def_span(def_id),
+                        self.tcx.def_span(self.item),
                         format!(
-                            "Try adding a contract to this function or use the unsound `{}` attribute instead.",
-                            KaniAttributeKind::Stub.as_ref(),
-                        )
+                            "Missing the kani-internal `{}` attribute. Contract attributes may \
+                            not have been expanded by Kani.",
+                            missing.as_ref()
+                        ),
                     )
-                    .emit();
-                    continue;
-                }
-                Some(Ok(replacement_name)) => replacement_name,
-                Some(Err(_)) => continue,
-            };
-            harness.stubs.push(self.stub_for_relative_item(name, replacement_name))
+                    .emit())
+            }
         }
     }
 
+    /// Check that the `checked_with`, `replaced_with` and `inner_check` attributes of this item
+    /// name functions that were generated for its contract, i.e. that every name consists of the
+    /// name of this item, the purpose of the function and a hash that all of them share.
+    ///
+    /// The names are generated by the contract macros and only resolved by the compiler, so a
+    /// mismatch means that the two are out of sync and verification would silently use the
+    /// wrong function. `span` is the location of the harness attribute that requested the
+    /// contract.
+    fn check_contract_targets(&self, span: Span) -> Result<(), ErrorGuaranteed> {
+        let name = self.item_name();
+        let targets = [
+            (KaniAttributeKind::CheckedWith, "recursion_wrapper"),
+            (KaniAttributeKind::ReplacedWith, "replace"),
+            (KaniAttributeKind::InnerCheck, "wrapper"),
+        ]
+        .into_iter()
+        .filter_map(|(kind, purpose)| {
+            let target = expect_key_string_value(self.tcx.sess, self.expect_maybe_one(kind)?);
+            Some(target.map(|target| (kind, purpose, target)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+        let hashes = targets
+            .iter()
+            .map(|(_, purpose, target)| {
+                target
+                    .as_str()
+                    .strip_prefix(&format!("{name}_{purpose}_"))
+                    .filter(|hash| !hash.is_empty() && hash.chars().all(|c| c.is_ascii_hexdigit()))
+            })
+            .collect::<Vec<_>>();
+        let consistent = hashes.iter().all(|hash| hash.is_some() && *hash == hashes[0]);
+        if consistent {
+            return Ok(());
+        }
+        let found = targets
+            .iter()
+            .map(|(kind, _, target)| format!("`{} = \"{target}\"`", kind.as_ref()))
+            .collect::<Vec<_>>();
+        Err(self
+            .tcx
+            .dcx()
+            .struct_span_err(
+                span,
+                format!(
+                    "Internal error: the contract of `{name}` refers to generated functions that \
+                    do not belong together: {}",
+                    found.join(", ")
+                ),
+            )
+            .with_span_note(self.tcx.def_span(self.item), "The contract is declared here.")
+            .with_help(
+                "This is a bug in the Kani contract macros, please report it at \
+                https://github.com/model-checking/kani/issues",
+            )
+            .emit())
+    }
+
     fn item_name(&self) -> Symbol {
         self.tcx.item_name(self.item)
     }
@@ -553,8 +1137,21 @@ fn check_proof_attribute(&self, proof_attribute: &Attribute) {
         if tcx.def_kind(self.item) != DefKind::Fn {
             tcx.dcx().span_err(span, "the `proof` attribute can only be applied to functions");
         } else if tcx.generics_of(self.item).requires_monomorphization(tcx) {
+            let name = self.item_name();
             tcx.dcx()
-                .span_err(span, "the `proof` attribute cannot be applied to generic functions");
+                .struct_span_err(
+                    span,
+                    "the `proof` attribute cannot be applied to generic functions",
+                )
+                .with_span_note(
+                    tcx.def_span(self.item),
+                    format!("`{name}` has the generic parameters `{}`", self.generic_params()),
+                )
+                .with_help(format!(
+                    "call `{name}` with concrete generic arguments from a separate harness that \
+                    is not generic"
+                ))
+                .emit();
         } else {
             let instance = Instance::mono(tcx, self.item);
             if !super::fn_abi(tcx, instance).args.is_empty() {
@@ -563,6 +1160,25 @@ fn check_proof_attribute(&self, proof_attribute: &Attribute) {
         }
     }
 
+    /// The type and const parameters of this item as they are declared, e.g. `T, const N: usize`.
+    /// Lifetimes are left out since they do not prevent an item from being a harness.
+    fn generic_params(&self) -> String {
+        let generics = self.tcx.generics_of(self.item);
+        (0..generics.count())
+            .map(|idx| generics.param_at(idx, self.tcx))
+            .filter_map(|param| match param.kind {
+                GenericParamDefKind::Lifetime => None,
+                GenericParamDefKind::Type { .. } => Some(param.name.to_string()),
+                GenericParamDefKind::Const { .. } => Some(format!(
+                    "const {}: {}",
+                    param.name,
+                    self.tcx.type_of(param.def_id).instantiate_identity()
+                )),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     fn stub_for_relative_item(&self, anchor: Symbol, replacement: Symbol) -> Stub {
         let local_id = self.item.expect_local();
         let current_module = self.tcx.parent_module_from_def_id(local_id);
@@ -575,21 +1191,86 @@ fn stub_for_relative_item(&self, anchor: Symbol, replacement: Symbol) -> Stub {
         Stub { original: original_str.to_string(), replacement }
     }
 
-    /// Parse and interpret the `kanitool::modifies(var1, var2, ...)` annotation into the vector
-    /// `[var1, var2, ...]`.
-    pub fn modifies_contract(&self) -> Option<Vec<Local>> {
+    /// Parse and interpret the `kanitool::modifies(var1 = (target1), var2 = (target2), ...)`
+    /// annotations into the vector `[(var1, "target1"), (var2, "target2"), ...]`, where `target`
+    /// is the target as written by the user.
+    ///
+    /// The targets of all clauses are sorted by local and deduplicated, so the order of the
+    /// resulting assigns clause does not depend on the order of the attributes.
+    pub fn modifies_contract(&self) -> Option<Vec<(Local, String)>> {
         let local_def_id = self.item.expect_local();
         self.map.get(&KaniAttributeKind::Modifies).map(|attr| {
-            attr.iter()
+            let mut places: Vec<(Local, String)> = attr
+                .iter()
                 .flat_map(|clause| match &clause.get_normal_item().args {
                     AttrArgs::Delimited(lvals) => {
                         parse_modify_values(self.tcx, local_def_id, &lvals.tokens)
                     }
                     _ => unreachable!(),
                 })
-                .collect()
+                .collect();
+            places.sort_unstable_by_key(|(local, _)| *local);
+            places.dedup_by_key(|(local, _)| *local);
+            places
         })
     }
+
+    /// Whether this inner check function belongs to a contract with a
+    /// `decreases` clause.
+    pub fn has_decreases(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::Decreases)
+    }
+
+    /// The indices of the arguments whose pointee is a target of the `modifies` clauses of the
+    /// contract, e.g. `0` for `modifies(*out)` on `fn fill(out: *mut u32)`.
+    ///
+    /// Works for the function with the contract and for the functions generated for it, which
+    /// all take the same arguments and refer to the inner check function that carries the
+    /// `modifies` clauses. The check function passes a pointer to every target to the inner
+    /// check function, and each of these pointers is traced back to an argument in its MIR.
+    pub fn modifies_pointer_args(&self) -> Vec<usize> {
+        if !self.item.is_local() {
+            return vec![];
+        }
+        let inner_check = if self.map.contains_key(&KaniAttributeKind::Modifies) {
+            Some(self.item)
+        } else {
+            self.inner_check().and_then(Result::ok)
+        };
+        let Some(inner_check) = inner_check else { return vec![] };
+        let targets =
+            KaniAttributes::for_item(self.tcx, inner_check).modifies_contract().unwrap_or_default();
+        if targets.is_empty() {
+            return vec![];
+        }
+        // Only the check function calls the inner check function.
+        let Some((body, args)) = self
+            .tcx
+            .hir()
+            .body_owners()
+            .map(LocalDefId::to_def_id)
+            .filter(|def_id| {
+                has_kani_attribute(self.tcx, *def_id, |kind| kind == KaniAttributeKind::InnerCheck)
+            })
+            .find_map(|def_id| {
+                let body = self.tcx.optimized_mir(def_id);
+                calls_of(body, inner_check).next().map(|args| (body, args))
+            })
+        else {
+            return vec![];
+        };
+        targets
+            .iter()
+            .filter_map(|(local, _)| args.get(local.checked_sub(1)?))
+            .filter_map(|arg| traced_argument(body, &arg.node))
+            .collect()
+    }
+
+    /// Whether this static is the recursion tracker of a contract, see
+    /// [`KaniAttributeKind::RecursionTracker`].
+    pub fn is_recursion_tracker(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::RecursionTracker)
+    }
 }
 
 /// Pattern macro for the comma token used in attributes.
@@ -600,13 +1281,14 @@ macro_rules! comma_tok {
 }
 
 /// Parse the token stream inside an attribute (like `kanitool::modifies`) as a comma separated
-/// sequence of function parameter names on `local_def_id` (must refer to a function). Then
-/// translates the names into [`Local`]s.
+/// sequence of function parameter names on `local_def_id` (must refer to a function), each
+/// followed by `= (target)`. Then translates the names into [`Local`]s and the targets into the
+/// source text they were written as.
 fn parse_modify_values<'a>(
     tcx: TyCtxt<'a>,
     local_def_id: LocalDefId,
     t: &'a TokenStream,
-) -> impl Iterator<Item = Local> + 'a {
+) -> impl Iterator<Item = (Local, String)> + 'a {
     let mir = tcx.optimized_mir(local_def_id);
     let mut iter = t.trees();
     std::iter::from_fn(move || {
@@ -619,14 +1301,25 @@ fn parse_modify_values<'a>(
                 if let TokenKind::Ident(id, _) = &token.kind {
                     let hir = tcx.hir();
                     let bid = hir.body_owned_by(local_def_id);
-                    Some(
-                        hir.body_param_names(bid)
-                            .zip(mir.args_iter())
-                            .find(|(name, _decl)| name.name == *id)
-                            .unwrap()
-                            .1
-                            .as_usize(),
-                    )
+                    let param = hir
+                        .body_param_names(bid)
+                        .zip(mir.args_iter())
+                        .find(|(name, _decl)| name.name == *id);
+                    if param.is_none() {
+                        let names = hir
+                            .body_param_names(bid)
+                            .map(|name| format!("`{name}`"))
+                            .collect::<Vec<_>>();
+                        let mut err = tcx.dcx().struct_span_err(
+                            tree.span(),
+                            format!("`{id}` is not a parameter of this function"),
+                        );
+                        if !names.is_empty() {
+                            err = err.with_help(format!("the parameters are {}", names.join(", ")));
+                        }
+                        err.emit();
+                    }
+                    param.map(|(_name, local)| local.as_usize())
                 } else {
                     wrong_token_err();
                     None
@@ -637,6 +1330,23 @@ fn parse_modify_values<'a>(
                 None
             }
         };
+        let source = match (iter.next(), iter.next()) {
+            (
+                Some(TokenTree::Token(Token { kind: TokenKind::Eq, .. }, _)),
+                Some(TokenTree::Delimited(.., target)),
+            ) => Some(rustc_ast_pretty::pprust::tts_to_string(target)),
+            (Some(unexpected), _) => {
+                tcx.sess
+                    .parse_sess
+                    .dcx
+                    .span_err(unexpected.span(), "Unexpected token, expected `= (target)`");
+                None
+            }
+            (None, _) => {
+                tcx.sess.parse_sess.dcx.span_err(tree.span(), "Expected `= (target)`");
+                None
+            }
+        };
         match iter.next() {
             None | Some(comma_tok!()) => (),
             Some(not_comma) => {
@@ -647,7 +1357,76 @@ fn parse_modify_values<'a>(
                 iter.by_ref().skip_while(|t| !matches!(t, comma_tok!())).count();
             }
         }
-        result
+        result.zip(source)
+    })
+}
+
+/// The arguments of every call to `callee` in `body`.
+fn calls_of<'a, 'tcx>(
+    body: &'a mir::Body<'tcx>,
+    callee: DefId,
+) -> impl Iterator<Item = &'a [Spanned<mir::Operand<'tcx>>]> {
+    body.basic_blocks.iter().filter_map(move |block| match &block.terminator().kind {
+        mir::TerminatorKind::Call { func, args, .. }
+            if func.const_fn_def().is_some_and(|(def_id, _)| def_id == callee) =>
+        {
+            Some(args.as_slice())
+        }
+        _ => None,
+    })
+}
+
+/// The index of the argument of `body` that the pointer `operand`, passed to the inner check
+/// function, is derived from, if any.
+///
+/// The pointer is the result of `kani::internal::Pointer::decouple_lifetime`, unless that call
+/// was inlined, and is followed back from the argument of that call through the copies, casts and
+/// borrows of the places it is assigned from. A borrow of a place behind an argument, like
+/// `&mut (*out).field`, is derived from that argument.
+fn traced_argument(body: &mir::Body, operand: &mir::Operand) -> Option<usize> {
+    let mut local = operand.place()?.local;
+    if let Some(pointer) = call_argument(body, local) {
+        local = pointer;
+    }
+    // Every step moves to a local that is assigned before, the bound only guards against cycles.
+    for _ in 0..body.local_decls.len() {
+        if (1..=body.arg_count).contains(&local.as_usize()) {
+            return Some(local.as_usize() - 1);
+        }
+        local = assigned_from(body, local)?;
+    }
+    None
+}
+
+/// The local passed as the first argument to the call that returns the local `local` of `body`.
+fn call_argument(body: &mir::Body, local: mir::Local) -> Option<mir::Local> {
+    body.basic_blocks.iter().find_map(|block| match &block.terminator().kind {
+        mir::TerminatorKind::Call { destination, args, .. }
+            if destination.local == local && destination.projection.is_empty() =>
+        {
+            args.first()?.node.place().map(|place| place.local)
+        }
+        _ => None,
+    })
+}
+
+/// The local whose value or place the local `local` of `body` is assigned from.
+fn assigned_from(body: &mir::Body, local: mir::Local) -> Option<mir::Local> {
+    body.basic_blocks.iter().flat_map(|block| &block.statements).find_map(|stmt| {
+        let mir::StatementKind::Assign(assign) = &stmt.kind else { return None };
+        let (place, rvalue) = &**assign;
+        if place.local != local || !place.projection.is_empty() {
+            return None;
+        }
+        match rvalue {
+            mir::Rvalue::Use(operand) | mir::Rvalue::Cast(_, operand, _) => {
+                operand.place().map(|place| place.local)
+            }
+            mir::Rvalue::Ref(_, _, place)
+            | mir::Rvalue::AddressOf(_, place)
+            | mir::Rvalue::CopyForDeref(place) => Some(place.local),
+            _ => None,
+        }
     })
 }
 
@@ -668,6 +1447,12 @@ pub fn is_function_contract_generated(tcx: TyCtxt, def_id: DefId) -> bool {
     has_kani_attribute(tcx, def_id, KaniAttributeKind::is_function_contract)
 }
 
+/// Test if this function is a contract predicate, i.e. it carries the
+/// `#[kani::predicate]` attribute.
+pub fn is_predicate(tcx: TyCtxt, def_id: DefId) -> bool {
+    has_kani_attribute(tcx, def_id, |a| matches!(a, KaniAttributeKind::Predicate))
+}
+
 /// Same as [`KaniAttributes::is_harness`] but more efficient because less
 /// attribute parsing is performed.
 pub fn is_proof_harness(tcx: TyCtxt, instance: InstanceStable) -> bool {
@@ -677,6 +1462,13 @@ pub fn is_proof_harness(tcx: TyCtxt, instance: InstanceStable) -> bool {
     })
 }
 
+/// Test if this function is a replacement client, see
+/// [`KaniAttributes::is_replacement_client`].
+pub fn is_replacement_client(tcx: TyCtxt, instance: InstanceStable) -> bool {
+    let def_id = rustc_internal::internal(instance.def.def_id());
+    has_kani_attribute(tcx, def_id, |a| matches!(a, KaniAttributeKind::ReplacementClient))
+}
+
 /// Does this `def_id` have `#[rustc_test_marker]`?
 pub fn is_test_harness_description(tcx: TyCtxt, item: impl CrateDef) -> bool {
     let def_id = rustc_internal::internal(item.def_id());
@@ -731,6 +1523,7 @@ fn expect_key_string_value(
     }
 }
 
+/// Return the first of `attributes` and report an error if there is more than one.
 fn expect_single<'a>(
     tcx: TyCtxt,
     kind: KaniAttributeKind,
@@ -739,15 +1532,30 @@ fn expect_single<'a>(
     let attr = attributes
         .first()
         .expect(&format!("expected at least one attribute {} in {attributes:?}", kind.as_ref()));
-    if attributes.len() > 1 {
-        tcx.dcx().span_err(
-            attr.span,
-            format!("only one '#[kani::{}]' attribute is allowed per harness", kind.as_ref()),
-        );
-    }
+    report_duplicates(
+        tcx,
+        attributes,
+        format!("only one '#[kani::{}]' attribute is allowed per harness", kind.as_ref()),
+    );
     attr
 }
 
+/// Report `msg` if there is more than one of `attributes`. The error points at the first
+/// attribute and has a note for each duplicate, like rustc does for duplicate attributes.
+fn report_duplicates(tcx: TyCtxt, attributes: &[&Attribute], msg: String) {
+    if let [first, duplicates @ ..] = attributes {
+        if !duplicates.is_empty() {
+            let err = tcx.dcx().struct_span_err(first.span, msg);
+            duplicates
+                .iter()
+                .fold(err, |err, duplicate| {
+                    err.with_span_note(duplicate.span, "attribute also specified here")
+                })
+                .emit();
+        }
+    }
+}
+
 /// Attribute used to mark a Kani lib API unstable.
 #[derive(Debug)]
 struct UnstableAttribute {
@@ -821,33 +1629,123 @@ fn expect_no_args(tcx: TyCtxt, kind: KaniAttributeKind, attr: &Attribute) {
     }
 }
 
-/// Return the unwind value from the given attribute.
-fn parse_unwind(tcx: TyCtxt, attr: &Attribute) -> Option<u32> {
-    // Get Attribute value and if it's not none, assign it to the metadata
-    match parse_integer(attr) {
-        None => {
-            // There are no integers or too many arguments given to the attribute
-            tcx.dcx().span_err(
-                attr.span,
-                "invalid argument for `unwind` attribute, expected an integer",
-            );
+/// The bound of a `#[kani::unwind]` attribute.
+enum UnwindBound {
+    /// `#[kani::unwind(<value>)]`, which applies to every loop of the harness.
+    Global(u32),
+    /// `#[kani::unwind(loop_id = <id>, value = <value>)]`, which applies to a single loop.
+    Loop(String, u32),
+}
+
+/// Parse the `#[kani::unwind]` attributes of a harness into the bound for every loop, of which
+/// there may be only one, and the bounds of individual loops.
+fn parse_unwinds(tcx: TyCtxt, attributes: &[&Attribute]) -> (Option<u32>, Vec<(String, u32)>) {
+    let mut global = None;
+    let mut global_attrs = Vec::new();
+    let mut loops: Vec<(String, u32)> = Vec::new();
+    for attr in attributes {
+        match parse_unwind(tcx, attr) {
+            Some(UnwindBound::Global(value)) => {
+                global.get_or_insert(value);
+                global_attrs.push(*attr);
+            }
+            Some(UnwindBound::Loop(id, value)) => {
+                if loops.iter().any(|(other, _)| *other == id) {
+                    tcx.dcx().span_err(
+                        attr.span,
+                        format!("the unwind bound of loop `{id}` is given more than once"),
+                    );
+                } else {
+                    loops.push((id, value));
+                }
+            }
+            None => {}
+        }
+    }
+    report_duplicates(
+        tcx,
+        &global_attrs,
+        "only one '#[kani::unwind]' attribute without a `loop_id` is allowed per harness"
+            .to_string(),
+    );
+    (global, loops)
+}
+
+/// Return the unwind bound from the given attribute, either a single integer or a `loop_id` and a
+/// `value`. Emits an error and returns `None` if the arguments are invalid.
+fn parse_unwind(tcx: TyCtxt, attr: &Attribute) -> Option<UnwindBound> {
+    let to_u32 = |value: u128| match value.try_into() {
+        Ok(val) => Some(val),
+        Err(_) => {
+            tcx.dcx().span_err(attr.span, "value above maximum permitted value - u32::MAX");
             None
         }
-        Some(unwind_integer_value) => {
-            if let Ok(val) = unwind_integer_value.try_into() {
-                Some(val)
-            } else {
-                tcx.dcx().span_err(attr.span, "value above maximum permitted value - u32::MAX");
-                None
+    };
+    // A single integer is the bound of every loop.
+    if let Some(unwind_integer_value) = parse_integer(attr) {
+        return to_u32(unwind_integer_value).map(UnwindBound::Global);
+    }
+    let attr_args = attr.meta_item_list().unwrap_or_default();
+    let is_loop_bound = attr_args.iter().any(|arg| {
+        arg.meta_item()
+            .is_some_and(|meta_item| matches!(meta_item.kind, MetaItemKind::NameValue(_)))
+    });
+    if !is_loop_bound {
+        // There are no integers or too many arguments given to the attribute
+        tcx.dcx()
+            .span_err(attr.span, "invalid argument for `unwind` attribute, expected an integer");
+        return None;
+    }
+    let invalid_loop_err = || {
+        tcx.dcx().span_err(
+            attr.span,
+            "invalid argument for `unwind` attribute, expected `loop_id = <id>, value = <integer>`",
+        );
+    };
+    let mut loop_id = None;
+    let mut value = None;
+    for arg in attr_args {
+        let Some(meta_item) = arg.meta_item() else {
+            invalid_loop_err();
+            return None;
+        };
+        let name = meta_item.ident().map(|ident| ident.name);
+        match (name.as_ref().map(Symbol::as_str), &meta_item.kind) {
+            (Some("loop_id"), MetaItemKind::NameValue(lit)) if loop_id.is_none() => {
+                loop_id = match lit.kind {
+                    LitKind::Int(id, ..) => Some(id.to_string()),
+                    LitKind::Str(id, _) => Some(id.to_string()),
+                    _ => None,
+                };
+                if loop_id.is_none() {
+                    invalid_loop_err();
+                    return None;
+                }
+            }
+            (Some("value"), MetaItemKind::NameValue(lit)) if value.is_none() => {
+                let LitKind::Int(bound, ..) = lit.kind else {
+                    invalid_loop_err();
+                    return None;
+                };
+                value = Some(to_u32(bound)?);
+            }
+            _ => {
+                invalid_loop_err();
+                return None;
             }
         }
     }
+    let (Some(loop_id), Some(value)) = (loop_id, value) else {
+        invalid_loop_err();
+        return None;
+    };
+    Some(UnwindBound::Loop(loop_id, value))
 }
 
 fn parse_stubs(tcx: TyCtxt, harness: DefId, attributes: &[&Attribute]) -> Vec<Stub> {
     let current_module = tcx.parent_module_from_def_id(harness.expect_local());
     let check_resolve = |attr: &Attribute, name: &str| {
-        let result = resolve::resolve_fn(tcx, current_module.to_local_def_id(), name);
+        let result = resolve::resolve_fn_instance(tcx, current_module.to_local_def_id(), name);
         if let Err(err) = result {
             tcx.dcx().span_err(attr.span, format!("failed to resolve `{name}`: {err}"));
         }
@@ -883,7 +1781,24 @@ fn parse_stubs(tcx: TyCtxt, harness: DefId, attributes: &[&Attribute]) -> Vec<St
         .collect()
 }
 
-fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcSolver> {
+/// Parse the solvers of all `#[kani::solver]` attributes of a harness, in the order they are
+/// listed. Each attribute may list several solvers, e.g. `#[kani::solver(kissat, minisat)]`.
+fn parse_solvers(tcx: TyCtxt, attributes: &[&Attribute]) -> Vec<CbmcSolver> {
+    let mut solvers: Vec<CbmcSolver> = Vec::new();
+    for attr in attributes {
+        for solver in parse_solver(tcx, attr) {
+            if solvers.contains(&solver) {
+                tcx.dcx()
+                    .span_err(attr.span, format!("the solver `{solver}` is listed more than once"));
+            } else {
+                solvers.push(solver);
+            }
+        }
+    }
+    solvers
+}
+
+fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Vec<CbmcSolver> {
     // TODO: Argument validation should be done as part of the `kani_macros` crate
     // <https://github.com/model-checking/kani/issues/2192>
     const ATTRIBUTE: &str = "#[kani::solver]";
@@ -895,44 +1810,175 @@ fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcSolver> {
     };
 
     let attr_args = attr.meta_item_list().unwrap();
-    if attr_args.len() != 1 {
+    if attr_args.is_empty() {
         tcx.dcx().span_err(
             attr.span,
-            format!(
-                "the `{ATTRIBUTE}` attribute expects a single argument. Got {} arguments.",
-                attr_args.len()
-            ),
+            format!("the `{ATTRIBUTE}` attribute expects at least one argument. Got 0 arguments."),
         );
-        return None;
-    }
-    let attr_arg = &attr_args[0];
-    let meta_item = attr_arg.meta_item();
-    if meta_item.is_none() {
-        invalid_arg_err(attr);
-        return None;
+        return vec![];
     }
-    let meta_item = meta_item.unwrap();
-    let ident = meta_item.ident().unwrap();
-    let ident_str = ident.as_str();
-    match &meta_item.kind {
-        MetaItemKind::Word => {
-            let solver = CbmcSolver::from_str(ident_str);
-            match solver {
-                Ok(solver) => Some(solver),
-                Err(_) => {
-                    tcx.dcx().span_err(attr.span, format!("unknown solver `{ident_str}`"));
-                    None
+    let mut solvers = vec![];
+    for attr_arg in attr_args.iter() {
+        let Some(meta_item) = attr_arg.meta_item() else {
+            invalid_arg_err(attr);
+            continue;
+        };
+        let ident = meta_item.ident().unwrap();
+        let ident_str = ident.as_str();
+        match &meta_item.kind {
+            MetaItemKind::Word => {
+                let solver = CbmcSolver::from_str(ident_str);
+                match solver {
+                    Ok(solver) => solvers.push(solver),
+                    Err(_) => {
+                        tcx.dcx().span_err(attr.span, format!("unknown solver `{ident_str}`"));
+                    }
                 }
             }
+            MetaItemKind::NameValue(lit) if ident_str == "bin" && lit.kind.is_str() => {
+                solvers.push(CbmcSolver::Binary { binary: lit.symbol.to_string(), args: vec![] })
+            }
+            MetaItemKind::List(items) if ident_str == "args" => {
+                let Some(CbmcSolver::Binary { args, .. }) = solvers.last_mut() else {
+                    tcx.dcx().span_err(
+                        meta_item.span,
+                        format!(
+                            "`args` in `{ATTRIBUTE}` must directly follow the SAT solver binary \
+                            (`bin=\"<SAT_SOLVER_BINARY>\"`) that it is passed to"
+                        ),
+                    );
+                    continue;
+                };
+                if !args.is_empty() {
+                    tcx.dcx().span_err(
+                        meta_item.span,
+                        format!(
+                            "only one `args` list is allowed per solver binary in `{ATTRIBUTE}`"
+                        ),
+                    );
+                    continue;
+                }
+                for item in items.iter() {
+                    match item.lit() {
+                        Some(lit) if lit.kind.is_str() => args.push(lit.symbol.to_string()),
+                        _ => {
+                            tcx.dcx().span_err(
+                                item.span(),
+                                format!(
+                                    "the `args` of a solver binary in `{ATTRIBUTE}` must be string \
+                                    literals, e.g. `args=[\"--quiet\"]`"
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+            _ => invalid_arg_err(attr),
         }
-        MetaItemKind::NameValue(lit) if ident_str == "bin" && lit.kind.is_str() => {
-            Some(CbmcSolver::Binary(lit.symbol.to_string()))
-        }
-        _ => {
-            invalid_arg_err(attr);
-            None
+    }
+    solvers
+}
+
+/// Parse the `(check = bool, ...)` arguments of a `#[kani::pointer_checks]` attribute.
+///
+/// Unknown check names and non-boolean values are reported as errors and skipped.
+fn parse_pointer_checks(tcx: TyCtxt, attr: &Attribute) -> BTreeMap<PointerCheck, bool> {
+    const ATTRIBUTE: &str = "#[kani::pointer_checks]";
+    let Some(attr_args) = attr.meta_item_list() else {
+        tcx.dcx().span_err(
+            attr.span,
+            format!("the `{ATTRIBUTE}` attribute expects a list of `<check> = <bool>` arguments"),
+        );
+        return BTreeMap::default();
+    };
+    attr_args
+        .iter()
+        .filter_map(|arg| {
+            let Some(MetaItem { path, kind: MetaItemKind::NameValue(lit), span, .. }) =
+                arg.meta_item()
+            else {
+                tcx.dcx()
+                    .span_err(arg.span(), format!("expected `<check> = <bool>` in `{ATTRIBUTE}`"));
+                return None;
+            };
+            let name =
+                path.segments.iter().map(|seg| seg.ident.as_str()).collect::<Vec<_>>().join("::");
+            let Ok(check) = PointerCheck::from_str(&name) else {
+                tcx.dcx()
+                    .struct_span_err(*span, format!("unknown pointer check `{name}`"))
+                    .with_help(format!(
+                        "valid pointer checks are: `{}`",
+                        PointerCheck::VARIANTS.join("`, `")
+                    ))
+                    .emit();
+                return None;
+            };
+            let LitKind::Bool(enabled) = lit.kind else {
+                tcx.dcx().span_err(
+                    lit.span,
+                    format!("expected `true` or `false` as the value of `{name}`"),
+                );
+                return None;
+            };
+            Some((check, enabled))
+        })
+        .collect()
+}
+
+/// Parse the `#[kani::should_panic]` attribute, which takes an optional `mode = "any" | "all"`
+/// argument and an optional `expected = "<message>"` argument. Without a mode, the mode is
+/// [`ShouldPanicMode::Any`].
+fn parse_should_panic(tcx: TyCtxt, attr: &Attribute) -> Option<(ShouldPanicMode, Option<String>)> {
+    const ATTRIBUTE: &str = "#[kani::should_panic]";
+    let invalid_arg_err = || {
+        tcx.dcx().span_err(
+            attr.span,
+            format!(
+                "invalid argument for `{ATTRIBUTE}` attribute, expected `mode = \"<mode>\"` or \
+                `expected = \"<message>\"`"
+            ),
+        )
+    };
+    let mut mode = None;
+    let mut expected = None;
+    for arg in attr.meta_item_list().unwrap_or_default() {
+        let Some((name, value)) = arg
+            .meta_item()
+            .and_then(|meta_item| Some((meta_item.ident()?.name, meta_item.value_str()?)))
+        else {
+            invalid_arg_err();
+            return None;
+        };
+        let slot = match name.as_str() {
+            "mode" => &mut mode,
+            "expected" => &mut expected,
+            _ => {
+                invalid_arg_err();
+                return None;
+            }
+        };
+        if slot.replace(value).is_some() {
+            tcx.dcx().span_err(attr.span, format!("duplicate `{name}` argument for `{ATTRIBUTE}`"));
+            return None;
         }
     }
+    let mode = match mode {
+        None => ShouldPanicMode::default(),
+        Some(mode) => match ShouldPanicMode::from_str(mode.as_str()) {
+            Ok(mode) => mode,
+            Err(_) => {
+                tcx.dcx()
+                    .struct_span_err(attr.span, format!("unknown `{ATTRIBUTE}` mode `{mode}`"))
+                    .with_help(format!(
+                        "valid modes are: `{}`",
+                        ShouldPanicMode::VARIANTS.join("`, `")
+                    ))
+                    .emit();
+                return None;
+            }
+        },
+    };
+    Some((mode, expected.map(|expected| expected.to_string())))
 }
 
 /// Extracts the integer value argument from the attribute provided
@@ -954,15 +2000,30 @@ fn parse_integer(attr: &Attribute) -> Option<u128> {
     }
 }
 
+/// Does the return type of the function `def_id` contain an `impl Trait` type?
+fn returns_impl_trait(tcx: TyCtxt, def_id: DefId) -> bool {
+    let output = tcx.fn_sig(def_id).skip_binder().output().skip_binder();
+    output
+        .walk()
+        .filter_map(|arg| arg.as_type())
+        .any(|ty| matches!(ty.kind(), TyKind::Alias(AliasKind::Opaque, _)))
+}
+
 /// Extracts a vector with the path arguments of an attribute.
 /// Emits an error if it couldn't convert any of the arguments.
+///
+/// Paths with generic arguments, e.g. `decode::<u32>`, cannot be written in an
+/// attribute, so the Kani macros pass them as string literals instead.
 fn parse_paths(attr: &Attribute) -> Result<Vec<String>, Span> {
     let attr_args = attr.meta_item_list();
     attr_args
         .unwrap_or_default()
         .iter()
         .map(|arg| match arg {
-            NestedMetaItem::Lit(item) => Err(item.span),
+            NestedMetaItem::Lit(item) => match item.kind {
+                LitKind::Str(path, _) if path.as_str().contains('<') => Ok(path.to_string()),
+                _ => Err(item.span),
+            },
             NestedMetaItem::MetaItem(item) => parse_path(item).ok_or(item.span),
         })
         .collect()
@@ -1013,6 +2074,17 @@ fn parse_str_value(attr: &Attribute) -> Option<String> {
     value.map(|sym| sym.to_string())
 }
 
+/// The name of the user facing attribute kind with the smallest edit distance to
+/// `name`, if any is close enough to be a likely typo.
+fn closest_attribute_kind(name: &str) -> Option<Symbol> {
+    let kinds = KaniAttributeKind::VARIANTS
+        .iter()
+        .filter(|kind| KaniAttributeKind::from_str(kind).unwrap().is_user_facing())
+        .map(|kind| Symbol::intern(kind))
+        .collect::<Vec<_>>();
+    rustc_span::edit_distance::find_best_match_for_name(&kinds, Symbol::intern(name), None)
+}
+
 /// If the attribute is named `kanitool::name`, this extracts `name`
 fn attr_kind(tcx: TyCtxt, attr: &Attribute) -> Option<KaniAttributeKind> {
     match &attr.kind {
@@ -1027,7 +2099,13 @@ fn attr_kind(tcx: TyCtxt, attr: &Attribute) -> Option<KaniAttributeKind> {
                 KaniAttributeKind::try_from(ident_str.as_str())
                     .map_err(|err| {
                         debug!(?err, "attr_kind_failed");
-                        tcx.dcx().span_err(attr.span, format!("unknown attribute `{ident_str}`"));
+                        let mut diag = tcx
+                            .dcx()
+                            .struct_span_err(attr.span, format!("unknown attribute `{ident_str}`"));
+                        if let Some(suggestion) = closest_attribute_kind(&ident_str) {
+                            diag.help(format!("did you mean `{suggestion}`?"));
+                        }
+                        diag.emit();
                         err
                     })
                     .ok()
@@ -3,12 +3,18 @@
 //! This module handles Kani metadata generation. For example, generating HarnessMetadata for a
 //! given function.
 
+use std::collections::HashMap;
 use std::default::Default;
 use std::path::Path;
 
 use crate::kani_middle::attributes::test_harness_name;
-use kani_metadata::{ArtifactType, HarnessAttributes, HarnessMetadata};
+use kani_metadata::{
+    ArtifactType, ContractClauseKind, ContractedFunction, HarnessAttributes, HarnessMetadata,
+    Location,
+};
+use rustc_hir::def::DefKind;
 use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
 use stable_mir::mir::mono::Instance;
 use stable_mir::CrateDef;
 
@@ -22,9 +28,21 @@ pub fn canonical_mangled_name(instance: Instance) -> String {
 }
 
 /// Create the harness metadata for a proof harness for a given function.
-pub fn gen_proof_metadata(tcx: TyCtxt, instance: Instance, base_name: &Path) -> HarnessMetadata {
+///
+/// If `replace_all_contracts` is set, a `proof_for_contract` harness replaces every other
+/// function with a contract by its contract (see [`KaniAttributes::replace_all_contracts`]).
+pub fn gen_proof_metadata(
+    tcx: TyCtxt,
+    instance: Instance,
+    base_name: &Path,
+    replace_all_contracts: bool,
+) -> HarnessMetadata {
     let def = instance.def;
-    let attributes = KaniAttributes::for_instance(tcx, instance).harness_attributes();
+    let kani_attributes = KaniAttributes::for_instance(tcx, instance);
+    let mut attributes = kani_attributes.harness_attributes();
+    if replace_all_contracts {
+        kani_attributes.replace_all_contracts(&mut attributes);
+    }
     let pretty_name = instance.name();
     let mangled_name = canonical_mangled_name(instance);
 
@@ -45,7 +63,50 @@ pub fn gen_proof_metadata(tcx: TyCtxt, instance: Instance, base_name: &Path) ->
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
         contract: Default::default(),
+        has_loop_contracts: false,
+        verified_with: None,
+    }
+}
+
+/// Collect all functions of the local crate that have a contract, together with the
+/// `proof_for_contract` harnesses in `harnesses` that check them.
+pub fn gen_contracted_functions<'a>(
+    tcx: TyCtxt,
+    harnesses: impl Iterator<Item = &'a HarnessMetadata>,
+) -> Vec<ContractedFunction> {
+    let mut checked_by: HashMap<&str, Vec<String>> = HashMap::new();
+    for harness in harnesses {
+        for target in &harness.attributes.proof_for_contract {
+            checked_by.entry(target).or_default().push(harness.pretty_name.clone());
+        }
     }
+    let mut functions = tcx
+        .hir()
+        .body_owners()
+        .map(|local_def_id| local_def_id.to_def_id())
+        .filter(|def_id| matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn))
+        .filter_map(|def_id| {
+            let attributes = KaniAttributes::for_item(tcx, def_id);
+            let checked_with = attributes.checked_with()?.ok()?.to_string();
+            let replaced_with = attributes.replaced_with()?.ok()?.to_string();
+            let function = tcx.def_path_str(def_id);
+            let loc = SourceLocation::new(rustc_internal::stable(tcx.def_span(def_id)));
+            let mut harnesses = checked_by.remove(function.as_str()).unwrap_or_default();
+            harnesses.sort();
+            Some(ContractedFunction {
+                function,
+                location: Location { filename: loc.filename, start_line: loc.start_line as u64 },
+                checked_with,
+                replaced_with,
+                harnesses,
+                requires: attributes.contract_clauses(ContractClauseKind::Requires),
+                ensures: attributes.contract_clauses(ContractClauseKind::Ensures),
+                modifies: attributes.contract_modifies(),
+            })
+        })
+        .collect::<Vec<_>>();
+    functions.sort_by(|a, b| a.function.cmp(&b.function));
+    functions
 }
 
 /// Create the harness metadata for a test description.
@@ -73,5 +134,7 @@ pub fn gen_test_metadata(
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
         contract: Default::default(),
+        has_loop_contracts: false,
+        verified_with: None,
     }
 }
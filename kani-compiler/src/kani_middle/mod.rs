@@ -18,11 +18,12 @@
 use rustc_session::config::OutputType;
 use rustc_smir::rustc_internal;
 use rustc_span::source_map::respan;
-use rustc_span::Span;
+use rustc_span::{sym, Span};
 use rustc_target::abi::call::FnAbi;
 use rustc_target::abi::{HasDataLayout, TargetDataLayout};
 use stable_mir::mir::mono::{Instance, InstanceKind, MonoItem};
 use stable_mir::mir::pretty::pretty_ty;
+use stable_mir::mir::{Mutability, ProjectionElem, Rvalue, StatementKind, TerminatorKind};
 use stable_mir::ty::{BoundVariableKind, RigidTy, Span as SpanStable, Ty, TyKind};
 use stable_mir::visitor::{Visitable, Visitor as TypeVisitor};
 use stable_mir::{CrateDef, DefId};
@@ -68,6 +69,23 @@ pub fn check_crate_items(tcx: TyCtxt, ignore_asm: bool) {
     tcx.dcx().abort_if_errors();
 }
 
+/// Check that every contract used by a harness of this crate is well-formed.
+///
+/// [`check_crate_items`] already validates the attributes of each item. This additionally
+/// resolves the target of every `proof_for_contract` and `stub_verified` attribute and checks
+/// that it carries a complete contract, which otherwise only happens when harnesses are collected
+/// for codegen. It will abort at the end if any error was found.
+pub fn check_contracts(tcx: TyCtxt) {
+    for item in tcx.hir_crate_items(()).items() {
+        let attributes = KaniAttributes::for_item(tcx, item.owner_id.def_id.to_def_id());
+        if attributes.is_harness() {
+            // We only care about the errors emitted while extracting the attributes.
+            let _ = attributes.harness_attributes();
+        }
+    }
+    tcx.dcx().abort_if_errors();
+}
+
 /// Check that all given items are supported and there's no misconfiguration.
 /// This method will exhaustively print any error / warning and it will abort at the end if any
 /// error was found.
@@ -86,6 +104,11 @@ pub fn check_reachable_items(tcx: TyCtxt, queries: &QueryDb, items: &[MonoItem])
             // Check if any unstable attribute was reached.
             KaniAttributes::for_def_id(tcx, def_id)
                 .check_unstable_features(&queries.args().unstable_features);
+            if let MonoItem::Fn(instance) = item {
+                if attributes::is_predicate(tcx, rustc_internal::internal(def_id)) {
+                    check_is_predicate_pure(tcx, *instance);
+                }
+            }
             def_ids.insert(def_id);
         }
 
@@ -102,6 +125,7 @@ pub fn check_reachable_items(tcx: TyCtxt, queries: &QueryDb, items: &[MonoItem])
 
 /// A basic check that ensures a function with a contract does not receive
 /// mutable pointers in its input and does not return raw pointers of any kind.
+/// A mutable pointer argument is allowed if its pointee is a `modifies` target.
 ///
 /// This is a temporary safety measure because contracts cannot yet reason
 /// about the heap.
@@ -175,10 +199,19 @@ fn is_raw_ptr(ty: Ty) -> bool {
 
     let fn_typ = bound_fn_sig.skip_binder();
 
-    for (input_ty, (is_prohibited, r#where, what)) in fn_typ
-        .inputs()
-        .iter()
-        .copied()
+    // A mutable pointer argument whose pointee is a `modifies` target is fine, since the
+    // contract declares what the function writes through it. Its pointee is still checked.
+    let def_id = rustc_internal::internal(instance.def.def_id());
+    let modifies_args = KaniAttributes::for_item(tcx, def_id).modifies_pointer_args();
+    let inputs = fn_typ.inputs().iter().enumerate().map(|(idx, ty)| {
+        let is_target = modifies_args.contains(&idx);
+        match ty.kind() {
+            TyKind::RigidTy(RigidTy::RawPtr(pointee, Mutability::Mut)) if is_target => pointee,
+            _ => *ty,
+        }
+    });
+
+    for (input_ty, (is_prohibited, r#where, what)) in inputs
         .zip(std::iter::repeat((is_raw_mutable_ptr as fn(_) -> _, "This argument", "mutable ")))
         .chain([(fn_typ.output(), (is_raw_ptr as fn(_) -> _, "The return", ""))])
     {
@@ -187,6 +220,46 @@ fn is_raw_ptr(ty: Ty) -> bool {
     }
 }
 
+/// Check that the body of a `#[kani::predicate]` function has no side effects
+/// that can be detected in its MIR, i.e. it does not write to memory through a
+/// pointer and does not allocate.
+///
+/// Only the body of the predicate itself is inspected, not the bodies of the
+/// functions it calls.
+fn check_is_predicate_pure(tcx: TyCtxt, instance: Instance) {
+    let Some(body) = instance.body() else { return };
+    let name = instance.def.name();
+    let report = |span: SpanStable, what: &str| {
+        tcx.dcx().span_err(
+            rustc_internal::internal(span),
+            format!("`{name}` is marked as `#[kani::predicate]` but {what}"),
+        );
+    };
+    let exchange_malloc = tcx.lang_items().exchange_malloc_fn();
+    for block in &body.blocks {
+        for statement in &block.statements {
+            if let StatementKind::Assign(place, rvalue) = &statement.kind {
+                if place.projection.iter().any(|elem| matches!(elem, ProjectionElem::Deref)) {
+                    report(statement.span, "writes to memory through a pointer");
+                }
+                if matches!(rvalue, Rvalue::ShallowInitBox(..)) {
+                    report(statement.span, "allocates memory");
+                }
+            }
+        }
+        if let TerminatorKind::Call { func, .. } = &block.terminator.kind {
+            if let TyKind::RigidTy(RigidTy::FnDef(fn_def, _)) =
+                func.ty(body.locals()).unwrap().kind()
+            {
+                let def_id = rustc_internal::internal(fn_def.def_id());
+                if Some(def_id) == exchange_malloc || tcx.is_diagnostic_item(sym::box_new, def_id) {
+                    report(block.terminator.span, "allocates memory");
+                }
+            }
+        }
+    }
+}
+
 /// Print MIR for the reachable items if the `--emit mir` option was provided to rustc.
 pub fn dump_mir_items(tcx: TyCtxt, items: &[MonoItem], output: &Path) {
     /// Convert MonoItem into a DefId.
@@ -13,10 +13,16 @@
 use std::fmt;
 use std::iter::Peekable;
 
+use rustc_ast::ptr::P;
+use rustc_ast::{self as ast, token, Mutability};
+use rustc_ast_pretty::pprust;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{DefId, LocalDefId, LocalModDefId, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc_hir::{ItemKind, UseKind};
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{GenericArgs, GenericArgsRef, GenericParamDefKind, Ty, TyCtxt};
+use rustc_parse::new_parser_from_source_str;
+use rustc_span::symbol::kw;
+use rustc_span::FileName;
 use tracing::debug;
 
 /// Attempts to resolve a simple path (in the form of a string) to a function / method `DefId`.
@@ -47,6 +53,215 @@ pub fn resolve_fn<'tcx>(
     }
 }
 
+/// Attempts to resolve a path to a function / method whose last segment may have generic
+/// arguments, e.g. `decode::<u32>`. Returns the generic arguments of the function instance
+/// if the path has any, or `None` if the path refers to the function as a whole.
+///
+/// The path is parsed with the rustc parser. The generic arguments may be primitive types,
+/// references, slices, tuples and paths to structs, enums and unions, which are resolved like
+/// the path of the function.
+pub fn resolve_fn_instance<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    current_module: LocalDefId,
+    path_str: &str,
+) -> Result<(DefId, Option<GenericArgsRef<'tcx>>), ResolveError<'tcx>> {
+    let invalid = |msg: &str| ResolveError::InvalidPath { msg: format!("{msg} in `{path_str}`") };
+    let Some(ty) = parse_ty(tcx, path_str) else {
+        return Err(invalid("expected a path"));
+    };
+    let ast::TyKind::Path(None, path) = &ty.kind else {
+        return Err(invalid("expected a path"));
+    };
+    let (last, prefix) = path.segments.split_last().unwrap();
+    if prefix.iter().any(|segment| segment.args.is_some()) {
+        return Err(invalid("generic arguments are only supported on the last path segment"));
+    }
+    let def_id = resolve_fn(tcx, current_module, &path_to_string(path))?;
+    let Some(generic_args) = &last.args else {
+        return Ok((def_id, None));
+    };
+    let args = generic_arg_types(generic_args)
+        .and_then(|types| resolve_generic_args(tcx, current_module, def_id, &types))
+        .map_err(|reason| ResolveError::InvalidGenericArgs {
+            args: tcx.sess.source_map().span_to_snippet(generic_args.span()).unwrap_or_default(),
+            reason,
+        })?;
+    Ok((def_id, Some(args)))
+}
+
+/// Parses `src` as a type with the rustc parser, which also accepts paths to functions such as
+/// `decode::<u32>`. Returns `None` if `src` is not exactly one type.
+fn parse_ty(tcx: TyCtxt, src: &str) -> Option<P<ast::Ty>> {
+    let mut parser = new_parser_from_source_str(
+        &tcx.sess.parse_sess,
+        FileName::Custom("kani path".to_string()),
+        src.to_string(),
+    );
+    let ty = parser.parse_ty().map_err(|err| err.cancel()).ok()?;
+    (parser.token == token::Eof).then_some(ty)
+}
+
+/// The path without generic arguments, e.g. `std::vec::Vec` for `std::vec::Vec<u8>`, in the
+/// form that [`resolve_path`] expects.
+fn path_to_string(path: &ast::Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| if segment.ident.name == kw::PathRoot { "" } else { segment.ident.as_str() })
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// The types in the angle brackets of `args`. Lifetimes are skipped since they are erased.
+fn generic_arg_types(args: &ast::GenericArgs) -> Result<Vec<&ast::Ty>, String> {
+    let ast::GenericArgs::AngleBracketed(args) = args else {
+        return Err("expected generic arguments in angle brackets".to_string());
+    };
+    args.args
+        .iter()
+        .filter_map(|arg| match arg {
+            ast::AngleBracketedArg::Arg(ast::GenericArg::Lifetime(_)) => None,
+            ast::AngleBracketedArg::Arg(ast::GenericArg::Type(ty)) => Some(Ok(&**ty)),
+            ast::AngleBracketedArg::Arg(ast::GenericArg::Const(_)) => {
+                Some(Err("const generic arguments are not supported".to_string()))
+            }
+            ast::AngleBracketedArg::Constraint(_) => {
+                Some(Err("associated type constraints are not supported".to_string()))
+            }
+        })
+        .collect()
+}
+
+/// Resolves the generic arguments of the item `def_id`. Lifetimes are erased, and type parameters
+/// that have a default may be omitted.
+fn resolve_generic_args<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    current_module: LocalDefId,
+    def_id: DefId,
+    generic_args: &[&ast::Ty],
+) -> Result<GenericArgsRef<'tcx>, String> {
+    let generics = tcx.generics_of(def_id);
+    let params = (0..generics.count()).map(|idx| generics.param_at(idx, tcx)).collect::<Vec<_>>();
+    let mut required = 0;
+    let mut allowed = 0;
+    for param in &params {
+        match param.kind {
+            GenericParamDefKind::Lifetime => {}
+            GenericParamDefKind::Type { has_default, .. } => {
+                allowed += 1;
+                if !has_default {
+                    required += 1;
+                }
+            }
+            GenericParamDefKind::Const { .. } => {
+                return Err(format!(
+                    "`{}` has const generic parameters, which are not supported",
+                    tcx.def_path_str(def_id)
+                ));
+            }
+        }
+    }
+    if generic_args.len() < required || generic_args.len() > allowed {
+        let expected = if required == allowed {
+            required.to_string()
+        } else {
+            format!("{required} to {allowed}")
+        };
+        return Err(format!(
+            "`{}` takes {expected} generic argument(s) but {} were given",
+            tcx.def_path_str(def_id),
+            generic_args.len()
+        ));
+    }
+    let types = generic_args
+        .iter()
+        .map(|arg| resolve_ty(tcx, current_module, arg))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut types = types.into_iter();
+    Ok(GenericArgs::for_item(tcx, def_id, |param, args| match param.kind {
+        GenericParamDefKind::Type { .. } => {
+            types.next().unwrap_or_else(|| tcx.type_of(param.def_id).instantiate(tcx, args)).into()
+        }
+        _ => tcx.lifetimes.re_erased.into(),
+    }))
+}
+
+/// Resolves a type that is used as a generic argument.
+fn resolve_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    current_module: LocalDefId,
+    ty: &ast::Ty,
+) -> Result<Ty<'tcx>, String> {
+    match &ty.kind {
+        ast::TyKind::Ref(_, ast::MutTy { ty: pointee, mutbl }) => {
+            let pointee = resolve_ty(tcx, current_module, pointee)?;
+            Ok(match mutbl {
+                Mutability::Mut => Ty::new_mut_ref(tcx, tcx.lifetimes.re_erased, pointee),
+                Mutability::Not => Ty::new_imm_ref(tcx, tcx.lifetimes.re_erased, pointee),
+            })
+        }
+        ast::TyKind::Tup(elems) => {
+            let elems = elems
+                .iter()
+                .map(|elem| resolve_ty(tcx, current_module, elem))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Ty::new_tup(tcx, &elems))
+        }
+        ast::TyKind::Slice(elem) => Ok(Ty::new_slice(tcx, resolve_ty(tcx, current_module, elem)?)),
+        ast::TyKind::Paren(inner) => resolve_ty(tcx, current_module, inner),
+        ast::TyKind::Never => Ok(tcx.types.never),
+        ast::TyKind::Path(None, path) => {
+            let (last, prefix) = path.segments.split_last().unwrap();
+            if prefix.is_empty() && last.args.is_none() {
+                if let Some(ty) = primitive_ty(tcx, last.ident.as_str()) {
+                    return Ok(ty);
+                }
+            }
+            if prefix.iter().any(|segment| segment.args.is_some()) {
+                return Err(format!(
+                    "generic arguments are only supported on the last path segment of `{}`",
+                    pprust::ty_to_string(ty)
+                ));
+            }
+            let def_id = resolve_path(tcx, current_module, &path_to_string(path))
+                .map_err(|err| err.to_string())?;
+            if !matches!(tcx.def_kind(def_id), DefKind::Struct | DefKind::Enum | DefKind::Union) {
+                return Err(format!("expected a type, found {}", description(tcx, def_id)));
+            }
+            let types = last.args.as_deref().map(generic_arg_types).transpose()?;
+            let args =
+                resolve_generic_args(tcx, current_module, def_id, &types.unwrap_or_default())?;
+            Ok(Ty::new_adt(tcx, tcx.adt_def(def_id), args))
+        }
+        _ => Err(format!("types such as `{}` are not supported", pprust::ty_to_string(ty))),
+    }
+}
+
+/// Returns the primitive type with the given name, if there is one.
+fn primitive_ty<'tcx>(tcx: TyCtxt<'tcx>, name: &str) -> Option<Ty<'tcx>> {
+    let types = &tcx.types;
+    let ty = match name {
+        "bool" => types.bool,
+        "char" => types.char,
+        "str" => types.str_,
+        "i8" => types.i8,
+        "i16" => types.i16,
+        "i32" => types.i32,
+        "i64" => types.i64,
+        "i128" => types.i128,
+        "isize" => types.isize,
+        "u8" => types.u8,
+        "u16" => types.u16,
+        "u32" => types.u32,
+        "u64" => types.u64,
+        "u128" => types.u128,
+        "usize" => types.usize,
+        "f32" => types.f32,
+        "f64" => types.f64,
+        _ => return None,
+    };
+    Some(ty)
+}
+
 /// Attempts to resolve a simple path (in the form of a string) to a `DefId`.
 /// The current module is provided as an argument in order to resolve relative
 /// paths.
@@ -66,6 +281,7 @@ pub(crate) fn resolve_path<'tcx>(
         let next_item = match def_kind {
             DefKind::ForeignMod | DefKind::Mod => resolve_in_module(tcx, base, &name),
             DefKind::Struct | DefKind::Enum | DefKind::Union => resolve_in_type(tcx, base, &name),
+            DefKind::Trait => resolve_in_trait(tcx, base, &name),
             kind => {
                 debug!(?base, ?kind, "resolve_path: unexpected item");
                 Err(ResolveError::UnexpectedType { tcx, item: base, expected: "module" })
@@ -84,6 +300,8 @@ pub enum ResolveError<'tcx> {
     ExtraSuper,
     /// Invalid path.
     InvalidPath { msg: String },
+    /// The generic arguments of a path, e.g. `<u32>`, could not be resolved.
+    InvalidGenericArgs { args: String, reason: String },
     /// Unable to find an item.
     MissingItem { tcx: TyCtxt<'tcx>, base: DefId, unresolved: String },
     /// Error triggered when the identifier points to an item with unexpected type.
@@ -115,6 +333,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 )
             }
             ResolveError::InvalidPath { msg } => write!(f, "{msg}"),
+            ResolveError::InvalidGenericArgs { args, reason } => {
+                write!(f, "failed to resolve the generic arguments `{args}`: {reason}")
+            }
             ResolveError::UnexpectedType { tcx, item: def_id, expected } => write!(
                 f,
                 "expected {expected}, found {} `{}`",
@@ -145,6 +366,9 @@ struct Path {
 const CRATE: &str = "crate";
 /// rustc represents initial `::` as `{{root}}`.
 const ROOT: &str = "{{root}}";
+/// A path string that starts with `::`, e.g. the stringified target of a
+/// `stub_verified` attribute, has an empty first segment instead.
+const EMPTY_ROOT: &str = "";
 /// Identifier for the current module.
 const SELF: &str = "self";
 /// Identifier for the parent of the current module.
@@ -169,7 +393,7 @@ fn resolve_prefix<'tcx>(
     // `self` may be followed be `super` (handled below).
     let first = segments.peek().unwrap().as_str();
     match first {
-        ROOT => {
+        ROOT | EMPTY_ROOT => {
             // Skip root and get the external crate from the name that follows `::`.
             let next = segments.nth(1);
             if let Some(next_name) = next {
@@ -391,6 +615,25 @@ fn resolve_in_glob_use(tcx: TyCtxt, res: &Res, name: &str) -> RelativeResolution
     }
 }
 
+/// Resolves an associated function of a trait, e.g. `Trait::method`. This is the definition in
+/// the trait itself, which only has a body if the method is provided by the trait.
+fn resolve_in_trait<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_id: DefId,
+    name: &str,
+) -> Result<DefId, ResolveError<'tcx>> {
+    debug!(?name, ?trait_id, "resolve_in_trait");
+    tcx.associated_item_def_ids(trait_id)
+        .iter()
+        .copied()
+        .find(|item| tcx.item_name(*item).as_str() == name)
+        .ok_or_else(|| ResolveError::MissingItem {
+            tcx,
+            base: trait_id,
+            unresolved: name.to_string(),
+        })
+}
+
 /// Resolves a method in a type. It currently does not resolve trait methods
 /// (see <https://github.com/model-checking/kani/issues/1997>).
 fn resolve_in_type<'tcx>(
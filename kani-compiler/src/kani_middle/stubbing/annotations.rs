@@ -7,22 +7,28 @@
 use kani_metadata::Stub;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::definitions::DefPathHash;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{GenericArgs, GenericArgsRef, ParamEnv, TyCtxt};
 
-use crate::kani_middle::resolve::resolve_fn;
+use super::{generic_args_key, StubTarget};
+use crate::kani_middle::resolve::resolve_fn_instance;
 
 /// Given a `kani::stub` attribute, tries to extract a pair of paths (the
 /// original function/method, and its stub). Returns `None` and errors if the
-/// attribute's arguments are not two paths.
-fn stub_def_ids(tcx: TyCtxt, harness: LocalDefId, stub: &Stub) -> Option<(DefId, DefId)> {
+/// attribute's arguments are not two paths. If the original path has generic
+/// arguments, e.g. `decode::<u32>`, they are returned as well.
+fn stub_def_ids<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    harness: LocalDefId,
+    stub: &Stub,
+) -> Option<((DefId, Option<GenericArgsRef<'tcx>>), DefId)> {
     // Resolve the attribute arguments to `DefId`s
     let current_module = tcx.parent_module_from_def_id(harness);
-    let resolve = |name: &str| -> Option<DefId> {
-        let maybe_resolved = resolve_fn(tcx, current_module.to_local_def_id(), name);
+    let resolve = |name: &str| -> Option<(DefId, Option<GenericArgsRef<'tcx>>)> {
+        let maybe_resolved = resolve_fn_instance(tcx, current_module.to_local_def_id(), name);
         match maybe_resolved {
-            Ok(def_id) => {
-                tracing::debug!(?def_id, "Resolved {name} to {}", tcx.def_path_str(def_id));
-                Some(def_id)
+            Ok((def_id, args)) => {
+                tracing::debug!(?def_id, ?args, "Resolved {name} to {}", tcx.def_path_str(def_id));
+                Some((def_id, args))
             }
             Err(err) => {
                 tcx.dcx()
@@ -32,22 +38,91 @@ fn stub_def_ids(tcx: TyCtxt, harness: LocalDefId, stub: &Stub) -> Option<(DefId,
         }
     };
     let orig = resolve(&stub.original);
-    let stub = resolve(&stub.replacement);
-    Some((orig?, stub?))
+    let replacement = resolve(&stub.replacement);
+    let (stub_id, stub_args) = replacement?;
+    if stub_args.is_some() {
+        tcx.dcx().span_err(
+            tcx.def_span(harness),
+            format!(
+                "the stub `{}` cannot have generic arguments; only the original function can",
+                stub.replacement
+            ),
+        );
+        return None;
+    }
+    Some((orig?, stub_id))
+}
+
+/// Checks that the stub of the instance `orig_id::<args>` is compatible with it: the stub must
+/// either take no generic parameters or the same number as the original function/method, and
+/// its signature must match the one of the instance.
+fn check_instance_compatibility<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    harness: LocalDefId,
+    orig_id: DefId,
+    args: GenericArgsRef<'tcx>,
+    stub_id: DefId,
+) -> bool {
+    let stub_args = if tcx.generics_of(stub_id).count() == 0 {
+        GenericArgs::empty()
+    } else if tcx.generics_of(stub_id).count() == args.len() {
+        args
+    } else {
+        tcx.dcx().span_err(
+            tcx.def_span(harness),
+            format!(
+                "mismatch in the number of generic parameters: the stub `{}` of `{}::<{}>` must take \
+                no generic parameters or as many as `{}`",
+                tcx.def_path_str(stub_id),
+                tcx.def_path_str(orig_id),
+                generic_args_key(tcx, args),
+                tcx.def_path_str(orig_id),
+            ),
+        );
+        return false;
+    };
+    let sig = |def_id: DefId, args: GenericArgsRef<'tcx>| {
+        let sig = tcx.fn_sig(def_id).instantiate(tcx, args);
+        tcx.normalize_erasing_late_bound_regions(ParamEnv::reveal_all(), sig)
+    };
+    let orig_sig = sig(orig_id, args);
+    let stub_sig = sig(stub_id, stub_args);
+    if orig_sig.inputs_and_output != stub_sig.inputs_and_output {
+        tcx.dcx().span_err(
+            tcx.def_span(harness),
+            format!(
+                "signature mismatch: stub `{}` has type `{stub_sig}` where `{}::<{}>` has type `{orig_sig}`",
+                tcx.def_path_str(stub_id),
+                tcx.def_path_str(orig_id),
+                generic_args_key(tcx, args),
+            ),
+        );
+        return false;
+    }
+    true
 }
 
 /// Updates the running map `stub_pairs` that maps a function/method to its
 /// stub. Errors if a function/method is mapped more than once.
+///
+/// A stub for an instance of a generic function, e.g. `decode::<u32>`, only
+/// replaces that instance, so its key includes the generic arguments.
 pub fn update_stub_mapping(
     tcx: TyCtxt,
     harness: LocalDefId,
     stub: &Stub,
-    stub_pairs: &mut BTreeMap<DefPathHash, DefPathHash>,
+    stub_pairs: &mut BTreeMap<StubTarget, DefPathHash>,
 ) {
-    if let Some((orig_id, stub_id)) = stub_def_ids(tcx, harness, stub) {
+    if let Some(((orig_id, orig_args), stub_id)) = stub_def_ids(tcx, harness, stub) {
+        if let Some(args) = orig_args {
+            if !check_instance_compatibility(tcx, harness, orig_id, args, stub_id) {
+                return;
+            }
+        }
         let orig_hash = tcx.def_path_hash(orig_id);
         let stub_hash = tcx.def_path_hash(stub_id);
-        let other_opt = stub_pairs.insert(orig_hash, stub_hash);
+        let orig_args = orig_args.map(|args| generic_args_key(tcx, args));
+        let other_opt = stub_pairs.insert((orig_hash, orig_args), stub_hash);
         if let Some(other) = other_opt {
             if other != stub_hash {
                 tcx.dcx().span_err(
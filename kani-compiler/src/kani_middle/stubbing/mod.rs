@@ -26,7 +26,7 @@ pub fn harness_stub_map(
     tcx: TyCtxt,
     harness: Instance,
     metadata: &HarnessMetadata,
-) -> BTreeMap<DefPathHash, DefPathHash> {
+) -> BTreeMap<StubTarget, DefPathHash> {
     let def_id = rustc_internal::internal(harness.def.def_id());
     let attrs = &metadata.attributes;
     let mut stub_pairs = BTreeMap::default();
@@ -5,6 +5,10 @@
 //! `transform`, which takes the `DefId` of a function/method and returns the
 //! body of its stub, if appropriate. The stub mapping it uses is set via rustc
 //! arguments.
+//!
+//! Stubs of a single instance of a generic function, e.g. `decode::<u32>`, cannot
+//! replace the body of the function, so they are applied by
+//! `transform_stubbed_instances` to the calls of that instance instead.
 
 use std::collections::{BTreeMap, HashMap};
 
@@ -16,7 +20,8 @@
 use rustc_middle::mir::{
     visit::MutVisitor, Body, Const, ConstValue, Local, LocalDecl, Location, Operand,
 };
-use rustc_middle::ty::{self, TyCtxt};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, GenericArgs, GenericArgsRef, TyCtxt, TypeVisitableExt};
 
 use tracing::debug;
 
@@ -24,7 +29,19 @@
 /// parameter `def_id`, and `None` if the function/method is not stubbed.
 pub fn get_stub(tcx: TyCtxt, def_id: DefId) -> Option<DefId> {
     let mapping = get_stub_mapping(tcx)?;
-    mapping.get(&def_id).copied()
+    mapping.get(&(def_id, None)).copied()
+}
+
+/// The function/method replaced by a stub. If only one instance of a generic
+/// function/method is replaced, the key of its generic arguments is included
+/// (see [`generic_args_key`]).
+pub type StubTarget = (DefPathHash, Option<String>);
+
+/// Returns a string that identifies the generic arguments of an instance across
+/// compilation sessions, e.g. `u32` for `decode::<u32>`. Lifetimes are ignored.
+pub fn generic_args_key<'tcx>(tcx: TyCtxt<'tcx>, args: GenericArgsRef<'tcx>) -> String {
+    let args = tcx.erase_regions(args);
+    with_no_trimmed_paths!(args.types().map(|ty| ty.to_string()).collect::<Vec<_>>().join(", "))
 }
 
 /// Returns the new body of a function/method if it has been stubbed out;
@@ -62,7 +79,7 @@ struct ForeignFunctionTransformer<'tcx> {
     /// Local declarations of the callee function. Kani searches here for foreign functions.
     local_decls: IndexVec<Local, LocalDecl<'tcx>>,
     /// Map of functions/methods to their correspondent stubs.
-    stub_map: HashMap<DefId, DefId>,
+    stub_map: HashMap<(DefId, Option<String>), DefId>,
 }
 
 impl<'tcx> MutVisitor<'tcx> for ForeignFunctionTransformer<'tcx> {
@@ -74,7 +91,7 @@ fn visit_operand(&mut self, operand: &mut Operand<'tcx>, _location: Location) {
         let func_ty = operand.ty(&self.local_decls, self.tcx);
         if let ty::FnDef(reachable_function, arguments) = *func_ty.kind() {
             if self.tcx.is_foreign_item(reachable_function) {
-                if let Some(stub) = self.stub_map.get(&reachable_function) {
+                if let Some(stub) = self.stub_map.get(&(reachable_function, None)) {
                     let Operand::Constant(function_definition) = operand else {
                         return;
                     };
@@ -88,6 +105,94 @@ fn visit_operand(&mut self, operand: &mut Operand<'tcx>, _location: Location) {
     }
 }
 
+/// Traverse `body` searching for calls to instances of generic functions that are
+/// stubbed individually, e.g. with `#[kani::stub(decode::<u32>, stub_decode)]`, and
+/// replace them with calls to their stub. Other instances of the function are left
+/// untouched. Calls whose generic arguments are only known once `body` is
+/// instantiated, i.e. calls from generic code, are not replaced, and we warn
+/// about them since they may end up calling the stubbed instance.
+pub fn transform_stubbed_instances<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+    if let Some(stub_map) = get_stub_mapping(tcx) {
+        let instance_stubs: HashMap<_, _> = stub_map
+            .into_iter()
+            .filter_map(|((def_id, args), stub)| Some(((def_id, args?), stub)))
+            .collect();
+        if !instance_stubs.is_empty() {
+            let mut visitor = InstanceStubTransformer { tcx, body: body.clone(), instance_stubs };
+            visitor.visit_body(body);
+        }
+    }
+}
+
+struct InstanceStubTransformer<'tcx> {
+    /// The compiler context.
+    tcx: TyCtxt<'tcx>,
+    /// A copy of the body being transformed, for its local declarations and spans.
+    body: Body<'tcx>,
+    /// Map of instances, given by the function/method and the key of its generic
+    /// arguments, to their correspondent stubs.
+    instance_stubs: HashMap<(DefId, String), DefId>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for InstanceStubTransformer<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_operand(&mut self, operand: &mut Operand<'tcx>, location: Location) {
+        let func_ty = operand.ty(&self.body.local_decls, self.tcx);
+        let ty::FnDef(reachable_function, arguments) = *func_ty.kind() else {
+            return;
+        };
+        if arguments.is_empty()
+            || !self.instance_stubs.keys().any(|(def_id, _)| *def_id == reachable_function)
+        {
+            return;
+        }
+        if arguments.has_param() {
+            let instances = self
+                .instance_stubs
+                .keys()
+                .filter(|(def_id, _)| *def_id == reachable_function)
+                .map(|(_, args)| {
+                    format!("`{}::<{args}>`", self.tcx.def_path_str(reachable_function))
+                })
+                .collect::<Vec<_>>();
+            self.tcx.dcx().span_warn(
+                self.body.source_info(location).span,
+                format!(
+                    "this call from generic code is not stubbed, even if it is instantiated as {}",
+                    instances.join(" or ")
+                ),
+            );
+            return;
+        }
+        let key = (reachable_function, generic_args_key(self.tcx, arguments));
+        if let Some(stub) = self.instance_stubs.get(&key) {
+            let Operand::Constant(function_definition) = operand else {
+                return;
+            };
+            debug!(
+                original = self.tcx.def_path_debug_str(reachable_function),
+                ?arguments,
+                replaced = self.tcx.def_path_debug_str(*stub),
+                "transform_stubbed_instances"
+            );
+            // The stub either has the same generic parameters as the original
+            // function/method or none at all.
+            let stub_arguments = if self.tcx.generics_of(*stub).count() == 0 {
+                GenericArgs::empty()
+            } else {
+                arguments
+            };
+            function_definition.const_ = Const::from_value(
+                ConstValue::ZeroSized,
+                self.tcx.type_of(*stub).instantiate(self.tcx, stub_arguments),
+            );
+        }
+    }
+}
+
 /// Checks whether the stub is compatible with the original function/method: do
 /// the arities and types (of the parameters and return values) match up? This
 /// does **NOT** check whether the type variables are constrained to implement
@@ -166,16 +271,17 @@ fn check_compatibility<'a, 'tcx>(
 const RUSTC_ARG_PREFIX: &str = "kani_stubs=";
 
 /// Serializes the stub mapping into a rustc argument.
-pub fn mk_rustc_arg(stub_mapping: &BTreeMap<DefPathHash, DefPathHash>) -> String {
+pub fn mk_rustc_arg(stub_mapping: &BTreeMap<StubTarget, DefPathHash>) -> String {
     // Serialize each `DefPathHash` as a pair of `u64`s, and the whole mapping
-    // as an association list.
+    // as an association list. The key of the generic arguments of an instance
+    // is kept next to the original function/method.
     let mut pairs = Vec::new();
-    for (k, v) in stub_mapping {
+    for ((k, args), v) in stub_mapping {
         let (k_a, k_b) = k.0.split();
         let kparts = (k_a.as_u64(), k_b.as_u64());
         let (v_a, v_b) = v.0.split();
         let vparts = (v_a.as_u64(), v_b.as_u64());
-        pairs.push((kparts, vparts));
+        pairs.push(((kparts, args), vparts));
     }
     // Store our serialized mapping as a fake LLVM argument (safe to do since
     // LLVM will never see them).
@@ -183,24 +289,24 @@ pub fn mk_rustc_arg(stub_mapping: &BTreeMap<DefPathHash, DefPathHash>) -> String
 }
 
 /// Deserializes the stub mapping from the rustc argument value.
-fn deserialize_mapping(tcx: TyCtxt, val: &str) -> HashMap<DefId, DefId> {
+fn deserialize_mapping(tcx: TyCtxt, val: &str) -> HashMap<(DefId, Option<String>), DefId> {
     type Item = (u64, u64);
     let item_to_def_id = |item: Item| -> DefId {
         let hash = DefPathHash(Fingerprint::new(item.0, item.1));
         tcx.def_path_hash_to_def_id(hash, &mut || panic!())
     };
-    let pairs: Vec<(Item, Item)> = serde_json::from_str(val).unwrap();
+    let pairs: Vec<((Item, Option<String>), Item)> = serde_json::from_str(val).unwrap();
     let mut m = HashMap::default();
-    for (k, v) in pairs {
+    for ((k, args), v) in pairs {
         let kid = item_to_def_id(k);
         let vid = item_to_def_id(v);
-        m.insert(kid, vid);
+        m.insert((kid, args), vid);
     }
     m
 }
 
 /// Retrieves the stub mapping from the compiler configuration.
-fn get_stub_mapping(tcx: TyCtxt) -> Option<HashMap<DefId, DefId>> {
+fn get_stub_mapping(tcx: TyCtxt) -> Option<HashMap<(DefId, Option<String>), DefId>> {
     // Use a static so that we compile the regex only once.
     lazy_static! {
         static ref RE: Regex = Regex::new(&format!("'{RUSTC_ARG_PREFIX}(.*)'")).unwrap();
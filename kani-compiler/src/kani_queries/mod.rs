@@ -6,7 +6,7 @@
 use kani_metadata::AssignsContract;
 use std::fmt::{Display, Formatter, Write};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::PathBuf,
     sync::{Arc, Mutex},
 };
@@ -19,7 +19,8 @@ pub struct QueryDb {
     args: Option<Arguments>,
     /// Information about all target harnesses.
     pub harnesses_info: HashMap<InternedString, PathBuf>,
-    modifies_contracts: HashMap<InternedString, AssignsContract>,
+    modifies_contracts: HashMap<InternedString, Vec<AssignsContract>>,
+    loop_contract_harnesses: HashSet<InternedString>,
 }
 
 impl QueryDb {
@@ -45,14 +46,14 @@ pub fn args(&self) -> &Arguments {
         self.args.as_ref().expect("Arguments have not been initialized")
     }
 
-    /// Register that a CBMC-level `assigns` contract for a function that is
+    /// Register the CBMC-level `assigns` contracts for the functions that are
     /// called from this harness.
-    pub fn register_assigns_contract(
+    pub fn register_assigns_contracts(
         &mut self,
         harness_name: InternedString,
-        contract: AssignsContract,
+        contracts: Vec<AssignsContract>,
     ) {
-        let replaced = self.modifies_contracts.insert(harness_name, contract);
+        let replaced = self.modifies_contracts.insert(harness_name, contracts);
         assert!(
             replaced.is_none(),
             "Invariant broken, tried adding second modifies contracts to: {harness_name}",
@@ -60,10 +61,22 @@ pub fn register_assigns_contract(
     }
 
     /// Lookup all CBMC-level `assigns` contract were registered with
-    /// [`Self::add_assigns_contract`].
-    pub fn assigns_contracts(&self) -> impl Iterator<Item = (&InternedString, &AssignsContract)> {
+    /// [`Self::register_assigns_contracts`].
+    pub fn assigns_contracts(
+        &self,
+    ) -> impl Iterator<Item = (&InternedString, &Vec<AssignsContract>)> {
         self.modifies_contracts.iter()
     }
+
+    /// Register that a loop with a contract is reachable from this harness.
+    pub fn register_loop_contracts(&mut self, harness_name: InternedString) {
+        self.loop_contract_harnesses.insert(harness_name);
+    }
+
+    /// Whether [`Self::register_loop_contracts`] was called for this harness.
+    pub fn has_loop_contracts(&self, harness_name: InternedString) -> bool {
+        self.loop_contract_harnesses.contains(&harness_name)
+    }
 }
 
 struct PrintList<I>(I);
@@ -87,6 +87,21 @@ pub struct StandaloneArgs {
 pub enum StandaloneSubcommand {
     /// Execute concrete playback testcases of a local crate.
     Playback(Box<playback_args::KaniPlaybackArgs>),
+
+    /// Check that all function contracts of a local crate are well-formed without running
+    /// verification.
+    CheckContracts(Box<KaniCheckContractsArgs>),
+}
+
+/// Check that all function contracts of a local crate are well-formed without running
+/// verification.
+#[derive(Debug, clap::Parser)]
+pub struct KaniCheckContractsArgs {
+    /// Rust crate's top file location.
+    pub input: PathBuf,
+
+    #[command(flatten)]
+    pub verify_opts: VerificationArgs,
 }
 
 #[derive(Debug, clap::Parser)]
@@ -112,6 +127,10 @@ pub enum CargoKaniSubcommand {
 
     /// Execute concrete playback testcases of a local package.
     Playback(Box<playback_args::CargoPlaybackArgs>),
+
+    /// Check that all function contracts of a local package are well-formed without running
+    /// verification.
+    CheckContracts,
 }
 
 // Common arguments for invoking Kani for verification purpose. This gets put into KaniContext,
@@ -137,6 +156,12 @@ pub struct VerificationArgs {
         value_enum
     )]
     pub concrete_playback: Option<ConcretePlaybackMode>,
+    /// Generate a standalone file with the concrete playback unit test(s) of each failing harness.
+    /// The file is written next to the harness source and can be included as a test module to
+    /// reproduce the counterexample outside of Kani.
+    /// This option requires `-Z concrete-playback` to be used.
+    #[arg(long, hide_short_help = true, conflicts_with_all(&["visualize", "concrete_playback"]))]
+    pub gen_concrete_test: bool,
     /// Keep temporary files generated throughout Kani process. This is already the default
     /// behavior for `cargo-kani`.
     #[arg(long, hide_short_help = true)]
@@ -147,6 +172,22 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true, requires("enable_unstable"),
         conflicts_with_all(&["function"]))]
     pub gen_c: bool,
+    /// Also replace the names of local variables in the C file generated by `--gen-c` with their
+    /// names in the source code.
+    #[arg(long, hide_short_help = true, requires("gen_c"))]
+    pub demangle_locals: bool,
+    /// Generate one C file for all harnesses instead of one per harness, e.g. to audit the code of
+    /// a whole crate. The goto models of the harnesses are linked together before they are
+    /// specialized for a harness and instrumented. Harnesses that use stubs or contracts are not
+    /// supported, since their code differs from the linked one.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("gen_c"), requires("enable_unstable"))]
+    pub gen_c_crate: bool,
+    /// Write the goto functions of each harness after contract instrumentation as JSON next to
+    /// its goto binary, e.g. to inspect which contracts were enforced or replaced.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub dump_goto_model: bool,
 
     /// Directory for all generated artifacts.
     #[arg(long)]
@@ -182,6 +223,10 @@ pub struct VerificationArgs {
     #[arg(long, requires("harnesses"))]
     pub exact: bool,
 
+    /// Also verify harnesses marked with `#[kani::slow]` when no harness filter is given.
+    #[arg(long)]
+    pub include_slow: bool,
+
     /// Link external C files referenced by Rust code.
     /// This is an experimental feature and requires `-Z c-ffi` to be used
     #[arg(long, hide = true, num_args(1..))]
@@ -192,6 +237,83 @@ pub struct VerificationArgs {
     /// Kani will only compile the crate. No verification will be performed
     #[arg(long, hide_short_help = true)]
     pub only_codegen: bool,
+    /// Fail before verification if the percentage of functions with a contract that are checked
+    /// by a `proof_for_contract` harness is below this threshold.
+    /// This option requires `-Z function-contracts` to be used.
+    #[arg(
+        long,
+        hide_short_help = true,
+        value_name = "PERCENT",
+        value_parser = clap::value_parser!(u8).range(0..=100)
+    )]
+    pub contract_coverage_threshold: Option<u8>,
+    /// List every function with a contract together with the functions that check and replace
+    /// it and the `proof_for_contract` harnesses that target it, instead of running verification.
+    /// Kani fails if any of these functions is not checked by a harness.
+    /// This option requires `-Z function-contracts` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub list_contracts: bool,
+    /// Write the contracts of the crate, including their clauses, `modifies` targets and
+    /// locations, to this JSON file. The file can be compared against a later version of the
+    /// crate with `--compare-contracts`.
+    /// This option requires `-Z function-contracts` to be used.
+    #[arg(long, hide_short_help = true, value_name = "FILE")]
+    pub export_contracts: Option<PathBuf>,
+    /// Report which contracts were added, removed or changed since the JSON file written by
+    /// `--export-contracts`, instead of running verification.
+    /// This option requires `-Z function-contracts` to be used.
+    #[arg(long, hide_short_help = true, value_name = "FILE")]
+    pub compare_contracts: Option<PathBuf>,
+    /// For every function with a contract, report separately whether its `proof_for_contract`
+    /// harnesses (enforcement) and the harnesses that use it through `stub_verified`
+    /// (replacement) succeeded. A function with a contract that is checked by a
+    /// `proof_for_contract` harness is also replaced in a generated harness, which calls it with
+    /// `kani::any()` arguments that satisfy its preconditions.
+    /// This option requires `-Z function-contracts` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub enforce_and_replace: bool,
+    /// Report a violated precondition of a function that was replaced by its contract
+    /// (`stub_verified`) at the call site that violated it, instead of at the contract.
+    /// This option requires `-Z function-contracts` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub precondition_call_site: bool,
+    /// In every `proof_for_contract` harness, replace all other functions of the crate that have
+    /// a contract by their contract, as if they were listed in `stub_verified`. This verifies
+    /// each contract assuming the contracts of the functions it calls.
+    /// This option requires `-Z function-contracts` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub replace_all_contracts: bool,
+    /// Only enforce the given kinds of contract clauses in `proof_for_contract` harnesses,
+    /// e.g. `--enforce-clauses=assigns` to check the write set of a function but not its
+    /// postconditions. By default all clauses are enforced.
+    /// This option requires `-Z function-contracts` to be used.
+    #[arg(long, hide_short_help = true, value_delimiter = ',', require_equals = true, value_enum)]
+    pub enforce_clauses: Option<Vec<ContractClause>>,
+    /// Only verify the `proof_for_contract` harnesses whose source, the source of a function of
+    /// the crate they reach, a contract they reach, or the verification options changed since
+    /// they last succeeded. The fingerprints of successful harnesses are stored in the output
+    /// directory.
+    /// This option requires `-Z function-contracts` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub changed_only: bool,
+    /// After verification, print a JSON report that tells for every `requires` and `ensures`
+    /// clause checked by a `proof_for_contract` harness whether it was reachable and checked
+    /// something, e.g. to find contracts that pass vacuously.
+    /// This option requires `-Z function-contracts` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub contract_report: bool,
+    /// Report the resources CBMC used for each harness (peak memory, solver time, formula size)
+    /// after verification. Peak memory is only measured on Linux. Pass `--stats=json` to print the report as JSON.
+    #[arg(
+        long,
+        hide_short_help = true,
+        num_args(0..=1),
+        require_equals = true,
+        default_missing_value = "text",
+        ignore_case = true,
+        value_enum
+    )]
+    pub stats: Option<StatsFormat>,
 
     /// Deprecated flag. This is a no-op since we no longer support the legacy linker and
     /// it will be removed in a future Kani release.
@@ -207,10 +329,30 @@ pub struct VerificationArgs {
     /// Specify the value used for loop unwinding for the specified harness in CBMC
     #[arg(long, requires("harnesses"))]
     pub unwind: Option<u32>,
+    /// Find the smallest unwind value, up to the given maximum, for which no unwinding assertion
+    /// of the selected harness fails. Each value is checked with a separate run of CBMC.
+    /// This feature is unstable and it requires `-Z search-unwind` to be used.
+    #[arg(
+        long,
+        value_name = "MAX",
+        requires("harnesses"),
+        conflicts_with("unwind"),
+        conflicts_with("default_unwind")
+    )]
+    pub search_unwind: Option<u32>,
     /// Specify the CBMC solver to use. Overrides the harness `solver` attribute.
     /// If no solver is specified (with --solver or harness attribute), Kani will use CaDiCaL.
     #[arg(long, value_parser = CbmcSolverValueParser::new(CbmcSolver::VARIANTS))]
     pub solver: Option<CbmcSolver>,
+    /// Stop CBMC after this many seconds. If the harness `solver` attribute lists more solvers,
+    /// verification is retried with the next one.
+    #[arg(long, value_name = "SECONDS")]
+    pub solver_timeout: Option<u64>,
+    /// Stop verifying a harness after this many seconds of CBMC runtime, report it as timed out
+    /// and continue with the next harness. The limit covers every CBMC run of the harness,
+    /// including the retries with the other solvers of its `solver` attribute.
+    #[arg(long, value_name = "SECONDS")]
+    pub harness_timeout: Option<u64>,
     /// Pass through directly to CBMC; must be the last flag.
     /// This feature is unstable and it requires `--enable_unstable` to be used
     #[arg(
@@ -285,7 +427,7 @@ pub struct VerificationArgs {
         long,
         hide_short_help = true,
         requires("enable_unstable"),
-        conflicts_with("concrete_playback")
+        conflicts_with_all(&["concrete_playback", "gen_concrete_test"])
     )]
     enable_stubbing: bool,
 
@@ -334,11 +476,27 @@ pub fn jobs(&self) -> Option<usize> {
         }
     }
 
+    /// The concrete playback mode selected either via `--concrete-playback` or via
+    /// `--gen-concrete-test`.
+    pub fn concrete_playback_mode(&self) -> Option<ConcretePlaybackMode> {
+        self.concrete_playback.or(self.gen_concrete_test.then_some(ConcretePlaybackMode::File))
+    }
+
+    /// The option the user passed to select the concrete playback mode, for error messages.
+    fn concrete_playback_flag(&self) -> &'static str {
+        if self.gen_concrete_test { "--gen-concrete-test" } else { "--concrete-playback" }
+    }
+
     /// Are experimental function contracts enabled?
     pub fn is_function_contracts_enabled(&self) -> bool {
         self.common_args.unstable_features.contains(UnstableFeature::FunctionContracts)
     }
 
+    /// Does a `proof_for_contract` harness enforce clauses of kind `clause`?
+    pub fn enforces_clause(&self, clause: ContractClause) -> bool {
+        self.enforce_clauses.as_ref().map_or(true, |clauses| clauses.contains(&clause))
+    }
+
     /// Is experimental stubbing enabled?
     pub fn is_stubbing_enabled(&self) -> bool {
         self.enable_stubbing
@@ -353,6 +511,25 @@ pub enum ConcretePlaybackMode {
     // Otherwise clap will default to `in-place`
     #[value(name = "inplace")]
     InPlace,
+    /// Only selected through `--gen-concrete-test`.
+    #[value(skip)]
+    File,
+}
+
+/// The kinds of contract clauses a `proof_for_contract` harness can enforce.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, strum_macros::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ContractClause {
+    /// The postconditions (`ensures`).
+    Ensures,
+    /// The write set (`modifies`), which CBMC enforces as an assigns clause.
+    Assigns,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum StatsFormat {
+    Text,
+    Json,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
@@ -437,6 +614,9 @@ fn check_no_cargo_opt(is_set: bool, name: &str) -> Result<(), Error> {
 impl ValidateArgs for StandaloneArgs {
     fn validate(&self) -> Result<(), Error> {
         self.verify_opts.validate()?;
+        if let Some(StandaloneSubcommand::CheckContracts(check_contracts)) = &self.command {
+            check_contracts.validate()?;
+        }
         // Cargo target arguments.
         check_no_cargo_opt(self.verify_opts.target.bins, "--bins")?;
         check_no_cargo_opt(self.verify_opts.target.lib, "--lib")?;
@@ -464,6 +644,29 @@ fn validate(&self) -> Result<(), Error> {
     }
 }
 
+impl ValidateArgs for KaniCheckContractsArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.verify_opts.validate()?;
+        if !self.verify_opts.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `check-contracts` subcommand is unstable and requires `-Z function-contracts` \
+                to be used.",
+            ));
+        }
+        if !self.input.is_file() {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!(
+                    "Invalid argument: Input invalid. `{}` is not a regular file.",
+                    self.input.display()
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
 impl<T> ValidateArgs for Option<T>
 where
     T: ValidateArgs,
@@ -479,6 +682,7 @@ fn validate(&self) -> Result<(), Error> {
             // Assess doesn't implement validation yet.
             CargoKaniSubcommand::Assess(_) => Ok(()),
             CargoKaniSubcommand::Playback(playback) => playback.validate(),
+            CargoKaniSubcommand::CheckContracts => Ok(()),
         }
     }
 }
@@ -496,6 +700,15 @@ fn validate(&self) -> Result<(), Error> {
                 "Assess is unstable and requires 'cargo kani --enable-unstable assess'",
             ));
         }
+        if matches!(self.command, Some(CargoKaniSubcommand::CheckContracts))
+            && !self.verify_opts.is_function_contracts_enabled()
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `check-contracts` subcommand is unstable and requires `-Z function-contracts` \
+                to be used.",
+            ));
+        }
         Ok(())
     }
 }
@@ -507,7 +720,7 @@ fn validate(&self) -> Result<(), Error> {
             self.cbmc_args.iter().any(|s| s.to_str().unwrap().starts_with("--unwind"));
         let natives_unwind = self.default_unwind.is_some() || self.unwind.is_some();
 
-        if self.randomize_layout.is_some() && self.concrete_playback.is_some() {
+        if self.randomize_layout.is_some() && self.concrete_playback_mode().is_some() {
             let random_seed = if let Some(seed) = self.randomize_layout.unwrap() {
                 format!(" -Z layout-seed={seed}")
             } else {
@@ -557,18 +770,23 @@ fn validate(&self) -> Result<(), Error> {
                 "Conflicting options: --concrete-playback=print and --quiet.",
             ));
         }
-        if self.concrete_playback.is_some() && self.output_format == OutputFormat::Old {
+        if self.concrete_playback_mode().is_some() && self.output_format == OutputFormat::Old {
             return Err(Error::raw(
                 ErrorKind::ArgumentConflict,
-                "Conflicting options: --concrete-playback isn't compatible with \
-                --output-format=old.",
+                format!(
+                    "Conflicting options: {} isn't compatible with --output-format=old.",
+                    self.concrete_playback_flag()
+                ),
             ));
         }
-        if self.concrete_playback.is_some() && self.jobs() != Some(1) {
+        if self.concrete_playback_mode().is_some() && self.jobs() != Some(1) {
             // Concrete playback currently embeds a lot of assumptions about the order in which harnesses get called.
             return Err(Error::raw(
                 ErrorKind::ArgumentConflict,
-                "Conflicting options: --concrete-playback isn't compatible with --jobs.",
+                format!(
+                    "Conflicting options: {} isn't compatible with --jobs.",
+                    self.concrete_playback_flag()
+                ),
             ));
         }
         if self.jobs.is_some() && self.output_format != OutputFormat::Terse {
@@ -595,6 +813,113 @@ fn validate(&self) -> Result<(), Error> {
             print_deprecated(&self.common_args, "--enable-stubbing", "-Z stubbing");
         }
 
+        if self.contract_coverage_threshold.is_some() && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--contract-coverage-threshold` argument is unstable and requires `-Z \
+                function-contracts` to be used.",
+            ));
+        }
+
+        if self.list_contracts && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--list-contracts` argument is unstable and requires `-Z \
+                function-contracts` to be used.",
+            ));
+        }
+
+        if self.export_contracts.is_some() && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--export-contracts` argument is unstable and requires `-Z \
+                function-contracts` to be used.",
+            ));
+        }
+
+        if self.compare_contracts.is_some() && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--compare-contracts` argument is unstable and requires `-Z \
+                function-contracts` to be used.",
+            ));
+        }
+
+        if self.enforce_and_replace && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--enforce-and-replace` argument is unstable and requires `-Z \
+                function-contracts` to be used.",
+            ));
+        }
+
+        if self.search_unwind.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::SearchUnwind)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--search-unwind` argument is unstable and requires `-Z search-unwind` to be \
+                used.",
+            ));
+        }
+
+        if self.search_unwind.is_some() && !self.checks.unwinding_on() {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "Conflicting options: --search-unwind requires unwinding checks to be enabled.",
+            ));
+        }
+
+        if self.precondition_call_site && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--precondition-call-site` argument is unstable and requires `-Z \
+                function-contracts` to be used.",
+            ));
+        }
+
+        if self.enforce_clauses.is_some() && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--enforce-clauses` argument is unstable and requires `-Z \
+                function-contracts` to be used.",
+            ));
+        }
+
+        if self.changed_only && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--changed-only` argument is unstable and requires `-Z \
+                function-contracts` to be used.",
+            ));
+        }
+
+        if self.replace_all_contracts && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--replace-all-contracts` argument is unstable and requires `-Z \
+                function-contracts` to be used.",
+            ));
+        }
+
+        if self.contract_report && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--contract-report` argument is unstable and requires `-Z \
+                function-contracts` to be used.",
+            ));
+        }
+
+        if self.gen_concrete_test
+            && !self.common_args.unstable_features.contains(UnstableFeature::ConcretePlayback)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--gen-concrete-test` argument is unstable and requires `-Z \
+                concrete-playback` to be used.",
+            ));
+        }
+
         if self.concrete_playback.is_some()
             && !self.common_args.unstable_features.contains(UnstableFeature::ConcretePlayback)
         {
@@ -668,7 +993,7 @@ fn parse_ref(
             if segments[0] != "bin" {
                 return Err(err);
             }
-            return Ok(CbmcSolver::Binary(segments[1].into()));
+            return Ok(CbmcSolver::Binary { binary: segments[1].into(), args: vec![] });
         } else if segments.len() == 1 {
             let solver = CbmcSolver::from_str(value);
             return solver.map_err(|_| err);
@@ -825,6 +1150,11 @@ fn check_restrict_cbmc_args() {
         check_opt!("--cbmc-args --json-ui", true, cbmc_args, vec!["--json-ui"]);
     }
 
+    #[test]
+    fn check_dump_goto_model_unstable() {
+        check_unstable_flag!("--dump-goto-model", dump_goto_model);
+    }
+
     #[test]
     fn check_disable_slicing_unstable() {
         check_unstable_flag!("--no-slice-formula", no_slice_formula);
@@ -843,6 +1173,22 @@ fn check_concrete_playback_unstable() {
 
         check("kani file.rs --concrete-playback=inplace");
         check("kani file.rs --concrete-playback=print");
+        check("kani file.rs --gen-concrete-test");
+    }
+
+    #[test]
+    fn check_gen_concrete_test() {
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs -Z concrete-playback --gen-concrete-test".split_whitespace(),
+        )
+        .unwrap();
+        assert_eq!(args.verify_opts.concrete_playback_mode(), Some(ConcretePlaybackMode::File));
+
+        let err = StandaloneArgs::try_parse_from(
+            "kani file.rs --gen-concrete-test --concrete-playback=print".split_whitespace(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
     }
 
     /// Check if parsing the given argument string results in the given error.
@@ -861,6 +1207,19 @@ fn check_concrete_playback_conflicts() {
             "kani --concrete-playback=inplace --output-format=old --enable-unstable test.rs",
             ErrorKind::ArgumentConflict,
         );
+
+        // The error names the option that selected concrete playback.
+        let message = |args: &str| {
+            let args = StandaloneArgs::try_parse_from(args.split_whitespace()).unwrap();
+            args.verify_opts.validate().unwrap_err().to_string()
+        };
+        let old = "kani --enable-unstable --gen-concrete-test --output-format=old test.rs";
+        assert!(message(old).contains("--gen-concrete-test isn't compatible with --output-format"));
+        let jobs = "kani --enable-unstable -Z concrete-playback --gen-concrete-test -j 2 test.rs";
+        assert!(message(jobs).contains("--gen-concrete-test isn't compatible with --jobs"));
+        let jobs =
+            "kani --enable-unstable -Z concrete-playback --concrete-playback=print -j 2 test.rs";
+        assert!(message(jobs).contains("--concrete-playback isn't compatible with --jobs"));
     }
 
     #[test]
@@ -897,6 +1256,254 @@ fn check_kani_playback() {
         assert!(matches!(args.command, Some(StandaloneSubcommand::Playback(..))));
     }
 
+    #[test]
+    fn check_contract_coverage_threshold() {
+        let args =
+            parse_unstable_disabled("-Z function-contracts --contract-coverage-threshold 80")
+                .unwrap();
+        assert_eq!(args.verify_opts.contract_coverage_threshold, Some(80));
+        assert!(args.verify_opts.validate().is_ok());
+
+        let err = parse_unstable_disabled("--contract-coverage-threshold 80")
+            .unwrap()
+            .verify_opts
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let err =
+            parse_unstable_disabled("-Z function-contracts --contract-coverage-threshold 101")
+                .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+    }
+
+    #[test]
+    fn check_demangle_locals() {
+        let args = parse_unstable_enabled("--gen-c --demangle-locals").unwrap();
+        assert!(args.verify_opts.demangle_locals);
+
+        let err = parse_unstable_enabled("--demangle-locals").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_gen_c_crate() {
+        let args = parse_unstable_enabled("--gen-c --gen-c-crate").unwrap();
+        assert!(args.verify_opts.gen_c_crate);
+
+        let err = parse_unstable_enabled("--gen-c-crate").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let err = parse_unstable_disabled("--gen-c --gen-c-crate").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_list_contracts() {
+        let args = parse_unstable_disabled("-Z function-contracts --list-contracts").unwrap();
+        assert!(args.verify_opts.list_contracts);
+        assert!(args.verify_opts.validate().is_ok());
+
+        let err = parse_unstable_disabled("--list-contracts")
+            .unwrap()
+            .verify_opts
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_export_and_compare_contracts() {
+        let args = parse_unstable_disabled(
+            "-Z function-contracts --export-contracts new.json --compare-contracts old.json",
+        )
+        .unwrap();
+        assert_eq!(args.verify_opts.export_contracts, Some(PathBuf::from("new.json")));
+        assert_eq!(args.verify_opts.compare_contracts, Some(PathBuf::from("old.json")));
+        assert!(args.verify_opts.validate().is_ok());
+
+        for flags in ["--export-contracts new.json", "--compare-contracts old.json"] {
+            let err = parse_unstable_disabled(flags).unwrap().verify_opts.validate().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+        }
+    }
+
+    #[test]
+    fn check_enforce_and_replace() {
+        let args = parse_unstable_disabled("-Z function-contracts --enforce-and-replace").unwrap();
+        assert!(args.verify_opts.enforce_and_replace);
+        assert!(args.verify_opts.validate().is_ok());
+
+        let err = parse_unstable_disabled("--enforce-and-replace")
+            .unwrap()
+            .verify_opts
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_precondition_call_site() {
+        let args =
+            parse_unstable_disabled("-Z function-contracts --precondition-call-site").unwrap();
+        assert!(args.verify_opts.precondition_call_site);
+        assert!(args.verify_opts.validate().is_ok());
+
+        let err = parse_unstable_disabled("--precondition-call-site")
+            .unwrap()
+            .verify_opts
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_include_slow() {
+        let args = StandaloneArgs::try_parse_from(["kani", "file.rs"]).unwrap();
+        assert!(!args.verify_opts.include_slow);
+        assert!(parse_unstable_disabled("--include-slow").unwrap().verify_opts.include_slow);
+    }
+
+    #[test]
+    fn check_enforce_clauses() {
+        let args = parse_unstable_disabled("-Z function-contracts").unwrap();
+        assert!(args.verify_opts.enforces_clause(ContractClause::Ensures));
+        assert!(args.verify_opts.enforces_clause(ContractClause::Assigns));
+
+        let args =
+            parse_unstable_disabled("-Z function-contracts --enforce-clauses=assigns").unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(!args.verify_opts.enforces_clause(ContractClause::Ensures));
+        assert!(args.verify_opts.enforces_clause(ContractClause::Assigns));
+
+        let args =
+            parse_unstable_disabled("-Z function-contracts --enforce-clauses=ensures,assigns")
+                .unwrap();
+        assert!(args.verify_opts.enforces_clause(ContractClause::Ensures));
+        assert!(args.verify_opts.enforces_clause(ContractClause::Assigns));
+
+        let err = parse_unstable_disabled("-Z function-contracts --enforce-clauses=requires")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+
+        let err = parse_unstable_disabled("--enforce-clauses=assigns")
+            .unwrap()
+            .verify_opts
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_changed_only() {
+        let args = parse_unstable_disabled("-Z function-contracts --changed-only").unwrap();
+        assert!(args.verify_opts.changed_only);
+        assert!(args.verify_opts.validate().is_ok());
+
+        let err =
+            parse_unstable_disabled("--changed-only").unwrap().verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_contract_report() {
+        let args = parse_unstable_disabled("-Z function-contracts --contract-report").unwrap();
+        assert!(args.verify_opts.contract_report);
+        assert!(args.verify_opts.validate().is_ok());
+
+        let err = parse_unstable_disabled("--contract-report")
+            .unwrap()
+            .verify_opts
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_replace_all_contracts() {
+        let args =
+            parse_unstable_disabled("-Z function-contracts --replace-all-contracts").unwrap();
+        assert!(args.verify_opts.replace_all_contracts);
+        assert!(args.verify_opts.validate().is_ok());
+
+        let err = parse_unstable_disabled("--replace-all-contracts")
+            .unwrap()
+            .verify_opts
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_stats() {
+        let args = StandaloneArgs::try_parse_from(["kani", "file.rs"]).unwrap();
+        assert_eq!(args.verify_opts.stats, None);
+        let args = parse_unstable_disabled("--stats").unwrap();
+        assert_eq!(args.verify_opts.stats, Some(StatsFormat::Text));
+        let args = parse_unstable_disabled("--stats=json").unwrap();
+        assert_eq!(args.verify_opts.stats, Some(StatsFormat::Json));
+        let err = parse_unstable_disabled("--stats=yaml").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn check_search_unwind() {
+        let args =
+            parse_unstable_disabled("-Z search-unwind --harness check --search-unwind 10").unwrap();
+        assert_eq!(args.verify_opts.search_unwind, Some(10));
+        assert!(args.verify_opts.validate().is_ok());
+
+        let err = parse_unstable_disabled("--harness check --search-unwind 10")
+            .unwrap()
+            .verify_opts
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let err = parse_unstable_disabled("-Z search-unwind --search-unwind 10").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let err = parse_unstable_disabled(
+            "-Z search-unwind --harness check --search-unwind 10 --unwind 2",
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+
+        let err = parse_unstable_disabled(
+            "-Z search-unwind --harness check --search-unwind 10 --no-unwinding-checks",
+        )
+        .unwrap()
+        .verify_opts
+        .validate()
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_cargo_kani_check_contracts() {
+        let parse = |args: &str| CargoKaniArgs::try_parse_from(args.split_whitespace()).unwrap();
+        let args = parse("cargo-kani -Z function-contracts check-contracts");
+        assert!(matches!(args.command, Some(CargoKaniSubcommand::CheckContracts)));
+        assert!(args.validate().is_ok());
+
+        let err = parse("cargo-kani check-contracts").validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_standalone_check_contracts() {
+        let parse = |args: &str| StandaloneArgs::try_parse_from(args.split_whitespace()).unwrap();
+        let args = parse("kani check-contracts -Z function-contracts file.rs");
+        let Some(StandaloneSubcommand::CheckContracts(check_contracts)) = &args.command else {
+            panic!("expected the `check-contracts` subcommand, found {:?}", args.command)
+        };
+        assert_eq!(check_contracts.input, PathBuf::from("file.rs"));
+        assert!(check_contracts.verify_opts.is_function_contracts_enabled());
+
+        let err = parse("kani check-contracts file.rs").validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
     #[test]
     fn check_standalone_does_not_accept_cargo_opts() {
         fn check_invalid_args<'a, I>(args: I)
@@ -2,10 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::{bail, Result};
-use kani_metadata::{CbmcSolver, HarnessMetadata};
+use kani_metadata::{AssignsTarget, CbmcSolver, HarnessMetadata, PointerCheck, ShouldPanicMode};
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::ffi::OsString;
 use std::fmt::Write;
-use std::path::Path;
+use std::fs::Permissions;
+use std::io::Write as _;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{Duration, Instant};
 
@@ -24,6 +29,9 @@
 pub enum VerificationStatus {
     Success,
     Failure,
+    /// CBMC was stopped because the harness exceeded the `--harness-timeout`, so the harness is
+    /// neither verified nor known to fail.
+    Timeout,
 }
 
 /// Represents failed properties in three different categories.
@@ -34,6 +42,12 @@ pub enum FailedProperties {
     None,
     // One or more panic-related failures
     PanicsOnly,
+    // One or more panic-related failures, but also paths on which the harness returns without
+    // panicking. Only detected for `#[kani::should_panic(mode = "all")]` harnesses.
+    PanicsOnSomePaths,
+    // One or more panic-related failures, but some of them without the expected message. Only
+    // detected for `#[kani::should_panic(expected = "...")]` harnesses.
+    UnexpectedPanics,
     // One or more failures that aren't panic-related
     Other,
 }
@@ -57,12 +71,101 @@ pub struct VerificationResult {
     pub runtime: Duration,
     /// Whether concrete playback generated a test
     pub generated_concrete_test: bool,
+    /// The solver CBMC used, or `None` if CBMC was not run.
+    pub solver: Option<CbmcSolver>,
+    /// Whether CBMC was stopped because it exceeded the `--solver-timeout`.
+    pub timed_out: bool,
+    /// The peak resident memory of CBMC in bytes, if it could be measured.
+    pub peak_memory: Option<u64>,
+}
+
+/// The resources used by one CBMC invocation, as reported in its status messages. Each field is
+/// `None` if CBMC did not report it, e.g. because verification stopped early.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct CbmcStats {
+    /// Seconds spent in symbolic execution.
+    pub symex_runtime: Option<f64>,
+    /// Seconds spent in the SAT/SMT solver.
+    pub solver_runtime: Option<f64>,
+    /// Seconds spent in the decision procedure, i.e. converting and solving the formula.
+    pub decision_procedure_runtime: Option<f64>,
+    /// The size of the program after symbolic execution.
+    pub program_steps: Option<u64>,
+    /// The number of variables of the propositional formula.
+    pub variables: Option<u64>,
+    /// The number of clauses of the propositional formula.
+    pub clauses: Option<u64>,
+}
+
+impl CbmcStats {
+    /// Extract the statistics from the messages CBMC printed. Runtimes are summed up over all
+    /// solver runs, for the formula size we keep the last one reported.
+    pub fn from_messages(items: &[ParserItem]) -> CbmcStats {
+        fn add_seconds(total: &mut Option<f64>, text: &str) {
+            if let Ok(seconds) = text.trim_end_matches('s').parse::<f64>() {
+                *total = Some(total.unwrap_or_default() + seconds);
+            }
+        }
+
+        let mut stats = CbmcStats::default();
+        for item in items {
+            let ParserItem::Message { message_text, .. } = item else { continue };
+            let text = message_text.as_str();
+            if let Some(seconds) = text.strip_prefix("Runtime Symex: ") {
+                add_seconds(&mut stats.symex_runtime, seconds);
+            } else if let Some(seconds) = text.strip_prefix("Runtime Solver: ") {
+                add_seconds(&mut stats.solver_runtime, seconds);
+            } else if let Some(seconds) = text.strip_prefix("Runtime decision procedure: ") {
+                add_seconds(&mut stats.decision_procedure_runtime, seconds);
+            } else if let Some(steps) = text
+                .strip_prefix("size of program expression: ")
+                .and_then(|rest| rest.strip_suffix(" steps"))
+            {
+                stats.program_steps = steps.parse().ok();
+            } else if let Some((variables, clauses)) =
+                text.strip_suffix(" clauses").and_then(|rest| rest.split_once(" variables, "))
+            {
+                if let (Ok(variables), Ok(clauses)) = (variables.parse(), clauses.parse()) {
+                    stats.variables = Some(variables);
+                    stats.clauses = Some(clauses);
+                }
+            }
+        }
+        stats
+    }
 }
 
 impl KaniSession {
-    /// Verify a goto binary that's been prepared with goto-instrument
+    /// Verify a goto binary that's been prepared with goto-instrument.
+    ///
+    /// If the harness lists several solvers, they are tried in order until one of them finishes
+    /// within the `--solver-timeout`. All of them together must finish within the
+    /// `--harness-timeout`, otherwise the result has the status [`VerificationStatus::Timeout`].
     pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<VerificationResult> {
-        let args: Vec<OsString> = self.cbmc_flags(file, harness)?;
+        let deadline =
+            self.args.harness_timeout.map(|secs| Instant::now() + Duration::from_secs(secs));
+        let solvers = self.solvers(harness);
+        let (last, fallbacks) = solvers.split_last().unwrap();
+        for solver in fallbacks {
+            let result = self.run_cbmc_with_solver(file, harness, solver, deadline)?;
+            if !result.timed_out || result.status == VerificationStatus::Timeout {
+                return Ok(result);
+            }
+            if !self.args.common_args.quiet {
+                println!("CBMC timed out with solver `{solver}`, trying the next solver...");
+            }
+        }
+        self.run_cbmc_with_solver(file, harness, last, deadline)
+    }
+
+    fn run_cbmc_with_solver(
+        &self,
+        file: &Path,
+        harness: &HarnessMetadata,
+        solver: &CbmcSolver,
+        deadline: Option<Instant>,
+    ) -> Result<VerificationResult> {
+        let args: Vec<OsString> = self.cbmc_flags_with_solver(file, harness, solver)?;
 
         // TODO get cbmc path from self
         let mut cmd = Command::new("cbmc");
@@ -70,11 +173,17 @@ pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<Verific
 
         let start_time = Instant::now();
 
-        let verification_results = if self.args.output_format == crate::args::OutputFormat::Old {
-            if self.run_terminal(cmd).is_err() {
-                VerificationResult::mock_failure()
-            } else {
-                VerificationResult::mock_success()
+        let timeout = cbmc_timeout(self.args.solver_timeout, deadline, start_time);
+        let mut verification_results = if self.args.output_format == OutputFormat::Old {
+            match self.run_terminal_with_timeout(cmd, timeout) {
+                Ok(false) => VerificationResult::mock_success(),
+                // CBMC was killed, i.e., exited with the status of SIGKILL.
+                Ok(true) => VerificationResult {
+                    results: Err(137),
+                    timed_out: true,
+                    ..VerificationResult::mock_failure()
+                },
+                Err(_) => VerificationResult::mock_failure(),
             }
         } else {
             // Add extra argument to receive the output in JSON format.
@@ -84,19 +193,39 @@ pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<Verific
             // Spawn the CBMC process and process its output below
             let cbmc_process_opt = self.run_piped(cmd)?;
             let cbmc_process = cbmc_process_opt.ok_or(anyhow::Error::msg("Failed to run cbmc"))?;
-            let output = process_cbmc_output(cbmc_process, |i| {
+            // Harnesses may enable the extra pointer checks with `#[kani::pointer_checks]`.
+            let extra_pointer_checks = self.args.extra_pointer_checks
+                || harness.attributes.pointer_checks.iter().any(|(check, enabled)| {
+                    *enabled && matches!(check, PointerCheck::Overflow | PointerCheck::Primitive)
+                });
+            let output = process_cbmc_output(cbmc_process, timeout, |i| {
                 kani_cbmc_output_filter(
                     i,
-                    self.args.extra_pointer_checks,
+                    extra_pointer_checks,
                     self.args.common_args.quiet,
                     &self.args.output_format,
                 )
             })?;
 
-            VerificationResult::from(output, harness.attributes.should_panic, start_time)
+            let mut result = VerificationResult::from(
+                output,
+                harness.attributes.should_panic,
+                harness.attributes.should_panic_expected.as_deref(),
+                start_time,
+            );
+            // Each run enforces the `modifies` clause of the first function under contract.
+            if let Some(contract) = harness.contract.first() {
+                result.describe_undeclared_writes(&contract.function, &contract.targets);
+            }
+            result
         };
+        if verification_results.timed_out
+            && deadline.is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            verification_results.status = VerificationStatus::Timeout;
+        }
 
-        Ok(verification_results)
+        Ok(VerificationResult { solver: Some(solver.clone()), ..verification_results })
     }
 
     /// used by call_cbmc_viewer, invokes different variants of CBMC.
@@ -116,25 +245,38 @@ pub fn call_cbmc(&self, args: Vec<OsString>, output: &Path) -> Result<()> {
         Ok(())
     }
 
-    /// "Internal," but also used by call_cbmc_viewer
+    /// "Internal," but also used by call_cbmc_viewer. Uses the first solver of the harness.
     pub fn cbmc_flags(
         &self,
         file: &Path,
         harness_metadata: &HarnessMetadata,
+    ) -> Result<Vec<OsString>> {
+        let solver = self.solvers(harness_metadata)[0];
+        self.cbmc_flags_with_solver(file, harness_metadata, solver)
+    }
+
+    fn cbmc_flags_with_solver(
+        &self,
+        file: &Path,
+        harness_metadata: &HarnessMetadata,
+        solver: &CbmcSolver,
     ) -> Result<Vec<OsString>> {
         let mut args = self.cbmc_check_flags();
+        apply_pointer_check_overrides(&harness_metadata.attributes.pointer_checks, &mut args);
 
         if let Some(object_bits) = self.args.cbmc_object_bits() {
             args.push("--object-bits".into());
             args.push(object_bits.to_string().into());
         }
 
-        if let Some(unwind_value) = resolve_unwind_value(&self.args, harness_metadata) {
-            args.push("--unwind".into());
-            args.push(unwind_value.to_string().into());
+        apply_unwind_value(&self.args, harness_metadata, &mut args);
+
+        if let Some(unwindset) = unwindset_value(harness_metadata) {
+            args.push("--unwindset".into());
+            args.push(unwindset.into());
         }
 
-        self.handle_solver_args(&harness_metadata.attributes.solver, &mut args)?;
+        self.handle_solver_args(solver, &mut args)?;
 
         if self.args.run_sanity_checks {
             args.push("--validate-goto-model".into());
@@ -142,13 +284,14 @@ pub fn cbmc_flags(
         }
 
         if !self.args.visualize
-            && self.args.concrete_playback.is_none()
+            && self.args.concrete_playback_mode().is_none()
             && !self.args.no_slice_formula
         {
             args.push("--slice-formula".into());
         }
 
-        if self.args.concrete_playback.is_some() {
+        // The call site of a violated precondition is recovered from the trace.
+        if self.args.concrete_playback_mode().is_some() || self.args.precondition_call_site {
             args.push("--trace".into());
         }
 
@@ -199,20 +342,19 @@ pub fn cbmc_check_flags(&self) -> Vec<OsString> {
         args
     }
 
-    pub fn handle_solver_args(
-        &self,
-        harness_solver: &Option<CbmcSolver>,
-        args: &mut Vec<OsString>,
-    ) -> Result<()> {
-        let solver = if let Some(solver) = &self.args.solver {
+    /// The solvers to try for `harness`, in order. Never empty.
+    pub fn solvers<'a>(&'a self, harness: &'a HarnessMetadata) -> Vec<&'a CbmcSolver> {
+        if let Some(solver) = &self.args.solver {
             // `--solver` option takes precedence over attributes
-            solver
-        } else if let Some(solver) = harness_solver {
-            solver
+            vec![solver]
+        } else if !harness.attributes.solver.is_empty() {
+            harness.attributes.solver.iter().collect()
         } else {
-            &DEFAULT_SOLVER
-        };
+            vec![&DEFAULT_SOLVER]
+        }
+    }
 
+    pub fn handle_solver_args(&self, solver: &CbmcSolver, args: &mut Vec<OsString>) -> Result<()> {
         match solver {
             CbmcSolver::Cadical => {
                 args.push("--sat-solver".into());
@@ -226,17 +368,59 @@ pub fn handle_solver_args(
                 // Minisat is currently CBMC's default solver, so no need to
                 // pass any arguments
             }
-            CbmcSolver::Binary(solver_binary) => {
+            CbmcSolver::Binary { binary, args: solver_args } => {
                 // Check if the specified binary exists in path
-                if which::which(solver_binary).is_err() {
-                    bail!("the specified solver \"{solver_binary}\" was not found in path")
+                if which::which(binary).is_err() {
+                    bail!("the specified solver \"{binary}\" was not found in path")
                 }
                 args.push("--external-sat-solver".into());
-                args.push(solver_binary.into());
+                if solver_args.is_empty() {
+                    args.push(binary.into());
+                } else {
+                    args.push(self.solver_wrapper(binary, solver_args)?.into());
+                }
             }
         }
         Ok(())
     }
+
+    /// CBMC invokes an external solver with the CNF file as its only argument. Write a script
+    /// that invokes `binary` with `solver_args` followed by the arguments that CBMC passes, and
+    /// return its path.
+    fn solver_wrapper(&self, binary: &str, solver_args: &[String]) -> Result<PathBuf> {
+        let mut script =
+            tempfile::Builder::new().prefix("kani_solver_").suffix(".sh").tempfile()?;
+        script.write_all(solver_wrapper_script(binary, solver_args).as_bytes())?;
+        script.as_file().set_permissions(Permissions::from_mode(0o755))?;
+        let path = script.into_temp_path().keep()?;
+        self.record_temporary_file(&path);
+        Ok(path)
+    }
+}
+
+/// A shell script that runs `binary` with `args` and then the arguments of the script.
+fn solver_wrapper_script(binary: &str, args: &[String]) -> String {
+    let quote = |arg: &str| format!("'{}'", arg.replace('\'', r"'\''"));
+    let command = std::iter::once(binary).chain(args.iter().map(String::as_str)).map(quote);
+    format!("#!/bin/sh\nexec {} \"$@\"\n", command.collect::<Vec<_>>().join(" "))
+}
+
+/// Turn the description CBMC gives an `assigns` check ("Check that X is assignable") into one
+/// that names the function under contract, the location it writes and the `modifies` targets
+/// of its contract as written by the user.
+fn undeclared_write_description(
+    description: &str,
+    function: &str,
+    targets: &[AssignsTarget],
+) -> Option<String> {
+    let location = description.strip_prefix("Check that ")?.strip_suffix(" is assignable")?;
+    let permitted = if targets.is_empty() {
+        "the contract has no `modifies` clause".to_string()
+    } else {
+        let targets = targets.iter().map(|target| target.source.as_str()).collect::<Vec<_>>();
+        format!("not permitted by `modifies({})`", targets.join(", "))
+    };
+    Some(format!("function `{function}` writes undeclared location `{location}`, {permitted}"))
 }
 
 impl VerificationResult {
@@ -250,7 +434,8 @@ impl VerificationResult {
     ///       (Do not mistake lack of results for success: report it as failure.)
     fn from(
         output: VerificationOutput,
-        should_panic: bool,
+        should_panic: Option<ShouldPanicMode>,
+        expected_panic: Option<&str>,
         start_time: Instant,
     ) -> VerificationResult {
         let runtime = start_time.elapsed();
@@ -258,7 +443,7 @@ fn from(
 
         if let Some(results) = results {
             let (status, failed_properties) =
-                verification_outcome_from_properties(&results, should_panic);
+                verification_outcome_from_properties(&results, should_panic, expected_panic);
             VerificationResult {
                 status,
                 failed_properties,
@@ -266,6 +451,9 @@ fn from(
                 results: Ok(results),
                 runtime,
                 generated_concrete_test: false,
+                solver: None,
+                timed_out: false,
+                peak_memory: output.peak_memory,
             }
         } else {
             // We never got results from CBMC - something went wrong (e.g. crash) so it's failure
@@ -276,6 +464,9 @@ fn from(
                 results: Err(output.process_status),
                 runtime,
                 generated_concrete_test: false,
+                solver: None,
+                timed_out: output.timed_out,
+                peak_memory: output.peak_memory,
             }
         }
     }
@@ -288,6 +479,9 @@ pub fn mock_success() -> VerificationResult {
             results: Ok(vec![]),
             runtime: Duration::from_secs(0),
             generated_concrete_test: false,
+            solver: None,
+            timed_out: false,
+            peak_memory: None,
         }
     }
 
@@ -302,6 +496,9 @@ fn mock_failure() -> VerificationResult {
             results: Err(42),
             runtime: Duration::from_secs(0),
             generated_concrete_test: false,
+            solver: None,
+            timed_out: false,
+            peak_memory: None,
         }
     }
 
@@ -325,9 +522,18 @@ pub fn render(
                 writeln!(result, "Verification Time: {}s", self.runtime.as_secs_f32()).unwrap();
                 result
             }
+            Err(_) if self.status == VerificationStatus::Timeout => {
+                let verification_result = console::style("TIMEOUT").yellow();
+                format!(
+                    "\nVERIFICATION:- {verification_result}\n\
+                    CBMC was stopped because the harness exceeded the harness timeout.\n",
+                )
+            }
             Err(exit_status) => {
                 let verification_result = console::style("FAILED").red();
-                let explanation = if *exit_status == 137 {
+                let explanation = if self.timed_out {
+                    "CBMC was stopped because it exceeded the solver timeout.\n"
+                } else if *exit_status == 137 {
                     "CBMC appears to have run out of memory. You may want to rerun your proof in \
                     an environment with additional memory or use stubbing to reduce the size of the \
                     code the verifier reasons about.\n"
@@ -343,6 +549,28 @@ pub fn render(
         }
     }
 
+    /// The resources CBMC reported to have used for this verification run.
+    pub fn stats(&self) -> CbmcStats {
+        CbmcStats::from_messages(self.messages.as_deref().unwrap_or_default())
+    }
+
+    /// Describe the failed `assigns` checks of the contract of `function` in terms of the
+    /// location the function writes to and the `targets` of its contract, e.g. "function `f`
+    /// writes undeclared location `*ptr`, not permitted by `modifies(&self.len)`".
+    fn describe_undeclared_writes(&mut self, function: &str, targets: &[AssignsTarget]) {
+        let Ok(properties) = &mut self.results else { return };
+        for prop in properties
+            .iter_mut()
+            .filter(|prop| prop.is_assigns_property() && prop.status == CheckStatus::Failure)
+        {
+            if let Some(description) =
+                undeclared_write_description(&prop.description, function, targets)
+            {
+                prop.description = description;
+            }
+        }
+    }
+
     /// Find the failed properties from this verification run
     pub fn failed_properties(&self) -> Vec<&Property> {
         if let Ok(properties) = &self.results {
@@ -354,30 +582,62 @@ pub fn failed_properties(&self) -> Vec<&Property> {
     }
 }
 
+/// The time a CBMC run that starts at `start` may take: the `--solver-timeout` in seconds,
+/// shortened to the time left until the `--harness-timeout` `deadline`.
+fn cbmc_timeout(
+    solver_timeout: Option<u64>,
+    deadline: Option<Instant>,
+    start: Instant,
+) -> Option<Duration> {
+    let solver_timeout = solver_timeout.map(Duration::from_secs);
+    let remaining = deadline.map(|deadline| deadline.saturating_duration_since(start));
+    match (solver_timeout, remaining) {
+        (Some(solver_timeout), Some(remaining)) => Some(solver_timeout.min(remaining)),
+        (timeout, None) | (None, timeout) => timeout,
+    }
+}
+
 /// We decide if verification succeeded based on properties, not (typically) on exit code
 fn verification_outcome_from_properties(
     properties: &[Property],
-    should_panic: bool,
+    should_panic: Option<ShouldPanicMode>,
+    expected_panic: Option<&str>,
 ) -> (VerificationStatus, FailedProperties) {
-    let failed_properties = determine_failed_properties(properties);
-    let status = if should_panic {
+    let failed_properties = determine_failed_properties(properties, expected_panic);
+    let status = if should_panic.is_some() {
         match failed_properties {
-            FailedProperties::None | FailedProperties::Other => VerificationStatus::Failure,
+            FailedProperties::None
+            | FailedProperties::PanicsOnSomePaths
+            | FailedProperties::UnexpectedPanics
+            | FailedProperties::Other => VerificationStatus::Failure,
             FailedProperties::PanicsOnly => VerificationStatus::Success,
         }
     } else {
         match failed_properties {
             FailedProperties::None => VerificationStatus::Success,
-            FailedProperties::PanicsOnly | FailedProperties::Other => VerificationStatus::Failure,
+            FailedProperties::PanicsOnly
+            | FailedProperties::PanicsOnSomePaths
+            | FailedProperties::UnexpectedPanics
+            | FailedProperties::Other => VerificationStatus::Failure,
         }
     };
     (status, failed_properties)
 }
 
-/// Determines the `FailedProperties` variant that corresponds to an array of properties
-fn determine_failed_properties(properties: &[Property]) -> FailedProperties {
-    let failed_properties: Vec<&Property> =
-        properties.iter().filter(|prop| prop.status == CheckStatus::Failure).collect();
+/// Determines the `FailedProperties` variant that corresponds to an array of properties.
+///
+/// If `expected_panic` is set, every panic must contain it. A harness that may panic with other
+/// messages as well is not considered to panic as expected, even if one of its panics matches.
+fn determine_failed_properties(
+    properties: &[Property],
+    expected_panic: Option<&str>,
+) -> FailedProperties {
+    // A harness that returns is only a failure for `should_panic(mode = "all")`, which adds these
+    // checks. It does not count as a failure other than a panic.
+    let (returns, failed_properties): (Vec<&Property>, Vec<&Property>) = properties
+        .iter()
+        .filter(|prop| prop.status == CheckStatus::Failure)
+        .partition(|prop| prop.is_should_panic_property());
     // Return `FAILURE` if there isn't at least one failed property
     if failed_properties.is_empty() {
         FailedProperties::None
@@ -386,11 +646,53 @@ fn determine_failed_properties(properties: &[Property]) -> FailedProperties {
         // Note: Panics caused by `panic!` and `assert!` fall into this class.
         let all_failed_checks_are_panics =
             failed_properties.iter().all(|prop| prop.property_class() == "assertion");
-        if all_failed_checks_are_panics {
+        if !all_failed_checks_are_panics {
+            FailedProperties::Other
+        } else if expected_panic.is_some_and(|expected| {
+            failed_properties.iter().any(|prop| !prop.description.contains(expected))
+        }) {
+            FailedProperties::UnexpectedPanics
+        } else if returns.is_empty() {
             FailedProperties::PanicsOnly
         } else {
-            FailedProperties::Other
+            FailedProperties::PanicsOnSomePaths
+        }
+    }
+}
+
+/// Enable or disable the pointer checks that were explicitly configured for a harness with
+/// `#[kani::pointer_checks]`, overriding the global configuration in `args`.
+fn apply_pointer_check_overrides(
+    pointer_checks: &BTreeMap<PointerCheck, bool>,
+    args: &mut Vec<OsString>,
+) {
+    for (check, enabled) in pointer_checks {
+        let flag = OsString::from(check.cbmc_flag());
+        args.retain(|arg| *arg != flag);
+        if *enabled {
+            args.push(flag);
+        }
+    }
+}
+
+/// Bound the unwinding of all loops of `harness` by its unwind value. The attribute
+/// `#[kani::unwind(0)]` does not bound any loop and removes the unwinding assertions from
+/// `cbmc_args` instead, so that no loop is reported as not fully unwound. A value of 0 given with
+/// `--unwind` or `--default-unwind` is passed to CBMC like any other bound.
+fn apply_unwind_value(
+    args: &VerificationArgs,
+    harness: &HarnessMetadata,
+    cbmc_args: &mut Vec<OsString>,
+) {
+    match resolve_unwind_value(args, harness) {
+        Some(0) if args.unwind.is_none() && harness.attributes.unwind_value == Some(0) => {
+            cbmc_args.retain(|arg| arg != "--unwinding-assertions")
+        }
+        Some(unwind_value) => {
+            cbmc_args.push("--unwind".into());
+            cbmc_args.push(unwind_value.to_string().into());
         }
+        None => {}
     }
 }
 
@@ -404,6 +706,26 @@ pub fn resolve_unwind_value(
     args.unwind.or(harness_metadata.attributes.unwind_value).or(args.default_unwind)
 }
 
+/// The value of CBMC's `--unwindset` option for the per-loop bounds of `harness`, e.g.
+/// `harness.0:10,other.1:3`. A loop id that is only a number refers to a loop of the harness.
+fn unwindset_value(harness: &HarnessMetadata) -> Option<String> {
+    let loops = &harness.attributes.unwind_loops;
+    if loops.is_empty() {
+        return None;
+    }
+    let bounds = loops
+        .iter()
+        .map(|(id, bound)| {
+            if id.chars().all(|c| c.is_ascii_digit()) {
+                format!("{}.{id}:{bound}", harness.mangled_name)
+            } else {
+                format!("{id}:{bound}")
+            }
+        })
+        .collect::<Vec<_>>();
+    Some(bounds.join(","))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::args;
@@ -412,6 +734,15 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn check_solver_wrapper_script() {
+        let args = ["--quiet".to_string(), "it's".to_string()];
+        assert_eq!(
+            solver_wrapper_script("kissat", &args),
+            "#!/bin/sh\nexec 'kissat' '--quiet' 'it'\\''s' \"$@\"\n"
+        );
+    }
+
     #[test]
     fn check_resolve_unwind_value() {
         // Command line unwind value for specific harnesses take precedence over default annotation value
@@ -443,4 +774,158 @@ fn resolve(args: &[&str], harness: &HarnessMetadata) -> Option<u32> {
         assert_eq!(resolve(&args_only_harness, &harness_some), Some(1));
         assert_eq!(resolve(&args_both, &harness_some), Some(1));
     }
+
+    #[test]
+    fn check_apply_unwind_value() {
+        let apply = |args: &[&str], harness: &HarnessMetadata| {
+            let args = args::StandaloneArgs::try_parse_from(args).unwrap().verify_opts;
+            let mut cbmc_args: Vec<OsString> = vec!["--unwinding-assertions".into()];
+            apply_unwind_value(&args, harness, &mut cbmc_args);
+            cbmc_args
+        };
+        let harness_none = mock_proof_harness("check_one", None, None, None);
+        let harness_three = mock_proof_harness("check_one", Some(3), None, None);
+        let harness_zero = mock_proof_harness("check_one", Some(0), None, None);
+        let args_empty = ["kani", "x.rs"];
+        let args_default_zero = ["kani", "x.rs", "--default-unwind", "0"];
+        let args_unwind_zero = ["kani", "x.rs", "--unwind", "0", "--harness", "check_one"];
+        assert_eq!(apply(&args_empty, &harness_none), ["--unwinding-assertions"]);
+        assert_eq!(apply(&args_empty, &harness_three), ["--unwinding-assertions", "--unwind", "3"]);
+        assert!(apply(&args_empty, &harness_zero).is_empty());
+        assert!(apply(&args_default_zero, &harness_zero).is_empty());
+        // Only the attribute turns off the unwinding assertions.
+        assert_eq!(
+            apply(&args_default_zero, &harness_none),
+            ["--unwinding-assertions", "--unwind", "0"]
+        );
+        assert_eq!(
+            apply(&args_unwind_zero, &harness_zero),
+            ["--unwinding-assertions", "--unwind", "0"]
+        );
+    }
+
+    #[test]
+    fn check_unwindset_value() {
+        let mut harness = mock_proof_harness("check_one", Some(2), None, None);
+        assert_eq!(unwindset_value(&harness), None);
+        harness.attributes.unwind_loops = vec![("0".into(), 10), ("helper.1".into(), 3)];
+        assert_eq!(unwindset_value(&harness).as_deref(), Some("check_one.0:10,helper.1:3"));
+    }
+
+    #[test]
+    fn check_undeclared_write_description() {
+        let targets =
+            [AssignsTarget { source: "&self.len".into() }, AssignsTarget { source: "*buf".into() }];
+        let describe = |description, function, targets| {
+            undeclared_write_description(description, function, targets)
+        };
+        assert_eq!(
+            describe("Check that *ptr is assignable", "modify", &targets).as_deref(),
+            Some(
+                "function `modify` writes undeclared location `*ptr`, not permitted by \
+                `modifies(&self.len, *buf)`"
+            )
+        );
+        assert_eq!(
+            describe("Check that var_3 is assignable", "m::f", &[]).as_deref(),
+            Some(
+                "function `m::f` writes undeclared location `var_3`, the contract has no \
+                `modifies` clause"
+            )
+        );
+        assert_eq!(describe("Check that ptr is valid", "modify", &targets), None);
+    }
+
+    #[test]
+    fn check_pointer_check_overrides() {
+        let mut args: Vec<OsString> = vec!["--bounds-check".into(), "--pointer-check".into()];
+        let overrides = BTreeMap::from([
+            (PointerCheck::Dereference, false),
+            (PointerCheck::Overflow, true),
+            (PointerCheck::Primitive, false),
+        ]);
+        apply_pointer_check_overrides(&overrides, &mut args);
+        assert_eq!(args, vec![OsString::from("--bounds-check"), "--pointer-overflow-check".into()]);
+    }
+
+    #[test]
+    fn check_cbmc_stats() {
+        let messages = [
+            "Runtime Symex: 0.25s",
+            "size of program expression: 1234 steps",
+            "Running propositional reduction",
+            "10 variables, 20 clauses",
+            "Runtime Solver: 0.5s",
+            "Runtime decision procedure: 0.75s",
+            "42 variables, 84 clauses",
+            "Runtime Solver: 1s",
+        ]
+        .map(|text| ParserItem::Message {
+            message_text: text.to_string(),
+            message_type: "STATUS-MESSAGE".to_string(),
+        });
+        assert_eq!(
+            CbmcStats::from_messages(&messages),
+            CbmcStats {
+                symex_runtime: Some(0.25),
+                solver_runtime: Some(1.5),
+                decision_procedure_runtime: Some(0.75),
+                program_steps: Some(1234),
+                variables: Some(42),
+                clauses: Some(84),
+            }
+        );
+        assert_eq!(CbmcStats::from_messages(&[]), CbmcStats::default());
+    }
+
+    #[test]
+    fn check_expected_panic() {
+        use crate::cbmc_output_parser::{PropertyId, SourceLocation};
+        let failed_panic = |description: &str| Property {
+            description: description.to_string(),
+            property_id: PropertyId { fn_name: None, class: "assertion".into(), id: 1 },
+            source_location: SourceLocation {
+                column: None,
+                file: None,
+                function: None,
+                line: None,
+            },
+            status: CheckStatus::Failure,
+            reach: None,
+            trace: None,
+        };
+        let bounds = failed_panic("index out of bounds: the length is less than or equal to 1");
+        let unwrap = failed_panic("called `Option::unwrap()` on a `None` value");
+        let outcome = |properties: &[Property], expected| {
+            let (status, _) = verification_outcome_from_properties(
+                properties,
+                Some(ShouldPanicMode::Any),
+                expected,
+            );
+            status
+        };
+
+        assert_eq!(outcome(&[bounds.clone()], None), VerificationStatus::Success);
+        assert_eq!(outcome(&[bounds.clone()], Some("out of bounds")), VerificationStatus::Success);
+        assert_eq!(outcome(&[unwrap.clone()], Some("out of bounds")), VerificationStatus::Failure);
+        // Only one of the panics has the expected message.
+        assert!(matches!(
+            determine_failed_properties(&[bounds, unwrap], Some("out of bounds")),
+            FailedProperties::UnexpectedPanics
+        ));
+    }
+
+    #[test]
+    fn check_cbmc_timeout() {
+        let start = Instant::now();
+        let deadline = Some(start + Duration::from_secs(5));
+        assert_eq!(cbmc_timeout(None, None, start), None);
+        assert_eq!(cbmc_timeout(Some(10), None, start), Some(Duration::from_secs(10)));
+        assert_eq!(cbmc_timeout(None, deadline, start), Some(Duration::from_secs(5)));
+        assert_eq!(cbmc_timeout(Some(3), deadline, start), Some(Duration::from_secs(3)));
+        assert_eq!(cbmc_timeout(Some(10), deadline, start), Some(Duration::from_secs(5)));
+        // A run that starts after the deadline is stopped right away.
+        let late = start + Duration::from_secs(6);
+        assert_eq!(cbmc_timeout(Some(10), deadline, late), Some(Duration::ZERO));
+    }
 }
@@ -1,18 +1,25 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use anyhow::Result;
+use anyhow::{bail, Result};
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::{OsStr, OsString};
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, BufWriter};
 use std::path::Path;
 use std::process::Command;
 
+use crate::args::ContractClause;
 use crate::metadata::collect_and_link_function_pointer_restrictions;
-use crate::project::Project;
+use crate::project::{Artifact, Project};
 use crate::session::KaniSession;
 use crate::util::alter_extension;
-use kani_metadata::{ArtifactType, HarnessMetadata};
+use kani_metadata::artifact::convert_type;
+use kani_metadata::{ArtifactType, AssignsContract, HarnessMetadata, UnstableFeature};
+
+/// The environment variable with extra arguments for the `goto-instrument` call that
+/// instruments contracts, e.g. `--malloc-may-fail`. Meant for debugging CBMC's contract support.
+pub(crate) const DFCC_ARGS_VAR: &str = "KANI_DFCC_ARGS";
 
 impl KaniSession {
     /// Instrument and optimize a goto binary in-place.
@@ -39,6 +46,15 @@ pub fn instrument_model(
 
         self.instrument_contracts(harness, output)?;
 
+        if self.args.dump_goto_model {
+            let model_outfile = alter_extension(output, "goto-model.json");
+            // Like the C file, the dump is kept for the user to inspect.
+            self.dump_goto_model(output, &model_outfile)?;
+            if !self.args.common_args.quiet {
+                println!("Instrumented goto model written to {}", model_outfile.to_string_lossy());
+            }
+        }
+
         if self.args.checks.undefined_function_on() {
             self.add_library(output)?;
             self.undefined_functions(output)?;
@@ -48,7 +64,7 @@ pub fn instrument_model(
 
         self.rewrite_back_edges(output)?;
 
-        if self.args.gen_c {
+        if self.args.gen_c && !self.args.gen_c_crate {
             let c_outfile = alter_extension(output, "c");
             // We don't put the C file into temporaries to be deleted.
 
@@ -61,7 +77,12 @@ pub fn instrument_model(
             let c_demangled = alter_extension(output, "demangled.c");
             let prett_name_map =
                 project.get_harness_artifact(&harness, ArtifactType::PrettyNameMap).unwrap();
-            self.demangle_c(prett_name_map, &c_outfile, &c_demangled)?;
+            let var_name_map = if self.args.demangle_locals {
+                project.get_harness_artifact(&harness, ArtifactType::VarNameMap)
+            } else {
+                None
+            };
+            self.demangle_c(prett_name_map, var_name_map, &c_outfile, &c_demangled)?;
             if !self.args.common_args.quiet {
                 println!("Demangled GotoC code written to {}", c_demangled.to_string_lossy())
             }
@@ -162,34 +183,154 @@ pub fn gen_c(&self, file: &Path, output_file: &Path) -> Result<()> {
         self.call_goto_instrument(args)
     }
 
-    /// Make CBMC enforce a function contract.
+    /// Generate a single .c file for all `harnesses` (i.e. --gen-c --gen-c-crate).
+    ///
+    /// The goto models of the harnesses are linked before they are specialized and instrumented
+    /// for a harness, so the C file contains every function that any of the harnesses uses. The
+    /// name maps of the harnesses are merged to demangle the result.
+    ///
+    /// Harnesses that use stubs or contracts are rejected, since the code they verify is not the
+    /// code of the linked models. So are projects whose artifacts were already merged.
+    pub fn gen_crate_c(&self, project: &Project, harnesses: &[&HarnessMetadata]) -> Result<()> {
+        if harnesses.is_empty() {
+            return Ok(());
+        }
+        if project.merged_artifacts {
+            bail!("`--gen-c-crate` is not supported for projects with merged artifacts");
+        }
+        let specialized = harnesses
+            .iter()
+            .filter(|harness| {
+                !harness.attributes.stubs.is_empty()
+                    || !harness.attributes.verified_stubs.is_empty()
+                    || !harness.contract.is_empty()
+            })
+            .map(|harness| harness.pretty_name.as_str())
+            .collect::<Vec<_>>();
+        if !specialized.is_empty() {
+            bail!(
+                "`--gen-c-crate` does not support harnesses that use stubs or contracts: `{}`. \
+                Use `--gen-c` to generate one C file per harness instead.",
+                specialized.join("`, `")
+            );
+        }
+        let gotos = project.get_harnesses_artifacts(harnesses, ArtifactType::SymTabGoto);
+        if gotos.is_empty() {
+            bail!("`--gen-c-crate` found no goto model to link for the selected harnesses");
+        }
+        let linked = project.outdir.join("linked-harnesses").with_extension(ArtifactType::Goto);
+        self.record_temporary_file(&linked);
+        let inputs = gotos.iter().map(|goto| goto.to_path_buf()).collect::<Vec<_>>();
+        self.link_goto_binary(&inputs, &linked)?;
+
+        let c_outfile = alter_extension(&linked, "c");
+        self.gen_c(&linked, &c_outfile)?;
+        if !self.args.common_args.quiet {
+            println!("Generated C code written to {}", c_outfile.to_string_lossy());
+        }
+
+        let pretty_name_map =
+            convert_type(&linked, ArtifactType::Goto, ArtifactType::PrettyNameMap);
+        self.record_temporary_file(&pretty_name_map);
+        merge_json_maps(
+            &project.get_harnesses_artifacts(harnesses, ArtifactType::PrettyNameMap),
+            &pretty_name_map,
+        )?;
+        let var_name_map = if self.args.demangle_locals {
+            let var_name_map = convert_type(&linked, ArtifactType::Goto, ArtifactType::VarNameMap);
+            self.record_temporary_file(&var_name_map);
+            merge_json_maps(
+                &project.get_harnesses_artifacts(harnesses, ArtifactType::VarNameMap),
+                &var_name_map,
+            )?;
+            Some(var_name_map)
+        } else {
+            None
+        };
+        let c_demangled = alter_extension(&linked, "demangled.c");
+        self.demangle_c(&pretty_name_map, var_name_map.as_ref(), &c_outfile, &c_demangled)?;
+        if !self.args.common_args.quiet {
+            println!("Demangled GotoC code written to {}", c_demangled.to_string_lossy())
+        }
+        Ok(())
+    }
+
+    /// Write the goto functions in `file` as JSON to `output_file`.
+    fn dump_goto_model(&self, file: &Path, output_file: &Path) -> Result<()> {
+        let mut cmd = Command::new("goto-instrument");
+        cmd.arg("--show-goto-functions").arg("--json-ui").arg(file);
+        let status = self.run_redirect(cmd, output_file)?;
+        if !status.success() {
+            bail!("goto-instrument failed to dump the goto model with status {status}");
+        }
+        Ok(())
+    }
+
+    /// Make CBMC enforce the function contracts of the harness and apply loop contracts.
+    ///
+    /// The first target of `proof_for_contract` is passed to `--enforce-contract` together with
+    /// the statics that must not be havocked. `goto-instrument` only enforces one function per
+    /// run, so a harness that checks several functions is instrumented once per function, with
+    /// that function moved to the front of its contracts. The recursion trackers and `decreases`
+    /// measures of the other targets are still excluded from havocking. The function contracts
+    /// are not enforced if the user excluded `assigns` clauses via `--enforce-clauses`. Loop
+    /// contracts are applied if `-Z loop-contracts` is enabled and a loop with a contract is
+    /// reachable from the harness, so other harnesses are not instrumented. The whitespace
+    /// separated arguments in the `KANI_DFCC_ARGS` environment variable are added after the
+    /// contract flags.
     pub fn instrument_contracts(&self, harness: &HarnessMetadata, file: &Path) -> Result<()> {
-        let Some(assigns) = harness.contract.as_ref() else { return Ok(()) };
-
-        let args: &[std::ffi::OsString] = &[
-            "--dfcc".into(),
-            (&harness.mangled_name).into(),
-            "--enforce-contract".into(),
-            assigns.contracted_function_name.as_str().into(),
-            "--nondet-static-exclude".into(),
-            assigns.recursion_tracker.as_str().into(),
-            file.into(),
-            file.into(),
-        ];
+        let assigns: &[AssignsContract] = if self.args.enforces_clause(ContractClause::Assigns) {
+            &harness.contract
+        } else {
+            &[]
+        };
+        let loop_contracts = harness.has_loop_contracts
+            && self.args.common_args.unstable_features.contains(UnstableFeature::LoopContracts);
+        if assigns.is_empty() && !loop_contracts {
+            return Ok(());
+        }
+
+        let mut args: Vec<OsString> = vec!["--dfcc".into(), (&harness.mangled_name).into()];
+        if let Some(enforced) = assigns.first() {
+            args.extend(["--enforce-contract".into(), (&enforced.contracted_function_name).into()]);
+        }
+        for assigns in assigns {
+            args.extend(["--nondet-static-exclude".into(), (&assigns.recursion_tracker).into()]);
+            if let Some(measure) = &assigns.decreases_measure {
+                args.extend(["--nondet-static-exclude".into(), measure.as_str().into()]);
+            }
+        }
+        if loop_contracts {
+            // The invariant of a loop contract is a call to the function that evaluates it.
+            args.extend([
+                "--apply-loop-contracts".into(),
+                "--loop-contracts-no-unwind".into(),
+                "--disable-side-effect-check".into(),
+            ]);
+        }
+        args.extend(split_extra_args(std::env::var(DFCC_ARGS_VAR).ok().as_deref()));
+        args.extend([file.into(), file.into()]);
         self.call_goto_instrument(args)
     }
 
     /// Generate a .demangled.c file from the .c file using the `prettyName`s from the symbol table
     ///
-    /// Currently, only top-level function names and (most) type names are demangled.
-    /// For local variables, it would be more complicated than a simple search and replace to obtain the demangled name.
+    /// Top-level function names and (most) type names are demangled. Local variables are only
+    /// demangled if a `var_name_map_file` is given (see [`demangle_locals`]).
     pub fn demangle_c(
         &self,
         pretty_name_map_file: &impl AsRef<Path>,
+        var_name_map_file: Option<&impl AsRef<Path>>,
         c_file: &Path,
         demangled_file: &Path,
     ) -> Result<()> {
         let mut c_code = std::fs::read_to_string(c_file)?;
+        // Locals are found by the mangled name of their function, so replace them first.
+        if let Some(var_name_map_file) = var_name_map_file {
+            let reader = BufReader::new(File::open(var_name_map_file)?);
+            let var_name_map: VarNameMap = serde_json::from_reader(reader)?;
+            c_code = demangle_locals(&c_code, &var_name_map);
+        }
         let reader = BufReader::new(File::open(pretty_name_map_file)?);
         let value: serde_json::Value = serde_json::from_reader(reader)?;
         let pretty_name_map = value.as_object().unwrap();
@@ -219,3 +360,237 @@ fn call_goto_instrument<S: AsRef<OsStr>>(
         self.run_suppress(cmd)
     }
 }
+
+/// Write the union of the JSON objects in `inputs` to `output`.
+///
+/// The name maps of different harnesses agree on the symbols they share, so it does not matter
+/// which of them provides an entry.
+fn merge_json_maps(inputs: &[&Artifact], output: &Path) -> Result<()> {
+    let mut merged = serde_json::Map::new();
+    for input in inputs {
+        let reader = BufReader::new(File::open(input)?);
+        let map: serde_json::Map<String, serde_json::Value> = serde_json::from_reader(reader)?;
+        merged.extend(map);
+    }
+    serde_json::to_writer(BufWriter::new(File::create(output)?), &merged)?;
+    Ok(())
+}
+
+/// The source names of the local variables of each function, by the base name of the variable.
+type VarNameMap = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Replace the names of the local variables in the definitions of the functions in
+/// `var_name_map` with their source names.
+///
+/// A function definition starts with a line that is not indented and declares the function, and
+/// ends with a line that only contains `}`. Only whole identifiers are replaced, and the new
+/// names are unique in the definition, see [`unique_local_names`].
+fn demangle_locals(c_code: &str, var_name_map: &VarNameMap) -> String {
+    let mut result = String::with_capacity(c_code.len());
+    let mut lines = c_code.split_inclusive('\n');
+    while let Some(line) = lines.next() {
+        let locals = if line.starts_with(char::is_whitespace) {
+            None
+        } else {
+            function_declared_by(line).and_then(|name| var_name_map.get(name))
+        };
+        let Some(locals) = locals else {
+            result.push_str(line);
+            continue;
+        };
+        let mut definition = vec![line];
+        if line.trim_end() != "}" {
+            for line in lines.by_ref() {
+                definition.push(line);
+                if line.trim_end() == "}" {
+                    break;
+                }
+            }
+        }
+        let names = unique_local_names(locals, &definition);
+        for line in definition {
+            result.push_str(&replace_identifiers(line, &names));
+        }
+    }
+    result
+}
+
+/// Split the value of an environment variable with extra arguments at whitespace.
+fn split_extra_args(value: Option<&str>) -> Vec<OsString> {
+    value.map_or_else(Vec::new, |value| value.split_whitespace().map(OsString::from).collect())
+}
+
+/// The name of the function whose definition starts with `line`, if any.
+fn function_declared_by(line: &str) -> Option<&str> {
+    let line = line.trim_end();
+    if line.starts_with("//") || line.ends_with(';') {
+        return None;
+    }
+    let (head, _) = line.split_once('(')?;
+    head.rsplit(|c: char| !is_identifier_char(c)).next().filter(|name| !name.is_empty())
+}
+
+/// Map each base name to a name derived from its source name that is unique in the function
+/// `definition`.
+///
+/// A source name that is used by several locals (e.g. because of shadowing) or that is already
+/// an identifier of the definition, such as a global or a local without a source name, gets the
+/// base name as a suffix. As the base names are unique in a function, a counter is only appended
+/// if the code already uses that name as well.
+fn unique_local_names<'a>(
+    locals: &'a BTreeMap<String, String>,
+    definition: &[&str],
+) -> BTreeMap<&'a str, String> {
+    let mut taken: BTreeSet<String> = definition
+        .iter()
+        .flat_map(|line| identifiers(line))
+        .filter(|identifier| !locals.contains_key(*identifier))
+        .map(str::to_string)
+        .collect();
+    let mut uses: BTreeMap<&str, usize> = BTreeMap::new();
+    for source_name in locals.values() {
+        *uses.entry(source_name).or_default() += 1;
+    }
+    let mut names = BTreeMap::new();
+    for (base_name, source_name) in locals {
+        let candidate = if uses[source_name.as_str()] > 1 || taken.contains(source_name) {
+            format!("{source_name}_{base_name}")
+        } else {
+            source_name.clone()
+        };
+        let mut name = candidate.clone();
+        let mut counter = 1;
+        while taken.contains(&name) {
+            name = format!("{candidate}_{counter}");
+            counter += 1;
+        }
+        taken.insert(name.clone());
+        names.insert(base_name.as_str(), name);
+    }
+    names
+}
+
+/// The identifiers in `line`, including numbers.
+fn identifiers(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| !is_identifier_char(c)).filter(|identifier| !identifier.is_empty())
+}
+
+/// Replace every identifier in `line` that is a key of `names` by its value.
+fn replace_identifiers(line: &str, names: &BTreeMap<&str, String>) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find(is_identifier_char) {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c: char| !is_identifier_char(c)).unwrap_or(rest.len());
+        let identifier = &rest[..end];
+        result.push_str(names.get(identifier).map_or(identifier, String::as_str));
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_split_extra_args() {
+        assert!(split_extra_args(None).is_empty());
+        assert!(split_extra_args(Some(" ")).is_empty());
+        assert_eq!(
+            split_extra_args(Some("--malloc-may-fail  --malloc-fail-null\n")),
+            ["--malloc-may-fail", "--malloc-fail-null"]
+        );
+    }
+
+    #[test]
+    fn check_demangle_locals() {
+        let c_code = "\
+// _RNvCs1_4main3foo
+void _RNvCs1_4main3foo(unsigned int var_1)
+{
+  unsigned int var_2;
+  unsigned int var_3;
+  unsigned int var_4;
+  var_2 = var_1 + 1;
+  my_var_2 = var_3;
+}
+
+void other(void)
+{
+  unsigned int var_2;
+}
+";
+        let var_name_map = VarNameMap::from([(
+            "_RNvCs1_4main3foo".to_string(),
+            BTreeMap::from([
+                ("var_1".to_string(), "x".to_string()),
+                ("var_2".to_string(), "y".to_string()),
+                ("var_3".to_string(), "y".to_string()),
+            ]),
+        )]);
+        assert_eq!(
+            demangle_locals(c_code, &var_name_map),
+            "\
+// _RNvCs1_4main3foo
+void _RNvCs1_4main3foo(unsigned int x)
+{
+  unsigned int y_var_2;
+  unsigned int y_var_3;
+  unsigned int var_4;
+  y_var_2 = x + 1;
+  my_var_2 = y_var_3;
+}
+
+void other(void)
+{
+  unsigned int var_2;
+}
+"
+        );
+    }
+
+    #[test]
+    fn check_demangle_locals_unique() {
+        let c_code = "\
+void _RNvCs1_4main3bar(void)
+{
+  unsigned int var_1;
+  unsigned int var_2;
+  unsigned int var_3;
+  unsigned int var_4;
+  var_1 = count;
+  count = var_2 + var_3 + var_4;
+}
+";
+        let var_name_map = VarNameMap::from([(
+            "_RNvCs1_4main3bar".to_string(),
+            BTreeMap::from([
+                ("var_1".to_string(), "count".to_string()),
+                ("var_2".to_string(), "y_var_3".to_string()),
+                ("var_3".to_string(), "y".to_string()),
+                ("var_4".to_string(), "y".to_string()),
+            ]),
+        )]);
+        assert_eq!(
+            demangle_locals(c_code, &var_name_map),
+            "\
+void _RNvCs1_4main3bar(void)
+{
+  unsigned int count_var_1;
+  unsigned int y_var_3;
+  unsigned int y_var_3_1;
+  unsigned int y_var_4;
+  count_var_1 = count;
+  count = y_var_3 + y_var_3_1 + y_var_4;
+}
+"
+        );
+    }
+}
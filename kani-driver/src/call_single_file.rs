@@ -6,6 +6,7 @@
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::args::ContractClause;
 use crate::session::{lib_folder, KaniSession};
 
 impl KaniSession {
@@ -18,7 +19,7 @@ pub fn compile_single_rust_file(
         outdir: &Path,
     ) -> Result<()> {
         let mut kani_args = self.kani_compiler_flags();
-        kani_args.push(format!("--reachability={}", self.reachability_mode()));
+        kani_args.extend(self.target_crate_flags());
 
         let mut rustc_args = self.kani_rustc_flags();
         rustc_args.push(file.into());
@@ -61,7 +62,17 @@ pub fn compile_single_rust_file(
 
     /// Create a compiler option that represents the reachability mod.
     pub fn reachability_arg(&self) -> String {
-        to_rustc_arg(vec![format!("--reachability={}", self.reachability_mode())])
+        to_rustc_arg(self.target_crate_flags())
+    }
+
+    /// The kani-compiler flags that should only be passed to the crate under verification, not
+    /// to its dependencies.
+    fn target_crate_flags(&self) -> Vec<String> {
+        let mut flags = vec![format!("--reachability={}", self.reachability_mode())];
+        if self.check_contracts_only {
+            flags.push("--check-contracts-only".into());
+        }
+        flags
     }
 
     /// These arguments are arguments passed to kani-compiler that are `kani` compiler specific.
@@ -100,6 +111,26 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
             flags.push("--coverage-checks".into());
         }
 
+        if !self.args.enforces_clause(ContractClause::Ensures) {
+            flags.push("--skip-postcondition-checks".into());
+        }
+
+        if self.args.contract_report {
+            flags.push("--contract-report".into());
+        }
+
+        if self.args.replace_all_contracts {
+            flags.push("--replace-all-contracts".into());
+        }
+
+        if self.args.enforce_and_replace {
+            flags.push("--enforce-and-replace".into());
+        }
+
+        if self.args.precondition_call_site {
+            flags.push("--precondition-call-site".into());
+        }
+
         flags.extend(self.args.common_args.unstable_features.as_arguments().map(str::to_string));
 
         // This argument will select the Kani flavour of the compiler. It will be removed before
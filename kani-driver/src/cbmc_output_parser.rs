@@ -34,6 +34,9 @@
 use std::os::unix::process::ExitStatusExt;
 use std::path::PathBuf;
 use std::process::{Child, ChildStdout};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
 
 const RESULT_ITEM_PREFIX: &str = "  {\n    \"result\":";
 
@@ -97,6 +100,10 @@ pub struct PropertyId {
 impl Property {
     const COVER_PROPERTY_CLASS: &'static str = "cover";
     const COVERAGE_PROPERTY_CLASS: &'static str = "code_coverage";
+    const PRECONDITION_PROPERTY_CLASS: &'static str = "precondition";
+    const ASSIGNS_PROPERTY_CLASS: &'static str = "assigns";
+    const SHOULD_PANIC_PROPERTY_CLASS: &'static str = "should_panic";
+    const HARNESS_EXIT_PROPERTY_CLASS: &'static str = "harness_exit";
 
     pub fn property_class(&self) -> String {
         self.property_id.class.clone()
@@ -112,6 +119,33 @@ pub fn is_cover_property(&self) -> bool {
         self.property_id.class == Self::COVER_PROPERTY_CLASS
     }
 
+    /// Returns true if this is the precondition of a function replaced by its contract
+    pub fn is_precondition_property(&self) -> bool {
+        self.property_id.class == Self::PRECONDITION_PROPERTY_CLASS
+    }
+
+    /// Returns true if this checks that a write is allowed by the assigns clause of a contract
+    pub fn is_assigns_property(&self) -> bool {
+        self.property_id.class == Self::ASSIGNS_PROPERTY_CLASS
+    }
+
+    /// Returns true if this checks that a harness which must panic on every path does not return
+    pub fn is_should_panic_property(&self) -> bool {
+        self.property_id.class == Self::SHOULD_PANIC_PROPERTY_CLASS
+    }
+
+    /// Returns true if this checks that the end of a `#[kani::expect_unreachable]` harness is
+    /// reachable
+    pub fn is_harness_exit_property(&self) -> bool {
+        self.property_id.class == Self::HARNESS_EXIT_PROPERTY_CLASS
+    }
+
+    /// Returns true if this property is reported like a cover property, i.e., as `SATISFIED`
+    /// if some path reaches it
+    pub fn has_cover_status(&self) -> bool {
+        self.is_cover_property() || self.is_harness_exit_property()
+    }
+
     pub fn property_name(&self) -> String {
         let class = &self.property_id.class;
         let id = self.property_id.id;
@@ -511,6 +545,11 @@ fn next(&mut self) -> Option<Self::Item> {
 pub struct VerificationOutput {
     pub process_status: i32,
     pub processed_items: Vec<ParserItem>,
+    /// Whether the process was stopped because it exceeded its timeout.
+    pub timed_out: bool,
+    /// The peak resident memory of the process in bytes, as last seen while reading its output.
+    /// Only measured on Linux.
+    pub peak_memory: Option<u64>,
 }
 
 /// The main function to process CBMC's output.
@@ -520,16 +559,44 @@ pub struct VerificationOutput {
 /// In general, a filter will pre-process an item (this may or may not transform the item),
 /// then formatted (according to the output format) and print.
 ///
+/// If `timeout` is set, the process is killed once it has run that long.
+///
 /// The cbmc process status is returned, along with the (post-filter) items.
 pub fn process_cbmc_output(
     mut process: Child,
+    timeout: Option<Duration>,
     eager_filter: impl FnMut(ParserItem) -> Option<ParserItem>,
 ) -> Result<VerificationOutput> {
-    let stdout = process.stdout.as_mut().unwrap();
-    let mut stdout_reader = BufReader::new(stdout);
-    let parser = Parser::new(&mut stdout_reader);
-    // This should run until stdout is closed (which should mean the process exited)
-    let processed_items: Vec<_> = parser.filter_map(eager_filter).collect();
+    let mut stdout = process.stdout.take().unwrap();
+    let pid = process.id();
+    let mut peak_memory = None;
+    let (finished, watchdog) = mpsc::channel::<()>();
+    let (processed_items, timed_out) = thread::scope(|scope| {
+        // The process is killed by a separate thread, since reading its output blocks.
+        let killer = timeout.map(|timeout| {
+            let process = &mut process;
+            scope.spawn(move || {
+                let timed_out = watchdog.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout);
+                if timed_out {
+                    // The process may have exited in the meantime, which is fine.
+                    let _ = process.kill();
+                }
+                timed_out
+            })
+        });
+        let mut stdout_reader = BufReader::new(&mut stdout);
+        let parser = Parser::new(&mut stdout_reader);
+        // This should run until stdout is closed (which should mean the process exited).
+        // The peak memory only grows, so the last value read before then is the one we report.
+        let processed_items: Vec<_> = parser
+            .inspect(|_| peak_memory = read_peak_memory(pid).or(peak_memory))
+            .filter_map(eager_filter)
+            .collect();
+        // The receiver is gone if the watchdog already timed out.
+        let _ = finished.send(());
+        let timed_out = killer.is_some_and(|killer| killer.join().unwrap());
+        (processed_items, timed_out)
+    });
     // This will get us the process's exit code
     let status = process.wait()?;
 
@@ -545,7 +612,17 @@ pub fn process_cbmc_output(
         (None, None) => unreachable!("Process exited with neither status code nor signal?"),
     };
 
-    Ok(VerificationOutput { process_status, processed_items })
+    Ok(VerificationOutput { process_status, processed_items, timed_out, peak_memory })
+}
+
+/// The peak resident memory in bytes of the running process `pid`, from the `VmHWM` entry of
+/// `/proc/<pid>/status`. Returns `None` if that file does not exist, e.g. because we are not on
+/// Linux or the process already exited.
+fn read_peak_memory(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    let kib = status.lines().find_map(|line| line.strip_prefix("VmHWM:"))?;
+    let kib = kib.trim().strip_suffix("kB")?.trim().parse::<u64>().ok()?;
+    Some(kib * 1024)
 }
 
 /// Takes (by ownership) a vector of messages, and returns that vector with the `Result`
@@ -764,4 +841,25 @@ fn check_result_deserialization_works() {
         assert!(parser_item.is_ok());
         assert!(result_struct.is_ok());
     }
+
+    #[test]
+    fn check_process_timeout() {
+        use std::process::{Command, Stdio};
+        let slow = Command::new("sleep").arg("5").stdout(Stdio::piped()).spawn().unwrap();
+        let output = process_cbmc_output(slow, Some(Duration::from_millis(100)), Some).unwrap();
+        assert!(output.timed_out);
+        assert!(output.processed_items.is_empty());
+
+        let quick = Command::new("true").stdout(Stdio::piped()).spawn().unwrap();
+        let output = process_cbmc_output(quick, Some(Duration::from_secs(5)), Some).unwrap();
+        assert!(!output.timed_out);
+        assert_eq!(output.process_status, 0);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn check_read_peak_memory() {
+        assert!(read_peak_memory(std::process::id()).is_some_and(|bytes| bytes > 0));
+        assert_eq!(read_peak_memory(u32::MAX), None);
+    }
 }
@@ -3,7 +3,7 @@
 
 use crate::args::OutputFormat;
 use crate::call_cbmc::{FailedProperties, VerificationStatus};
-use crate::cbmc_output_parser::{CheckStatus, ParserItem, Property, TraceItem};
+use crate::cbmc_output_parser::{CheckStatus, ParserItem, Property, SourceLocation, TraceItem};
 use console::style;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -289,25 +289,25 @@ pub fn format_result(
                 failed_tests.push(prop);
             }
             CheckStatus::Undetermined => {
-                if prop.is_cover_property() {
+                if prop.has_cover_status() {
                     number_covers_undetermined += 1;
                 } else {
                     number_checks_undetermined += 1;
                 }
             }
             CheckStatus::Unreachable => {
-                if prop.is_cover_property() {
+                if prop.has_cover_status() {
                     number_covers_unreachable += 1;
                 } else {
                     number_checks_unreachable += 1;
                 }
             }
             CheckStatus::Satisfied => {
-                assert!(prop.is_cover_property());
+                assert!(prop.has_cover_status());
                 number_covers_satisfied += 1;
             }
             CheckStatus::Unsatisfiable => {
-                assert!(prop.is_cover_property());
+                assert!(prop.has_cover_status());
                 number_covers_unsatisfiable += 1;
             }
             _ => (),
@@ -389,7 +389,13 @@ pub fn format_result(
     }
 
     for prop in failed_tests {
-        let failure_message = build_failure_message(prop.description.clone(), &prop.trace.clone());
+        let failure_message = if prop.is_precondition_property() && prop.trace.is_some() {
+            // The location was already moved to the call site, which is not
+            // the last step of the trace.
+            build_failure_message_at(prop.description.clone(), prop.source_location.clone())
+        } else {
+            build_failure_message(prop.description.clone(), &prop.trace.clone())
+        };
         result_str.push_str(&failure_message);
     }
 
@@ -402,6 +408,12 @@ pub fn format_result(
         match failed_properties {
             FailedProperties::None => " (encountered no panics, but at least one was expected)",
             FailedProperties::PanicsOnly => " (encountered one or more panics as expected)",
+            FailedProperties::PanicsOnSomePaths => {
+                " (encountered one or more panics, but not on every path as expected)"
+            }
+            FailedProperties::UnexpectedPanics => {
+                " (encountered one or more panics, but not all with the expected message)"
+            }
             FailedProperties::Other => {
                 " (encountered failures other than panics, which were unexpected)"
             }
@@ -510,8 +522,13 @@ fn build_failure_message(description: String, trace: &Option<Vec<TraceItem>>) ->
     if failure_source_wrap.is_none() {
         return backup_failure_message;
     }
-    let failure_source = failure_source_wrap.unwrap();
+    build_failure_message_at(description, failure_source_wrap.unwrap())
+}
 
+/// Builds a message for a failed property that points to `failure_source`, if
+/// it is detailed enough.
+fn build_failure_message_at(description: String, failure_source: SourceLocation) -> String {
+    let backup_failure_message = format!("Failed Checks: {description}\n");
     if failure_source.file.is_some()
         && failure_source.function.is_some()
         && failure_source.line.is_some()
@@ -592,8 +609,10 @@ pub fn postprocess_result(properties: Vec<Property>, extra_ptr_checks: bool) ->
 
     let updated_properties =
         update_properties_with_reach_status(properties_filtered, has_fundamental_failures);
+    let updated_properties = relocate_precondition_failures(updated_properties);
     let results_after_code_coverage = update_results_of_code_covererage_checks(updated_properties);
-    update_results_of_cover_checks(results_after_code_coverage)
+    let results_after_cover = update_results_of_cover_checks(results_after_code_coverage);
+    update_results_of_harness_exit_checks(results_after_cover)
 }
 
 /// Determines if there is property with status `FAILURE` and the given description
@@ -607,7 +626,7 @@ fn has_check_failure(properties: &Vec<Property>, description: &str) -> bool {
 }
 
 // Determines if there were unwinding assertion failures in a set of properties
-fn has_unwinding_assertion_failures(properties: &Vec<Property>) -> bool {
+pub fn has_unwinding_assertion_failures(properties: &Vec<Property>) -> bool {
     has_check_failure(&properties, UNWINDING_ASSERT_DESC)
         || has_check_failure(&properties, UNWINDING_ASSERT_REC_DESC)
 }
@@ -697,6 +716,37 @@ fn update_properties_with_reach_status(
     properties
 }
 
+/// Attributes failed preconditions of replaced functions to the caller.
+///
+/// The precondition of a function that was replaced by its contract is checked
+/// inside the replacement, so CBMC reports it at the contract. If the property
+/// comes with a trace (e.g. with `--precondition-call-site`), we report it at
+/// the call that violated the precondition instead, i.e. the innermost function
+/// call that had not returned when the check failed.
+fn relocate_precondition_failures(mut properties: Vec<Property>) -> Vec<Property> {
+    for prop in properties.iter_mut() {
+        if !prop.is_precondition_property() || prop.status != CheckStatus::Failure {
+            continue;
+        }
+        let Some(trace) = &prop.trace else { continue };
+        let mut call_stack = vec![];
+        for step in trace {
+            match step.step_type.as_str() {
+                "function-call" => call_stack.push(step.source_location.clone()),
+                "function-return" => {
+                    call_stack.pop();
+                }
+                _ => {}
+            }
+        }
+        if let Some(Some(call_site)) = call_stack.pop() {
+            prop.description = format!("client violates callee precondition: {}", prop.description);
+            prop.source_location = call_site;
+        }
+    }
+    properties
+}
+
 /// Update the results of `code_coverage` (NOT `cover`) properties.
 /// - `SUCCESS` -> `UNCOVERED`
 /// - `FAILURE` -> `COVERED`
@@ -738,6 +788,30 @@ fn update_results_of_cover_checks(mut properties: Vec<Property>) -> Vec<Property
     }
     properties
 }
+/// Update the results of the checks at the end of a `#[kani::expect_unreachable]` harness.
+/// Each one is an `assert(false)` before a return, so it fails if the return is reachable.
+/// - If some return is reachable: FAILURE -> SATISFIED, SUCCESS -> UNREACHABLE
+/// - Otherwise the assumptions of the harness prune every path, which is a failure of every check
+fn update_results_of_harness_exit_checks(mut properties: Vec<Property>) -> Vec<Property> {
+    let exit_reached = properties
+        .iter()
+        .any(|prop| prop.is_harness_exit_property() && prop.status == CheckStatus::Failure);
+    for prop in properties.iter_mut().filter(|prop| prop.is_harness_exit_property()) {
+        if exit_reached {
+            if prop.status == CheckStatus::Success {
+                prop.status = CheckStatus::Unreachable;
+            } else if prop.status == CheckStatus::Failure {
+                prop.status = CheckStatus::Satisfied;
+            }
+        } else if prop.status == CheckStatus::Success {
+            prop.status = CheckStatus::Failure;
+            prop.description =
+                "the end of the harness is unreachable, its assumptions prune every path".into();
+        }
+    }
+    properties
+}
+
 /// Some Kani-generated asserts have a unique ID in their description of the form:
 /// ```text
 /// [KANI_CHECK_ID_<crate-fn-name>_<index>]
@@ -15,7 +15,7 @@
 use std::fs::{read_to_string, File};
 use std::hash::{Hash, Hasher};
 use std::io::{BufRead, BufReader, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tempfile::NamedTempFile;
 
@@ -26,7 +26,7 @@ pub fn gen_and_add_concrete_playback(
         harness: &HarnessMetadata,
         verification_result: &mut VerificationResult,
     ) -> Result<()> {
-        let playback_mode = match self.args.concrete_playback {
+        let playback_mode = match self.args.concrete_playback_mode() {
             Some(playback_mode) => playback_mode,
             None => return Ok(()),
         };
@@ -90,6 +90,16 @@ pub fn gen_and_add_concrete_playback(
                             &harness.original_file
                         ));
                     }
+                    ConcretePlaybackMode::File => {
+                        let test_file = self.write_tests_to_file(harness, &unit_tests)?;
+                        if !self.args.common_args.quiet {
+                            println!(
+                                "INFO: Concrete playback unit test(s) for `{}` written to {}.",
+                                &harness.pretty_name,
+                                test_file.display()
+                            );
+                        }
+                    }
                 }
                 verification_result.generated_concrete_test = true;
             }
@@ -97,6 +107,27 @@ pub fn gen_and_add_concrete_playback(
         Ok(())
     }
 
+    /// Write the unit tests into a standalone file next to the harness source file.
+    ///
+    /// The generated file is meant to be included as a test module of the module that declares
+    /// the harness, which is why the harness is brought into scope with `use super::*`.
+    /// Returns the path of the generated file.
+    fn write_tests_to_file(
+        &self,
+        harness: &HarnessMetadata,
+        unit_tests: &[UnitTest],
+    ) -> Result<PathBuf> {
+        let harness_name = harness.get_harness_name_unqualified();
+        let src_path = Path::new(&harness.original_file);
+        let src_dir = src_path.parent().unwrap_or(Path::new("."));
+        let file_name = format!("kani_concrete_playback_{harness_name}.rs");
+        let test_file = src_dir.join(&file_name);
+        let code = format_test_file(&file_name, unit_tests);
+        std::fs::write(&test_file, code.join("\n") + "\n")
+            .with_context(|| format!("Failed to write `{}`", test_file.display()))?;
+        Ok(test_file)
+    }
+
     /// Add the unit test to the user's source code, format it, and short circuit if code already present.
     fn modify_src_code(
         &self,
@@ -262,6 +293,29 @@ fn format_unit_test(harness_name: &str, concrete_vals: &[ConcreteVal]) -> UnitTe
     UnitTest { code: full_func, name: func_name }
 }
 
+/// Generate the contents of a standalone test file from a list of unit tests.
+///
+/// Only the values produced by `kani::any()` are reconstructed, so harnesses that build complex
+/// pointer graphs from other sources of non-determinism may not replay faithfully.
+fn format_test_file(file_name: &str, unit_tests: &[UnitTest]) -> Vec<String> {
+    let header = [
+        "// Concrete playback unit tests generated by Kani.".to_string(),
+        "// To run them, include this file in the module that declares the harness:".to_string(),
+        "//".to_string(),
+        "// #[cfg(test)]".to_string(),
+        format!("// #[path = \"{file_name}\"]"),
+        "// mod kani_concrete_playback;".to_string(),
+        "".to_string(),
+        "use super::*;".to_string(),
+    ]
+    .into_iter();
+    header
+        .chain(unit_tests.iter().flat_map(|unit_test| {
+            std::iter::once(String::new()).chain(unit_test.code.iter().cloned())
+        }))
+        .collect()
+}
+
 /// Format an initializer expression for a number of concrete values.
 fn format_concrete_vals(concrete_vals: &[ConcreteVal]) -> impl Iterator<Item = String> + '_ {
     /*
@@ -532,6 +586,22 @@ fn check_hashes_are_unique() {
         assert_ne!(hash_base, hash_diff_interp_val);
     }
 
+    #[test]
+    fn format_test_file_includes_all_tests() {
+        let concrete_vals = [ConcreteVal { byte_arr: vec![0, 0], interp_val: "0".to_string() }];
+        let unit_tests = [
+            format_unit_test("harness_one", &concrete_vals),
+            format_unit_test("harness_two", &concrete_vals),
+        ];
+        let code = format_test_file("kani_concrete_playback_harness.rs", &unit_tests);
+        assert!(code.contains(&"use super::*;".to_string()));
+        assert!(code.contains(&"// #[path = \"kani_concrete_playback_harness.rs\"]".to_string()));
+        for unit_test in unit_tests.iter() {
+            assert!(code.contains(&format!("fn {}() {{", unit_test.name)));
+        }
+        assert_eq!(code.iter().filter(|line| *line == "#[test]").count(), 2);
+    }
+
     #[test]
     fn check_rustfmt_args_no_line_ranges() {
         let file_line_ranges = [FileLineRange { file: "file1".to_string(), line_range: None }];
@@ -0,0 +1,223 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Support for `--changed-only`, which skips the `proof_for_contract` harnesses that succeeded
+//! before and did not change since.
+//!
+//! Whether a harness changed is decided by the fingerprint that the compiler stores in its
+//! [`AssignsContract`](kani_metadata::AssignsContract). It covers the source of the harness and
+//! of every function and static of the crate the harness reaches, whether it has a contract or
+//! not, and the contracts it checks or replaces via `stub_verified`. It is combined with a
+//! fingerprint of the options that affect verification, such as the enabled checks, so that a
+//! harness is verified again when they change. The fingerprints of the harnesses that succeeded
+//! are kept in a baseline file in the output directory. Runs that only enforce some contract
+//! clauses with `--enforce-clauses` do not add to the baseline.
+
+use crate::args::ContractClause;
+use crate::call_cbmc::VerificationStatus;
+use crate::call_goto_instrument::DFCC_ARGS_VAR;
+use crate::session::KaniSession;
+use anyhow::{Context, Result};
+use kani_metadata::HarnessMetadata;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+/// The name of the baseline file in the output directory.
+const BASELINE_FILE: &str = "kani-contract-baseline.json";
+
+/// The fingerprints of the `proof_for_contract` harnesses that succeeded, by harness name.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ContractBaseline {
+    fingerprints: BTreeMap<String, String>,
+    /// The fingerprint of the options of this run, see [`KaniSession::options_fingerprint`].
+    #[serde(skip)]
+    options: String,
+    /// Whether this run only enforces some of the contract clauses, in which case its successes
+    /// are not recorded.
+    #[serde(skip)]
+    partial: bool,
+}
+
+impl ContractBaseline {
+    /// Load the baseline stored in `outdir` for a run of `session`. If there is none yet, the
+    /// baseline is empty and every harness is verified.
+    pub(crate) fn load(outdir: &Path, session: &KaniSession) -> Result<Self> {
+        let path = baseline_path(outdir);
+        let mut baseline = if path.exists() {
+            let file = File::open(&path).with_context(|| {
+                format!("Failed to open contract baseline `{}`", path.display())
+            })?;
+            serde_json::from_reader(BufReader::new(file))
+                .with_context(|| format!("Failed to read contract baseline `{}`", path.display()))?
+        } else {
+            Self::default()
+        };
+        baseline.options = session.options_fingerprint();
+        baseline.partial = ![ContractClause::Ensures, ContractClause::Assigns]
+            .into_iter()
+            .all(|clause| session.args.enforces_clause(clause));
+        Ok(baseline)
+    }
+
+    /// Store the baseline in `outdir`, replacing the previous one.
+    pub(crate) fn save(&self, outdir: &Path) -> Result<()> {
+        let path = baseline_path(outdir);
+        let file = File::create(&path)
+            .with_context(|| format!("Failed to create contract baseline `{}`", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .with_context(|| format!("Failed to write contract baseline `{}`", path.display()))
+    }
+
+    /// Did `harness` succeed before with the same fingerprint it has now?
+    fn is_unchanged(&self, harness: &HarnessMetadata) -> bool {
+        self.fingerprint(harness).is_some_and(|fingerprint| {
+            self.fingerprints.get(&harness.pretty_name) == Some(&fingerprint)
+        })
+    }
+
+    /// Split `harnesses` into the ones that have to be verified and the ones that are unchanged.
+    /// Harnesses that do not check a contract are always verified.
+    fn partition<'a>(
+        &self,
+        harnesses: Vec<&'a HarnessMetadata>,
+    ) -> (Vec<&'a HarnessMetadata>, Vec<&'a HarnessMetadata>) {
+        harnesses.into_iter().partition(|harness| !self.is_unchanged(harness))
+    }
+
+    /// Remember the fingerprints of the contract harnesses that succeeded and forget the ones
+    /// that failed, so that they are verified again by the next run. A partial run only forgets.
+    pub(crate) fn record(&mut self, statuses: &[(&HarnessMetadata, VerificationStatus)]) {
+        for (harness, status) in statuses {
+            let Some(fingerprint) = self.fingerprint(harness) else {
+                continue;
+            };
+            if *status == VerificationStatus::Success && !self.partial {
+                self.fingerprints.insert(harness.pretty_name.clone(), fingerprint);
+            } else {
+                self.fingerprints.remove(&harness.pretty_name);
+            }
+        }
+    }
+
+    /// The fingerprint of a contract harness in this run, which combines the fingerprints of all
+    /// contracts it checks with the fingerprint of the options. `None` if the harness does not
+    /// check a contract.
+    fn fingerprint(&self, harness: &HarnessMetadata) -> Option<String> {
+        if harness.contract.is_empty() {
+            return None;
+        }
+        let fingerprints = harness.contract.iter().map(|contract| contract.fingerprint.as_str());
+        Some(format!("{};{}", fingerprints.collect::<Vec<_>>().join(","), self.options))
+    }
+}
+
+fn baseline_path(outdir: &Path) -> PathBuf {
+    outdir.join(BASELINE_FILE)
+}
+
+impl KaniSession {
+    /// A hash of the options that affect the result of verifying a harness: the enabled checks,
+    /// unwinding, solver and object bits, the extra CBMC and `goto-instrument` arguments, the
+    /// unstable features and the contract clauses that are enforced.
+    fn options_fingerprint(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.cbmc_check_flags().hash(&mut hasher);
+        self.args.cbmc_object_bits().hash(&mut hasher);
+        (self.args.default_unwind, self.args.unwind).hash(&mut hasher);
+        self.args.solver.as_ref().map(ToString::to_string).hash(&mut hasher);
+        self.args.cbmc_args.hash(&mut hasher);
+        std::env::var_os(DFCC_ARGS_VAR).hash(&mut hasher);
+        format!("{:?}", self.args.common_args.unstable_features).hash(&mut hasher);
+        format!("{:?}", self.args.enforce_clauses).hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Remove the harnesses that are unchanged according to `baseline` from `harnesses` and
+    /// report them as skipped. Also returns how many harnesses were skipped.
+    pub(crate) fn skip_unchanged_harnesses<'a>(
+        &self,
+        baseline: &ContractBaseline,
+        harnesses: Vec<&'a HarnessMetadata>,
+    ) -> (Vec<&'a HarnessMetadata>, usize) {
+        let (changed, unchanged) = baseline.partition(harnesses);
+        if !self.args.common_args.quiet {
+            for harness in &unchanged {
+                println!(
+                    "Skipped harness {}: unchanged since it last succeeded",
+                    harness.pretty_name
+                );
+            }
+            if changed.is_empty() && !unchanged.is_empty() {
+                println!("All selected harnesses are unchanged since they last succeeded.");
+            }
+        }
+        (changed, unchanged.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::mock_proof_harness;
+    use kani_metadata::AssignsContract;
+
+    fn mock_contract_harness(name: &str, fingerprint: &str) -> HarnessMetadata {
+        let mut harness = mock_proof_harness(name, None, None, None);
+        harness.contract = vec![AssignsContract {
+            function: "foo".into(),
+            contracted_function_name: "foo".into(),
+            recursion_tracker: "foo_recursion_wrapper".into(),
+            decreases_measure: None,
+            fingerprint: fingerprint.into(),
+            clauses: vec![],
+            targets: vec![],
+        }];
+        harness
+    }
+
+    #[test]
+    fn check_changed_harnesses() {
+        let unchanged = mock_contract_harness("unchanged", "a");
+        let changed = mock_contract_harness("changed", "b");
+        let failed = mock_contract_harness("failed", "c");
+        let plain = mock_proof_harness("plain", None, None, None);
+
+        let mut baseline = ContractBaseline::default();
+        assert_eq!(baseline.partition(vec![&unchanged, &plain]).1.len(), 0);
+
+        baseline.record(&[
+            (&unchanged, VerificationStatus::Success),
+            (&mock_contract_harness("changed", "old"), VerificationStatus::Success),
+            (&failed, VerificationStatus::Success),
+            (&plain, VerificationStatus::Success),
+        ]);
+        baseline.record(&[(&failed, VerificationStatus::Failure)]);
+
+        let (to_verify, skipped) = baseline.partition(vec![&unchanged, &changed, &failed, &plain]);
+        let names = |harnesses: Vec<&HarnessMetadata>| {
+            harnesses.into_iter().map(|h| h.pretty_name.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(names(to_verify), ["changed", "failed", "plain"]);
+        assert_eq!(names(skipped), ["unchanged"]);
+    }
+
+    #[test]
+    fn check_options_and_partial_runs() {
+        let harness = mock_contract_harness("harness", "a");
+        let mut baseline = ContractBaseline { options: "checks".into(), ..Default::default() };
+        baseline.record(&[(&harness, VerificationStatus::Success)]);
+        assert_eq!(baseline.partition(vec![&harness]).1.len(), 1);
+
+        // The same harness is verified again when the options change.
+        baseline.options = "other checks".into();
+        assert_eq!(baseline.partition(vec![&harness]).1.len(), 0);
+
+        // A partial run does not record its successes.
+        baseline.partial = true;
+        baseline.record(&[(&harness, VerificationStatus::Success)]);
+        assert_eq!(baseline.partition(vec![&harness]).1.len(), 0);
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Support for `--export-contracts` and `--compare-contracts`, which record the contracts of a
+//! crate in a canonical JSON file and report how the contracts differ from such a file, e.g. one
+//! written for an earlier commit.
+//!
+//! Everything recorded about a contract comes from the [`ContractedFunction`]s of the crate, which
+//! the compiler emits for every function with a contract, whether a harness checks it or not.
+
+use crate::session::KaniSession;
+use anyhow::{Context, Result};
+use kani_metadata::{ContractedFunction, KaniMetadata, Location};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+/// The contracts of a crate, by the fully qualified name of the function under contract.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ContractSnapshot {
+    contracts: BTreeMap<String, ContractSummary>,
+}
+
+/// A contract as recorded by `--export-contracts`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct ContractSummary {
+    location: Location,
+    checked_with: String,
+    replaced_with: String,
+    requires: Vec<String>,
+    ensures: Vec<String>,
+    modifies: Vec<String>,
+}
+
+impl ContractSnapshot {
+    /// Collect the contracts of every crate in `metadata`.
+    pub(crate) fn new(metadata: &[KaniMetadata]) -> Self {
+        let contracts = metadata
+            .iter()
+            .flat_map(|md| md.contracted_functions.iter())
+            .map(|function| (function.function.clone(), ContractSummary::new(function)))
+            .collect();
+        Self { contracts }
+    }
+
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open contracts file `{}`", path.display()))?;
+        serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("Failed to read contracts file `{}`", path.display()))
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create contracts file `{}`", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .with_context(|| format!("Failed to write contracts file `{}`", path.display()))
+    }
+
+    /// Describe how the contracts changed from `old` to `self`, one line per function.
+    ///
+    /// A contract changed if its clauses or `modifies` targets differ. A contract that only moved
+    /// or whose generated functions were renamed is the same contract.
+    fn compare(&self, old: &ContractSnapshot) -> Vec<String> {
+        let removed = old
+            .contracts
+            .keys()
+            .filter(|function| !self.contracts.contains_key(*function))
+            .map(|function| format!("removed: {function}"));
+        let added_or_changed = self.contracts.iter().filter_map(|(function, new)| {
+            let Some(old) = old.contracts.get(function) else {
+                return Some(format!("added: {function}"));
+            };
+            let changes = [
+                ("requires", &old.requires, &new.requires),
+                ("ensures", &old.ensures, &new.ensures),
+                ("modifies", &old.modifies, &new.modifies),
+            ]
+            .into_iter()
+            .filter(|(_, old, new)| old != new)
+            .map(|(clause, old, new)| {
+                format!("{clause} `{}` -> `{}`", old.join("`, `"), new.join("`, `"))
+            })
+            .collect::<Vec<_>>();
+            (!changes.is_empty()).then(|| format!("changed: {function} ({})", changes.join("; ")))
+        });
+        removed.chain(added_or_changed).collect()
+    }
+}
+
+impl ContractSummary {
+    fn new(function: &ContractedFunction) -> Self {
+        Self {
+            location: function.location.clone(),
+            checked_with: function.checked_with.clone(),
+            replaced_with: function.replaced_with.clone(),
+            requires: function.requires.clone(),
+            ensures: function.ensures.clone(),
+            modifies: function.modifies.clone(),
+        }
+    }
+}
+
+impl KaniSession {
+    /// Print how the contracts in `metadata` differ from the ones stored in `path` by
+    /// `--export-contracts`.
+    pub(crate) fn compare_contracts(&self, metadata: &[KaniMetadata], path: &Path) -> Result<()> {
+        let old = ContractSnapshot::load(path)?;
+        let changes = ContractSnapshot::new(metadata).compare(&old);
+        if changes.is_empty() {
+            println!("No contract changed since `{}`.", path.display());
+        } else {
+            println!("Contracts that changed since `{}`:", path.display());
+            for change in &changes {
+                println!(" - {change}");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_summary(requires: &[&str], modifies: &[&str]) -> ContractSummary {
+        ContractSummary {
+            location: Location { filename: "lib.rs".into(), start_line: 1 },
+            checked_with: "check".into(),
+            replaced_with: "replace".into(),
+            requires: requires.iter().map(|s| s.to_string()).collect(),
+            ensures: vec![],
+            modifies: modifies.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn mock_snapshot(contracts: &[(&str, ContractSummary)]) -> ContractSnapshot {
+        ContractSnapshot {
+            contracts: contracts.iter().map(|(f, c)| (f.to_string(), c.clone())).collect(),
+        }
+    }
+
+    #[test]
+    fn check_compare_contracts() {
+        let old = mock_snapshot(&[
+            ("kept", mock_summary(&["x > 0"], &[])),
+            ("moved", mock_summary(&[], &[])),
+            ("removed", mock_summary(&[], &[])),
+            ("weakened", mock_summary(&["x > 0", "x < 10"], &["*p"])),
+        ]);
+        let mut moved = mock_summary(&[], &[]);
+        moved.location.start_line = 10;
+        moved.checked_with = "check_2".into();
+        let new = mock_snapshot(&[
+            ("added", mock_summary(&[], &[])),
+            ("kept", mock_summary(&["x > 0"], &[])),
+            ("moved", moved),
+            ("weakened", mock_summary(&["x > 0"], &["*p", "*q"])),
+        ]);
+        assert_eq!(
+            new.compare(&old),
+            [
+                "removed: removed",
+                "added: added",
+                "changed: weakened (requires `x > 0`, `x < 10` -> `x > 0`; modifies `*p` -> `*p`, \
+                `*q`)",
+            ]
+        );
+        assert!(new.compare(&new).is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Support for `--contract-report`, which tells for every `requires` and `ensures` clause checked
+//! by a `proof_for_contract` harness whether it was reachable and checked something.
+//!
+//! The compiler records the clauses of every checked contract in its
+//! [`AssignsContract`](kani_metadata::AssignsContract), together with the location CBMC reports
+//! for their properties. A postcondition is an assertion, so its status is the status of that
+//! assertion. With `--contract-report` the compiler also adds a cover property after each
+//! precondition, which is only satisfiable if some input fulfills it.
+
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use anyhow::Result;
+use kani_metadata::{ContractClauseKind, ContractClauseMetadata};
+use serde::Serialize;
+
+/// The clauses of the contract of `function`, as checked by `harness`.
+#[derive(Debug, Serialize)]
+struct ContractReport<'a> {
+    harness: &'a str,
+    function: &'a str,
+    clauses: Vec<ClauseReport<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ClauseReport<'a> {
+    kind: ContractClauseKind,
+    condition: &'a str,
+    status: ClauseStatus,
+    file: &'a str,
+    line: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ClauseStatus {
+    /// The precondition is satisfiable, or the postcondition holds on every path.
+    Checked,
+    /// The postcondition does not hold on some path.
+    Failed,
+    /// No input satisfies the precondition, so every postcondition holds vacuously.
+    Unsatisfiable,
+    /// No path reaches the clause, e.g. because of an earlier precondition or a panic.
+    Unreachable,
+    /// The condition is a constant, so it does not constrain the function.
+    Trivial,
+    /// CBMC reported no result for the clause, e.g. because `--enforce-clauses` excluded it.
+    NotChecked,
+}
+
+impl KaniSession {
+    /// Print the contract report for the `proof_for_contract` harnesses in `results` as JSON.
+    pub(crate) fn print_contract_report(&self, results: &[HarnessResult<'_>]) -> Result<()> {
+        let reports = results
+            .iter()
+            .flat_map(|result| {
+                let properties = result.result.results.as_deref().unwrap_or_default();
+                result.harness.contract.iter().map(move |contract| ContractReport {
+                    harness: &result.harness.pretty_name,
+                    function: &contract.function,
+                    clauses: contract
+                        .clauses
+                        .iter()
+                        .map(|clause| ClauseReport {
+                            kind: clause.kind,
+                            condition: &clause.condition,
+                            status: clause_status(clause, properties),
+                            file: &clause.location.filename,
+                            line: clause.location.start_line,
+                        })
+                        .collect(),
+                })
+            })
+            .collect::<Vec<_>>();
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+        Ok(())
+    }
+}
+
+/// Find the property CBMC reported for `clause` and summarize its status.
+fn clause_status(clause: &ContractClauseMetadata, properties: &[Property]) -> ClauseStatus {
+    let class = match clause.kind {
+        ContractClauseKind::Requires => "cover",
+        ContractClauseKind::Ensures => "assertion",
+    };
+    let line = clause.location.start_line.to_string();
+    let property = properties.iter().find(|prop| {
+        prop.property_class() == class
+            && prop.description == clause.condition
+            && prop.source_location.file.as_ref() == Some(&clause.location.filename)
+            && prop.source_location.line.as_ref() == Some(&line)
+    });
+    let Some(property) = property else { return ClauseStatus::NotChecked };
+    match property.status {
+        CheckStatus::Failure => ClauseStatus::Failed,
+        CheckStatus::Unsatisfiable => ClauseStatus::Unsatisfiable,
+        CheckStatus::Unreachable => ClauseStatus::Unreachable,
+        CheckStatus::Success | CheckStatus::Satisfied if clause.constant => ClauseStatus::Trivial,
+        CheckStatus::Success | CheckStatus::Satisfied => ClauseStatus::Checked,
+        CheckStatus::Covered | CheckStatus::Uncovered | CheckStatus::Undetermined => {
+            ClauseStatus::NotChecked
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{PropertyId, SourceLocation};
+    use kani_metadata::Location;
+
+    fn mock_clause(
+        kind: ContractClauseKind,
+        condition: &str,
+        constant: bool,
+    ) -> ContractClauseMetadata {
+        ContractClauseMetadata {
+            kind,
+            condition: condition.into(),
+            location: Location { filename: "lib.rs".into(), start_line: 3 },
+            constant,
+        }
+    }
+
+    fn mock_property(class: &str, description: &str, line: u64, status: CheckStatus) -> Property {
+        Property {
+            description: description.into(),
+            property_id: PropertyId { fn_name: None, class: class.into(), id: 1 },
+            source_location: SourceLocation {
+                column: None,
+                file: Some("lib.rs".into()),
+                function: None,
+                line: Some(line.to_string()),
+            },
+            status,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn check_clause_status() {
+        use ContractClauseKind::{Ensures, Requires};
+        let properties = [
+            mock_property("cover", "x > 0", 3, CheckStatus::Satisfied),
+            mock_property("assertion", "result > x", 3, CheckStatus::Success),
+            mock_property("assertion", "true", 3, CheckStatus::Success),
+            mock_property("assertion", "result < x", 3, CheckStatus::Unreachable),
+            mock_property("assertion", "result == x", 4, CheckStatus::Failure),
+        ];
+        let status = |clause| clause_status(&clause, &properties);
+        assert_eq!(status(mock_clause(Requires, "x > 0", false)), ClauseStatus::Checked);
+        assert_eq!(status(mock_clause(Ensures, "result > x", false)), ClauseStatus::Checked);
+        assert_eq!(status(mock_clause(Ensures, "true", true)), ClauseStatus::Trivial);
+        assert_eq!(status(mock_clause(Ensures, "result < x", false)), ClauseStatus::Unreachable);
+        // The property of another clause with the same condition does not count.
+        assert_eq!(status(mock_clause(Ensures, "result == x", false)), ClauseStatus::NotChecked);
+        assert_eq!(status(mock_clause(Ensures, "x > 0", false)), ClauseStatus::NotChecked);
+    }
+}
@@ -4,13 +4,16 @@
 use anyhow::{bail, Result};
 use kani_metadata::{ArtifactType, HarnessMetadata};
 use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeSet;
 use std::path::Path;
 
-use crate::args::OutputFormat;
-use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::args::{ContractClause, OutputFormat, StatsFormat};
+use crate::call_cbmc::{CbmcStats, VerificationResult, VerificationStatus};
+use crate::cbmc_property_renderer::has_unwinding_assertion_failures;
 use crate::project::Project;
 use crate::session::KaniSession;
-use crate::util::error;
+use crate::util::{alter_extension, error};
 
 /// A HarnessRunner is responsible for checking all proof harnesses. The data in this structure represents
 /// "background information" that the controlling driver (e.g. cargo-kani or kani) computed.
@@ -58,13 +61,11 @@ pub(crate) fn check_all_harnesses(
                     let goto_file =
                         self.project.get_harness_artifact(&harness, ArtifactType::Goto).unwrap();
 
-                    self.sess.instrument_model(goto_file, goto_file, &self.project, &harness)?;
-
-                    if self.sess.args.synthesize_loop_contracts {
-                        self.sess.synthesize_loop_contracts(goto_file, &goto_file, &harness)?;
-                    }
-
-                    let result = self.sess.check_harness(goto_file, &report_dir, harness)?;
+                    let result = if harness.contract.len() > 1 {
+                        self.check_each_contract(goto_file, &report_dir, harness)?
+                    } else {
+                        self.instrument_and_check(goto_file, &report_dir, harness)?
+                    };
                     Ok(HarnessResult { harness, result })
                 })
                 .collect::<Result<Vec<_>>>()
@@ -73,6 +74,103 @@ pub(crate) fn check_all_harnesses(
         Ok(results)
     }
 
+    /// Instrument the goto model of `harness` in place and verify it.
+    fn instrument_and_check(
+        &self,
+        goto_file: &Path,
+        report_dir: &Path,
+        harness: &HarnessMetadata,
+    ) -> Result<VerificationResult> {
+        self.sess.instrument_model(goto_file, goto_file, self.project, harness)?;
+
+        if self.sess.args.synthesize_loop_contracts {
+            self.sess.synthesize_loop_contracts(goto_file, goto_file, harness)?;
+        }
+
+        self.sess.check_harness(goto_file, report_dir, harness)
+    }
+
+    /// Verify a harness that checks the contracts of several functions once per function.
+    ///
+    /// `goto-instrument` only enforces the `modifies` clause of one function per run, so every
+    /// run instruments its own copy of the goto model with a different function first, see
+    /// [`KaniSession::instrument_contracts`]. The `requires` and `ensures` clauses of all targets
+    /// are checked in every run. The result is the first failing run, if any.
+    fn check_each_contract(
+        &self,
+        goto_file: &Path,
+        report_dir: &Path,
+        harness: &HarnessMetadata,
+    ) -> Result<VerificationResult> {
+        let mut combined: Option<VerificationResult> = None;
+        for (idx, contract) in harness.contract.iter().enumerate() {
+            if !self.sess.args.common_args.quiet {
+                println!(
+                    "Enforcing the modifies clause of `{}` in harness {}",
+                    contract.function, harness.pretty_name
+                );
+            }
+            let target_file = alter_extension(goto_file, &format!("contract-{idx}.out"));
+            self.sess.record_temporary_file(&target_file);
+            std::fs::copy(goto_file, &target_file)?;
+            let mut target = harness.clone();
+            target.contract.rotate_left(idx);
+            let result = self.instrument_and_check(&target_file, report_dir, &target)?;
+            combined = Some(match combined {
+                Some(previous) if previous.status != VerificationStatus::Success => previous,
+                _ => result,
+            });
+        }
+        Ok(combined.expect("harness checks at least two contracts"))
+    }
+
+    /// Verify `harness` with increasing unwind bounds, starting at 1, until no unwinding assertion
+    /// fails, and report the first such bound. Returns an error if no bound up to `max_unwind` is
+    /// sufficient.
+    pub(crate) fn search_unwind(
+        &self,
+        harness: &'pr HarnessMetadata,
+        max_unwind: u32,
+    ) -> Result<()> {
+        self.check_stubbing(&[harness])?;
+        let goto_file = self.project.get_harness_artifact(harness, ArtifactType::Goto).unwrap();
+        self.sess.instrument_model(goto_file, goto_file, self.project, harness)?;
+
+        let quiet = self.sess.args.common_args.quiet;
+        let mut candidate = harness.clone();
+        for unwind in 1..=max_unwind {
+            if !quiet {
+                println!("Checking harness {} with unwind bound {unwind}...", harness.pretty_name);
+            }
+            candidate.attributes.unwind_value = Some(unwind);
+            let result = self.sess.run_cbmc(goto_file, &candidate)?;
+            let properties = match &result.results {
+                Ok(properties) => properties,
+                Err(exit_status) => bail!("CBMC failed with status {exit_status}"),
+            };
+            if has_unwinding_assertion_failures(properties) {
+                continue;
+            }
+            if !quiet {
+                println!(
+                    "Minimal sufficient unwind bound for harness {}: {unwind}",
+                    harness.pretty_name
+                );
+                if result.status == VerificationStatus::Failure {
+                    println!(
+                        "Verification fails with this bound. Use `#[kani::unwind({unwind})]` \
+                        and rerun to see the failed checks."
+                    );
+                }
+            }
+            return Ok(());
+        }
+        bail!(
+            "No unwind bound up to {max_unwind} is sufficient for harness {}",
+            harness.pretty_name
+        )
+    }
+
     /// Return an error if the user is trying to verify a harness with stubs without enabling the
     /// experimental feature.
     fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
@@ -127,16 +225,94 @@ pub(crate) fn check_harness(
                     "{}",
                     result.render(
                         &self.args.output_format,
-                        harness.attributes.should_panic,
+                        harness.attributes.should_panic.is_some(),
                         self.args.coverage
                     )
                 );
+                if let Some(clauses) = self.enforced_clauses(harness) {
+                    println!("Enforced contract clauses: {clauses}");
+                }
+                // Report which of the harness solvers finished in time.
+                if harness.attributes.solver.len() > 1
+                    && let Some(solver) = &result.solver
+                {
+                    println!("Solver: {solver}");
+                }
             }
             self.gen_and_add_concrete_playback(harness, &mut result)?;
             Ok(result)
         }
     }
 
+    /// The contract clauses enforced by `harness`, if it is a `proof_for_contract` harness and
+    /// the user restricted them via `--enforce-clauses`.
+    fn enforced_clauses(&self, harness: &HarnessMetadata) -> Option<String> {
+        if harness.attributes.proof_for_contract.is_empty() {
+            return None;
+        }
+        let clauses = self.args.enforce_clauses.as_ref()?;
+        let names = [ContractClause::Ensures, ContractClause::Assigns]
+            .into_iter()
+            .filter(|clause| clauses.contains(clause))
+            .map(|clause| clause.to_string())
+            .collect::<Vec<_>>();
+        Some(names.join(", "))
+    }
+
+    /// Print, for every function whose contract is checked by one of the harnesses in `results`,
+    /// the outcome of the enforcement phase (its `proof_for_contract` harnesses) and of the
+    /// replacement phase (the harnesses that replace it via `stub_verified`).
+    ///
+    /// The replacement results rely on the contract being correct, so they are reported as not
+    /// trusted if the enforcement phase failed. The replacement phase includes the replacement
+    /// client that the compiler adds for every checked contract, so it is only skipped if the
+    /// function could not be called from a generated harness, e.g. because it is generic.
+    pub(crate) fn print_contract_phases(&self, results: &[HarnessResult<'_>]) {
+        if self.args.common_args.quiet {
+            return;
+        }
+        let statuses = results.iter().map(|r| (r.harness, r.result.status)).collect::<Vec<_>>();
+        let phases = contract_phases(&statuses);
+        if phases.is_empty() {
+            return;
+        }
+        println!("Contract phases:");
+        for ContractPhases { function, enforcement, replacement } in phases {
+            let replacement = match (enforcement, replacement) {
+                (_, PhaseStatus::Missing) => "SKIPPED (no `stub_verified` harness)",
+                (PhaseStatus::Failure, _) => "NOT TRUSTED (enforcement failed)",
+                (_, PhaseStatus::Success) => "SUCCESS",
+                (_, PhaseStatus::Failure) => "FAILURE",
+            };
+            let enforcement = match enforcement {
+                PhaseStatus::Success => "SUCCESS",
+                PhaseStatus::Failure => "FAILURE",
+                PhaseStatus::Missing => unreachable!("only enforced contracts are reported"),
+            };
+            println!(" - {function}: enforcement {enforcement}, replacement {replacement}");
+        }
+    }
+
+    /// Print the resources CBMC used for each harness in `results`, either as one line per
+    /// harness or as a JSON array.
+    pub(crate) fn print_stats(
+        &self,
+        results: &[HarnessResult<'_>],
+        format: StatsFormat,
+    ) -> Result<()> {
+        let records = results.iter().map(HarnessStats::new).collect::<Vec<_>>();
+        match format {
+            StatsFormat::Json => println!("{}", serde_json::to_string_pretty(&records)?),
+            StatsFormat::Text => {
+                println!("Statistics:");
+                for record in &records {
+                    println!(" - {}", record.render());
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Concludes a session by printing a summary report and exiting the process with an
     /// error code (if applicable).
     ///
@@ -145,12 +321,15 @@ pub(crate) fn check_harness(
     pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result<()> {
         let (successes, failures): (Vec<_>, Vec<_>) =
             results.iter().partition(|r| r.result.status == VerificationStatus::Success);
+        let (timeouts, failures): (Vec<_>, Vec<_>) =
+            failures.into_iter().partition(|r| r.result.status == VerificationStatus::Timeout);
 
         let succeeding = successes.len();
         let failing = failures.len();
-        let total = succeeding + failing;
+        let timing_out = timeouts.len();
+        let total = succeeding + failing + timing_out;
 
-        if self.args.concrete_playback.is_some()
+        if self.args.concrete_playback_mode().is_some()
             && !self.args.common_args.quiet
             && results.iter().all(|r| !r.result.generated_concrete_test)
         {
@@ -161,16 +340,22 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
 
         // We currently omit a summary if there was just 1 harness
         if !self.args.common_args.quiet && !self.args.visualize {
-            if failing > 0 {
+            if failing > 0 || timing_out > 0 {
                 println!("Summary:");
             }
             for failure in failures.iter() {
                 println!("Verification failed for - {}", failure.harness.pretty_name);
             }
+            for timeout in timeouts.iter() {
+                println!("Verification timed out for - {}", timeout.harness.pretty_name);
+            }
 
             if total > 0 {
+                // Timeouts are only mentioned if there are any, i.e. with `--harness-timeout`.
+                let timeouts =
+                    if timing_out > 0 { format!(" {timing_out} timeouts,") } else { String::new() };
                 println!(
-                    "Complete - {succeeding} successfully verified harnesses, {failing} failures, {total} total."
+                    "Complete - {succeeding} successfully verified harnesses, {failing} failures,{timeouts} {total} total."
                 );
             } else {
                 match (self.args.harnesses.as_slice(), &self.args.function) {
@@ -197,7 +382,7 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
             }
         }
 
-        if failing > 0 {
+        if failing > 0 || timing_out > 0 {
             // Failure exit code without additional error message
             drop(self);
             std::process::exit(1);
@@ -206,3 +391,197 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
         Ok(())
     }
 }
+
+/// The resource usage of a single harness, as reported by `--stats`.
+#[derive(Debug, Serialize)]
+struct HarnessStats<'a> {
+    harness: &'a str,
+    status: &'static str,
+    /// Wall-clock seconds of the CBMC invocation.
+    runtime: f64,
+    /// The peak resident memory of CBMC in bytes, if it could be measured.
+    peak_memory: Option<u64>,
+    /// The solver of the last CBMC invocation, i.e. the one that did not time out.
+    sat_solver: Option<String>,
+    #[serde(flatten)]
+    cbmc: CbmcStats,
+}
+
+impl<'a> HarnessStats<'a> {
+    fn new(result: &'a HarnessResult<'_>) -> Self {
+        HarnessStats {
+            harness: &result.harness.pretty_name,
+            status: match result.result.status {
+                VerificationStatus::Success => "SUCCESS",
+                VerificationStatus::Failure => "FAILURE",
+                VerificationStatus::Timeout => "TIMEOUT",
+            },
+            runtime: result.result.runtime.as_secs_f64(),
+            peak_memory: result.result.peak_memory,
+            sat_solver: result.result.solver.as_ref().map(ToString::to_string),
+            cbmc: result.result.stats(),
+        }
+    }
+
+    /// A one line summary, omitting what was not measured or CBMC did not report.
+    fn render(&self) -> String {
+        let CbmcStats {
+            symex_runtime,
+            solver_runtime,
+            decision_procedure_runtime,
+            program_steps,
+            variables,
+            clauses,
+        } = &self.cbmc;
+        let details = [
+            self.peak_memory.map(|bytes| format!("peak memory {:.1} MiB", bytes as f64 / 1048576.0)),
+            symex_runtime.map(|secs| format!("symex {secs:.3}s")),
+            solver_runtime.map(|secs| format!("solver {secs:.3}s")),
+            decision_procedure_runtime.map(|secs| format!("decision procedure {secs:.3}s")),
+            program_steps.map(|steps| format!("{steps} steps")),
+            variables.map(|variables| format!("{variables} variables")),
+            clauses.map(|clauses| format!("{clauses} clauses")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        let mut line = format!("{}: {} in {:.3}s", self.harness, self.status, self.runtime);
+        if !details.is_empty() {
+            line.push_str(&format!(" ({})", details.join(", ")));
+        }
+        line
+    }
+}
+
+/// The combined outcome of the harnesses of one phase of checking a contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PhaseStatus {
+    Success,
+    Failure,
+    /// No harness belongs to this phase.
+    Missing,
+}
+
+impl PhaseStatus {
+    /// A harness that timed out did not verify its phase, so it counts as a failure.
+    fn from_statuses(statuses: impl Iterator<Item = VerificationStatus>) -> Self {
+        statuses.fold(PhaseStatus::Missing, |phase, status| match (phase, status) {
+            (PhaseStatus::Failure, _)
+            | (_, VerificationStatus::Failure | VerificationStatus::Timeout) => {
+                PhaseStatus::Failure
+            }
+            _ => PhaseStatus::Success,
+        })
+    }
+}
+
+/// The outcome of enforcing the contract of `function` and of using it as a replacement.
+#[derive(Debug, PartialEq, Eq)]
+struct ContractPhases<'a> {
+    function: &'a str,
+    enforcement: PhaseStatus,
+    replacement: PhaseStatus,
+}
+
+/// Group the harness results by the contract they enforce or use as a replacement. Only
+/// functions with at least one `proof_for_contract` harness are reported, sorted by name.
+fn contract_phases<'a>(
+    results: &[(&'a HarnessMetadata, VerificationStatus)],
+) -> Vec<ContractPhases<'a>> {
+    let functions = results
+        .iter()
+        .flat_map(|(harness, _)| &harness.attributes.proof_for_contract)
+        .map(String::as_str)
+        .collect::<BTreeSet<_>>();
+    functions
+        .into_iter()
+        .map(|function| {
+            let phase = |in_phase: &dyn Fn(&HarnessMetadata) -> bool| {
+                PhaseStatus::from_statuses(
+                    results
+                        .iter()
+                        .filter(|(harness, _)| in_phase(harness))
+                        .map(|(_, status)| *status),
+                )
+            };
+            ContractPhases {
+                function,
+                enforcement: phase(&|harness| {
+                    harness.attributes.proof_for_contract.iter().any(|target| target == function)
+                }),
+                replacement: phase(&|harness| {
+                    harness.attributes.verified_stubs.iter().any(|stub| stub == function)
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::mock_proof_harness;
+
+    fn mock_harness(enforces: &[&str], replaces: &[&str]) -> HarnessMetadata {
+        let mut harness = mock_proof_harness("harness", None, None, None);
+        harness.attributes.proof_for_contract = enforces.iter().map(|s| s.to_string()).collect();
+        harness.attributes.verified_stubs = replaces.iter().map(|s| s.to_string()).collect();
+        harness
+    }
+
+    #[test]
+    fn check_render_stats() {
+        let harness = mock_harness(&[], &[]);
+        let mut stats = HarnessStats {
+            harness: &harness.pretty_name,
+            status: "SUCCESS",
+            runtime: 1.5,
+            peak_memory: None,
+            sat_solver: Some("kissat".into()),
+            cbmc: CbmcStats::default(),
+        };
+        assert_eq!(stats.render(), "harness: SUCCESS in 1.500s");
+        stats.cbmc.solver_runtime = Some(0.25);
+        stats.cbmc.clauses = Some(100);
+        assert_eq!(stats.render(), "harness: SUCCESS in 1.500s (solver 0.250s, 100 clauses)");
+        stats.peak_memory = Some(3 * 1024 * 1024 / 2);
+        assert_eq!(
+            stats.render(),
+            "harness: SUCCESS in 1.500s (peak memory 1.5 MiB, solver 0.250s, 100 clauses)"
+        );
+    }
+
+    #[test]
+    fn check_contract_phases() {
+        use VerificationStatus::{Failure, Success};
+        let check_foo = mock_harness(&["foo"], &[]);
+        let check_bar = mock_harness(&["bar"], &[]);
+        let check_foo_and_bar = mock_harness(&["foo", "bar"], &[]);
+        let use_foo = mock_harness(&[], &["foo"]);
+        let use_foo_and_baz = mock_harness(&[], &["foo", "baz"]);
+        let unrelated = mock_harness(&[], &[]);
+        let phases = contract_phases(&[
+            (&check_foo, Success),
+            (&check_foo_and_bar, Success),
+            (&use_foo, Success),
+            (&use_foo_and_baz, Failure),
+            (&check_bar, Failure),
+            (&unrelated, Failure),
+        ]);
+        assert_eq!(
+            phases,
+            [
+                ContractPhases {
+                    function: "bar",
+                    enforcement: PhaseStatus::Failure,
+                    replacement: PhaseStatus::Missing,
+                },
+                ContractPhases {
+                    function: "foo",
+                    enforcement: PhaseStatus::Success,
+                    replacement: PhaseStatus::Failure,
+                },
+            ]
+        );
+    }
+}
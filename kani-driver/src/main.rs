@@ -3,17 +3,21 @@
 #![feature(let_chains)]
 #![feature(array_methods)]
 use std::ffi::OsString;
+use std::path::Path;
 use std::process::ExitCode;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 
 use args::{check_is_valid, CargoKaniSubcommand};
 use args_toml::join_args;
 
 use crate::args::StandaloneSubcommand;
 use crate::concrete_playback::playback::{playback_cargo, playback_standalone};
+use crate::contract_baseline::ContractBaseline;
+use crate::contract_diff::ContractSnapshot;
 use crate::project::Project;
 use crate::session::KaniSession;
+use crate::util::crate_name;
 use crate::version::print_kani_version;
 use clap::Parser;
 use tracing::debug;
@@ -31,6 +35,9 @@
 mod cbmc_output_parser;
 mod cbmc_property_renderer;
 mod concrete_playback;
+mod contract_baseline;
+mod contract_diff;
+mod contract_report;
 mod harness_runner;
 mod metadata;
 mod project;
@@ -79,6 +86,9 @@ fn cargokani_main(input_args: Vec<OsString>) -> Result<()> {
         Some(CargoKaniSubcommand::Playback(args)) => {
             return playback_cargo(*args);
         }
+        Some(CargoKaniSubcommand::CheckContracts) => {
+            return check_contracts(session, None);
+        }
         None => {}
     }
 
@@ -95,8 +105,18 @@ fn standalone_main() -> Result<()> {
     let args = args::StandaloneArgs::parse();
     check_is_valid(&args);
 
-    if let Some(StandaloneSubcommand::Playback(args)) = args.command {
-        return playback_standalone(*args);
+    match args.command {
+        Some(StandaloneSubcommand::Playback(args)) => {
+            return playback_standalone(*args);
+        }
+        Some(StandaloneSubcommand::CheckContracts(args)) => {
+            let session = session::KaniSession::new(args.verify_opts)?;
+            if !session.args.common_args.quiet {
+                print_kani_version(InvocationType::Standalone);
+            }
+            return check_contracts(session, Some(&args.input));
+        }
+        None => {}
     }
 
     let session = session::KaniSession::new(args.verify_opts)?;
@@ -109,16 +129,102 @@ fn standalone_main() -> Result<()> {
     if session.args.only_codegen { Ok(()) } else { verify_project(project, session) }
 }
 
+/// Compile the target package, or the `input` file of standalone Kani, and check that its
+/// contracts are well-formed.
+///
+/// Any malformed contract is reported as a compilation error, so reaching the end means that
+/// every contract was accepted.
+fn check_contracts(mut session: KaniSession, input: Option<&Path>) -> Result<()> {
+    session.check_contracts_only = true;
+    match input {
+        None => {
+            session.cargo_build(false)?;
+        }
+        Some(input) => {
+            let outdir = match &session.args.target_dir {
+                Some(target_dir) => {
+                    std::fs::create_dir_all(target_dir)?;
+                    target_dir.clone()
+                }
+                None => input.canonicalize()?.parent().unwrap().to_path_buf(),
+            };
+            session.compile_single_rust_file(input, &crate_name(input), &outdir)?;
+        }
+    }
+    if !session.args.common_args.quiet {
+        println!("Checked all function contracts successfully.");
+    }
+    Ok(())
+}
+
 /// Run verification on the given project.
 fn verify_project(project: Project, session: KaniSession) -> Result<()> {
     debug!(?project, "verify_project");
-    let harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    if let Some(path) = &session.args.export_contracts {
+        ContractSnapshot::new(&project.metadata).save(path)?;
+    }
+    if session.args.list_contracts {
+        return session.list_contracts(&project.metadata);
+    }
+    if let Some(path) = &session.args.compare_contracts {
+        return session.compare_contracts(&project.metadata, path);
+    }
+    if let Some(threshold) = session.args.contract_coverage_threshold {
+        session.check_contract_coverage(&project.metadata, threshold)?;
+    }
+    let mut harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    let mut baseline = session
+        .args
+        .changed_only
+        .then(|| ContractBaseline::load(&project.outdir, &session))
+        .transpose()?;
+    if let Some(baseline) = &baseline {
+        let (changed, skipped) = session.skip_unchanged_harnesses(baseline, harnesses);
+        if changed.is_empty() && skipped > 0 {
+            return Ok(());
+        }
+        harnesses = changed;
+    }
     debug!(n = harnesses.len(), ?harnesses, "verify_project");
+    if session.args.gen_c_crate {
+        // The goto models are specialized in place for each harness, so link them before.
+        session.gen_crate_c(&project, &harnesses)?;
+    }
 
     // Verification
     let runner = harness_runner::HarnessRunner { sess: &session, project: &project };
+    if let Some(max_unwind) = session.args.search_unwind {
+        return match harnesses.as_slice() {
+            [harness] => runner.search_unwind(harness, max_unwind),
+            _ => bail!(
+                "`--search-unwind` checks one harness at a time, but {} harnesses were selected",
+                harnesses.len()
+            ),
+        };
+    }
     let results = runner.check_all_harnesses(&harnesses)?;
 
+    if let Some(baseline) = &mut baseline {
+        let statuses = results.iter().map(|r| (r.harness, r.result.status)).collect::<Vec<_>>();
+        baseline.record(&statuses);
+        baseline.save(&project.outdir)?;
+    }
+    let solvers = results
+        .iter()
+        .filter(|r| !r.result.timed_out)
+        .filter_map(|r| Some((r.harness, r.result.solver.as_ref()?)))
+        .collect::<Vec<_>>();
+    project.record_solvers(&solvers)?;
+
+    if session.args.enforce_and_replace {
+        session.print_contract_phases(&results);
+    }
+    if session.args.contract_report {
+        session.print_contract_report(&results)?;
+    }
+    if let Some(format) = session.args.stats {
+        session.print_stats(&results, format)?;
+    }
     session.print_final_summary(&results)
 }
 
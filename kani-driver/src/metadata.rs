@@ -6,8 +6,8 @@
 use tracing::{debug, trace};
 
 use kani_metadata::{
-    HarnessAttributes, HarnessMetadata, InternedString, KaniMetadata, TraitDefinedMethod,
-    VtableCtxResults,
+    ContractedFunction, HarnessAttributes, HarnessMetadata, InternedString, KaniMetadata,
+    TraitDefinedMethod, VtableCtxResults,
 };
 use std::collections::{BTreeSet, HashMap};
 use std::fs::File;
@@ -97,6 +97,7 @@ pub fn merge_kani_metadata(files: Vec<KaniMetadata>) -> KaniMetadata {
         proof_harnesses: vec![],
         unsupported_features: vec![],
         test_harnesses: vec![],
+        contracted_functions: vec![],
     };
     for md in files {
         // Note that we're taking ownership of the original vec, and so we can move the data into the new data structure.
@@ -105,6 +106,7 @@ pub fn merge_kani_metadata(files: Vec<KaniMetadata>) -> KaniMetadata {
         // https://github.com/model-checking/kani/issues/1758
         result.unsupported_features.extend(md.unsupported_features);
         result.test_harnesses.extend(md.test_harnesses);
+        result.contracted_functions.extend(md.contracted_functions);
     }
     result
 }
@@ -125,7 +127,7 @@ pub fn determine_targets<'a>(
         let all_targets = &harnesses;
 
         if harnesses.is_empty() {
-            Ok(Vec::from(all_harnesses))
+            Ok(default_harnesses(all_harnesses, self.args.include_slow))
         } else {
             let harnesses_found: Vec<&HarnessMetadata> =
                 find_proof_harnesses(&harnesses, all_harnesses, self.args.exact);
@@ -154,6 +156,99 @@ pub fn determine_targets<'a>(
     }
 }
 
+impl KaniSession {
+    /// Fail if the share of contracted functions that have a `proof_for_contract` harness is
+    /// below `threshold` percent.
+    ///
+    /// The computed percentage and the functions without any harness are printed unless the
+    /// session is quiet.
+    pub fn check_contract_coverage(&self, metadata: &[KaniMetadata], threshold: u8) -> Result<()> {
+        let functions =
+            metadata.iter().flat_map(|md| md.contracted_functions.iter()).collect::<Vec<_>>();
+        let (coverage, unchecked) = contract_coverage(&functions);
+        if !self.args.common_args.quiet {
+            println!(
+                "Contract coverage: {coverage:.2}% ({} of {} functions with contracts are checked \
+                by a harness)",
+                functions.len() - unchecked.len(),
+                functions.len()
+            );
+            if !unchecked.is_empty() {
+                println!("Functions with contracts that are not checked by any harness:");
+                for function in &unchecked {
+                    println!(
+                        " - {} ({}:{})",
+                        function.function, function.location.filename, function.location.start_line
+                    );
+                }
+            }
+        }
+        if coverage < f64::from(threshold) {
+            bail!(
+                "Contract coverage of {coverage:.2}% is below the required threshold of {threshold}%"
+            );
+        }
+        Ok(())
+    }
+
+    /// Print every function with a contract, the generated functions that check and replace it
+    /// and the `proof_for_contract` harnesses that target it.
+    ///
+    /// Fail if any of the functions is not checked by a harness.
+    pub fn list_contracts(&self, metadata: &[KaniMetadata]) -> Result<()> {
+        let mut functions =
+            metadata.iter().flat_map(|md| md.contracted_functions.iter()).collect::<Vec<_>>();
+        functions.sort_by(|a, b| a.function.cmp(&b.function));
+        let (_, unchecked) = contract_coverage(&functions);
+        println!("Functions with contracts:");
+        for function in &functions {
+            println!("{}", describe_contracted_function(function));
+        }
+        println!(
+            "{} of {} functions with contracts are checked by a harness.",
+            functions.len() - unchecked.len(),
+            functions.len()
+        );
+        if !unchecked.is_empty() {
+            bail!("{} function(s) with contracts are not checked by any harness", unchecked.len());
+        }
+        Ok(())
+    }
+}
+
+/// Describe `function` for `--list-contracts`.
+fn describe_contracted_function(function: &ContractedFunction) -> String {
+    let harnesses = if function.harnesses.is_empty() {
+        "none".to_string()
+    } else {
+        function.harnesses.join(", ")
+    };
+    format!(
+        " - {} ({}:{})\n     check: {}\n     replace: {}\n     harnesses: {harnesses}",
+        function.function,
+        function.location.filename,
+        function.location.start_line,
+        function.checked_with,
+        function.replaced_with,
+    )
+}
+
+/// Compute the percentage of `functions` that are checked by at least one harness, and return it
+/// together with the functions that are not checked.
+///
+/// A crate without any contract has full coverage.
+fn contract_coverage<'a>(
+    functions: &[&'a ContractedFunction],
+) -> (f64, Vec<&'a ContractedFunction>) {
+    let unchecked =
+        functions.iter().copied().filter(|f| f.harnesses.is_empty()).collect::<Vec<_>>();
+    if functions.is_empty() {
+        return (100.0, unchecked);
+    }
+    let checked = functions.len() - unchecked.len();
+    (checked as f64 * 100.0 / functions.len() as f64, unchecked)
+}
+
 /// Sort harnesses such that for two harnesses in the same file, it is guaranteed that later
 /// appearing harnesses get processed earlier.
 /// This is necessary for the concrete playback feature (with in-place unit test modification)
@@ -185,9 +280,20 @@ pub fn mock_proof_harness(
         attributes: HarnessAttributes { unwind_value, proof: true, ..Default::default() },
         goto_file: model_file,
         contract: Default::default(),
+        has_loop_contracts: false,
+        verified_with: None,
     }
 }
 
+/// The harnesses to verify when the user did not select any. Harnesses marked with
+/// `#[kani::slow]` are only included if `include_slow` is set.
+fn default_harnesses<'a>(
+    all_harnesses: &[&'a HarnessMetadata],
+    include_slow: bool,
+) -> Vec<&'a HarnessMetadata> {
+    all_harnesses.iter().copied().filter(|h| include_slow || !h.attributes.slow).collect()
+}
+
 /// Search for a proof harness with a particular name.
 /// At the present time, we use `no_mangle` so collisions shouldn't happen,
 /// but this function is written to be robust against that changing in the future.
@@ -226,6 +332,53 @@ fn find_proof_harnesses<'a>(
 mod tests {
     use super::*;
 
+    fn mock_contracted_function(name: &str, harnesses: &[&str]) -> ContractedFunction {
+        ContractedFunction {
+            function: name.into(),
+            location: kani_metadata::Location { filename: "lib.rs".into(), start_line: 1 },
+            checked_with: format!("{name}_recursion_wrapper_0"),
+            replaced_with: format!("{name}_replace_0"),
+            harnesses: harnesses.iter().map(|h| h.to_string()).collect(),
+            requires: vec![],
+            ensures: vec![],
+            modifies: vec![],
+        }
+    }
+
+    #[test]
+    fn check_contract_coverage() {
+        assert_eq!(contract_coverage(&[]), (100.0, vec![]));
+
+        let checked = mock_contracted_function("checked", &["check_checked"]);
+        let unchecked = mock_contracted_function("unchecked", &[]);
+        let other = mock_contracted_function("other", &["check_other", "check_other_2"]);
+        let (coverage, missing) = contract_coverage(&[&checked, &unchecked, &other, &unchecked]);
+        assert_eq!(coverage, 50.0);
+        assert_eq!(missing, [&unchecked, &unchecked]);
+    }
+
+    #[test]
+    fn check_describe_contracted_function() {
+        let checked = mock_contracted_function("checked", &["check_a", "check_b"]);
+        assert_eq!(
+            describe_contracted_function(&checked),
+            " - checked (lib.rs:1)\n     check: checked_recursion_wrapper_0\n     replace: \
+            checked_replace_0\n     harnesses: check_a, check_b"
+        );
+        let unchecked = mock_contracted_function("unchecked", &[]);
+        assert!(describe_contracted_function(&unchecked).ends_with("harnesses: none"));
+    }
+
+    #[test]
+    fn check_default_harnesses() {
+        let fast = mock_proof_harness("fast", None, None, None);
+        let mut slow = mock_proof_harness("slow", None, None, None);
+        slow.attributes.slow = true;
+        let all = [&fast, &slow];
+        assert_eq!(default_harnesses(&all, false), [&fast]);
+        assert_eq!(default_harnesses(&all, true), [&fast, &slow]);
+    }
+
     #[test]
     fn check_find_proof_harness_without_exact() {
         let harnesses = vec![
@@ -21,7 +21,8 @@
 use crate::util::{crate_name, guess_rlib_name};
 use anyhow::{Context, Result};
 use kani_metadata::{
-    artifact::convert_type, ArtifactType, ArtifactType::*, HarnessMetadata, KaniMetadata,
+    artifact::convert_type, ArtifactType, ArtifactType::*, CbmcSolver, HarnessMetadata,
+    KaniMetadata,
 };
 use std::fs::File;
 use std::io::BufWriter;
@@ -100,6 +101,43 @@ pub fn get_harness_artifact(
         })
     }
 
+    /// Return the artifacts of the given type for all `harnesses`, without duplicates.
+    ///
+    /// Harnesses that share a goto model share its artifacts, so each artifact is only returned
+    /// once, in the order of the first harness that uses it.
+    pub fn get_harnesses_artifacts(
+        &self,
+        harnesses: &[&HarnessMetadata],
+        typ: ArtifactType,
+    ) -> Vec<&Artifact> {
+        let mut artifacts: Vec<&Artifact> = vec![];
+        for artifact in harnesses.iter().filter_map(|h| self.get_harness_artifact(h, typ)) {
+            if !artifacts.contains(&artifact) {
+                artifacts.push(artifact);
+            }
+        }
+        artifacts
+    }
+
+    /// Record the solver that verified each harness in the metadata files of the project, see
+    /// [`HarnessMetadata::verified_with`]. The other harnesses keep their entry.
+    pub fn record_solvers(&self, solvers: &[(&HarnessMetadata, &CbmcSolver)]) -> Result<()> {
+        for artifact in self.artifacts.iter().filter(|artifact| artifact.has_type(Metadata)) {
+            let mut metadata: KaniMetadata = from_json(artifact)?;
+            for harness in
+                metadata.proof_harnesses.iter_mut().chain(metadata.test_harnesses.iter_mut())
+            {
+                if let Some((_, solver)) =
+                    solvers.iter().find(|(verified, _)| verified.pretty_name == harness.pretty_name)
+                {
+                    harness.verified_with = Some((*solver).clone());
+                }
+            }
+            dump_metadata(&metadata, artifact);
+        }
+        Ok(())
+    }
+
     /// Try to build a new project from the build result metadata.
     ///
     /// This method will parse the metadata in order to gather all artifacts generated by the
@@ -130,10 +168,12 @@ fn try_new(
 
                 // All other harness artifacts that may have been generated as part of the build.
                 artifacts.extend(
-                    [SymTab, TypeMap, VTableRestriction, PrettyNameMap].iter().filter_map(|typ| {
-                        let artifact = Artifact::try_from(&symtab_out, *typ).ok()?;
-                        Some(artifact)
-                    }),
+                    [SymTab, TypeMap, VTableRestriction, PrettyNameMap, VarNameMap]
+                        .iter()
+                        .filter_map(|typ| {
+                            let artifact = Artifact::try_from(&symtab_out, *typ).ok()?;
+                            Some(artifact)
+                        }),
                 );
                 artifacts.push(symtab_out);
                 artifacts.push(goto);
@@ -259,13 +299,15 @@ pub fn cargo_project(session: &KaniSession, keep_going: bool) -> Result<Project>
             .iter()
             .map(|md_file| from_json(md_file))
             .collect::<Result<Vec<_>>>()?;
-        Project::try_new(
+        let mut project = Project::try_new(
             session,
             outdir,
             metadata,
             Some(outputs.cargo_metadata),
             outputs.failed_targets,
-        )
+        )?;
+        project.artifacts.extend(outputs.metadata);
+        Ok(project)
     }
 }
 
@@ -333,11 +375,10 @@ fn build(self) -> Result<Project> {
         };
 
         // Create the project with the artifacts built by the compiler.
-        let result = Project::try_new(self.session, self.outdir, vec![metadata], None, None);
-        if let Ok(project) = &result {
-            self.session.record_temporary_files(&project.artifacts);
-        }
-        result
+        let mut project = Project::try_new(self.session, self.outdir, vec![metadata], None, None)?;
+        self.session.record_temporary_files(&project.artifacts);
+        project.artifacts.push(self.metadata);
+        Ok(project)
     }
 }
 
@@ -10,7 +10,8 @@
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, ExitStatus, Stdio};
 use std::sync::Mutex;
-use std::time::Instant;
+use std::thread;
+use std::time::{Duration, Instant};
 use strum_macros::Display;
 use tracing::level_filters::LevelFilter;
 use tracing_subscriber::{layer::SubscriberExt, EnvFilter, Registry};
@@ -31,6 +32,10 @@ pub struct KaniSession {
     /// proof attributes.
     pub codegen_tests: bool,
 
+    /// Stop the compilation of the target crate after its contracts have been checked. No code
+    /// is generated and nothing is verified.
+    pub check_contracts_only: bool,
+
     /// The location we found the 'kani_rustc' command
     pub kani_compiler: PathBuf,
     /// The location we found 'kani_lib.c'
@@ -58,6 +63,7 @@ pub fn new(args: VerificationArgs) -> Result<Self> {
         Ok(KaniSession {
             args,
             codegen_tests: false,
+            check_contracts_only: false,
             kani_compiler: install.kani_compiler()?,
             kani_lib_c: install.kani_lib_c()?,
             temporaries: Mutex::new(vec![]),
@@ -121,6 +127,15 @@ pub fn run_terminal(&self, cmd: Command) -> Result<()> {
         run_terminal(&self.args.common_args, cmd)
     }
 
+    /// Call [run_terminal_with_timeout] with the verbosity configured by the user.
+    pub fn run_terminal_with_timeout(
+        &self,
+        cmd: Command,
+        timeout: Option<Duration>,
+    ) -> Result<bool> {
+        run_terminal_with_timeout(&self.args.common_args, cmd, timeout)
+    }
+
     /// Call [run_suppress] with the verbosity configured by the user.
     pub fn run_suppress(&self, cmd: Command) -> Result<()> {
         run_suppress(&self.args.common_args, cmd)
@@ -182,6 +197,56 @@ pub fn run_terminal(verbosity: &impl Verbosity, mut cmd: Command) -> Result<()>
     Ok(())
 }
 
+/// Run a job like [run_terminal], but kill it once it has run for `timeout`.
+/// Returns whether the job was killed.
+pub fn run_terminal_with_timeout(
+    verbosity: &impl Verbosity,
+    mut cmd: Command,
+    timeout: Option<Duration>,
+) -> Result<bool> {
+    let Some(timeout) = timeout else {
+        return run_terminal(verbosity, cmd).map(|()| false);
+    };
+    if verbosity.quiet() {
+        cmd.stdout(std::process::Stdio::null());
+        cmd.stderr(std::process::Stdio::null());
+    }
+    if verbosity.verbose() {
+        println!("[Kani] Running: `{}`", render_command(&cmd).to_string_lossy());
+    }
+    let program = cmd.get_program().to_string_lossy().to_string();
+    let status = with_timer(
+        verbosity,
+        || -> Result<Option<ExitStatus>> {
+            let start = Instant::now();
+            let mut child = cmd
+                .spawn()
+                .context(format!("Failed to invoke {}", cmd.get_program().to_string_lossy()))?;
+            // The output goes to the terminal, so there is nothing to read and we can poll.
+            loop {
+                if let Some(status) = child.try_wait()? {
+                    return Ok(Some(status));
+                }
+                if start.elapsed() >= timeout {
+                    // The process may have exited in the meantime, which is fine.
+                    let _ = child.kill();
+                    child.wait()?;
+                    return Ok(None);
+                }
+                thread::sleep(Duration::from_millis(10));
+            }
+        },
+        &program,
+    )?;
+    match status {
+        None => Ok(true),
+        Some(status) if !status.success() => {
+            bail!("{} exited with status {}", cmd.get_program().to_string_lossy(), status)
+        }
+        Some(_) => Ok(false),
+    }
+}
+
 /// Run a job, but only output (unless --quiet) if it fails, and fail if there's a problem.
 pub fn run_suppress(verbosity: &impl Verbosity, mut cmd: Command) -> Result<()> {
     if verbosity.is_set() {
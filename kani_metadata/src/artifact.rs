@@ -25,6 +25,9 @@ pub enum ArtifactType {
     /// A `json` file that stores the name to prettyName mapping for symbols
     /// (used to demangle names from the C dump).
     PrettyNameMap,
+    /// A `json` file that maps the name of each function to the source names of its local
+    /// variables (used to demangle local variable names from the C dump).
+    VarNameMap,
 }
 
 impl ArtifactType {
@@ -37,6 +40,7 @@ const fn extension(&self) -> &'static str {
             ArtifactType::TypeMap => "type_map.json",
             ArtifactType::VTableRestriction => "restrictions.json",
             ArtifactType::PrettyNameMap => "pretty_name_map.json",
+            ArtifactType::VarNameMap => "var_name_map.json",
         }
     }
 }
@@ -64,7 +68,8 @@ pub fn convert_type(path: &Path, from: ArtifactType, to: ArtifactType) -> PathBu
         | ArtifactType::SymTabGoto
         | ArtifactType::TypeMap
         | ArtifactType::VTableRestriction
-        | ArtifactType::PrettyNameMap => {
+        | ArtifactType::PrettyNameMap
+        | ArtifactType::VarNameMap => {
             result.set_extension("");
             result.set_extension(to);
         }
@@ -2,11 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
 /// An enum for CBMC solver options. All variants are handled by Kani, except for
-/// the `Binary` one, which it passes as is to CBMC's `--external-sat-solver`
-/// option.
+/// the `Binary` one, which it passes to CBMC's `--external-sat-solver` option.
 #[derive(
     Debug,
     Clone,
@@ -31,6 +31,24 @@ pub enum CbmcSolver {
 
     /// A solver binary variant whose argument gets passed to
     /// `--external-sat-solver`. The specified binary must exist in path.
+    ///
+    /// The solver is invoked with `args` before the name of the CNF file.
     #[strum(disabled, serialize = "bin=<SAT_SOLVER_BINARY>")]
-    Binary(String),
+    Binary { binary: String, args: Vec<String> },
+}
+
+impl fmt::Display for CbmcSolver {
+    /// Print the solver the way it is written in the `#[kani::solver]` attribute.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CbmcSolver::Binary { binary, args } if args.is_empty() => {
+                write!(f, "bin=\"{binary}\"")
+            }
+            CbmcSolver::Binary { binary, args } => {
+                let args = args.iter().map(|arg| format!("{arg:?}")).collect::<Vec<_>>();
+                write!(f, "bin=\"{binary}\", args=[{}]", args.join(", "))
+            }
+            _ => f.write_str(self.as_ref()),
+        }
+    }
 }
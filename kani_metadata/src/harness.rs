@@ -1,17 +1,59 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::CbmcSolver;
+use crate::{CbmcSolver, Location, PointerCheck};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
 /// A CBMC-level `assigns` contract that needs to be enforced on a function.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct AssignsContract {
+    /// The fully qualified name of the function under contract.
+    pub function: String,
     /// The target of the contract
     pub contracted_function_name: String,
     /// A static global variable used to track recursion that must not be havocked.
     pub recursion_tracker: String,
+    /// The static global variable holding the measure of a `decreases` clause, if the contract
+    /// has one. Like the recursion tracker it must not be havocked.
+    pub decreases_measure: Option<String>,
+    /// A hash of the harness, of the contract and body of the function under contract and of
+    /// the contracts of the functions reachable from the harness. Used by `--changed-only` to
+    /// skip harnesses whose fingerprint did not change since they last succeeded.
+    pub fingerprint: String,
+    /// The `requires` and `ensures` clauses of the contract, in the order they are checked.
+    pub clauses: Vec<ContractClauseMetadata>,
+    /// The targets of the `modifies` clauses of the contract, in the order of the variables the
+    /// compiler binds them to, i.e., the order of the CBMC `assigns` contract.
+    pub targets: Vec<AssignsTarget>,
+}
+
+/// A target of a `modifies` clause, which becomes one target of the CBMC `assigns` contract.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AssignsTarget {
+    /// The target as written by the user.
+    pub source: String,
+}
+
+/// A `requires` or `ensures` clause of a contract that a harness checks.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ContractClauseMetadata {
+    pub kind: ContractClauseKind,
+    /// The condition as written by the user.
+    pub condition: String,
+    /// The location CBMC reports for the property of this clause.
+    pub location: Location,
+    /// Whether the condition is a constant, in which case it does not depend on the function.
+    pub constant: bool,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContractClauseKind {
+    Requires,
+    Ensures,
 }
 
 /// We emit this structure for each annotated proof harness (`#[kani::proof]`) we find.
@@ -33,8 +75,15 @@ pub struct HarnessMetadata {
     pub goto_file: Option<PathBuf>,
     /// The `#[kani::<>]` attributes added to a harness.
     pub attributes: HarnessAttributes,
-    /// A CBMC-level assigns contract that should be enforced when running this harness.
-    pub contract: Option<AssignsContract>,
+    /// The CBMC-level assigns contracts that should be enforced when running this harness, one
+    /// for each target of `proof_for_contract`.
+    pub contract: Vec<AssignsContract>,
+    /// Whether a loop reachable from this harness has a `#[kani::loop_contract]`.
+    pub has_loop_contracts: bool,
+    /// The solver that finished verifying this harness, i.e. the one of the `solver` attribute
+    /// that did not time out. The compiler leaves it empty, the driver records it in the metadata
+    /// file after verification.
+    pub verified_with: Option<CbmcSolver>,
 }
 
 /// The attributes added by the user to control how a harness is executed.
@@ -42,14 +91,60 @@ pub struct HarnessMetadata {
 pub struct HarnessAttributes {
     /// Whether the harness has been annotated with proof.
     pub proof: bool,
-    /// Whether the harness is expected to panic or not.
-    pub should_panic: bool,
-    /// Optional data to store solver.
-    pub solver: Option<CbmcSolver>,
+    /// The fully qualified names of the functions whose contracts this harness checks, if it was
+    /// annotated with `proof_for_contract`.
+    pub proof_for_contract: Vec<String>,
+    /// The fully qualified name of the function this harness checks for idempotence, if it was
+    /// annotated with `proof_idempotent`.
+    pub proof_idempotent: Option<String>,
+    /// Whether the harness is expected to panic, and on which paths.
+    pub should_panic: Option<ShouldPanicMode>,
+    /// The text every panic of a `should_panic` harness must contain, if it was given with
+    /// `#[kani::should_panic(expected = "...")]`.
+    pub should_panic_expected: Option<String>,
+    /// Whether verification fails if the assumptions of the harness prune every path, i.e., if
+    /// the end of the harness is unreachable.
+    pub expect_unreachable: bool,
+    /// Whether the harness is excluded from the default verification run.
+    pub slow: bool,
+    /// The solvers to try, in order. The next solver is only used if the previous one timed out.
+    pub solver: Vec<CbmcSolver>,
     /// Optional data to store unwind value.
     pub unwind_value: Option<u32>,
+    /// The unwind bounds of individual loops, by loop id, from
+    /// `#[kani::unwind(loop_id = ..., value = ...)]`. A loop id is either the number of a loop of
+    /// the harness or a full CBMC loop id, e.g. `function.0`.
+    pub unwind_loops: Vec<(String, u32)>,
     /// The stubs used in this harness.
     pub stubs: Vec<Stub>,
+    /// The fully qualified names of the functions this harness replaces by their contract, if it
+    /// was annotated with `stub_verified`.
+    pub verified_stubs: Vec<String>,
+    /// Pointer checks that were explicitly enabled or disabled for this harness.
+    pub pointer_checks: BTreeMap<PointerCheck, bool>,
+}
+
+/// Which paths of a `#[kani::should_panic(mode = "...")]` harness are expected to panic.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum ShouldPanicMode {
+    /// At least one path panics, and there are no failures other than panics.
+    #[default]
+    Any,
+    /// Like [`Self::Any`], but in addition every path panics, i.e., the harness never returns.
+    All,
 }
 
 /// The stubbing type.
@@ -10,11 +10,13 @@
 pub use artifact::ArtifactType;
 pub use cbmc_solver::CbmcSolver;
 pub use harness::*;
+pub use pointer_check::PointerCheck;
 pub use vtable::*;
 
 pub mod artifact;
 mod cbmc_solver;
 mod harness;
+mod pointer_check;
 pub mod unstable;
 mod vtable;
 
@@ -32,6 +34,29 @@ pub struct KaniMetadata {
     pub unsupported_features: Vec<UnsupportedFeature>,
     /// If crates are built in test-mode, then test harnesses will be recorded here.
     pub test_harnesses: Vec<HarnessMetadata>,
+    /// The functions in this crate that carry a contract.
+    pub contracted_functions: Vec<ContractedFunction>,
+}
+
+/// A function with a contract and the harnesses that check this contract.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContractedFunction {
+    /// The fully qualified name of the function.
+    pub function: String,
+    /// The location where the function is defined.
+    pub location: Location,
+    /// The name of the generated function that checks the contract (`checked_with`).
+    pub checked_with: String,
+    /// The name of the generated function that replaces calls by the contract (`replaced_with`).
+    pub replaced_with: String,
+    /// The `proof_for_contract` harnesses that target this function.
+    pub harnesses: Vec<String>,
+    /// The source text of the `requires` clauses of the contract.
+    pub requires: Vec<String>,
+    /// The source text of the `ensures` clauses of the contract.
+    pub ensures: Vec<String>,
+    /// The source text of the targets of the `modifies` clauses of the contract.
+    pub modifies: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// The CBMC pointer checks that can be toggled for an individual harness with the
+/// `#[kani::pointer_checks(<check> = <bool>, ...)]` attribute.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum PointerCheck {
+    /// Validity of dereferenced pointers (`--pointer-check`).
+    Dereference,
+    /// Overflow in pointer arithmetic (`--pointer-overflow-check`).
+    Overflow,
+    /// Invalid pointers in pointer primitives such as relational operations
+    /// (`--pointer-primitive-check`).
+    Primitive,
+}
+
+impl PointerCheck {
+    /// The CBMC flag that enables this check.
+    pub fn cbmc_flag(self) -> &'static str {
+        match self {
+            PointerCheck::Dereference => "--pointer-check",
+            PointerCheck::Overflow => "--pointer-overflow-check",
+            PointerCheck::Primitive => "--pointer-primitive-check",
+        }
+    }
+}
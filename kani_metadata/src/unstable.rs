@@ -82,6 +82,12 @@ pub enum UnstableFeature {
     LineCoverage,
     /// Enable function contracts [RFC 9](https://model-checking.github.io/kani/rfc/rfcs/0009-function-contracts.html)
     FunctionContracts,
+    /// Verify loops annotated with `#[kani::loop_contract]` using their contract
+    /// instead of unwinding them.
+    LoopContracts,
+    /// Allow searching for the smallest sufficient unwind bound of a harness with
+    /// `--search-unwind`.
+    SearchUnwind,
 }
 
 impl UnstableFeature {
@@ -60,6 +60,19 @@
 //! also has access to the value returned from the function in a variable called
 //! `result`.
 //!
+//! If an argument is itself called `result`, that name refers to the argument
+//! instead, and Kani warns about its use in a postcondition. Write
+//! [`kani::result()`](crate::result) to refer to the returned value unambiguously, e.g.
+//! `#[ensures(kani::result() <= result)]`.
+//!
+//! Instead of using `result`, a postcondition may also be written as a closure
+//! whose single argument is bound to a reference to the returned value. This
+//! lets you destructure it, e.g. for a function returning a pair:
+//!
+//! ```
+//! #[ensures(|(low, high)| low <= high)]
+//! ```
+//!
 //! You may combine as many [`requires`][macro@requires] and
 //! [`ensures`][macro@ensures] attributes on a single function as you please.
 //! They all get enforced (as if their conditions were `&&`ed together) and the
@@ -92,6 +105,11 @@
 //! The harness is checked like any other by running `cargo kani` and can be
 //! specifically selected with `--harness my_div_harness`.
 //!
+//! A harness like this one, which only calls the function with `kani::any()`
+//! arguments, can also be generated by adding
+//! [`auto_harness`](macro@auto_harness) to the function. The generated harness
+//! is named `my_div_auto_harness`.
+//!
 //! Once we have verified that our contract holds, we can use perhaps it's
 //! coolest feature: verified stubbing. This allows us to use the conditions of
 //! the contract *instead* of it's implementation. This can be very powerful for
@@ -116,6 +134,19 @@
 //! In this example the contract is sufficient to prove that the element access
 //! in the last line cannot be out-of-bounds.
 //!
+//! The function to stub may also be defined in a dependency, e.g. when the
+//! contracts live in a base crate and the harnesses in a downstream crate. Use
+//! the path to the function in that crate, e.g.
+//! `#[kani::stub_verified(::base_crate::my_div)]`. The dependency must be
+//! compiled by Kani, which is the case for the dependencies of a package
+//! verified with `cargo kani`.
+//!
+//! For modular verification of a larger crate, pass `--replace-all-contracts`
+//! instead of listing every callee. Each
+//! [`proof_for_contract`][macro@proof_for_contract] harness then replaces every
+//! other function of the crate that has a contract by its contract, so each
+//! contract is verified assuming the contracts of the functions it calls.
+//!
 //! ## Specification Attributes Overview
 //!
 //! The basic two specification attributes available for describing
@@ -138,6 +169,10 @@
 //! replaced by a call to `kani::any`. As such the return value must implement
 //! the `kani::Arbitrary` trait.
 //!
+//! For a function returning a `Result`, [`ensures_ok`](macro@ensures_ok) is a
+//! shorthand for `#[kani::ensures(result.is_ok())]`, stating that the function
+//! succeeds whenever its preconditions hold.
+//!
 //! In Kani, function contracts are optional. As such a function with at least
 //! one specification attribute is considered to "have a contract" and any
 //! absent specification type defaults to its most general interpretation
@@ -146,6 +181,342 @@
 //! of a [`proof_for_contract`][macro@proof_for_contract] of
 //! [`stub_verified`][macro@stub_verified] attribute.
 //!
+//! ## Entry Snapshots
+//!
+//! A postcondition is evaluated after the function returns, so it usually sees
+//! the arguments as the function left them. To compare against the state on
+//! entry use `old_with(expr, snapshot_fn)` inside an [`ensures`](macro@ensures)
+//! clause. Kani evaluates `snapshot_fn(expr)` before the function body runs
+//! and substitutes the result for the call. This lets you capture a summary of
+//! a value that is expensive or impossible to clone:
+//!
+//! ```
+//! struct Log {
+//!     entries: u32,
+//! }
+//!
+//! #[kani::modifies(log)]
+//! #[kani::ensures(log.entries == old_with(&*log, |l: &Log| l.entries) + 1)]
+//! fn append(log: &mut Log) {
+//!     log.entries += 1;
+//! }
+//! ```
+//!
+//! Since [`std::mem::discriminant`] returns a plain `Copy` value it makes a
+//! good snapshot function for checking that a method preserves the variant of
+//! an enum:
+//!
+//! ```
+//! enum Connection {
+//!     Idle,
+//!     Open { sent: u32 },
+//! }
+//!
+//! impl Connection {
+//!     #[kani::modifies(self)]
+//!     #[kani::ensures(
+//!         std::mem::discriminant(self) == old_with(&*self, std::mem::discriminant)
+//!     )]
+//!     fn send(&mut self) {
+//!         if let Connection::Open { sent } = self {
+//!             *sent = sent.wrapping_add(1);
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! If the summary can be computed directly from the arguments, `old(expr)` is a
+//! shorthand that snapshots the value of `expr` on entry. Together with
+//! [`is_sorted`](crate::is_sorted) this states that an operation preserves the
+//! order of a collection:
+//!
+//! ```
+//! struct SortedVec {
+//!     v: Vec<u32>,
+//! }
+//!
+//! impl SortedVec {
+//!     #[kani::modifies(self)]
+//!     #[kani::ensures(!old(kani::is_sorted(&self.v)) || kani::is_sorted(&self.v))]
+//!     fn insert(&mut self, x: u32) {
+//!         let idx = self.v.partition_point(|&y| y < x);
+//!         self.v.insert(idx, x);
+//!     }
+//! }
+//! ```
+//!
+//! The value of `expr` must not borrow from the arguments, the same as for the
+//! result of a snapshot function. To snapshot the whole value behind a `&mut`
+//! argument write `old(*v)`, which Kani evaluates as a clone of `*v`. This
+//! suits in-place transformations:
+//!
+//! ```
+//! #[kani::requires(v.len() == 2)]
+//! #[kani::modifies(&v[0], &v[1])]
+//! #[kani::ensures(v.len() == old(v.len()))]
+//! #[kani::ensures(v[0] <= v[1] && old(*v).contains(&v[0]) && old(*v).contains(&v[1]))]
+//! fn normalize(v: &mut Vec<u32>) {
+//!     if v[0] > v[1] {
+//!         v.swap(0, 1);
+//!     }
+//! }
+//! ```
+//!
+//! A query method of `self` or of one of its fields may be used in the same
+//! way, e.g. `old(self.book_exists(id))` or `old(self.available.len())`. The
+//! method and its arguments are evaluated on entry and only the result is
+//! kept, `self` is not cloned. The result must therefore not borrow from
+//! `self`; copy or clone it instead, e.g. `old(self.book(id).copied())`.
+//!
+//! ```
+//! struct Library {
+//!     books: [Option<u32>; 4],
+//! }
+//!
+//! impl Library {
+//!     fn book(&self, id: usize) -> Option<&u32> {
+//!         self.books[id].as_ref()
+//!     }
+//!
+//!     #[kani::requires(id < 4)]
+//!     #[kani::modifies(self)]
+//!     #[kani::ensures(result == old(self.book(id).copied()))]
+//!     fn remove(&mut self, id: usize) -> Option<u32> {
+//!         self.books[id].take()
+//!     }
+//! }
+//! ```
+//!
+//! Kani does not check `snapshot_fn`, it is the responsibility of the user to
+//! make sure it is free of side effects and does not panic. Its result is kept
+//! alive until the postcondition has been evaluated, so it should be a plain
+//! value (ideally `Copy`) that does not borrow from `expr`. A snapshot function
+//! that violates these obligations makes the contract unsound.
+//!
+//! ## Call Counts
+//!
+//! For higher-order functions it is often important how many times a closure
+//! argument is invoked. Inside an [`ensures`](macro@ensures) clause
+//! `call_count(f)` (or `kani::call_count(f)`) evaluates to the number of times
+//! the function body called its argument `f`:
+//!
+//! ```
+//! #[kani::ensures(kani::call_count(f) == n as usize)]
+//! fn repeat<F: FnMut(u32) -> u32>(n: u8, init: u32, mut f: F) -> u32 {
+//!     let mut acc = init;
+//!     for _ in 0..n {
+//!         acc = f(acc);
+//!     }
+//!     acc
+//! }
+//! ```
+//!
+//! `f` must be an argument whose type is either `impl Fn(..)`/`impl FnMut(..)`
+//! or a type parameter with such a bound. Every call made through `f` during
+//! the function call is counted, including calls made by functions that `f` is
+//! passed on to. When the contract is used as a stub the closure is never
+//! called and the count is only constrained by the postcondition.
+//!
+//! ## Implications and Quantifiers
+//!
+//! In [`requires`](macro@requires) and [`ensures`](macro@ensures) clauses
+//! `a ==> b` is shorthand for `!(a) || (b)`. The implication binds weaker
+//! than any Rust operator, so it extends to the surrounding parentheses,
+//! commas or closure parameters.
+//!
+//! A property of every element of a fixed-size array can be stated with
+//! `kani::forall(|i| i < N ==> p)`, where `N` is an integer literal of at most
+//! 64. Kani unrolls the quantifier into the conjunction of `p` for `i` in
+//! `0..N` (or `0..=N` for `i <= N`), so it doesn't depend on quantifier support
+//! in the solver. The quantifier must be called as `kani::forall`, a function
+//! called just `forall` is left alone:
+//!
+//! ```
+//! #[kani::ensures(kani::forall(|i| i < 8 ==> arr[i] == 0))]
+//! #[kani::modifies(arr)]
+//! fn zero(arr: &mut [u32; 8]) {
+//!     for elem in arr.iter_mut() {
+//!         *elem = 0;
+//!     }
+//! }
+//! ```
+//!
+//! The range may also be written out in the closure, as in
+//! `kani::forall(|i in 2..8| p)` or `kani::forall(|i in 0..=n| p)`, and
+//! `kani::exists(|i in a..b| p)` states that `p` holds for at least one `i` in
+//! the range. An `exists` over literal bounds is unrolled into a disjunction.
+//!
+//! If a bound is not an integer literal, e.g.
+//! `kani::forall(|i| i < v.len() ==> v[i] > 0)`, or the range has more than 64
+//! elements, the quantifier becomes a loop over the range that evaluates `p`
+//! for each `i`. This works both where the condition is checked and where it
+//! is assumed, but the loop has to be unwound for the whole range, so the
+//! harness needs an [`unwind`](macro@crate::unwind) bound larger than the
+//! longest range.
+//!
+//! A `forall` without a bound, such as `kani::forall(|i: u32| i > x ==> p)`,
+//! needs the type of its variable, and Kani evaluates `p` for an arbitrary
+//! value of that type instead. Where the condition is checked, e.g. a
+//! postcondition in its [`proof_for_contract`](macro@proof_for_contract)
+//! harness, this proves `p` for every value. Where it is assumed, e.g. a
+//! precondition in that harness or a postcondition of a
+//! [`stub_verified`](macro@stub_verified) replacement, Kani only assumes `p`
+//! for one arbitrary value. This is sound but weaker than the quantifier, so
+//! verification may fail spuriously. An `exists` always needs a range.
+//!
+//! ## Predicates
+//!
+//! A condition that is shared by several contracts can be moved into a
+//! function marked with [`predicate`](macro@predicate) and called from the
+//! [`requires`](macro@requires) and [`ensures`](macro@ensures) clauses:
+//!
+//! ```
+//! struct Range {
+//!     lo: u32,
+//!     hi: u32,
+//! }
+//!
+//! #[kani::predicate]
+//! fn valid(r: &Range) -> bool {
+//!     r.lo <= r.hi
+//! }
+//!
+//! #[kani::requires(valid(&r))]
+//! #[kani::ensures(valid(&result))]
+//! fn widen(r: Range) -> Range {
+//!     Range { lo: r.lo.saturating_sub(1), hi: r.hi.saturating_add(1) }
+//! }
+//! ```
+//!
+//! A predicate is evaluated like a regular function call wherever it is used.
+//! It may not have a contract of its own, and Kani reports an error if its
+//! body writes to memory through a pointer or allocates. Only the body of the
+//! predicate is inspected, so functions called from a predicate must be free
+//! of side effects as well.
+//!
+//! ## Pure Functions
+//!
+//! A function with a contract that only reads its arguments and returns a
+//! value can be marked with [`pure`](macro@pure):
+//!
+//! ```
+//! #[kani::pure]
+//! #[kani::requires(!v.is_empty())]
+//! #[kani::ensures(v.contains(&result))]
+//! fn first(v: &[u32]) -> u32 {
+//!     v[0]
+//! }
+//! ```
+//!
+//! This states explicitly what a contract without a
+//! [`modifies`](macro@modifies) clause already means: the write set of the
+//! function is empty. A checking harness fails if the function writes to
+//! memory outside of its own stack frame. When the function is used with
+//! [`stub_verified`](macro@stub_verified), only its result is replaced by a
+//! value that satisfies the postconditions; no memory is havocked. Kani
+//! reports an error if a function marked `pure` has no contract or has a
+//! `modifies` clause.
+//!
+//! A function with a contract may also be called from the clauses of another
+//! contract. By default the harness that checks the other contract runs the
+//! body of such a helper. Adding [`stub_verified`](macro@stub_verified) for
+//! the helper to that harness uses its contract instead, both in the clauses
+//! and in the body of the function under contract:
+//!
+//! ```
+//! #[kani::pure]
+//! #[kani::ensures(result == (x % 2 == 0))]
+//! fn is_even(x: u32) -> bool {
+//!     x % 2 == 0
+//! }
+//!
+//! #[kani::requires(x < u32::MAX)]
+//! #[kani::ensures(is_even(result))]
+//! fn round_up_even(x: u32) -> u32 {
+//!     x + x % 2
+//! }
+//!
+//! #[kani::proof_for_contract(round_up_even)]
+//! #[kani::stub_verified(is_even)]
+//! fn check_round_up_even() {
+//!     let _ = round_up_even(kani::any());
+//! }
+//! ```
+//!
+//! ## Contracts in `impl` Blocks
+//!
+//! Contracts may be placed on methods and associated functions. The
+//! conditions can rely on the generic parameters and bounds of the surrounding
+//! `impl` block:
+//!
+//! ```
+//! struct Pair<T> {
+//!     first: T,
+//!     second: T,
+//! }
+//!
+//! impl<T: Ord + Copy> Pair<T> {
+//!     #[kani::ensures(result >= self.first && result >= self.second)]
+//!     fn max(&self) -> T {
+//!         if self.first < self.second { self.second } else { self.first }
+//!     }
+//! }
+//! ```
+//!
+//! Kani can only recognize an associated function as such if it takes `self`
+//! or mentions `Self` somewhere. For an associated function that does neither,
+//! e.g. `fn new() -> Pair<T>`, compilation fails with an error about a missing
+//! generated function (such as `new_check_..`). Spelling the type as `Self`
+//! (`fn new() -> Self`) fixes this.
+//!
+//! Contracts cannot be placed on the methods of a trait implementation. Put
+//! the contract on an inherent method instead and call it from the trait
+//! method. A [`proof_for_contract`][macro@proof_for_contract] harness for the
+//! inherent method may then also reach it through a trait object, e.g.
+//! `&mut dyn Trait` created from the concrete type. Kani reports an error if
+//! the harness never calls the function under contract.
+//!
+//! A method provided by a trait, i.e. one with a default body, may have a
+//! contract. Name it through the trait, e.g. `Trait::method`, in
+//! [`proof_for_contract`][macro@proof_for_contract] and
+//! [`stub_verified`](macro@stub_verified). A checking harness verifies the
+//! contract for the implementors that it calls the method on, as long as they
+//! inherit the provided body:
+//!
+//! ```
+//! trait Counter {
+//!     fn count(&self) -> u32;
+//!
+//!     #[kani::requires(self.count() < u32::MAX)]
+//!     #[kani::ensures(result == self.count() + 1)]
+//!     fn next(&self) -> u32 {
+//!         self.count() + 1
+//!     }
+//! }
+//! ```
+//!
+//! The generated functions are added to the trait as provided methods. A
+//! `modifies` clause makes them generic, so a trait with such a contract
+//! cannot be used as a trait object.
+//!
+//! ## Contracts on Functions Without a Body
+//!
+//! A contract may also describe a function whose body is not available to
+//! Kani, such as a function imported over FFI. Write the declaration outside
+//! of an `extern` block, with the ABI in its signature, and put the contract on
+//! it:
+//!
+//! ```ignore
+//! #[kani::requires(x != i32::MIN)]
+//! #[kani::ensures(result >= 0)]
+//! extern "C" fn ffi_abs(x: i32) -> i32;
+//! ```
+//!
+//! The declaration is moved into an `extern` block, so calling it is `unsafe`
+//! as usual. Since there is no body, the contract cannot be checked with a
+//! [`proof_for_contract`](macro@proof_for_contract) harness. It is trusted and
+//! can only be used with [`stub_verified`](macro@stub_verified).
+//!
 //! ## Contract Use Attributes Overview
 //!
 //! Contract are used both to verify function behavior and to leverage the
@@ -154,21 +525,63 @@
 //! Verifying function behavior currently requires the designation of at least
 //! one checking harness with the
 //! [`proof_for_contract`](macro@proof_for_contract) attribute. A harness may
-//! only have one `proof_for_contract` attribute and it may not also have a
-//! `proof` attribute.
+//! not also have a `proof` attribute.
+//!
+//! One harness may check the contracts of several closely related functions,
+//! e.g. `#[kani::proof_for_contract(Library::lend, Library::return_book)]`.
+//! Every call to one of the targets in the harness is checked against its
+//! contract, and a function may only be named once. CBMC can only enforce the
+//! `modifies` clause of one function at a time, so Kani verifies such a harness
+//! once per target, enforcing the `modifies` clause of that target, and reports
+//! the harness as failed if any of these runs fails. The `requires` and
+//! `ensures` clauses of all targets are checked in every run. The rules below
+//! apply to every target.
 //!
 //! The checking harness is expected to set up the arguments that `foo` should
 //! be called with and initialized any `static mut` globals that are reachable.
 //! All of these should be initialized to as general value as possible, usually
 //! achieved using `kani::any`. The harness must call e.g. `foo` at least once
 //! and if `foo` has type parameters, only one instantiation of those parameters
-//! is admissible. Violating either results in a compile error.
+//! is admissible. Violating either results in a compile error. The same holds
+//! for const generics: the clauses of `fn foo<const N: usize>(arr: [u32; N])`
+//! may refer to `N`, but a checking harness may only call `foo` with a single
+//! value for `N`. Use one harness per value you want to check.
 //!
 //! If any inputs have special invariants you *can* use `kani::assume` to
 //! enforce them but this may introduce unsoundness. In general all restrictions
 //! on input parameters should be part of the [`requires`](macro@requires)
 //! clause of the function contract.
 //!
+//! By default a checking harness enforces every `ensures` and `modifies`
+//! clause of the contract. While developing a contract it can help to check
+//! one kind of clause at a time: passing `--enforce-clauses=assigns` to Kani
+//! only checks the write set, and `--enforce-clauses=ensures` only checks the
+//! postconditions. Preconditions are always assumed. A contract checked this
+//! way is only partially verified, so Kani prints the clauses it enforced
+//! after the result of each checking harness.
+//!
+//! With `--changed-only`, Kani skips the checking harnesses that succeeded
+//! before and did not change since. A harness counts as changed if its own
+//! code, the contract or body of the function it checks, or the contract of any
+//! function with a contract that it reaches changed. Harnesses are also
+//! verified again when the options that affect verification change, e.g. the
+//! enabled checks or the unwinding bound. The fingerprints of the harnesses
+//! that succeeded are stored in the output directory, except for runs with
+//! `--enforce-clauses`, which do not check the whole contract. Other changes,
+//! e.g. to a function without a contract that the checked function calls, are
+//! not detected, so run all harnesses before relying on the result.
+//!
+//! `--list-contracts` prints every function with a contract, the generated
+//! functions that check and replace it and the `proof_for_contract` harnesses
+//! that target it, without running verification. It fails if any function
+//! with a contract is not checked by a harness, so it can be used in CI.
+//!
+//! `--contract-report` prints, for every checking harness, the `requires` and
+//! `ensures` clauses of the checked functions as JSON. Each clause is marked
+//! as checked, failed, trivial (a constant condition), unsatisfiable (a
+//! precondition no input fulfills) or unreachable (a postcondition no path
+//! reaches).
+//!
 //! Once the contract has been verified it may be used as a verified stub. For
 //! this the [`stub_verified`](macro@stub_verified) attribute is used.
 //! `stub_verified` is a harness attribute, like
@@ -176,8 +589,14 @@
 //! annotated with [`proof`](macro@crate::proof). It may also be used on a
 //! `proof_for_contract` proof.
 //!
-//! Unlike `proof_for_contract` multiple `stub_verified` attributes are allowed
-//! on the same proof harness though they must target different functions.
+//! Multiple `stub_verified` attributes are allowed on the same proof harness
+//! though they must target different functions.
+//!
+//! A function that returns `impl Trait` may have a contract and be checked with
+//! `proof_for_contract`, but it cannot be used with `stub_verified`: its result
+//! has a type that only the compiler knows, so Kani cannot create an arbitrary
+//! value of it. For the same reason recursive calls of such a function are not
+//! replaced by its contract during inductive verification.
 //!
 //! ## Inductive Verification
 //!
@@ -198,6 +617,31 @@
 //! [#2823](https://github.com/model-checking/kani/issues/2823) to enable
 //! opt-out of inductive verification.
 //!
+//! ## Termination
+//!
+//! Inductive verification proves that a recursive function satisfies its
+//! contract *if* it terminates. To also prove termination, give the function a
+//! measure with the [`decreases`](macro@decreases) attribute: an integer
+//! expression over the arguments that must shrink with every recursive call.
+//!
+//! ```
+//! #[kani::requires(x != 0 && y != 0)]
+//! #[kani::decreases(y)]
+//! fn gcd(x: u8, y: u8) -> u8 {
+//!     let r = x % y;
+//!     if r == 0 { y } else { gcd(y, r) }
+//! }
+//! ```
+//!
+//! When checking the contract, Kani evaluates the measure on entry to the
+//! function and asserts that every recursive call has a measure that is
+//! non-negative and strictly smaller. Since the measure cannot decrease
+//! forever, the recursion terminates.
+//!
+//! Only integer measures and direct self-recursion are supported. The measure
+//! is converted to `i128` for the comparison. Like the conditions of a
+//! contract it must be free of side effects and must not panic.
+//!
 //! ## Write Sets
 //!
 //! The [`modifies`](macro@modifies) attribute is used to describe which
@@ -225,4 +669,56 @@
 //! Rust pointer type (`&T`, `&mut T`, `*const T` or `*mut T`). In addition `T`
 //! must implement [`Arbitrary`](super::Arbitrary). This is used to assign
 //! `kani::any()` to the location when the function is used in a `stub_verified`.
-pub use super::{ensures, modifies, proof_for_contract, requires, stub_verified};
+//!
+//! A pointer to a field, e.g. `&self.config.limits.max`, only allows that
+//! field to be modified, no matter how deeply it is nested. The other fields
+//! of the surrounding structs keep their value, also when the function is used
+//! in a `stub_verified`.
+//!
+//! The opposite shorthand is `*self` (or `*ptr` for any pointer argument),
+//! which allows the function to write any part of the pointed-to object, as if
+//! each of its fields had been listed. This is coarser than listing the fields
+//! you actually write: when the function is used in a `stub_verified`, all of
+//! its fields are havocked, not just the ones the function writes.
+//!
+//! A field of a place behind a pointer, e.g. `(*u).field`, is borrowed in the
+//! same way. This is how a single field of a `union` is named: it allows the
+//! function to write the bytes of that field, so writing a wider field of the
+//! same `union` is still rejected.
+//!
+//! Functions with a contract may not take `*mut T` arguments in general,
+//! since the contract could not tell what the function does with them. An
+//! exception are out-pointers whose pointee is a `modifies` target, e.g.
+//! `#[kani::modifies(*out)]` on `unsafe fn fill(out: *mut T)`.
+//!
+//! `T` may also be a slice `[U]` with `U: Arbitrary`, which allows every
+//! element of the slice to be modified. For a buffer described by a raw
+//! pointer and a length, build the slice pointer with
+//! [`core::ptr::slice_from_raw_parts_mut`]:
+//!
+//! ```
+//! struct Buffer {
+//!     ptr: *mut u8,
+//!     cap: usize,
+//! }
+//!
+//! impl Buffer {
+//!     #[kani::requires(idx < self.cap)]
+//!     #[kani::modifies(core::ptr::slice_from_raw_parts_mut(self.ptr, self.cap))]
+//!     fn write(&mut self, idx: usize, val: u8) {
+//!         unsafe { *self.ptr.add(idx) = val }
+//!     }
+//! }
+//! ```
+//!
+//! Other unsized targets, such as trait objects, are not supported.
+//!
+//! A checking harness fails if the function writes to any location that is
+//! not covered by its `modifies` clauses, unless the `assigns` clauses were
+//! excluded with `--enforce-clauses`. The failure names the function and its
+//! `modifies` targets, e.g. "function `a_function` writes undeclared location
+//! `*ptr`, not permitted by `modifies(&self.len)`".
+pub use super::{
+    auto_harness, decreases, ensures, ensures_ok, modifies, predicate, proof_for_contract,
+    requires, stub_verified,
+};
@@ -1,13 +1,20 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::Arbitrary;
+use std::cell::{Cell, UnsafeCell};
+use std::sync::atomic::{
+    AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32,
+    AtomicU64, AtomicU8, AtomicUsize,
+};
+
 /// Helper trait for code generation for `modifies` contracts.
 ///
 /// We allow the user to provide us with a pointer-like object that we convert as needed.
 #[doc(hidden)]
 pub trait Pointer<'a> {
     /// Type of the pointed-to data
-    type Inner;
+    type Inner: ?Sized;
 
     /// Used for checking assigns contracts where we pass immutable references to the function.
     ///
@@ -19,7 +26,7 @@ pub trait Pointer<'a> {
     unsafe fn assignable(self) -> &'a mut Self::Inner;
 }
 
-impl<'a, 'b, T> Pointer<'a> for &'b T {
+impl<'a, 'b, T: ?Sized> Pointer<'a> for &'b T {
     type Inner = T;
     unsafe fn decouple_lifetime(&self) -> &'a Self::Inner {
         std::mem::transmute(*self)
@@ -31,7 +38,7 @@ unsafe fn assignable(self) -> &'a mut Self::Inner {
     }
 }
 
-impl<'a, 'b, T> Pointer<'a> for &'b mut T {
+impl<'a, 'b, T: ?Sized> Pointer<'a> for &'b mut T {
     type Inner = T;
 
     #[allow(clippy::transmute_ptr_to_ref)]
@@ -44,7 +51,7 @@ unsafe fn assignable(self) -> &'a mut Self::Inner {
     }
 }
 
-impl<'a, T> Pointer<'a> for *const T {
+impl<'a, T: ?Sized> Pointer<'a> for *const T {
     type Inner = T;
     unsafe fn decouple_lifetime(&self) -> &'a Self::Inner {
         &**self as &'a T
@@ -56,7 +63,7 @@ unsafe fn assignable(self) -> &'a mut Self::Inner {
     }
 }
 
-impl<'a, T> Pointer<'a> for *mut T {
+impl<'a, T: ?Sized> Pointer<'a> for *mut T {
     type Inner = T;
     unsafe fn decouple_lifetime(&self) -> &'a Self::Inner {
         &**self as &'a T
@@ -68,6 +75,123 @@ unsafe fn assignable(self) -> &'a mut Self::Inner {
     }
 }
 
+/// Helper trait for havocking the targets of a `modifies` clause when a
+/// contract is used as a stub.
+///
+/// Sized targets are overwritten with a single nondeterministic value. Slice
+/// targets, e.g. `core::ptr::slice_from_raw_parts_mut(ptr, len)`, have each of
+/// their elements overwritten. So do the types with interior mutability that a
+/// target behind a shared reference may have, `Cell`, `UnsafeCell` and the
+/// atomics.
+#[doc(hidden)]
+#[rustc_on_unimplemented(
+    message = "`{Self}` cannot be used as a `modifies` target",
+    label = "the target of this `modifies` clause",
+    note = "`modifies` targets must point to a type that implements `kani::Arbitrary`, or to a \
+            slice of such a type"
+)]
+pub trait Havoc {
+    fn havoc(&mut self);
+}
+
+impl<T: Arbitrary> Havoc for T {
+    fn havoc(&mut self) {
+        *self = T::any();
+    }
+}
+
+impl<T: Arbitrary> Havoc for [T] {
+    fn havoc(&mut self) {
+        self.iter_mut().for_each(Havoc::havoc)
+    }
+}
+
+impl<T: Arbitrary> Havoc for Cell<T> {
+    fn havoc(&mut self) {
+        *self.get_mut() = T::any();
+    }
+}
+
+impl<T: Arbitrary> Havoc for UnsafeCell<T> {
+    fn havoc(&mut self) {
+        *self.get_mut() = T::any();
+    }
+}
+
+/// Atomics are havocked through their exclusive reference like a `Cell`.
+macro_rules! havoc_atomic {
+    ( $atomic: ty ) => {
+        impl Havoc for $atomic {
+            fn havoc(&mut self) {
+                *self.get_mut() = crate::any();
+            }
+        }
+    };
+}
+
+havoc_atomic!(AtomicBool);
+havoc_atomic!(AtomicI8);
+havoc_atomic!(AtomicI16);
+havoc_atomic!(AtomicI32);
+havoc_atomic!(AtomicI64);
+havoc_atomic!(AtomicIsize);
+havoc_atomic!(AtomicU8);
+havoc_atomic!(AtomicU16);
+havoc_atomic!(AtomicU32);
+havoc_atomic!(AtomicU64);
+havoc_atomic!(AtomicUsize);
+
+/// Creates an argument of type `T` for the replacement client that the contract
+/// macros generate for a function with a contract.
+///
+/// The client is generated for every such function, so it has to compile even
+/// if an argument cannot be created. The macros therefore call
+/// [`AnyClientArgument::any_argument`] on `&&ClientArgument::<T>::new()`. If
+/// `T` implements [`Arbitrary`] the method resolves to the implementation for
+/// `&ClientArgument<T>`, otherwise auto-deref falls back to the one for
+/// `ClientArgument<T>`, which fails verification.
+#[doc(hidden)]
+pub struct ClientArgument<T>(std::marker::PhantomData<T>);
+
+impl<T> ClientArgument<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        ClientArgument(std::marker::PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait AnyClientArgument<T> {
+    fn any_argument(&self) -> T;
+}
+
+impl<T: Arbitrary> AnyClientArgument<T> for &ClientArgument<T> {
+    fn any_argument(&self) -> T {
+        crate::any()
+    }
+}
+
+impl<T> AnyClientArgument<T> for ClientArgument<T> {
+    fn any_argument(&self) -> T {
+        crate::panic(
+            "cannot call the function in its replacement client because an argument does not \
+            implement `kani::Arbitrary`, write a `#[kani::stub_verified]` harness instead",
+        )
+    }
+}
+
+/// Used by the contract macros for a `modifies` target that is behind a shared
+/// reference or shared pointer parameter. The compiler rejects the target with
+/// `msg` unless the type behind `target` has interior mutability, since other
+/// memory behind a shared reference cannot be assigned.
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniSharedModifiesTarget"]
+pub fn shared_modifies_target<T: ?Sized>(target: *const T, msg: &'static str) {
+    let _ = target;
+    let _ = msg;
+}
+
 /// A way to break the ownerhip rules. Only used by contracts where we can
 /// guarantee it is done safely.
 #[inline(never)]
@@ -76,3 +200,100 @@ unsafe fn assignable(self) -> &'a mut Self::Inner {
 pub fn untracked_deref<T>(_: &T) -> T {
     todo!()
 }
+
+/// Like [`crate::assert`], but used for the precondition of a contract that
+/// replaces a function. With `--precondition-call-site` the compiler gives these
+/// assertions their own property class so that precondition violations can be
+/// attributed to the caller.
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniCheckPrecondition"]
+pub fn check_precondition(cond: bool, msg: &'static str) {
+    let _ = cond;
+    let _ = msg;
+}
+
+/// Like [`crate::assume`], but used for the precondition of a contract whose
+/// check function is being verified. The compiler records the clause so that
+/// the driver can report whether it was satisfiable.
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniAssumePrecondition"]
+pub fn assume_precondition(cond: bool, msg: &'static str) {
+    let _ = cond;
+    let _ = msg;
+}
+
+/// Like [`crate::assert`], but used for the postcondition of a contract whose
+/// check function is being verified. The compiler drops these assertions if the
+/// user asked not to enforce `ensures` clauses.
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniCheckPostcondition"]
+pub fn check_postcondition(cond: bool, msg: &'static str) {
+    let _ = cond;
+    let _ = msg;
+}
+
+/// The `decreases` measure on entry to the outermost active call of a function
+/// whose contract is being checked, together with the hash identifying that
+/// function.
+///
+/// The compiler excludes this static from nondeterministic initialization
+/// during contract checks, so it starts out as `None`.
+#[rustc_diagnostic_item = "KaniDecreasesMeasure"]
+static mut DECREASES_MEASURE: Option<(u64, i128)> = None;
+
+/// Record `measure` as the `decreases` measure of the function identified by
+/// `key`. Returns the previous record, which must be restored with
+/// [`exit_decreases`] once the call returns.
+#[doc(hidden)]
+pub fn enter_decreases(key: u64, measure: i128) -> Option<(u64, i128)> {
+    unsafe {
+        let previous = DECREASES_MEASURE;
+        DECREASES_MEASURE = Some((key, measure));
+        previous
+    }
+}
+
+/// Restore the record returned by [`enter_decreases`].
+#[doc(hidden)]
+pub fn exit_decreases(previous: Option<(u64, i128)>) {
+    unsafe { DECREASES_MEASURE = previous }
+}
+
+/// The measure that a recursive call of the function identified by `key` must
+/// stay below, if that function's contract is being checked.
+#[doc(hidden)]
+pub fn decreases_bound(key: u64) -> Option<i128> {
+    match unsafe { DECREASES_MEASURE } {
+        Some((active, measure)) if active == key => Some(measure),
+        _ => None,
+    }
+}
+
+/// Register the contract of the loop whose guard calls this function, see
+/// `#[kani::loop_contract]`. The closures are created before the loop and are
+/// passed by pointer so that the places they capture can be modified by the
+/// loop.
+///
+/// The compiler replaces the call by `true` and attaches a call of this
+/// function, which evaluates the invariant, to the backedges of the loop. The
+/// places captured by `assigns` become the assigns clause of the loop.
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniLoopContract"]
+pub fn loop_contract<I: Fn() -> bool, A: Fn()>(invariant: *const I, assigns: *const A) -> bool {
+    let _ = assigns;
+    unsafe { (*invariant)() }
+}
+
+/// Register the invariant of the loop whose guard calls this function, see
+/// `#[kani::invariant]`. This is [`loop_contract`] without an assigns clause,
+/// so CBMC infers the places that the loop modifies.
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniLoopInvariant"]
+pub fn loop_invariant<I: Fn() -> bool>(invariant: *const I) -> bool {
+    unsafe { (*invariant)() }
+}
@@ -95,6 +95,76 @@ macro_rules! implies {
     };
 }
 
+/// Checks whether the memory referenced by `a` overlaps with the memory
+/// referenced by `b`.
+///
+/// This is intended to be used in function contracts, e.g. to state that a
+/// returned reference does not alias one of the arguments:
+///
+/// ```rust
+/// #[kani::ensures(!kani::aliases(result.0, result.1))]
+/// fn split(s: &mut [u8]) -> (&mut [u8], &mut [u8]) {
+///     s.split_at_mut(s.len() / 2)
+/// }
+/// ```
+///
+/// Two references alias if they point into the same allocation and their
+/// address ranges intersect. References to zero-sized values never alias.
+#[inline(always)]
+pub fn aliases<T: ?Sized, U: ?Sized>(a: &T, b: &U) -> bool {
+    overlaps(
+        a as *const T as *const u8,
+        core::mem::size_of_val(a),
+        b as *const U as *const u8,
+        core::mem::size_of_val(b),
+    )
+}
+
+/// Checks whether the elements of `s` are in non-decreasing order.
+///
+/// This is intended to be used in function contracts, e.g. together with
+/// `old` to state that an operation preserves the order of a collection:
+///
+/// ```rust
+/// #[kani::modifies(v)]
+/// #[kani::ensures(!old(kani::is_sorted(v)) || kani::is_sorted(v))]
+/// fn push_max(v: &mut Vec<u8>) {
+///     v.push(u8::MAX);
+/// }
+/// ```
+pub fn is_sorted<T: PartialOrd>(s: &[T]) -> bool {
+    s.windows(2).all(|w| w[0] <= w[1])
+}
+
+/// Refers to the value returned from the function in a postcondition, even if
+/// an argument is called `result`:
+///
+/// ```rust
+/// #[kani::ensures(kani::result() <= result)]
+/// fn min_with(result: u32, other: u32) -> u32 {
+///     result.min(other)
+/// }
+/// ```
+///
+/// The contract macros replace every call to this function in an
+/// [`ensures`](macro@ensures) clause. It has no meaning anywhere else, and
+/// calling it outside of a postcondition panics.
+pub fn result<T>() -> T {
+    panic!("`kani::result()` can only be used in an `ensures` clause")
+}
+
+/// Do the `a_len` bytes starting at `a` overlap with the `b_len` bytes starting
+/// at `b`?
+///
+/// During verification this is replaced by a check that also takes the
+/// provenance of both pointers into account.
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniOverlaps"]
+fn overlaps(a: *const u8, a_len: usize, b: *const u8, b_len: usize) -> bool {
+    let (a, b) = (a as usize, b as usize);
+    a_len != 0 && b_len != 0 && a < b + b_len && b < a + a_len
+}
+
 /// Creates an assertion of the specified condition and message.
 ///
 /// # Example:
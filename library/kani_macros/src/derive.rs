@@ -9,13 +9,25 @@
 //! struct S;
 //!
 //! ```
+//!
+//! A type may declare an invariant that every symbolic value must satisfy:
+//!
+//! ```
+//! #[derive(Arbitrary)]
+//! #[type_invariant(is_valid)]
+//! struct Even(u8);
+//!
+//! impl Even {
+//!     fn is_valid(&self) -> bool { self.0 % 2 == 0 }
+//! }
+//! ```
 use proc_macro2::{Ident, Span, TokenStream};
 use proc_macro_error::abort;
 use quote::{quote, quote_spanned};
 use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Fields, GenericParam, Generics,
-    Index,
+    parse_macro_input, parse_quote, Attribute, Data, DataEnum, DeriveInput, Fields, GenericParam,
+    Generics, Index,
 };
 
 pub fn expand_derive_arbitrary(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -28,6 +40,15 @@ pub fn expand_derive_arbitrary(item: proc_macro::TokenStream) -> proc_macro::Tok
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let body = fn_any_body(&item_name, &derive_item.data);
+    let body = if let Some(invariant) = type_invariant(&derive_item.attrs) {
+        quote! {
+            let result = { #body };
+            kani::assume(result.#invariant());
+            result
+        }
+    } else {
+        body
+    };
     let expanded = quote! {
         // The generated implementation.
         impl #impl_generics kani::Arbitrary for #item_name #ty_generics #where_clause {
@@ -39,6 +60,23 @@ fn any() -> Self {
     proc_macro::TokenStream::from(expanded)
 }
 
+/// Extract the name of the invariant method from a `#[type_invariant(method)]` attribute, if any.
+///
+/// At most one such attribute is allowed and it must contain a single identifier.
+fn type_invariant(attrs: &[Attribute]) -> Option<Ident> {
+    let mut invariants = attrs.iter().filter(|attr| attr.path().is_ident("type_invariant"));
+    let invariant = invariants.next()?;
+    if let Some(extra) = invariants.next() {
+        abort!(extra.span(), "only one `#[type_invariant]` attribute is allowed per type");
+    }
+    match invariant.parse_args::<Ident>() {
+        Ok(method) => Some(method),
+        Err(_) => abort!(invariant.span(), "expected the name of an invariant method";
+            help = "use `#[type_invariant(method)]` where `method` is a `fn(&self) -> bool`"
+        ),
+    }
+}
+
 /// Add a bound `T: Arbitrary` to every type parameter T.
 fn add_trait_bound(mut generics: Generics) -> Generics {
     generics.params.iter_mut().for_each(|param| {
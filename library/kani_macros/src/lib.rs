@@ -43,18 +43,55 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// allows users to exercise [negative testing](https://en.wikipedia.org/wiki/Negative_testing)
 /// for Rust unit tests.
 ///
+/// By default, or with `#[kani::should_panic(mode = "any")]`, the harness is expected to panic on
+/// at least one path. With `#[kani::should_panic(mode = "all")]`, it is expected to panic on
+/// every path, i.e., verification fails if the harness can return normally.
+///
+/// With `#[kani::should_panic(expected = "<message>")]`, every panic must contain the given
+/// message, so verification fails if the harness can panic in a different way.
+///
 /// # Limitations
 ///
 /// The `#[kani::should_panic]` attribute verifies that there are one or more failed checks related to panics.
-/// At the moment, it's not possible to pin it down to specific panics.
+/// Without an `expected` message, it's not possible to pin it down to specific panics.
 #[proc_macro_attribute]
 pub fn should_panic(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::should_panic(attr, item)
 }
 
+/// Checks that the assumptions of a proof harness do not make it unreachable.
+///
+/// A harness whose assumptions or preconditions are contradictory, e.g. because a `requires`
+/// clause is over-constrained, is reported as successful because every check holds vacuously.
+/// With this attribute, Kani checks that the end of the harness is reachable and verification
+/// fails if the assumptions prune every path.
+///
+/// The attribute `#[kani::expect_unreachable]` can only be called alongside `#[kani::proof]`
+/// or `#[kani::proof_for_contract]`, and not together with `#[kani::should_panic]`.
+#[proc_macro_attribute]
+pub fn expect_unreachable(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::expect_unreachable(attr, item)
+}
+
+/// Excludes a proof harness from the default verification run.
+///
+/// Use this for expensive harnesses that should not slow down the common
+/// `cargo kani` run. A slow harness is only verified if it is selected
+/// explicitly with `--harness`, or if `--include-slow` is passed.
+///
+/// The attribute `#[kani::slow]` can only be called alongside `#[kani::proof]`
+/// or `#[kani::proof_for_contract]`.
+#[proc_macro_attribute]
+pub fn slow(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::slow(attr, item)
+}
+
 /// Set Loop unwind limit for proof harnesses
 /// The attribute `#[kani::unwind(arg)]` can only be called alongside `#[kani::proof]`.
 /// arg - Takes in a integer value (u32) that represents the unwind value for the harness.
+///
+/// `#[kani::unwind(loop_id = id, value = arg)]` sets the unwind value of a single loop instead,
+/// where `id` is the number of a loop of the harness or a CBMC loop id such as `"function.0"`.
 #[proc_macro_attribute]
 pub fn unwind(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::unwind(attr, item)
@@ -65,7 +102,8 @@ pub fn unwind(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// The attribute `#[kani::stub(original, replacement)]` can only be used alongside `#[kani::proof]`.
 ///
 /// # Arguments
-/// * `original` - The function or method to replace, specified as a path.
+/// * `original` - The function or method to replace, specified as a path. The path may end with
+///   generic arguments, e.g. `decode::<u32>`, to only replace that instance of a generic function.
 /// * `replacement` - The function or method to use as a replacement, specified as a path.
 #[proc_macro_attribute]
 pub fn stub(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -74,14 +112,33 @@ pub fn stub(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Select the SAT solver to use with CBMC for this harness
 ///
-/// The attribute `#[kani::solver(arg)]` can only be used alongside `#[kani::proof]`.
+/// The attribute `#[kani::solver(arg, ...)]` can only be used alongside `#[kani::proof]`.
+///
+/// arg - name of solver, e.g. kissat. If several solvers are listed, the next one is tried when
+/// the previous one exceeds the `--solver-timeout`.
 ///
-/// arg - name of solver, e.g. kissat
+/// A SAT solver binary, e.g. `bin="kissat"`, may be followed by the command-line arguments
+/// to invoke it with, e.g. `args=["--quiet"]`.
 #[proc_macro_attribute]
 pub fn solver(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::solver(attr, item)
 }
 
+/// Enable or disable individual CBMC pointer checks for this harness.
+///
+/// The attribute `#[kani::pointer_checks(check = bool, ...)]` can only be used alongside
+/// `#[kani::proof]`. It overrides the global configuration for the given checks and only for this
+/// harness.
+///
+/// Valid checks are:
+/// * `dereference` - Validity of dereferenced pointers.
+/// * `overflow` - Overflow in pointer arithmetic.
+/// * `primitive` - Invalid pointers in pointer primitives, e.g. relational operations.
+#[proc_macro_attribute]
+pub fn pointer_checks(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::pointer_checks(attr, item)
+}
+
 /// Mark an API as unstable. This should only be used inside the Kani sysroot.
 /// See https://model-checking.github.io/kani/rfc/rfcs/0006-unstable-api.html for more details.
 #[doc(hidden)]
@@ -91,8 +148,24 @@ pub fn unstable(attr: TokenStream, item: TokenStream) -> TokenStream {
 }
 
 /// Allow users to auto generate Arbitrary implementations by using `#[derive(Arbitrary)]` macro.
+///
+/// If the type carries a `#[type_invariant(method)]` attribute, the generated `any()` will assume
+/// that `method` holds for the generated value, i.e. `kani::any::<T>()` only yields values that
+/// satisfy the invariant. `method` must have the signature `fn(&self) -> bool`.
+///
+/// ```ignore
+/// #[derive(kani::Arbitrary)]
+/// #[type_invariant(is_valid)]
+/// struct Even(u8);
+///
+/// impl Even {
+///     fn is_valid(&self) -> bool {
+///         self.0 % 2 == 0
+///     }
+/// }
+/// ```
 #[proc_macro_error]
-#[proc_macro_derive(Arbitrary)]
+#[proc_macro_derive(Arbitrary, attributes(type_invariant))]
 pub fn derive_arbitrary(item: TokenStream) -> TokenStream {
     derive::expand_derive_arbitrary(item)
 }
@@ -105,7 +178,8 @@ pub fn derive_arbitrary(item: TokenStream) -> TokenStream {
 /// The contents of the attribute is a condition over the input values to the
 /// annotated function. All Rust syntax is supported, even calling other
 /// functions, but the computations must be side effect free, e.g. it cannot
-/// perform I/O or use mutable memory.
+/// perform I/O or use mutable memory. Conditions that need intermediate results
+/// can be written as a block, e.g. `{ let n = v.len(); n > 0 && n < 8 }`.
 ///
 /// Kani requires each function that uses a contract (this attribute or
 /// [`ensures`][macro@ensures]) to have at least one designated
@@ -127,6 +201,26 @@ pub fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// the computations must be side effect free, e.g. it cannot perform I/O or use
 /// mutable memory.
 ///
+/// To refer to the state of an argument on entry to the function use
+/// `old_with(expr, snapshot_fn)`. `snapshot_fn(expr)` is evaluated before the
+/// function body runs and the call is replaced by the result. See the
+/// [module-level documentation](../kani/contracts/index.html#entry-snapshots)
+/// for the obligations this places on `snapshot_fn`. The shorthand `old(expr)`
+/// snapshots the value of `expr` itself, e.g. `old(v.len())`. `old(*v)` clones
+/// the value behind the reference `v`, so it must implement `Clone`. As with
+/// [`requires`][macro@requires] the condition may be a block with intermediate
+/// `let` bindings, but `expr` must not refer to them, because it is evaluated
+/// before the function body runs.
+///
+/// For an argument `f` with an `Fn` or `FnMut` bound, `call_count(f)` is the
+/// number of times the function body called `f`. See the
+/// [module-level documentation](../kani/contracts/index.html#call-counts).
+///
+/// `a ==> b` stands for `!(a) || (b)` and `kani::forall(|i| i < N ==> p)`,
+/// where `N` is an integer literal of at most 64, is unrolled into the
+/// conjunction of `p` for every `i` in `0..N`. See the
+/// [module-level documentation](../kani/contracts/index.html#implications-and-quantifiers).
+///
 /// Kani requires each function that uses a contract (this attribute or
 /// [`requires`][macro@requires]) to have at least one designated
 /// [`proof_for_contract`][macro@proof_for_contract] harness for checking the
@@ -136,11 +230,34 @@ pub fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::ensures(attr, item)
 }
 
+/// Add the postcondition `result.is_ok()` to this function, i.e. state that
+/// it always succeeds if its preconditions hold.
+///
+/// This is part of the function contract API, for more general information see
+/// the [module-level documentation](../kani/contracts/index.html).
+///
+/// The annotated function must return a `Result`. Inputs that violate a
+/// [`requires`][macro@requires] clause are not constrained, so the function may
+/// still return an `Err` for them:
+///
+/// ```ignore
+/// #[kani::requires(divisor != 0)]
+/// #[kani::ensures_ok]
+/// fn checked_div(dividend: u32, divisor: u32) -> Result<u32, DivError> {
+///     dividend.checked_div(divisor).ok_or(DivError::ByZero)
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn ensures_ok(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::ensures_ok(attr, item)
+}
+
 /// Designates this function as a harness to check a function contract.
 ///
 /// The argument to this macro is the relative path (e.g. `foo` or
 /// `super::some_mod::foo` or `crate::SomeStruct::foo`) to the function, the
-/// contract of which should be checked.
+/// contract of which should be checked. Several comma-separated paths check the
+/// contracts of all of these functions in one harness.
 ///
 /// This is part of the function contract API, for more general information see
 /// the [module-level documentation](../kani/contracts/index.html).
@@ -149,6 +266,58 @@ pub fn proof_for_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::proof_for_contract(attr, item)
 }
 
+/// Designates this function as a harness that checks that a function is
+/// idempotent, i.e. that `f(f(x)) == f(x)` for every `x`.
+///
+/// The argument to this macro is the relative path to a function `f` of type
+/// `T -> T` where `T` implements [`Arbitrary`](../kani/trait.Arbitrary.html),
+/// `Clone` and `PartialEq`. The harness applies `f` to a symbolic input, applies
+/// it again to the result and asserts that both results are equal. The body of
+/// the annotated function runs before this check.
+///
+/// If `f` has a contract, its preconditions are assumed for both applications
+/// and its postconditions are checked, as in a
+/// [`proof_for_contract`][macro@proof_for_contract] harness.
+///
+/// ```ignore
+/// fn clamp(x: u8) -> u8 {
+///     x.min(100)
+/// }
+///
+/// #[kani::proof_idempotent(clamp)]
+/// fn clamp_is_idempotent() {}
+/// ```
+#[proc_macro_attribute]
+pub fn proof_idempotent(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::proof_idempotent(attr, item)
+}
+
+/// Generate a [`proof_for_contract`][macro@proof_for_contract] harness for the
+/// annotated function, which calls it with `kani::any()` for every argument.
+///
+/// The harness is named after the function with an `_auto_harness` suffix. As
+/// in any `proof_for_contract` harness, the preconditions of the contract are
+/// assumed and its postconditions are checked. Every argument type must
+/// implement [`Arbitrary`](../kani/trait.Arbitrary.html). For an argument of
+/// type `&T` or `&mut T`, `T` must implement it instead.
+///
+/// Only free functions without type or const generic parameters and without
+/// `impl Trait` arguments are supported. Placing the attribute on an associated
+/// function is a compile error.
+///
+/// ```ignore
+/// #[kani::requires(divisor != 0)]
+/// #[kani::ensures(result <= dividend)]
+/// #[kani::auto_harness]
+/// fn div(dividend: u32, divisor: u32) -> u32 {
+///     dividend / divisor
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn auto_harness(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::auto_harness(attr, item)
+}
+
 /// `stub_verified(TARGET)` is a harness attribute (to be used on
 /// [`proof`][macro@proof] or [`proof_for_contract`][macro@proof_for_contract]
 /// function) that replaces all occurrences of `TARGET` reachable from this
@@ -160,6 +329,10 @@ pub fn proof_for_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// You may use multiple `stub_verified` attributes on a single harness.
 ///
+/// At every call of `TARGET` the preconditions of its contract are checked. If
+/// the harness violates one, Kani reports it at the contract. Pass
+/// `--precondition-call-site` to report the offending call instead.
+///
 /// This is part of the function contract API, for more general information see
 /// the [module-level documentation](../kani/contracts/index.html).
 #[proc_macro_attribute]
@@ -175,7 +348,8 @@ pub fn stub_verified(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// The contents of the attribute is a series of comma-separated expressions referencing the
 /// arguments of the function. Each expression is expected to return a pointer type, i.e. `*const T`,
 /// `*mut T`, `&T` or `&mut T`. The pointed-to type must implement
-/// [`Arbitrary`](../kani/arbitrary/trait.Arbitrary.html).
+/// [`Arbitrary`](../kani/arbitrary/trait.Arbitrary.html). A dereference such as `*self` is
+/// shorthand for a pointer to the whole object, i.e. `&*self`, and allows writes to any of its fields.
 ///
 /// All Rust syntax is supported, even calling other functions, but the computations must be side
 /// effect free, e.g. it cannot perform I/O or use mutable memory.
@@ -188,6 +362,116 @@ pub fn modifies(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::modifies(attr, item)
 }
 
+/// Add a termination measure to this recursive function.
+///
+/// This is part of the function contract API, for more general information see
+/// the [module-level documentation](../kani/contracts/index.html).
+///
+/// The contents of the attribute is an integer expression over the input
+/// values to the annotated function. When the contract is checked, Kani
+/// asserts that the measure of every recursive call is non-negative and
+/// strictly smaller than the measure on entry to the function, which proves
+/// that the recursion terminates. See the
+/// [module-level documentation](../kani/contracts/index.html#termination)
+/// for details.
+///
+/// Kani requires each function that uses a contract to have at least one designated
+/// [`proof_for_contract`][macro@proof_for_contract] harness for checking the
+/// contract.
+#[proc_macro_attribute]
+pub fn decreases(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::decreases(attr, item)
+}
+
+/// Mark this function as a side effect free predicate that may be used in the
+/// conditions of function contracts.
+///
+/// This is part of the function contract API, for more general information see
+/// the [module-level documentation](../kani/contracts/index.html#predicates).
+///
+/// A predicate is evaluated like any other function when the conditions that
+/// use it are evaluated. It cannot have a contract of its own and its body may
+/// not write to memory through a pointer or allocate.
+#[proc_macro_attribute]
+pub fn predicate(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::predicate(attr, item)
+}
+
+/// Declare that this function with a contract does not modify any memory that
+/// is visible to its caller.
+///
+/// This is part of the function contract API, for more general information see
+/// the [module-level documentation](../kani/contracts/index.html#pure-functions).
+///
+/// A pure function has an empty write set. Checking its contract fails if it
+/// writes to memory outside of its own stack frame, and when it is used with
+/// [`stub_verified`][macro@stub_verified] only its result is replaced. Kani
+/// reports an error if the function has no contract or has a
+/// [`modifies`][macro@modifies] clause.
+#[proc_macro_attribute]
+pub fn pure(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::pure(attr, item)
+}
+
+/// Add a loop contract to the annotated `while`, `loop` or `for` loop, which
+/// lets Kani verify the loop without unwinding it.
+///
+/// The attribute takes two arguments:
+///
+/// * `invariant = <expr>` - A boolean expression that holds before the loop
+///   and after every iteration of it.
+/// * `assigns = [<place>, ...]` - The places the loop may modify, e.g. `x`,
+///   `s.len` or `*self`.
+///
+/// Kani checks that the invariant holds when the loop is entered and that each
+/// iteration preserves it while only modifying the listed places. The code after
+/// the loop may assume the invariant and the negation of the loop guard.
+///
+/// The attribute is placed on a statement, which requires
+/// `#![feature(stmt_expr_attributes)]` and `#![feature(proc_macro_hygiene)]`.
+/// It is unstable and requires `-Z loop-contracts`.
+#[proc_macro_attribute]
+pub fn loop_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::loop_contract(attr, item)
+}
+
+/// Add a loop invariant to the annotated `while`, `loop` or `for` loop, e.g.
+/// `#[kani::invariant(x >= 1)]`.
+///
+/// This is a loop contract (see [`loop_contract`][macro@loop_contract]) without an assigns
+/// clause: CBMC infers the places that the loop may modify.
+///
+/// The attribute is placed on a statement, which requires
+/// `#![feature(stmt_expr_attributes)]` and `#![feature(proc_macro_hygiene)]`.
+/// It is unstable and requires `-Z loop-contracts`.
+#[proc_macro_attribute]
+pub fn invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::invariant(attr, item)
+}
+
+/// Assert that the postconditions of a function hold at this point of the
+/// program, e.g. while working towards a proof of its contract.
+///
+/// The argument is a call of a function with a contract, optionally followed by
+/// the result to check. The call is not executed. Instead the `ensures` clauses
+/// of the function are evaluated with its arguments and the result, which is
+/// the variable `result` in scope if it is not given:
+///
+/// ```ignore
+/// let result = checked_div(dividend, divisor);
+/// kani::assert_contract_holds!(checked_div(dividend, divisor));
+/// let quotient = checked_div(dividend, 2);
+/// kani::assert_contract_holds!(checked_div(dividend, 2), quotient);
+/// ```
+///
+/// A postcondition that uses `old`, `old_with` or `call_count` refers to the
+/// entry of the function, so it cannot be checked this way and fails
+/// verification.
+#[proc_macro]
+pub fn assert_contract_holds(item: TokenStream) -> TokenStream {
+    attr_impl::assert_contract_holds(item)
+}
+
 /// This module implements Kani attributes in a way that only Kani's compiler can understand.
 /// This code should only be activated when pre-building Kani's sysroot.
 #[cfg(kani_sysroot)]
@@ -195,15 +479,21 @@ mod sysroot {
     use proc_macro_error::{abort, abort_call_site};
 
     mod contracts;
+    mod loop_contracts;
 
-    pub use contracts::{ensures, modifies, proof_for_contract, requires, stub_verified};
+    pub use contracts::{
+        assert_contract_holds, auto_harness, decreases, ensures, ensures_ok, modifies,
+        proof_for_contract, proof_idempotent, requires, stub_verified,
+    };
+    pub use loop_contracts::{invariant, loop_contract};
 
     use super::*;
 
     use {
         quote::{format_ident, quote},
-        syn::parse::{Parse, ParseStream},
-        syn::{parse_macro_input, ItemFn},
+        syn::parse::{Parse, ParseStream, Parser},
+        syn::punctuated::Punctuated,
+        syn::{parse_macro_input, ItemFn, Token},
     };
 
     /// Annotate the harness with a #[kanitool::<name>] with optional arguments.
@@ -330,11 +620,93 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     }
 
-    kani_attribute!(should_panic, no_args);
-    kani_attribute!(solver);
-    kani_attribute!(stub);
+    /// Annotate the harness with `#[kanitool::stub(...)]`.
+    ///
+    /// Paths with generic arguments, e.g. `decode::<u32>`, cannot be arguments of an attribute
+    /// that the compiler reads, so they are passed as string literals instead. Any other argument
+    /// is passed as is and checked by the compiler.
+    pub fn stub(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let args = proc_macro2::TokenStream::from(attr);
+        let fn_item = parse_macro_input!(item as ItemFn);
+        let args = match Punctuated::<syn::Expr, Token![,]>::parse_terminated.parse2(args.clone()) {
+            Ok(exprs) => {
+                let exprs = exprs.into_iter().map(|expr| match expr {
+                    syn::Expr::Path(syn::ExprPath { qself: None, ref path, .. })
+                        if path.segments.iter().any(|segment| !segment.arguments.is_none()) =>
+                    {
+                        let path = path_to_string(path);
+                        quote!(#path)
+                    }
+                    expr => quote!(#expr),
+                });
+                quote!(#(#exprs),*)
+            }
+            Err(_) => args,
+        };
+        quote!(
+            #[kanitool::stub(#args)]
+            #fn_item
+        )
+        .into()
+    }
+
+    /// Annotate the harness with `#[kanitool::solver(...)]`.
+    ///
+    /// The arguments of a solver binary are given as a list, e.g. `args = ["--quiet"]`, which
+    /// cannot be an argument of an attribute that the compiler reads. It is passed as
+    /// `args("--quiet")` instead. Any other argument is passed as is and checked by the compiler.
+    pub fn solver(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let args = proc_macro2::TokenStream::from(attr);
+        let fn_item = parse_macro_input!(item as ItemFn);
+        let args = match Punctuated::<syn::Expr, Token![,]>::parse_terminated.parse2(args.clone()) {
+            Ok(exprs) => {
+                let exprs = exprs.into_iter().map(|expr| match expr {
+                    syn::Expr::Assign(syn::ExprAssign { ref left, ref right, .. })
+                        if matches!(left.as_ref(), syn::Expr::Path(p) if p.path.is_ident("args")) =>
+                    {
+                        match right.as_ref() {
+                            syn::Expr::Array(array) => {
+                                let elems = &array.elems;
+                                quote!(args(#elems))
+                            }
+                            _ => quote!(#expr),
+                        }
+                    }
+                    expr => quote!(#expr),
+                });
+                quote!(#(#exprs),*)
+            }
+            Err(_) => args,
+        };
+        quote!(
+            #[kanitool::solver(#args)]
+            #fn_item
+        )
+        .into()
+    }
+
+    /// Print a path with generic arguments without the spaces that `quote` adds between tokens,
+    /// e.g. `decode::<Vec<u8>>`.
+    fn path_to_string(path: &syn::Path) -> String {
+        quote!(#path)
+            .to_string()
+            .replace(" :: ", "::")
+            .replace(":: ", "::")
+            .replace(" <", "<")
+            .replace("< ", "<")
+            .replace(" >", ">")
+            .replace(" ,", ",")
+            .replace("& ", "&")
+    }
+
+    kani_attribute!(expect_unreachable, no_args);
+    kani_attribute!(pointer_checks);
+    kani_attribute!(should_panic);
+    kani_attribute!(slow, no_args);
     kani_attribute!(unstable);
     kani_attribute!(unwind);
+    kani_attribute!(predicate, no_args);
+    kani_attribute!(pure, no_args);
 }
 
 /// This module provides dummy implementations of Kani attributes which cannot be interpreted by
@@ -354,6 +726,29 @@ pub fn $name(_attr: TokenStream, item: TokenStream) -> TokenStream {
         };
     }
 
+    /// Encode a contract proc macro which ignores the given attribute.
+    ///
+    /// A function declaration without a body, e.g. `extern "C" fn foo(x: i32) -> i32;`, is not
+    /// valid outside of an `extern` block, so it is moved into one.
+    macro_rules! contract_no_op {
+        ($name:ident) => {
+            pub fn $name(_attr: TokenStream, item: TokenStream) -> TokenStream {
+                declaration_in_extern_block(item)
+            }
+        };
+    }
+
+    fn declaration_in_extern_block(item: TokenStream) -> TokenStream {
+        match syn::parse::<syn::ForeignItemFn>(item.clone()) {
+            Ok(syn::ForeignItemFn { attrs, vis, mut sig, .. }) if sig.abi.is_some() => {
+                let abi = sig.abi.take();
+                sig.unsafety = None;
+                quote::quote!(#abi { #(#attrs)* #vis #sig; }).into()
+            }
+            _ => item,
+        }
+    }
+
     /// Add #[allow(dead_code)] to a proof harness to avoid dead code warnings.
     pub fn proof(_attr: TokenStream, item: TokenStream) -> TokenStream {
         let mut result = TokenStream::new();
@@ -362,14 +757,30 @@ pub fn proof(_attr: TokenStream, item: TokenStream) -> TokenStream {
         result
     }
 
+    /// Contracts are not checked outside of Kani, so neither is this assertion.
+    pub fn assert_contract_holds(_item: TokenStream) -> TokenStream {
+        quote::quote!(()).into()
+    }
+
+    no_op!(expect_unreachable);
+    no_op!(pointer_checks);
     no_op!(should_panic);
+    no_op!(slow);
     no_op!(solver);
     no_op!(stub);
     no_op!(unstable);
     no_op!(unwind);
-    no_op!(requires);
-    no_op!(ensures);
-    no_op!(modifies);
+    contract_no_op!(requires);
+    contract_no_op!(ensures);
+    contract_no_op!(ensures_ok);
+    contract_no_op!(modifies);
+    contract_no_op!(decreases);
+    no_op!(predicate);
+    no_op!(pure);
     no_op!(proof_for_contract);
+    no_op!(proof_idempotent);
+    no_op!(auto_harness);
     no_op!(stub_verified);
+    no_op!(loop_contract);
+    no_op!(invariant);
 }
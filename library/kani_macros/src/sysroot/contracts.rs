@@ -104,10 +104,12 @@
 //! Decorates the original function with `#[kanitool::checked_by =
 //! "<fn_name>_check_<fn_hash>"]`.
 //!
-//! The check function is a copy of the original function with preconditions
-//! added before the body and postconditions after as well as injected before
-//! every `return` (see [`PostconditionInjector`]). Attributes on the original
-//! function are also copied to the check function.
+//! The check function assumes the preconditions, calls a wrapper function that
+//! contains the original body and asserts the postconditions on its result.
+//! Because the body runs in its own function, every exit from it, including a
+//! `return` or an early return through the `?` operator, reaches the
+//! postconditions. Attributes on the original function are also copied to the
+//! check function.
 //!
 //! ## Replace Function
 //!
@@ -123,6 +125,25 @@
 //! body is replaced by `kani::any()`, which generates a non-deterministic
 //! value.
 //!
+//! ## Postcondition Function
+//!
+//! Generates a `<fn_name>_kani_postcondition` function that takes the arguments
+//! of the original function and its result as an additional last argument, and
+//! returns whether the `ensures` clauses hold for them. It is marked with the
+//! `#[kanitool::is_contract_generated(postcondition)]` attribute and carries no
+//! hash in its name, so that `kani::assert_contract_holds!` can derive it from
+//! the path of the original function.
+//!
+//! ## Replacement Client
+//!
+//! Generates a `<fn_name>_replacement_client` function without arguments that
+//! assumes the `requires` clauses for arguments created with `kani::any()` and
+//! calls the original function with them. It is marked with the
+//! `#[kanitool::replacement_client = "<fn_name>"]` and
+//! `#[kanitool::is_contract_generated(replacement_client)]` attributes. With
+//! `--enforce-and-replace` the compiler verifies it as a harness that replaces
+//! the original function by its contract.
+//!
 //! ## Inductive Verification
 //!
 //! To efficiently check recursive functions we verify them inductively. To
@@ -137,30 +158,31 @@
 //!
 //! Since the induction revolves around the recursive call we can simply set it
 //! up upon entry into the body of the function under verification. We use a
-//! global variable that tracks whether we are re-entering the function
-//! recursively and starts off as `false`. On entry to the function we flip the
-//! variable to `true` and dispatch to the check (induction step). If the check
-//! recursively calls our function our re-entry tracker now reads `true` and we
+//! static counter of the active checks of the contract that starts off as `0`.
+//! On entry to the function we increment the counter and dispatch to the check
+//! (induction step). If the check calls our function again, directly or through
+//! other functions as with mutual recursion, the counter is no longer `0` and we
 //! dispatch to the replacement (application of induction hypothesis). Because
 //! the replacement function only checks the conditions and does not perform
 //! other computation we will only ever go "one recursion level deep", making
 //! inductive verification very efficient. Once the check function returns we
-//! flip the tracker variable back to `false` in case the function is called
-//! more than once in its harness.
+//! decrement the counter again in case the function is called more than once in
+//! its harness.
 //!
 //! To facilitate all this we generate a `<fn_name>_recursion_wrapper_<fn_hash>`
 //! function with the following shape:
 //!
 //! ```ignored
 //! fn recursion_wrapper_...(fn args ...) {
-//!     static mut REENTRY: bool = false;
+//!     #[kanitool::recursion_tracker]
+//!     static mut RECURSION_DEPTH: u32 = 0;
 //!
-//!     if unsafe { REENTRY } {
+//!     if unsafe { RECURSION_DEPTH } > 0 {
 //!         call_replace(fn args...)
 //!     } else {
-//!         unsafe { reentry = true };
+//!         unsafe { RECURSION_DEPTH += 1 };
 //!         let result = call_check(fn args...);
-//!         unsafe { reentry = false };
+//!         unsafe { RECURSION_DEPTH -= 1 };
 //!         result
 //!     }
 //! }
@@ -169,6 +191,26 @@
 //! We register this function as `#[kanitool::checked_with =
 //! "recursion_wrapper_..."]` instead of the check function.
 //!
+//! The counter must not be havocked when the contract is checked. The compiler
+//! finds it by the `kanitool::recursion_tracker` attribute among the statics
+//! declared in the recursion wrapper, so its name does not matter.
+//!
+//! If the original function is `#[track_caller]` the recursion wrapper is too,
+//! as are the check and replace functions, which copy all attributes of the
+//! original. This keeps the implicit caller location argument consistent
+//! across all generated functions.
+//!
+//! The original function and the replace function are also decorated with
+//! `#[kanitool::contract_fingerprint = "..."]`, a hash of the complete contract
+//! and body of the original function. The compiler combines the fingerprints
+//! reachable from a `proof_for_contract` harness so that the driver can skip
+//! the harness with `--changed-only` if none of them changed.
+//!
+//! The original function also records the source text of every clause of its
+//! contract, e.g. `#[kanitool::contract_requires = "divisor != 0"]`, so that the
+//! contracts of a crate can be exported without checking them, see
+//! [`contract_clause_attrs`].
+//!
 //! # Complete example
 //!
 //! ```
@@ -184,6 +226,8 @@
 //! ```
 //! #[kanitool::checked_with = "div_recursion_wrapper_965916"]
 //! #[kanitool::replaced_with = "div_replace_965916"]
+//! #[kanitool::contract_requires = "divisor != 0"]
+//! #[kanitool::contract_ensures = "result <= dividend"]
 //! fn div(dividend: u32, divisor: u32) -> u32 { dividend / divisor }
 //!
 //! #[allow(dead_code)]
@@ -192,8 +236,11 @@
 //! fn div_check_965916(dividend: u32, divisor: u32) -> u32 {
 //!     let dividend_renamed = kani::internal::untracked_deref(&dividend);
 //!     let divisor_renamed = kani::internal::untracked_deref(&divisor);
-//!     let result = { kani::assume(divisor != 0); { dividend / divisor } };
-//!     kani::assert(result <= dividend_renamed, "result <= dividend");
+//!     let result = {
+//!         kani::internal::assume_precondition(divisor != 0, "divisor != 0");
+//!         { dividend / divisor }
+//!     };
+//!     kani::internal::check_postcondition(result <= dividend_renamed, "result <= dividend");
 //!     std::mem::forget(dividend_renamed);
 //!     std::mem::forget(divisor_renamed);
 //!     result
@@ -203,7 +250,7 @@
 //! #[allow(unused_variables)]
 //! #[kanitool::is_contract_generated(replace)]
 //! fn div_replace_965916(dividend: u32, divisor: u32) -> u32 {
-//!     kani::assert(divisor != 0, "divisor != 0");
+//!     kani::internal::check_precondition(divisor != 0, "divisor != 0");
 //!     let dividend_renamed = kani::internal::untracked_deref(&dividend);
 //!     let divisor_renamed = kani::internal::untracked_deref(&divisor);
 //!     let result = kani::any();
@@ -217,14 +264,15 @@
 //! #[allow(unused_variables)]
 //! #[kanitool::is_contract_generated(recursion_wrapper)]
 //! fn div_recursion_wrapper_965916(dividend: u32, divisor: u32) -> u32 {
-//!     static mut REENTRY: bool = false;
+//!     #[kanitool::recursion_tracker]
+//!     static mut RECURSION_DEPTH: u32 = 0;
 //!
-//!     if unsafe { REENTRY } {
+//!     if unsafe { RECURSION_DEPTH } > 0 {
 //!         div_replace_965916(dividend, divisor)
 //!     } else {
-//!         unsafe { reentry = true };
+//!         unsafe { RECURSION_DEPTH += 1 };
 //!         let result = div_check_965916(dividend, divisor);
-//!         unsafe { reentry = false };
+//!         unsafe { RECURSION_DEPTH -= 1 };
 //!         result
 //!     }
 //! }
@@ -232,7 +280,7 @@
 
 use proc_macro::{Diagnostic, TokenStream};
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use std::{
     borrow::Cow,
     collections::{HashMap, HashSet},
@@ -252,11 +300,78 @@ pub fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
     requires_ensures_main(attr, item, ContractConditionsType::Ensures)
 }
 
+/// Expands `kani::assert_contract_holds!(function(args..))` into an assertion
+/// that the postconditions of `function` hold, see [`contract_assertion`].
+#[allow(dead_code)]
+pub fn assert_contract_holds(item: TokenStream) -> TokenStream {
+    contract_assertion(item.into()).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// The assertion that the postconditions of `function` hold for the bindings
+/// in scope, where `tokens` is `function(args..)` or `function(args..), result`.
+///
+/// The call is not executed. Instead its arguments and `result`, the variable of
+/// this name if it is not given, are passed to the postcondition function that
+/// `ensures` generates for `function` (see [`postcondition_function_name`]),
+/// which evaluates the `ensures` clauses of the contract. The function takes its
+/// arguments by value, so it is given shallow copies, which it forgets again.
+fn contract_assertion(tokens: TokenStream2) -> Result<TokenStream2, syn::Error> {
+    use syn::parse::Parser;
+    let expected = "expected a call of the function with a contract, e.g. \
+        `div(dividend, divisor)`, optionally followed by its result";
+    let mut args = syn::punctuated::Punctuated::<Expr, Token![,]>::parse_terminated
+        .parse2(tokens)?
+        .into_iter();
+    let (Some(call), result, None) = (args.next(), args.next(), args.next()) else {
+        return Err(syn::Error::new(Span::call_site(), expected));
+    };
+    let Expr::Call(syn::ExprCall { func, args: call_args, .. }) = &call else {
+        return Err(syn::Error::new_spanned(call, expected));
+    };
+    let Expr::Path(function) = func.as_ref() else {
+        return Err(syn::Error::new_spanned(func, expected));
+    };
+    let mut postcondition = function.clone();
+    let name = &mut postcondition.path.segments.last_mut().unwrap().ident;
+    *name = postcondition_function_name(name);
+    let result = result.unwrap_or_else(|| syn::parse_quote!(result));
+    let copies = (0..=call_args.len())
+        .map(|i| Ident::new(&format!("copy{i}"), Span::mixed_site()))
+        .collect::<Vec<_>>();
+    let values = call_args.iter().chain(std::iter::once(&result));
+    let holds = Ident::new("holds", Span::mixed_site());
+    Ok(quote!({
+        #(let #copies = kani::internal::untracked_deref(&(#values));)*
+        #[allow(unused_unsafe)]
+        let #holds = unsafe { #postcondition(#(#copies),*) };
+        kani::assert(#holds, concat!("contract of `", stringify!(#function), "` holds"))
+    }))
+}
+
+/// Shorthand for `#[kani::ensures(result.is_ok())]`.
+#[allow(dead_code)]
+pub fn ensures_ok(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            TokenStream2::from(attr).span(),
+            "`#[kani::ensures_ok]` does not take any arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+    requires_ensures_main(quote!(result.is_ok()).into(), item, ContractConditionsType::Ensures)
+}
+
 #[allow(dead_code)]
 pub fn modifies(attr: TokenStream, item: TokenStream) -> TokenStream {
     requires_ensures_main(attr, item, ContractConditionsType::Modifies)
 }
 
+#[allow(dead_code)]
+pub fn decreases(attr: TokenStream, item: TokenStream) -> TokenStream {
+    requires_ensures_main(attr, item, ContractConditionsType::Decreases)
+}
+
 /// This is very similar to the kani_attribute macro, but it instead creates
 /// key-value style attributes which I find a little easier to parse.
 macro_rules! passthrough {
@@ -283,11 +398,22 @@ pub fn $name(attr: TokenStream, item: TokenStream) -> TokenStream {
 passthrough!(stub_verified, false);
 
 pub fn proof_for_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let args = proc_macro2::TokenStream::from(attr);
+    let targets = parse_macro_input!(
+        attr with syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated
+    );
+    if targets.is_empty() {
+        return syn::Error::new(
+            Span::call_site(),
+            "`#[kani::proof_for_contract]` expects the path of at least one function",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let targets = targets.iter();
     let ItemFn { attrs, vis, sig, block } = parse_macro_input!(item as ItemFn);
     quote!(
         #[allow(dead_code)]
-        #[kanitool::proof_for_contract = stringify!(#args)]
+        #(#[kanitool::proof_for_contract = stringify!(#targets)])*
         #(#attrs)*
         #vis #sig {
             let _ = std::boxed::Box::new(0_usize);
@@ -297,6 +423,121 @@ pub fn proof_for_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
     .into()
 }
 
+pub fn proof_idempotent(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let target = parse_macro_input!(attr as syn::Path);
+    let ItemFn { attrs, vis, sig, block } = parse_macro_input!(item as ItemFn);
+    let (once, twice) =
+        (Ident::new("once", Span::mixed_site()), Ident::new("twice", Span::mixed_site()));
+    quote!(
+        #[allow(dead_code)]
+        #[kanitool::proof]
+        #[kanitool::proof_idempotent = stringify!(#target)]
+        #(#attrs)*
+        #vis #sig {
+            #block
+            let #once = #target(kani::any());
+            let #twice = #target(::core::clone::Clone::clone(&#once));
+            kani::assert(#twice == #once, concat!(stringify!(#target), " is idempotent"));
+        }
+    )
+    .into()
+}
+
+pub fn auto_harness(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            TokenStream2::from(attr).span(),
+            "`#[kani::auto_harness]` does not take any arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let item_fn = parse_macro_input!(item as ItemFn);
+    // If the attribute is placed below a contract attribute it is also copied
+    // onto the functions generated from the contract. Only the original
+    // function gets a harness.
+    if !matches!(
+        ContractFunctionState::from_attributes(&item_fn.attrs),
+        ContractFunctionState::Original | ContractFunctionState::Untouched
+    ) {
+        return item_fn.into_token_stream().into();
+    }
+    match auto_harness_for(&item_fn) {
+        Ok(harness) => quote!(#item_fn #harness).into(),
+        Err(e) => {
+            let error = e.into_compile_error();
+            quote!(#item_fn #error).into()
+        }
+    }
+}
+
+/// A `proof_for_contract` harness for `function` that calls it with
+/// `kani::any()` for every argument. An argument of type `&T` or `&mut T` is
+/// given a reference to a `kani::any::<T>()`.
+///
+/// Associated functions that neither take `self` nor mention `Self` look like
+/// free functions to [`is_probably_impl_fn`]. For those we also emit an empty
+/// module spanned at the attribute. Modules are not allowed in an `impl`, so
+/// rustc rejects the attribute itself instead of only reporting that the
+/// harness cannot find the function.
+fn auto_harness_for(function: &ItemFn) -> syn::Result<TokenStream2> {
+    let sig = &function.sig;
+    if is_probably_impl_fn(function) {
+        return Err(syn::Error::new(
+            sig.ident.span(),
+            "`#[kani::auto_harness]` can only be used on free functions, write a \
+            `#[kani::proof_for_contract]` harness for associated functions",
+        ));
+    }
+    if let Some(param) =
+        sig.generics.params.iter().find(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+    {
+        return Err(syn::Error::new(
+            param.span(),
+            "`#[kani::auto_harness]` cannot choose the generic arguments of a function, write \
+            a `#[kani::proof_for_contract]` harness that instantiates them",
+        ));
+    }
+    let mut impl_trait = ImplTraitDetector(None);
+    sig.inputs.iter().for_each(|input| impl_trait.visit_fn_arg(input));
+    if let Some(span) = impl_trait.0 {
+        return Err(syn::Error::new(
+            span,
+            "`#[kani::auto_harness]` cannot choose the type of an `impl Trait` argument, write \
+            a `#[kani::proof_for_contract]` harness that picks one",
+        ));
+    }
+    let args = sig.inputs.iter().map(|input| {
+        let FnArg::Typed(syn::PatType { ty, .. }) = input else {
+            unreachable!("a free function has no receiver")
+        };
+        match ty.as_ref() {
+            syn::Type::Reference(syn::TypeReference { mutability: Some(_), elem, .. }) => {
+                quote!(&mut kani::any::<#elem>())
+            }
+            syn::Type::Reference(syn::TypeReference { elem, .. }) => {
+                quote!(&kani::any::<#elem>())
+            }
+            _ => quote!(kani::any()),
+        }
+    });
+    let name = &sig.ident;
+    let harness_name = Ident::new(&format!("{name}_auto_harness"), name.span());
+    let call = quote!(#name(#(#args),*));
+    let call = if sig.unsafety.is_some() { quote!(unsafe { #call }) } else { call };
+    let free_fn_check =
+        Ident::new(&format!("{name}_auto_harness_requires_a_free_fn"), Span::call_site());
+    Ok(quote!(
+        #[doc(hidden)]
+        mod #free_fn_check {}
+
+        #[kani::proof_for_contract(#name)]
+        fn #harness_name() {
+            #call;
+        }
+    ))
+}
+
 /// Classifies the state a function is in in the contract handling pipeline.
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum ContractFunctionState {
@@ -312,6 +553,12 @@ enum ContractFunctionState {
     /// of a contract attribute.
     Replace,
     ModifiesWrapper,
+    /// This is the postcondition function that was generated from a previous
+    /// evaluation of a contract attribute, see [`postcondition_function_name`].
+    Postcondition,
+    /// This is the replacement client that was generated from a previous
+    /// evaluation of a contract attribute, see [`replacement_client_name`].
+    ReplacementClient,
 }
 
 impl<'a> TryFrom<&'a syn::Attribute> for ContractFunctionState {
@@ -329,6 +576,8 @@ fn try_from(attribute: &'a syn::Attribute) -> Result<Self, Self::Error> {
                     "check" => Ok(Self::Check),
                     "replace" => Ok(Self::Replace),
                     "wrapper" => Ok(Self::ModifiesWrapper),
+                    "postcondition" => Ok(Self::Postcondition),
+                    "replacement_client" => Ok(Self::ReplacementClient),
                     _ => {
                         Err(Some(lst.span().unwrap().error("Expected `check` or `replace` ident")))
                     }
@@ -336,7 +585,11 @@ fn try_from(attribute: &'a syn::Attribute) -> Result<Self, Self::Error> {
             }
         }
         if let syn::Meta::NameValue(nv) = &attribute.meta {
-            if matches_path(&nv.path, &["kanitool", "checked_with"]) {
+            // A declaration without a body has no check function, so it is
+            // only marked with `replaced_with`.
+            if matches_path(&nv.path, &["kanitool", "checked_with"])
+                || matches_path(&nv.path, &["kanitool", "replaced_with"])
+            {
                 return Ok(ContractFunctionState::Original);
             }
         }
@@ -392,6 +645,7 @@ enum ContractConditionsType {
     Requires,
     Ensures,
     Modifies,
+    Decreases,
 }
 
 /// Clause-specific information mostly generated by parsing the attribute.
@@ -406,11 +660,28 @@ enum ContractConditionsData {
         /// Translation map from original argument names to names of the copies
         /// we will be emitting.
         argument_names: HashMap<Ident, Ident>,
+        /// Entry snapshots lifted out of `old` and `old_with` calls in `attr`. Each is
+        /// bound to its name before the function body runs.
+        snapshots: Vec<(Ident, Expr)>,
+        /// Closure arguments whose invocations are counted for `call_count`
+        /// calls in `attr`.
+        call_counts: Vec<CallCounter>,
         /// The contents of the attribute.
         attr: Expr,
     },
     Modifies {
+        /// Pointers to the targets.
         attr: Vec<Expr>,
+        /// The targets as written by the user, which the compiler records so the
+        /// driver can name them when an `assigns` check fails.
+        sources: Vec<Expr>,
+        /// Calls to `kani::internal::shared_modifies_target` for the targets that
+        /// are behind a shared reference or shared pointer parameter.
+        shared: Vec<TokenStream2>,
+    },
+    Decreases {
+        /// The measure, an integer expression over the arguments.
+        attr: Expr,
     },
 }
 
@@ -418,30 +689,270 @@ impl ContractConditionsData {
     /// Constructs a [`Self::Ensures`] from the signature of the decorated
     /// function and the contents of the decorating attribute.
     ///
+    /// A closure-style postcondition `|pat| body` is first turned into a block
+    /// that binds `pat` to a reference to the return value, see
+    /// [`bind_closure_result`].
+    ///
     /// Renames the [`Ident`]s used in `attr` and stores the translation map in
-    /// `argument_names`.
-    fn new_ensures(sig: &Signature, mut attr: Expr) -> Self {
+    /// `argument_names`, then replaces `kani::result()` calls with the binding of
+    /// the return value. Warns about uses of `result` that refer to an argument
+    /// of this name. Also lifts any `old` and `old_with` calls into `snapshots` and
+    /// any `call_count` calls into `call_counts`.
+    ///
+    /// Fails if a `call_count` refers to an argument that is not a closure.
+    fn new_ensures(sig: &Signature, attr: Expr) -> Result<Self, syn::Error> {
+        let mut attr = bind_closure_result(sig, attr)?;
+        let mut respanner = ResultRespanner::default();
+        respanner.visit_expr_mut(&mut attr);
+        // Has to run before the renaming, so that the counted arguments still
+        // have their original names.
+        let mut call_count_lifter = CallCountLifter::default();
+        call_count_lifter.visit_expr_mut(&mut attr);
+        let call_counts = call_count_lifter
+            .counters
+            .into_iter()
+            .map(|(counter, argument)| CallCounter::new(sig, counter, argument))
+            .collect::<Result<_, _>>()?;
         let argument_names = rename_argument_occurrences(sig, &mut attr);
-        ContractConditionsData::Ensures { argument_names, attr }
+        if argument_names.keys().any(|name| name == "result") {
+            for span in respanner.uses {
+                span.unwrap()
+                    .warning(
+                        "`result` refers to the argument of this name, not to the return value",
+                    )
+                    .help("use `kani::result()` to refer to the return value")
+                    .emit();
+            }
+        }
+        ResultCallRewriter.visit_expr_mut(&mut attr);
+        let mut lifter = OldWithLifter::default();
+        lifter.visit_expr_mut(&mut attr);
+        if let Some(error) = lifter.error {
+            return Err(error);
+        }
+        for value in unused_snapshots(&lifter.snapshots, &attr) {
+            value
+                .span()
+                .unwrap()
+                .warning("this value is captured on entry but not used in the postcondition")
+                .emit();
+        }
+        Ok(ContractConditionsData::Ensures {
+            argument_names,
+            snapshots: lifter.snapshots,
+            call_counts,
+            attr,
+        })
     }
 
     /// Constructs a [`Self::Modifies`] from the contents of the decorating attribute.
     ///
-    /// Responsible for parsing the attribute.
-    fn new_modifies(attr: TokenStream, output: &mut TokenStream2) -> Self {
-        let attr = chunks_by(TokenStream2::from(attr), is_token_stream_2_comma)
-            .map(syn::parse2)
-            .filter_map(|expr| match expr {
-                Err(e) => {
-                    output.extend(e.into_compile_error());
-                    None
+    /// Responsible for parsing the attribute. A place target like `*self` or
+    /// `(*u).field` is turned into a pointer to that place, i.e. `&*self` or
+    /// `&(*u).field`. So is a parameter that is a smart pointer, such as a
+    /// `self: Box<Self>` or `self: Pin<&mut Self>` receiver, which becomes
+    /// `&*self`. Targets that are repeated verbatim are only kept once.
+    /// Targets that name memory behind a shared reference or shared pointer
+    /// parameter of `sig` can only be assigned if they have interior
+    /// mutability. Whether they do is only known once their type is, so the
+    /// compiler checks them, see [`shared_reference_root`]. Ranges with a step,
+    /// `[from..to; step]`, are rejected, see [`strided_index`].
+    fn new_modifies(sig: &Signature, attr: TokenStream, output: &mut TokenStream2) -> Self {
+        let mut seen = HashSet::new();
+        let sources: Vec<Expr> = chunks_by(TokenStream2::from(attr), is_token_stream_2_comma)
+            .filter(|chunk: &TokenStream2| seen.insert(chunk.to_string()))
+            .map(|chunk| match strided_index(chunk.clone()) {
+                Some(index) => Err(syn::Error::new(
+                    index.span(),
+                    "a `modifies` target cannot select every n-th element with \
+                    `[from..to; step]`, name the whole range `[from..to]` instead",
+                )),
+                None => syn::parse2(chunk),
+            })
+            .filter_map(|expr| expr.map_err(|e| output.extend(e.into_compile_error())).ok())
+            .collect();
+        let attr: Vec<Expr> = sources
+            .iter()
+            .map(|expr| {
+                if is_deref_place(expr) {
+                    syn::parse_quote!(&#expr)
+                } else if is_smart_pointer_param(sig, expr) {
+                    syn::parse_quote!(&*#expr)
+                } else {
+                    expr.clone()
                 }
-                Ok(expr) => Some(expr),
             })
             .collect();
+        let shared = sources
+            .iter()
+            .zip(&attr)
+            .filter_map(|(expr, pointer)| {
+                let (param, ty) = shared_reference_root(sig, expr)?;
+                let kind =
+                    if matches!(ty, syn::Type::Reference(_)) { "reference" } else { "pointer" };
+                let msg = format!(
+                    "this `modifies` target is behind the shared {kind} `{param}`, and memory \
+                    behind a shared {kind} cannot be assigned unless it has interior mutability"
+                );
+                Some(quote_spanned!(expr.span()=>
+                    kani::internal::shared_modifies_target(#pointer, #msg);
+                ))
+            })
+            .collect();
+
+        ContractConditionsData::Modifies { attr, sources, shared }
+    }
+}
+
+/// Find an index with a step, like `[0..8; 2]` in `&mut buf[0..8; 2]`, in the
+/// tokens of a `modifies` target. Such a target is no Rust expression and would
+/// otherwise be reported as a generic syntax error.
+///
+/// A bracketed group only indexes if it follows an identifier, literal or
+/// other group, which tells it apart from an array repeat expression such as
+/// `&mut [0; 4]`.
+fn strided_index(tokens: TokenStream2) -> Option<proc_macro2::Group> {
+    use proc_macro2::{Delimiter, TokenTree};
+    let has_step = |group: &proc_macro2::Group| {
+        group.stream().into_iter().any(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ';'))
+    };
+    let mut indexes = false;
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => {
+                if indexes && group.delimiter() == Delimiter::Bracket && has_step(&group) {
+                    return Some(group);
+                }
+                if let Some(index) = strided_index(group.stream()) {
+                    return Some(index);
+                }
+                indexes = group.delimiter() != Delimiter::Brace;
+            }
+            TokenTree::Ident(ident) => indexes = ident != "mut",
+            TokenTree::Literal(_) => indexes = true,
+            TokenTree::Punct(_) => indexes = false,
+        }
+    }
+    None
+}
 
-        ContractConditionsData::Modifies { attr }
+/// Is `expr` a place behind a pointer, i.e. a dereference followed by any
+/// number of field accesses, such as `*self` or `(*u).field`?
+///
+/// Such a place is not a pointer itself, so it has to be borrowed to be used
+/// as a `modifies` target, e.g. to name a single field of a `union`.
+fn is_deref_place(expr: &Expr) -> bool {
+    match expr {
+        Expr::Unary(syn::ExprUnary { op: syn::UnOp::Deref(_), .. }) => true,
+        Expr::Field(syn::ExprField { base, .. })
+        | Expr::Paren(syn::ExprParen { expr: base, .. }) => is_deref_place(base),
+        _ => false,
+    }
+}
+
+/// The parameters of `sig` that are bound to a plain identifier, with their
+/// types. For a receiver this is the type of `self`, e.g. `&mut Self` or
+/// `Pin<&mut Self>`.
+fn param_types(sig: &Signature) -> impl Iterator<Item = (Ident, &syn::Type)> {
+    sig.inputs.iter().filter_map(|input| match input {
+        FnArg::Receiver(receiver) => Some((receiver.self_token.into(), receiver.ty.as_ref())),
+        FnArg::Typed(syn::PatType { pat, ty, .. }) => match pat.as_ref() {
+            syn::Pat::Ident(pat) => Some((pat.ident.clone(), ty.as_ref())),
+            _ => None,
+        },
+    })
+}
+
+/// The last segment of `ty` if it is a path type, e.g. `Pin<&mut Self>` for
+/// `core::pin::Pin<&mut Self>`.
+fn last_type_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last(),
+        syn::Type::Paren(syn::TypeParen { elem, .. }) => last_type_segment(elem),
+        _ => None,
+    }
+}
+
+/// The pointer wrapped by `Pin`, e.g. `&mut Self` for `Pin<&mut Self>`.
+fn pinned_pointer(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = last_type_segment(ty).filter(|segment| segment.ident == "Pin")?;
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else { return None };
+    match args.args.first()? {
+        syn::GenericArgument::Type(pointer) => Some(pointer),
+        _ => None,
+    }
+}
+
+/// Does `ty` only give shared access to the memory it points to? This is the
+/// case for shared references and for the reference counted `Rc` and `Arc`,
+/// also when they are pinned.
+fn is_shared_pointer(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(syn::TypeReference { mutability, .. }) => mutability.is_none(),
+        _ => pinned_pointer(ty).map_or_else(
+            || last_type_segment(ty).is_some_and(|s| s.ident == "Rc" || s.ident == "Arc"),
+            is_shared_pointer,
+        ),
+    }
+}
+
+/// Is `expr` a parameter of `sig` whose type is a `Box`, `Rc`, `Arc` or a
+/// `Pin` of a pointer? Unlike references and raw pointers these are not
+/// `kani::internal::Pointer`s themselves, so the target has to be reborrowed.
+fn is_smart_pointer_param(sig: &Signature, expr: &Expr) -> bool {
+    let Expr::Path(syn::ExprPath { path, .. }) = expr else { return false };
+    let Some(ident) = path.get_ident() else { return false };
+    param_types(sig).any(|(name, ty)| {
+        &name == ident
+            && (pinned_pointer(ty).is_some()
+                || last_type_segment(ty).is_some_and(|segment| {
+                    segment.ident == "Box" || segment.ident == "Rc" || segment.ident == "Arc"
+                }))
+    })
+}
+
+/// If the `modifies` target `expr` names memory behind a parameter of `sig`
+/// that is a shared reference or shared pointer, return that parameter and its
+/// type.
+///
+/// The memory is behind the parameter if `expr` is the parameter itself, or a
+/// place that is reached from it by field accesses and dereferencing it, such as
+/// `*r`, `&r.field` or `&(*self).field` for `&self`. Anything else, e.g. a method
+/// call or a field holding a raw pointer, may point elsewhere and is accepted.
+/// Receivers with an arbitrary self type are handled like any other parameter,
+/// so `self: Rc<Self>` and `self: Pin<&Self>` are shared, whereas
+/// `self: Box<Self>` and `self: Pin<&mut Self>` are not.
+///
+/// The compiler rejects the targets found here unless their type has interior
+/// mutability, e.g. a `Cell`, which cannot be told from the syntax.
+fn shared_reference_root<'a>(sig: &'a Signature, expr: &Expr) -> Option<(Ident, &'a syn::Type)> {
+    /// The root of a place that only consists of field accesses and a
+    /// dereference of the root, and whether the place is behind the root.
+    fn place_root(expr: &Expr) -> Option<(&syn::Path, bool)> {
+        match expr {
+            Expr::Path(path) => Some((&path.path, false)),
+            Expr::Paren(syn::ExprParen { expr, .. }) => place_root(expr),
+            Expr::Field(syn::ExprField { base, .. }) => Some((place_root(base)?.0, true)),
+            Expr::Unary(syn::ExprUnary { op: syn::UnOp::Deref(_), expr, .. }) => {
+                match place_root(expr)? {
+                    (root, false) => Some((root, true)),
+                    (_, true) => None,
+                }
+            }
+            _ => None,
+        }
     }
+    let root = match expr {
+        Expr::Reference(syn::ExprReference { expr, .. }) => match place_root(expr)? {
+            (root, true) => root,
+            (_, false) => return None,
+        },
+        _ if is_deref_place(expr) => place_root(expr)?.0,
+        Expr::Path(path) => &path.path,
+        _ => return None,
+    };
+    let root = root.get_ident()?;
+    param_types(sig).find(|(name, ty)| name == root && is_shared_pointer(ty))
 }
 
 impl<'a> ContractConditionsHandler<'a> {
@@ -462,12 +973,18 @@ fn new(
     ) -> Result<Self, syn::Error> {
         let condition_type = match is_requires {
             ContractConditionsType::Requires => {
-                ContractConditionsData::Requires { attr: syn::parse(attr)? }
+                ContractConditionsData::Requires { attr: parse_condition(attr.into())? }
+            }
+            ContractConditionsType::Ensures => ContractConditionsData::new_ensures(
+                &annotated_fn.sig,
+                parse_condition(attr.into())?,
+            )?,
+            ContractConditionsType::Modifies => {
+                ContractConditionsData::new_modifies(&annotated_fn.sig, attr, output)
             }
-            ContractConditionsType::Ensures => {
-                ContractConditionsData::new_ensures(&annotated_fn.sig, syn::parse(attr)?)
+            ContractConditionsType::Decreases => {
+                ContractConditionsData::Decreases { attr: syn::parse(attr)? }
             }
-            ContractConditionsType::Modifies => ContractConditionsData::new_modifies(attr, output),
         };
 
         Ok(Self { function_state, condition_type, annotated_fn, attr_copy, output, hash })
@@ -486,17 +1003,19 @@ fn make_check_body(&mut self) -> TokenStream2 {
         match &self.condition_type {
             ContractConditionsData::Requires { attr } => {
                 quote!(
-                    kani::assume(#attr);
+                    kani::internal::assume_precondition(#attr, stringify!(#attr_copy));
                     #(#inner)*
                 )
             }
-            ContractConditionsData::Ensures { argument_names, attr } => {
+            ContractConditionsData::Ensures { argument_names, snapshots, call_counts, attr } => {
                 let (arg_copies, copy_clean) = make_unsafe_argument_copies(&argument_names);
+                let snapshots = make_snapshot_bindings(snapshots);
+                let call_counts = call_counts.iter().map(CallCounter::make_counting_closure);
 
                 // The code that enforces the postconditions and cleans up the shallow
                 // argument copies (with `mem::forget`).
                 let exec_postconditions = quote!(
-                    kani::assert(#attr, stringify!(#attr_copy));
+                    kani::internal::check_postcondition(#attr, stringify!(#attr_copy));
                     #copy_clean
                 );
 
@@ -508,12 +1027,14 @@ fn make_check_body(&mut self) -> TokenStream2 {
 
                 quote!(
                     #arg_copies
+                    #snapshots
+                    #(#call_counts)*
                     #(#inner)*
                     #exec_postconditions
                     result
                 )
             }
-            ContractConditionsData::Modifies { attr } => {
+            ContractConditionsData::Modifies { attr, shared, .. } => {
                 let wrapper_name = self.make_wrapper_name().to_string();
 
                 let wrapper_args = if let Some(wrapper_call_args) =
@@ -530,10 +1051,42 @@ fn make_check_body(&mut self) -> TokenStream2 {
                 };
 
                 quote!(
+                    #(#shared)*
                     #(let #wrapper_args = unsafe { kani::internal::Pointer::decouple_lifetime(&#attr) };)*
                     #(#inner)*
                 )
             }
+            ContractConditionsData::Decreases { attr } => {
+                let key = self.contract_hash();
+                let previous = Ident::new("decreases_previous", Span::mixed_site());
+
+                assert!(matches!(
+                    inner.pop(),
+                    Some(syn::Stmt::Expr(syn::Expr::Path(pexpr), None))
+                        if pexpr.path.get_ident().map_or(false, |id| id == "result")
+                ));
+
+                quote!(
+                    let #previous = kani::internal::enter_decreases(#key, (#attr) as i128);
+                    #(#inner)*
+                    kani::internal::exit_decreases(#previous);
+                    result
+                )
+            }
+        }
+    }
+
+    /// The hash that all functions generated for this contract carry in their
+    /// name. It identifies the contracted function at runtime, e.g. for
+    /// `decreases`.
+    fn contract_hash(&self) -> u64 {
+        if let Some(hash) = self.hash {
+            hash
+        } else {
+            let str_name = self.annotated_fn.sig.ident.to_string();
+            let hash = str_name.rsplit('_').next().unwrap();
+            u64::from_str_radix(hash, 16)
+                .unwrap_or_else(|_| unreachable!("Odd name for function {str_name}"))
         }
     }
 
@@ -557,13 +1110,14 @@ fn make_wrapper_name(&self) -> Ident {
     /// Get the sequence of statements of the previous check body or create the default one.
     fn ensure_bootstrapped_check_body(&self) -> Vec<syn::Stmt> {
         let wrapper_name = self.make_wrapper_name();
-        let return_type = return_type_to_type(&self.annotated_fn.sig.output);
+        let return_type = result_type_annotation(&self.annotated_fn.sig.output);
         if self.is_first_emit() {
             let args = exprs_for_args(&self.annotated_fn.sig.inputs);
+            let generic_args = generic_args_of(&self.annotated_fn.sig);
             let wrapper_call = if is_probably_impl_fn(self.annotated_fn) {
-                quote!(Self::#wrapper_name)
+                quote!(Self::#wrapper_name #generic_args)
             } else {
-                quote!(#wrapper_name)
+                quote!(#wrapper_name #generic_args)
             };
             syn::parse_quote!(
                 let result : #return_type = #wrapper_call(#(#args),*);
@@ -584,7 +1138,7 @@ fn ensure_bootstrapped_check_body(&self) -> Vec<syn::Stmt> {
     /// let result : ResultType = kani::any();
     ///
     /// // multiple argument havockings
-    /// *unsafe { kani::internal::Pointer::assignable(argument) } = kani::any();
+    /// kani::internal::Havoc::havoc(unsafe { kani::internal::Pointer::assignable(argument) });
     /// // multiple postconditions
     /// kani::assume(postcond);
     /// // multiple argument copy (used in postconditions) cleanups
@@ -597,9 +1151,32 @@ fn ensure_bootstrapped_check_body(&self) -> Vec<syn::Stmt> {
     /// and the second one the rest, excluding the return.
     ///
     /// If this is the first time we're emitting replace we create the return havoc and nothing else.
+    ///
+    /// An `impl Trait` result cannot be havocked, so the replace function of a function returning
+    /// `impl Trait` calls the check function instead (see [`returns_impl_trait`]). That binding is
+    /// tagged with `#[kanitool::is_contract_generated(check)]` so it can be recognized again.
     fn ensure_bootstrapped_replace_body(&self) -> (Vec<syn::Stmt>, Vec<syn::Stmt>) {
         if self.is_first_emit() {
-            let return_type = return_type_to_type(&self.annotated_fn.sig.output);
+            let sig = &self.annotated_fn.sig;
+            if returns_impl_trait(&sig.output) {
+                let check_fn_name =
+                    identifier_for_generated_function(&sig.ident, "check", self.contract_hash());
+                let args = exprs_for_args(&sig.inputs);
+                let generic_args = generic_args_of(sig);
+                let check_call = if is_probably_impl_fn(self.annotated_fn) {
+                    quote!(Self::#check_fn_name #generic_args)
+                } else {
+                    quote!(#check_fn_name #generic_args)
+                };
+                return (
+                    vec![syn::parse_quote!(
+                        #[kanitool::is_contract_generated(check)]
+                        let result : _ = #check_call(#(#args),*);
+                    )],
+                    vec![],
+                );
+            }
+            let return_type = return_type_to_type(&sig.output);
             (vec![syn::parse_quote!(let result : #return_type = kani::any();)], vec![])
         } else {
             let stmts = &self.annotated_fn.block.stmts;
@@ -632,16 +1209,20 @@ fn make_replace_body(&self) -> TokenStream2 {
             ContractConditionsData::Requires { attr } => {
                 let Self { attr_copy, .. } = self;
                 quote!(
-                    kani::assert(#attr, stringify!(#attr_copy));
+                    kani::internal::check_precondition(#attr, stringify!(#attr_copy));
                     #(#before)*
                     #(#after)*
                     result
                 )
             }
-            ContractConditionsData::Ensures { attr, argument_names } => {
+            ContractConditionsData::Ensures { attr, argument_names, snapshots, call_counts } => {
                 let (arg_copies, copy_clean) = make_unsafe_argument_copies(&argument_names);
+                let snapshots = make_snapshot_bindings(snapshots);
+                let call_counts = call_counts.iter().map(CallCounter::make_havocked_count);
                 quote!(
                     #arg_copies
+                    #snapshots
+                    #(#call_counts)*
                     #(#before)*
                     #(#after)*
                     kani::assume(#attr);
@@ -649,10 +1230,31 @@ fn make_replace_body(&self) -> TokenStream2 {
                     result
                 )
             }
-            ContractConditionsData::Modifies { attr } => {
+            ContractConditionsData::Modifies { attr, shared, .. } => {
+                quote!(
+                    #(#shared)*
+                    #(#before)*
+                    #(kani::internal::Havoc::havoc(unsafe { kani::internal::Pointer::assignable(#attr) });)*
+                    #(#after)*
+                    result
+                )
+            }
+            ContractConditionsData::Decreases { attr } => {
+                let key = self.contract_hash();
+                let Self { attr_copy, .. } = self;
+                let (bound, measure) = (
+                    Ident::new("decreases_bound", Span::mixed_site()),
+                    Ident::new("decreases_measure", Span::mixed_site()),
+                );
                 quote!(
+                    if let Some(#bound) = kani::internal::decreases_bound(#key) {
+                        let #measure = (#attr) as i128;
+                        kani::assert(
+                            0 <= #measure && #measure < #bound,
+                            concat!("recursive call decreases ", stringify!(#attr_copy))
+                        );
+                    }
                     #(#before)*
-                    #(*unsafe { kani::internal::Pointer::assignable(#attr) } = kani::any();)*
                     #(#after)*
                     result
                 )
@@ -709,6 +1311,197 @@ fn emit_replace_function(&mut self, replace_function_ident: Ident) {
         ));
     }
 
+    /// Emit the postcondition function into the output stream.
+    ///
+    /// The postcondition function takes the arguments of the original function
+    /// and its result as the additional argument `result`, and returns whether
+    /// the `ensures` clauses hold for them. On the first emit `result` is
+    /// `None` and a name for the argument is chosen that no argument of the
+    /// original function uses. Every `ensures` clause adds a conjunct to the
+    /// `let holds = ..;` statement of the body, see
+    /// [`Self::make_postcondition_conjunct`]. The arguments are shallow copies
+    /// made by `kani::assert_contract_holds!`, so they are forgotten again.
+    fn emit_postcondition_function(&mut self, ident: Ident, result: Option<Ident>) {
+        let holds = Ident::new("holds", Span::mixed_site());
+        let (result, stmts) = if let Some(result) = result {
+            let conjunct = self.make_postcondition_conjunct(&result).unwrap();
+            let mut stmts = self.annotated_fn.block.stmts.clone();
+            let init = stmts
+                .iter_mut()
+                .find_map(|stmt| match stmt {
+                    syn::Stmt::Local(syn::Local { pat: syn::Pat::Type(pat), init, .. })
+                        if matches!(pat.pat.as_ref(), syn::Pat::Ident(id) if id.ident == "holds") =>
+                    {
+                        init.as_mut()
+                    }
+                    _ => None,
+                })
+                .unwrap_or_else(|| panic!("ICE: Could not find the postconditions in {ident}"));
+            let previous = init.expr.clone();
+            *init.expr = syn::parse_quote!(#previous && #conjunct);
+            (result, stmts)
+        } else {
+            let mut arguments = ArgumentIdentCollector::new();
+            arguments.visit_signature(&self.annotated_fn.sig);
+            let mut name = String::from("result");
+            while arguments.0.iter().any(|argument| argument == name.as_str()) {
+                name.push('_');
+            }
+            let result = Ident::new(&name, Span::call_site());
+            let conjunct = self.make_postcondition_conjunct(&result).unwrap_or(quote!(true));
+            let arguments = arguments.0.iter();
+            let stmts = syn::parse_quote!(
+                let #holds: bool = #conjunct;
+                #(std::mem::forget(#arguments);)*
+                std::mem::forget(#result);
+                #holds
+            );
+            self.output.extend(quote!(
+                #[allow(dead_code, unused_variables, unused_mut, unreachable_code)]
+                #[doc(hidden)]
+            ));
+            self.output.extend(
+                self.annotated_fn
+                    .attrs
+                    .iter()
+                    .filter(|attr| is_inherited_attr(attr))
+                    .flat_map(Attribute::to_token_stream),
+            );
+            self.output.extend(quote!(#[kanitool::is_contract_generated(postcondition)]));
+            (result, stmts)
+        };
+        if matches!(self.function_state, ContractFunctionState::Postcondition) {
+            self.output.extend(self.annotated_fn.attrs.iter().flat_map(Attribute::to_token_stream));
+        }
+        let vis = &self.annotated_fn.vis;
+        let mut sig = self.annotated_fn.sig.clone();
+        sig.ident = ident;
+        sig.constness = None;
+        sig.asyncness = None;
+        sig.abi = None;
+        let result_type = return_type_to_type(&sig.output).into_owned();
+        sig.inputs.push(syn::parse_quote!(#result: #result_type));
+        sig.output = syn::parse_quote!(-> bool);
+        self.output.extend(quote!(
+            #vis #sig {
+                #(#stmts)*
+            }
+        ));
+    }
+
+    /// The conjunct that an `ensures` clause adds to the postcondition
+    /// function, or `None` for any other clause. `result` is the argument that
+    /// holds the result.
+    ///
+    /// Like in the check function the clause is evaluated on shallow copies of
+    /// the arguments and of the result. A clause that refers to the entry of the
+    /// function with `old`, `old_with` or `call_count` cannot be evaluated
+    /// after the fact, so its conjunct fails verification instead.
+    fn make_postcondition_conjunct(&self, result: &Ident) -> Option<TokenStream2> {
+        let ContractConditionsData::Ensures { argument_names, snapshots, call_counts, attr } =
+            &self.condition_type
+        else {
+            return None;
+        };
+        let attr_copy = &self.attr_copy;
+        if !snapshots.is_empty() || !call_counts.is_empty() {
+            return Some(quote!(kani::panic(concat!(
+                "the postcondition `",
+                stringify!(#attr_copy),
+                "` refers to the entry of the function and cannot be checked by \
+                `kani::assert_contract_holds!`"
+            ))));
+        }
+        let (arg_copies, copy_clean) = make_unsafe_argument_copies(argument_names);
+        let holds = Ident::new("holds", Span::mixed_site());
+        Some(quote!({
+            #arg_copies
+            let result = kani::internal::untracked_deref(&#result);
+            let #holds: bool = #attr;
+            #copy_clean
+            std::mem::forget(result);
+            #holds
+        }))
+    }
+
+    /// Emit the replacement client into the output stream.
+    ///
+    /// The replacement client is a function without arguments that calls the
+    /// function with the contract on arguments created with `kani::any()`, like
+    /// the harness of `#[kani::auto_harness]` does. With `--enforce-and-replace`
+    /// the compiler verifies it as a harness that uses the contract via
+    /// `stub_verified`. Every `requires` clause adds an assumption on the
+    /// arguments before the call, so that the client only checks calls that
+    /// satisfy the preconditions.
+    ///
+    /// Arguments of a type that does not implement `kani::Arbitrary` fail the
+    /// verification of the client, see `kani::internal::ClientArgument`. No
+    /// client is emitted if its arguments cannot be created at all, see
+    /// [`replacement_client_arguments`].
+    fn emit_replacement_client(&mut self, ident: Ident) {
+        let assumption = match &self.condition_type {
+            ContractConditionsData::Requires { attr } => Some(quote!(kani::assume(#attr);)),
+            _ => None,
+        };
+        if !self.is_first_emit() {
+            let call = self.annotated_fn.block.stmts.len() - 1;
+            if let Some(assumption) = assumption {
+                self.annotated_fn.block.stmts.insert(call, syn::parse_quote!(#assumption));
+            }
+            self.output.extend(self.annotated_fn.to_token_stream());
+            return;
+        }
+        let Some((bindings, args)) = replacement_client_arguments(self.annotated_fn) else {
+            return;
+        };
+        let sig = &self.annotated_fn.sig;
+        let function = &sig.ident;
+        let function_str = syn::LitStr::new(&function.to_string(), Span::call_site());
+        let call = quote!(#function(#(#args),*));
+        let call = if sig.unsafety.is_some() { quote!(unsafe { #call }) } else { call };
+        let attrs = self.annotated_fn.attrs.iter().filter(|attr| is_inherited_attr(attr));
+        self.output.extend(quote!(
+            #[allow(dead_code, unused_mut)]
+            #[doc(hidden)]
+            #(#attrs)*
+            #[kanitool::replacement_client = #function_str]
+            #[kanitool::is_contract_generated(replacement_client)]
+            fn #ident() {
+                use kani::internal::AnyClientArgument as _;
+                #(#bindings)*
+                #assumption
+                let _ = #call;
+            }
+        ));
+    }
+
+    /// Emit the original declaration of a function without a body, e.g. of an
+    /// FFI import, and register `replace_function_ident` and `fingerprint` on
+    /// it.
+    ///
+    /// A declaration is only valid in an `extern` block, so we move it into one
+    /// with the ABI of its signature. There is no body to check, so no check
+    /// function is generated and the contract can only be used via
+    /// `stub_verified`.
+    fn emit_declaration(&mut self, replace_function_ident: &Ident, fingerprint: u64) {
+        let ItemFn { attrs, vis, sig, .. } = &self.annotated_fn;
+        let mut foreign_sig = sig.clone();
+        let abi = foreign_sig.abi.take();
+        foreign_sig.unsafety = None;
+        let replace_fn_name_str =
+            syn::LitStr::new(&replace_function_ident.to_string(), Span::call_site());
+        let fingerprint_str = syn::LitStr::new(&format!("{fingerprint:016x}"), Span::call_site());
+        self.output.extend(quote!(
+            #abi {
+                #(#attrs)*
+                #[kanitool::replaced_with = #replace_fn_name_str]
+                #[kanitool::contract_fingerprint = #fingerprint_str]
+                #vis #foreign_sig;
+            }
+            #[kanitool::contract_fingerprint = #fingerprint_str]
+        ));
+    }
+
     /// Emit attributes common to check or replace function into the output
     /// stream.
     fn emit_common_header(&mut self) {
@@ -717,16 +1510,23 @@ fn emit_common_header(&mut self) {
                 #[allow(dead_code, unused_variables)]
             ));
         }
-        self.output.extend(self.annotated_fn.attrs.iter().flat_map(Attribute::to_token_stream));
+        self.output.extend(
+            self.annotated_fn
+                .attrs
+                .iter()
+                .filter(|attr| is_inherited_attr(attr))
+                .flat_map(Attribute::to_token_stream),
+        );
     }
 
     /// Emit a modifies wrapper, possibly augmenting a prior, existing one.
     ///
     /// We only augment if this clause is a `modifies` clause. In that case we
-    /// expand its signature with one new argument of type `&impl Arbitrary` for
-    /// each expression in the clause.
+    /// expand its signature with one new argument of type `&impl Havoc` for
+    /// each expression in the clause. `Havoc` covers both `Arbitrary` types and
+    /// slices of them.
     fn emit_augmented_modifies_wrapper(&mut self) {
-        if let ContractConditionsData::Modifies { attr } = &self.condition_type {
+        if let ContractConditionsData::Modifies { attr, sources, .. } = &self.condition_type {
             let wrapper_args = make_wrapper_args(self.annotated_fn.sig.inputs.len(), attr.len());
             let sig = &mut self.annotated_fn.sig;
             for arg in wrapper_args.clone() {
@@ -735,7 +1535,9 @@ fn emit_augmented_modifies_wrapper(&mut self) {
                     attrs: vec![],
                     colon_token: Token![:](Span::call_site()),
                     pat: Box::new(syn::Pat::Verbatim(quote!(#arg))),
-                    ty: Box::new(syn::Type::Verbatim(quote!(&#lifetime impl kani::Arbitrary))),
+                    ty: Box::new(syn::Type::Verbatim(
+                        quote!(&#lifetime (impl kani::internal::Havoc + ?Sized)),
+                    )),
                 }));
                 sig.generics.params.push(syn::GenericParam::Lifetime(syn::LifetimeParam {
                     lifetime,
@@ -744,7 +1546,12 @@ fn emit_augmented_modifies_wrapper(&mut self) {
                     attrs: vec![],
                 }));
             }
-            self.output.extend(quote!(#[kanitool::modifies(#(#wrapper_args),*)]))
+            self.output.extend(quote!(#[kanitool::modifies(#(#wrapper_args = (#sources)),*)]))
+        }
+        if matches!(self.condition_type, ContractConditionsData::Decreases { .. }) {
+            // Tells the compiler that the static holding the measure must not
+            // be havocked when this contract is checked.
+            self.output.extend(quote!(#[kanitool::decreases]))
         }
         self.emit_common_header();
 
@@ -824,33 +1631,76 @@ fn try_as_result_assign_mut(stmt: &mut syn::Stmt) -> Option<&mut syn::LocalInit>
     try_as_result_assign_pat!(stmt, as_mut)
 }
 
-/// Is this statement `let result : <...> = kani::any();`.
+/// Is this statement `let result : <...> = kani::any();`, or the call to the check function
+/// tagged with `#[kanitool::is_contract_generated(check)]` in the replace function of a function
+/// that returns `impl Trait`.
 fn is_replace_return_havoc(stmt: &syn::Stmt) -> bool {
     let Some(syn::LocalInit { diverge: None, expr: e, .. }) = try_as_result_assign(stmt) else {
         return false;
     };
+    let syn::Stmt::Local(syn::Local { attrs: local_attrs, .. }) = stmt else {
+        return false;
+    };
 
-    matches!(
-        e.as_ref(),
-        Expr::Call(syn::ExprCall {
-            func,
-            args,
-            ..
-        })
-        if args.is_empty()
-        && matches!(
-            func.as_ref(),
-            Expr::Path(syn::ExprPath {
-                qself: None,
-                path,
-                attrs,
-            })
-            if path.segments.len() == 2
-            && path.segments[0].ident == "kani"
-            && path.segments[1].ident == "any"
-            && attrs.is_empty()
-        )
-    )
+    let Expr::Call(syn::ExprCall { func, args, .. }) = e.as_ref() else {
+        return false;
+    };
+    let Expr::Path(syn::ExprPath { qself: None, path, attrs }) = func.as_ref() else {
+        return false;
+    };
+    let is_kani_any = args.is_empty()
+        && path.segments.len() == 2
+        && path.segments[0].ident == "kani"
+        && path.segments[1].ident == "any";
+    let is_check_call = local_attrs.iter().any(|attr| {
+        matches!(ContractFunctionState::try_from(attr), Ok(ContractFunctionState::Check))
+    });
+    attrs.is_empty() && (is_kani_any || is_check_call)
+}
+
+/// Whether the generated check, replace and modifies wrapper functions carry `attr` of the
+/// annotated function as well.
+///
+/// Conditional compilation, documentation and the remaining contract attributes are inherited.
+/// Attributes that fix the symbol of the function or make it a test belong to the original
+/// function alone, on a generated function they would clash with it.
+fn is_inherited_attr(attr: &Attribute) -> bool {
+    const NOT_INHERITED: [&str; 4] = ["no_mangle", "export_name", "link_section", "test"];
+    !NOT_INHERITED.iter().any(|name| attr.path().is_ident(name))
+}
+
+/// Whether the recursion wrapper carries `attr` of the annotated function as well.
+///
+/// The wrapper calls the check and replace functions, so it must be compiled out whenever they
+/// are. `#[track_caller]` adds an implicit location argument to the function, the wrapper needs it
+/// as well so that the caller location is passed through consistently. Other attributes, including
+/// `cfg_attr` which may expand to a contract clause, do not apply to the wrapper.
+fn is_recursion_wrapper_attr(attr: &Attribute) -> bool {
+    attr.path().is_ident("cfg") || attr.path().is_ident("track_caller")
+}
+
+/// Does the return type mention `impl Trait`? The hidden type of such a result is only known to
+/// the compiler, so it can neither be named in the generated code nor created with `kani::any()`.
+fn returns_impl_trait(return_type: &syn::ReturnType) -> bool {
+    struct ImplTraitFinder(bool);
+    impl<'ast> Visit<'ast> for ImplTraitFinder {
+        fn visit_type_impl_trait(&mut self, _: &'ast syn::TypeImplTrait) {
+            self.0 = true;
+        }
+    }
+    let mut finder = ImplTraitFinder(false);
+    finder.visit_return_type(return_type);
+    finder.0
+}
+
+/// The type annotation of the `result` binding in generated functions: the return type, or `_` if
+/// it mentions `impl Trait`, which cannot be written in a `let`.
+fn result_type_annotation(return_type: &syn::ReturnType) -> Cow<syn::Type> {
+    if returns_impl_trait(return_type) {
+        Cow::Owned(syn::Type::Infer(syn::TypeInfer { underscore_token: Default::default() }))
+    } else {
+        return_type_to_type(return_type)
+    }
 }
 
 /// For each argument create an expression that passes this argument along unmodified.
@@ -952,7 +1802,7 @@ fn try_as_wrapper_call_args<'a>(
     match init_expr.as_mut() {
         Expr::Call(syn::ExprCall { func: box_func, args, .. }) => match box_func.as_ref() {
             syn::Expr::Path(syn::ExprPath { qself: None, path, .. })
-                if path.get_ident().map_or(false, |id| id == wrapper_fn_name) =>
+                if path.segments.last().map_or(false, |seg| seg.ident == wrapper_fn_name) =>
             {
                 Some(args)
             }
@@ -990,9 +1840,9 @@ fn return_type_to_type(return_type: &syn::ReturnType) -> Cow<syn::Type> {
 /// This is called when we first emit a replace function. Later we can rely on
 /// this bound already being present.
 fn attach_require_kani_any(sig: &mut Signature) {
-    if matches!(sig.output, ReturnType::Default) {
+    if matches!(sig.output, ReturnType::Default) || returns_impl_trait(&sig.output) {
         // It's the default return type, e.g. `()` so we can skip adding the
-        // constraint.
+        // constraint. An `impl Trait` result is never created with `kani::any()`.
         return;
     }
     let return_ty = return_type_to_type(&sig.output);
@@ -1030,6 +1880,18 @@ fn attach_require_kani_any(sig: &mut Signature) {
     }))
 }
 
+/// Parse the item a contract attribute is placed on. A function declaration
+/// without a body, e.g. `extern "C" fn abs(x: i32) -> i32;`, is parsed into an
+/// [`ItemFn`] with an empty body and flagged by the returned `bool`.
+fn parse_contracted_item(item: TokenStream) -> Result<(ItemFn, bool), syn::Error> {
+    match syn::parse::<syn::ForeignItemFn>(item.clone()) {
+        Ok(syn::ForeignItemFn { attrs, vis, sig, .. }) => {
+            Ok((ItemFn { attrs, vis, sig, block: Box::new(syn::parse_quote!({})) }, true))
+        }
+        Err(_) => syn::parse(item).map(|item_fn| (item_fn, false)),
+    }
+}
+
 /// We make shallow copies of the argument for the postconditions in both
 /// `requires` and `ensures` clauses and later clean them up.
 ///
@@ -1047,6 +1909,58 @@ fn make_unsafe_argument_copies(
     )
 }
 
+/// The values of the `snapshots` whose variable is neither used in the postcondition `attr`
+/// nor by another snapshot.
+///
+/// [`OldWithLifter`] replaces every call it lifts by the variable of its snapshot, so a snapshot is
+/// only unused if its value is discarded right away, as in `let _ = old(x);` or `old(x);`. Such a
+/// capture has no effect on the postcondition and usually hides a typo.
+fn unused_snapshots<'a>(snapshots: &'a [(Ident, Expr)], attr: &Expr) -> Vec<&'a Expr> {
+    let mut collector = IdentCollector::default();
+    collector.visit_expr(attr);
+    snapshots.iter().for_each(|(_, value)| collector.visit_expr(value));
+    snapshots
+        .iter()
+        .filter(|(name, _)| !collector.0.contains(name))
+        .map(|(_, value)| value)
+        .collect()
+}
+
+/// Collects every identifier that is used as a path expression, except in statements that only
+/// discard it.
+#[derive(Default)]
+struct IdentCollector(HashSet<Ident>);
+
+impl<'ast> Visit<'ast> for IdentCollector {
+    fn visit_expr_path(&mut self, i: &'ast syn::ExprPath) {
+        if let Some(ident) = i.path.get_ident() {
+            self.0.insert(ident.clone());
+        }
+    }
+
+    fn visit_stmt(&mut self, i: &'ast syn::Stmt) {
+        let discarded = match i {
+            syn::Stmt::Local(syn::Local {
+                pat: syn::Pat::Wild(_),
+                init: Some(syn::LocalInit { expr, diverge: None, .. }),
+                ..
+            }) => Some(expr.as_ref()),
+            syn::Stmt::Expr(expr, Some(_)) => Some(expr),
+            _ => None,
+        };
+        if !matches!(discarded, Some(Expr::Path(_))) {
+            syn::visit::visit_stmt(self, i);
+        }
+    }
+}
+
+/// Emit the `let` bindings that capture the `old` and `old_with` snapshots on entry.
+fn make_snapshot_bindings(snapshots: &[(Ident, Expr)]) -> TokenStream2 {
+    let names = snapshots.iter().map(|(name, _)| name);
+    let values = snapshots.iter().map(|(_, value)| value);
+    quote!(#(let #names = #values;)*)
+}
+
 /// The main meat of handling requires/ensures contracts.
 ///
 /// See the [module level documentation][self] for a description of how the code
@@ -1060,7 +1974,10 @@ fn requires_ensures_main(
 
     let mut output = proc_macro2::TokenStream::new();
     let item_stream_clone = item.clone();
-    let mut item_fn = parse_macro_input!(item as ItemFn);
+    let (mut item_fn, is_declaration) = match parse_contracted_item(item) {
+        Ok(parsed) => parsed,
+        Err(e) => return e.into_compile_error().into(),
+    };
 
     let function_state = ContractFunctionState::from_attributes(&item_fn.attrs);
 
@@ -1073,11 +1990,45 @@ fn requires_ensures_main(
         //
         // Since this is the only function state case that doesn't need a
         // handler to be constructed, we do this match early, separately.
+        return if is_declaration { item_stream_clone } else { item_fn.into_token_stream().into() };
+    }
+
+    if matches!(function_state, ContractFunctionState::Postcondition)
+        && !matches!(is_requires, ContractConditionsType::Ensures)
+    {
+        // The postcondition function only evaluates the `ensures` clauses, the
+        // other clauses leave it unchanged.
         return item_fn.into_token_stream().into();
     }
 
-    let hash = matches!(function_state, ContractFunctionState::Untouched)
+    if matches!(function_state, ContractFunctionState::ReplacementClient)
+        && !matches!(is_requires, ContractConditionsType::Requires)
+    {
+        // The replacement client only assumes the `requires` clauses, the
+        // other clauses leave it unchanged.
+        return item_fn.into_token_stream().into();
+    }
+
+    // The postcondition function takes the result as its last argument. Until
+    // it is emitted again, its signature is turned back into the one of the
+    // original function, which the clause is written against.
+    let postcondition_result = matches!(function_state, ContractFunctionState::Postcondition)
+        .then(|| split_postcondition_result(&mut item_fn.sig));
+
+    if is_declaration && item_fn.sig.abi.is_none() {
+        return syn::Error::new(
+            item_fn.sig.span(),
+            "a function with a contract but without a body must be declared `extern`, e.g. \
+            `extern \"C\" fn foo(x: i32) -> i32;`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let hash = matches!(function_state, ContractFunctionState::Untouched)
         .then(|| short_hash_of_token_stream(&item_stream_clone));
+    let fingerprint = matches!(function_state, ContractFunctionState::Untouched)
+        .then(|| fingerprint_of_contract(is_requires, &attr_copy, &item_stream_clone));
 
     let original_function_name = item_fn.sig.ident.clone();
 
@@ -1109,6 +2060,12 @@ fn requires_ensures_main(
             // Analogous to above
             handler.emit_replace_function(original_function_name);
         }
+        ContractFunctionState::Postcondition => {
+            handler.emit_postcondition_function(original_function_name, postcondition_result);
+        }
+        ContractFunctionState::ReplacementClient => {
+            handler.emit_replacement_client(original_function_name);
+        }
         ContractFunctionState::Original => {
             unreachable!("Impossible: This is handled via short circuiting earlier.")
         }
@@ -1127,6 +2084,21 @@ fn requires_ensures_main(
             // and "replace" functions.
             let item_hash = hash.unwrap();
 
+            if is_declaration {
+                let replace_fn_name = identifier_for_generated_function(
+                    &original_function_name,
+                    "replace",
+                    item_hash,
+                );
+                handler.emit_declaration(&replace_fn_name, fingerprint.unwrap());
+                handler.emit_replace_function(replace_fn_name);
+                handler.emit_postcondition_function(
+                    postcondition_function_name(&original_function_name),
+                    None,
+                );
+                return output.into();
+            }
+
             let check_fn_name =
                 identifier_for_generated_function(&original_function_name, "check", item_hash);
             let replace_fn_name =
@@ -1149,6 +2121,8 @@ fn requires_ensures_main(
                 syn::LitStr::new(&handler.make_wrapper_name().to_string(), Span::call_site());
             let recursion_wrapper_name_str =
                 syn::LitStr::new(&recursion_wrapper_name.to_string(), Span::call_site());
+            let fingerprint_str =
+                syn::LitStr::new(&format!("{:016x}", fingerprint.unwrap()), Span::call_site());
 
             // The order of `attrs` and `kanitool::{checked_with,
             // is_contract_generated}` is important here, because macros are
@@ -1160,46 +2134,83 @@ fn requires_ensures_main(
             // emit the check function.
             let is_impl_fn = is_probably_impl_fn(&handler.annotated_fn);
             let ItemFn { attrs, vis, sig, block } = &handler.annotated_fn;
+            let clause_attrs = contract_clause_attrs(is_requires, &handler.attr_copy, attrs);
             handler.output.extend(quote!(
                 #(#attrs)*
                 #[kanitool::checked_with = #recursion_wrapper_name_str]
                 #[kanitool::replaced_with = #replace_fn_name_str]
                 #[kanitool::inner_check = #wrapper_fn_name_str]
+                #[kanitool::contract_fingerprint = #fingerprint_str]
+                #clause_attrs
                 #vis #sig {
                     #block
                 }
             ));
 
+            let wrapper_attrs = attrs.iter().filter(|attr| is_recursion_wrapper_attr(attr));
+            let depth = Ident::new("RECURSION_DEPTH", Span::mixed_site());
+
+            // The original function keeps its `const`, so it can still be used in
+            // const contexts. The generated functions cannot be `const` because
+            // they call into `kani` and track the recursion in a `static mut`.
             let mut wrapper_sig = sig.clone();
             attach_require_kani_any(&mut wrapper_sig);
             wrapper_sig.ident = recursion_wrapper_name;
+            wrapper_sig.constness = None;
 
             let args = pats_to_idents(&mut wrapper_sig.inputs).collect::<Vec<_>>();
             let also_args = args.iter();
+            let generic_args = generic_args_of(sig);
             let (call_check, call_replace) = if is_impl_fn {
-                (quote!(Self::#check_fn_name), quote!(Self::#replace_fn_name))
+                (
+                    quote!(Self::#check_fn_name #generic_args),
+                    quote!(Self::#replace_fn_name #generic_args),
+                )
             } else {
-                (quote!(#check_fn_name), quote!(#replace_fn_name))
+                (quote!(#check_fn_name #generic_args), quote!(#replace_fn_name #generic_args))
             };
+            // Both branches must return the same `impl Trait` type. A function returning
+            // `impl Trait` cannot return the result of a recursive call anyway.
+            let call_replace =
+                if returns_impl_trait(&sig.output) { call_check.clone() } else { call_replace };
 
             handler.output.extend(quote!(
                 #[allow(dead_code, unused_variables)]
+                #(#wrapper_attrs)*
+                #[kanitool::inner_check = #wrapper_fn_name_str]
                 #[kanitool::is_contract_generated(recursion_wrapper)]
                 #wrapper_sig {
-                    static mut REENTRY: bool = false;
-                    if unsafe { REENTRY } {
+                    #[kanitool::recursion_tracker]
+                    static mut #depth: u32 = 0;
+                    if unsafe { #depth } > 0 {
                         #call_replace(#(#args),*)
                     } else {
-                        unsafe { REENTRY = true };
+                        unsafe { #depth += 1 };
                         let result = #call_check(#(#also_args),*);
-                        unsafe { REENTRY = false };
+                        unsafe { #depth -= 1 };
                         result
                     }
                 }
             ));
 
+            handler.annotated_fn.sig.constness = None;
+            // The generated functions refer to the inner check function as well,
+            // so the compiler can find the `modifies` clauses of their contract.
+            handler.output.extend(quote!(#[kanitool::inner_check = #wrapper_fn_name_str]));
             handler.emit_check_function(check_fn_name);
+            // The replace function stands in for the original function where the
+            // contract is used via `stub_verified`, so it carries the fingerprint too.
+            handler.output.extend(quote!(
+                #[kanitool::inner_check = #wrapper_fn_name_str]
+                #[kanitool::contract_fingerprint = #fingerprint_str]
+            ));
             handler.emit_replace_function(replace_fn_name);
+            handler.output.extend(quote!(#[kanitool::inner_check = #wrapper_fn_name_str]));
+            handler.emit_postcondition_function(
+                postcondition_function_name(&original_function_name),
+                None,
+            );
+            handler.emit_replacement_client(replacement_client_name(&original_function_name));
             handler.emit_augmented_modifies_wrapper();
         }
     }
@@ -1207,6 +2218,26 @@ fn requires_ensures_main(
     output.into()
 }
 
+/// The explicit generic arguments, e.g. `::<T, N>`, with which a function that
+/// has the same generics as `sig` calls the generated functions.
+///
+/// Const generics have to be passed explicitly since they are not always
+/// inferable from the arguments, e.g. if `N` only occurs in a contract clause.
+/// Lifetimes are left to inference.
+fn generic_args_of(sig: &Signature) -> TokenStream2 {
+    let args = sig
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Type(ty) => Some(&ty.ident),
+            syn::GenericParam::Const(cnst) => Some(&cnst.ident),
+            syn::GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Vec<_>>();
+    if args.is_empty() { quote!() } else { quote!(::<#(#args),*>) }
+}
+
 /// Convert every use of a pattern in this signature to a simple, fresh, binding-only
 /// argument ([`syn::PatIdent`]) and return the [`Ident`] that was generated.
 fn pats_to_idents<P>(
@@ -1242,6 +2273,15 @@ fn visit_receiver(&mut self, _node: &'ast syn::Receiver) {
     }
 }
 
+/// Finds the first `impl Trait` type, whose concrete type only the caller knows.
+struct ImplTraitDetector(Option<Span>);
+
+impl<'ast> Visit<'ast> for ImplTraitDetector {
+    fn visit_type_impl_trait(&mut self, i: &'ast syn::TypeImplTrait) {
+        self.0.get_or_insert(i.span());
+    }
+}
+
 /// Try to determine if this function is part of an `impl`.
 ///
 /// Detects *methods* by the presence of a receiver argument. Heuristically
@@ -1287,6 +2327,12 @@ fn visit_receiver(&mut self, _node: &'ast syn::Receiver) {
 /// }
 /// ```
 ///
+/// Note that only the call path depends on this heuristic. Generic parameters
+/// and bounds of a surrounding `impl<T: Ord> Vec<T>` are also invisible to us,
+/// but all generated functions are emitted next to the original one, inside
+/// the same `impl` block, so they are in scope for the contract conditions
+/// without us having to copy them.
+///
 /// **Side note:** You may be tempted to suggest that we could try and parse
 /// `syn::ImplItemFn` and distinguish that from `syn::ItemFn` to distinguish
 /// associated function from plain functions. However parsing in an attribute
@@ -1300,13 +2346,21 @@ fn is_probably_impl_fn(fun: &ItemFn) -> bool {
 
 /// Create a unique hash for a token stream (basically a [`std::hash::Hash`]
 /// impl for `proc_macro2::TokenStream`).
+///
+/// Only the text of the tokens is hashed, never their spans, so the hash is
+/// stable across recompilations. Invisible groups, which `macro_rules!` inserts
+/// around substituted fragments, are hashed transparently so that a function
+/// generated by a macro gets the same hash as if it had been written by hand.
 fn hash_of_token_stream<H: std::hash::Hasher>(hasher: &mut H, stream: proc_macro2::TokenStream) {
-    use proc_macro2::TokenTree;
+    use proc_macro2::{Delimiter, TokenTree};
     use std::hash::Hash;
     for token in stream {
         match token {
             TokenTree::Ident(i) => i.hash(hasher),
             TokenTree::Punct(p) => p.as_char().hash(hasher),
+            TokenTree::Group(g) if g.delimiter() == Delimiter::None => {
+                hash_of_token_stream(hasher, g.stream());
+            }
             TokenTree::Group(g) => {
                 std::mem::discriminant(&g.delimiter()).hash(hasher);
                 hash_of_token_stream(hasher, g.stream());
@@ -1327,6 +2381,76 @@ fn short_hash_of_token_stream(stream: &proc_macro::TokenStream) -> u64 {
     long_hash % SIX_HEX_DIGITS_MASK
 }
 
+/// The attributes that record the clauses of the complete contract of a
+/// function on the original function, `#[kanitool::contract_requires = "..."]`
+/// and the analogous `contract_ensures` and `contract_modifies`, the latter once
+/// per target. The compiler reports them for every function with a contract,
+/// also when no harness checks it.
+///
+/// `attr` is the clause of kind `kind` that is being expanded, `attrs` are the
+/// attributes of the function, which still hold the clauses that follow it.
+fn contract_clause_attrs(
+    kind: ContractConditionsType,
+    attr: &TokenStream2,
+    attrs: &[Attribute],
+) -> TokenStream2 {
+    let remaining = attrs.iter().filter_map(|attr| {
+        let segments = &attr.path().segments;
+        if segments.len() != 2 || segments[0].ident != "kani" {
+            return None;
+        }
+        let kind = match segments[1].ident.to_string().as_str() {
+            "requires" => ContractConditionsType::Requires,
+            "ensures" => ContractConditionsType::Ensures,
+            "modifies" => ContractConditionsType::Modifies,
+            _ => return None,
+        };
+        let syn::Meta::List(list) = &attr.meta else { return None };
+        Some((kind, list.tokens.clone()))
+    });
+    std::iter::once((kind, attr.clone()))
+        .chain(remaining)
+        .flat_map(|(kind, tokens)| clause_attrs(kind, tokens))
+        .collect()
+}
+
+/// The attributes that record a single clause, see [`contract_clause_attrs`].
+fn clause_attrs(kind: ContractConditionsType, tokens: TokenStream2) -> Vec<TokenStream2> {
+    match kind {
+        ContractConditionsType::Requires => {
+            vec![quote!(#[kanitool::contract_requires = stringify!(#tokens)])]
+        }
+        ContractConditionsType::Ensures => {
+            vec![quote!(#[kanitool::contract_ensures = stringify!(#tokens)])]
+        }
+        ContractConditionsType::Modifies => {
+            chunks_by::<_, TokenStream2>(tokens, is_token_stream_2_comma)
+                .map(|target| quote!(#[kanitool::contract_modifies = stringify!(#target)]))
+                .collect()
+        }
+        ContractConditionsType::Decreases => vec![],
+    }
+}
+
+/// Hash the complete contract of a function together with its body.
+///
+/// `attr` is the contract attribute that is being expanded, which is no longer
+/// part of `item`, while `item` still carries the remaining contract attributes.
+/// Unlike [`short_hash_of_token_stream`] this hash is not used for names but
+/// lets `--changed-only` detect that a contract changed, so it keeps all bits.
+fn fingerprint_of_contract(
+    kind: ContractConditionsType,
+    attr: &TokenStream2,
+    item: &proc_macro::TokenStream,
+) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::default();
+    std::mem::discriminant(&kind).hash(&mut hasher);
+    hash_of_token_stream(&mut hasher, attr.clone());
+    hash_of_token_stream(&mut hasher, proc_macro2::TokenStream::from(item.clone()));
+    hasher.finish()
+}
+
 /// Makes consistent names for a generated function which was created for
 /// `purpose`, from an attribute that decorates `related_function` with the
 /// hash `hash`.
@@ -1339,6 +2463,98 @@ fn identifier_for_generated_function(
     Ident::new(&identifier, proc_macro2::Span::mixed_site())
 }
 
+/// The name of the postcondition function generated for `function`, which
+/// `kani::assert_contract_holds!` calls. Unlike the other generated functions
+/// it carries no hash, so that it can be named from the path of `function`.
+fn postcondition_function_name(function: &Ident) -> Ident {
+    use syn::ext::IdentExt;
+    Ident::new(&format!("{}_kani_postcondition", function.unraw()), function.span())
+}
+
+/// The name of the replacement client generated for `function`. The client is
+/// a harness that `--enforce-and-replace` uses to check the replacement of
+/// `function` by its contract, see
+/// [`ContractConditionsHandler::emit_replacement_client`].
+fn replacement_client_name(function: &Ident) -> Ident {
+    use syn::ext::IdentExt;
+    Ident::new(&format!("{}_replacement_client", function.unraw()), Span::call_site())
+}
+
+/// The statements that bind the arguments of `function` in its replacement
+/// client, and the arguments with which the client calls `function`.
+///
+/// Every argument is bound to its name, so that the `requires` clauses can
+/// refer to it. An argument of type `&T` or `&mut T` is given a
+/// reference to a value of type `T`. Returns `None` if the client cannot
+/// create the arguments without choosing a type or a pattern, i.e. for
+/// associated, generic and `async` functions, for `impl Trait` arguments and
+/// results, for references to unsized types and for arguments that are
+/// destructured.
+fn replacement_client_arguments(function: &ItemFn) -> Option<(Vec<TokenStream2>, Vec<Expr>)> {
+    let sig = &function.sig;
+    let mut impl_trait = ImplTraitDetector(None);
+    sig.inputs.iter().for_each(|input| impl_trait.visit_fn_arg(input));
+    if is_probably_impl_fn(function)
+        || !sig.generics.params.is_empty()
+        || sig.asyncness.is_some()
+        || sig.variadic.is_some()
+        || impl_trait.0.is_some()
+        || returns_impl_trait(&sig.output)
+    {
+        return None;
+    }
+    let any_argument =
+        |ty: &syn::Type| quote!((&&kani::internal::ClientArgument::<#ty>::new()).any_argument());
+    let is_unsized = |ty: &syn::Type| match ty {
+        syn::Type::Slice(_) | syn::Type::TraitObject(_) => true,
+        syn::Type::Path(path) => path.qself.is_none() && path.path.is_ident("str"),
+        _ => false,
+    };
+    let mut bindings = vec![];
+    let mut args = vec![];
+    for input in &sig.inputs {
+        let FnArg::Typed(syn::PatType { pat, ty, .. }) = input else { return None };
+        let value = match ty.as_ref() {
+            syn::Type::Reference(syn::TypeReference { elem, .. }) if is_unsized(elem) => {
+                return None;
+            }
+            syn::Type::Reference(syn::TypeReference { mutability: Some(_), elem, .. }) => {
+                let value = any_argument(elem);
+                quote!(&mut #value)
+            }
+            syn::Type::Reference(syn::TypeReference { elem, .. }) => {
+                let value = any_argument(elem);
+                quote!(&#value)
+            }
+            _ => any_argument(ty),
+        };
+        match pat.as_ref() {
+            syn::Pat::Ident(syn::PatIdent { by_ref: None, subpat: None, ident, .. }) => {
+                bindings.push(quote!(let #pat: #ty = #value;));
+                args.push(syn::parse_quote!(#ident));
+            }
+            _ => return None,
+        }
+    }
+    Some((bindings, args))
+}
+
+/// Turn the signature of a postcondition function back into the one of the
+/// function with the contract: remove the last argument, which holds the
+/// result, and make its type the return type again. Returns the name of the
+/// removed argument.
+fn split_postcondition_result(sig: &mut Signature) -> Ident {
+    let Some(FnArg::Typed(syn::PatType { pat, ty, .. })) = sig.inputs.pop().map(|p| p.into_value())
+    else {
+        unreachable!("the postcondition function should take the result as its last argument")
+    };
+    let syn::Pat::Ident(result) = *pat else {
+        unreachable!("the result argument of the postcondition function should be an identifier")
+    };
+    sig.output = syn::ReturnType::Type(Default::default(), ty);
+    result.ident
+}
+
 fn is_token_stream_2_comma(t: &proc_macro2::TokenTree) -> bool {
     matches!(t, proc_macro2::TokenTree::Punct(p) if p.as_char() == ',')
 }
@@ -1364,6 +2580,10 @@ fn chunks_by<'a, T, C: Default + Extend<T>>(
 }
 
 /// Collect all named identifiers used in the argument patterns of a function.
+///
+/// Types and generics are not visited: they cannot bind an argument, and the
+/// expressions they contain, like the length in `[u8; N]`, are evaluated at
+/// compile time.
 struct ArgumentIdentCollector(HashSet<Ident>);
 
 impl ArgumentIdentCollector {
@@ -1380,15 +2600,21 @@ fn visit_pat_ident(&mut self, i: &'ast syn::PatIdent) {
     fn visit_receiver(&mut self, _: &'ast syn::Receiver) {
         self.0.insert(Ident::new("self", proc_macro2::Span::call_site()));
     }
+    fn visit_type(&mut self, _: &'ast syn::Type) {}
+    fn visit_generics(&mut self, _: &'ast syn::Generics) {}
 }
 
 /// Applies the contained renaming (key renamed to value) to every ident pattern
 /// and ident expr visited.
+///
+/// Types are left untouched, since an argument cannot be used at the type
+/// level. This keeps e.g. the const generic `N` in `size_of::<[u8; N]>()` and
+/// the associated const in `<Self as Buffer>::CAP` intact.
 struct Renamer<'a>(&'a HashMap<Ident, Ident>);
 
 impl<'a> VisitMut for Renamer<'a> {
     fn visit_expr_path_mut(&mut self, i: &mut syn::ExprPath) {
-        if i.path.segments.len() == 1 {
+        if i.qself.is_none() && i.path.segments.len() == 1 {
             i.path
                 .segments
                 .first_mut()
@@ -1404,6 +2630,160 @@ fn visit_pat_ident_mut(&mut self, i: &mut syn::PatIdent) {
             i.ident = new.clone();
         }
     }
+
+    fn visit_type_mut(&mut self, _: &mut syn::Type) {}
+}
+
+/// Turns a closure-style postcondition `|pat| body` into
+/// `{ let pat = &kani::result(); body }`, so the pattern binds a reference to
+/// the return value, e.g. `|(low, high)| low <= high` for a function returning a
+/// pair. Any other postcondition is returned unchanged.
+///
+/// Fails if the closure does not take exactly one argument, or if it
+/// destructures a tuple whose arity does not match the return type of `sig`.
+fn bind_closure_result(sig: &Signature, attr: Expr) -> Result<Expr, syn::Error> {
+    let Expr::Closure(closure) = attr else { return Ok(attr) };
+    let mut inputs = closure.inputs.iter();
+    let (Some(pat), None) = (inputs.next(), inputs.next()) else {
+        return Err(syn::Error::new_spanned(
+            &closure.inputs,
+            "a postcondition closure must take exactly one argument, which is bound to the \
+            return value",
+        ));
+    };
+    if let syn::Pat::Tuple(tuple) = pat {
+        let is_exact = !tuple.elems.iter().any(|elem| matches!(elem, syn::Pat::Rest(_)));
+        let returned = match &sig.output {
+            syn::ReturnType::Default => Some(0),
+            syn::ReturnType::Type(_, ty) => match ty.as_ref() {
+                syn::Type::Tuple(ty) => Some(ty.elems.len()),
+                _ => None,
+            },
+        };
+        let bound = tuple.elems.len();
+        match returned {
+            Some(returned) if is_exact && returned != bound => {
+                return Err(syn::Error::new_spanned(
+                    pat,
+                    format!(
+                        "this pattern binds a tuple of {bound} values, but the function returns \
+                        a tuple of {returned} values"
+                    ),
+                ));
+            }
+            _ => {}
+        }
+    }
+    let body = &closure.body;
+    Ok(syn::parse_quote!({
+        let #pat = &kani::result();
+        #body
+    }))
+}
+
+/// Gives every use of the `result` binding the call site span of the contract
+/// attribute.
+///
+/// The `result` binding we generate for postconditions is created with that
+/// span. If the postcondition was forwarded into the attribute by a
+/// `macro_rules!` macro, its `result` carries the hygiene of the macro caller
+/// instead and would not resolve to our binding.
+///
+/// A `result` that is bound inside the postcondition, e.g. by a `let`, a
+/// closure or a `match` arm, is a different variable and keeps its span. To
+/// detect this we track the variables that are in scope in `locals`, like
+/// [`OldWithLifter`] does.
+///
+/// Records the original span of every respanned use in `uses`, so we can warn
+/// about uses that refer to an argument called `result` instead.
+#[derive(Default)]
+struct ResultRespanner {
+    uses: Vec<Span>,
+    locals: Vec<Ident>,
+}
+
+impl ResultRespanner {
+    fn bind(&mut self, pat: &syn::Pat) {
+        let mut collector = ArgumentIdentCollector::new();
+        collector.visit_pat(pat);
+        self.locals.extend(collector.0);
+    }
+}
+
+impl VisitMut for ResultRespanner {
+    fn visit_expr_path_mut(&mut self, i: &mut syn::ExprPath) {
+        if let Some(ident) = i.path.get_ident() {
+            if ident == "result" && !self.locals.iter().any(|local| local == "result") {
+                self.uses.push(ident.span());
+                i.path.segments[0].ident = Ident::new("result", Span::call_site());
+            }
+        }
+    }
+
+    fn visit_block_mut(&mut self, i: &mut syn::Block) {
+        let scope = self.locals.len();
+        syn::visit_mut::visit_block_mut(self, i);
+        self.locals.truncate(scope);
+    }
+
+    /// The variables of a `let` are only in scope after its initializer.
+    fn visit_local_mut(&mut self, i: &mut syn::Local) {
+        if let Some(init) = &mut i.init {
+            self.visit_expr_mut(&mut init.expr);
+            if let Some((_, diverge)) = &mut init.diverge {
+                self.visit_expr_mut(diverge);
+            }
+        }
+        self.bind(&i.pat);
+    }
+
+    fn visit_expr_closure_mut(&mut self, i: &mut syn::ExprClosure) {
+        let scope = self.locals.len();
+        i.inputs.iter().for_each(|pat| self.bind(pat));
+        self.visit_expr_mut(&mut i.body);
+        self.locals.truncate(scope);
+    }
+
+    fn visit_arm_mut(&mut self, i: &mut syn::Arm) {
+        let scope = self.locals.len();
+        self.bind(&i.pat);
+        syn::visit_mut::visit_arm_mut(self, i);
+        self.locals.truncate(scope);
+    }
+
+    fn visit_expr_for_loop_mut(&mut self, i: &mut syn::ExprForLoop) {
+        self.visit_expr_mut(&mut i.expr);
+        let scope = self.locals.len();
+        self.bind(&i.pat);
+        self.visit_block_mut(&mut i.body);
+        self.locals.truncate(scope);
+    }
+
+    /// Conservatively keeps the variables of an `if let` or `while let` in
+    /// scope until the end of the enclosing block.
+    fn visit_expr_let_mut(&mut self, i: &mut syn::ExprLet) {
+        self.visit_expr_mut(&mut i.expr);
+        self.bind(&i.pat);
+    }
+}
+
+/// Replaces every `kani::result()` call in a postcondition with the `result`
+/// binding of the return value.
+///
+/// Runs after the arguments were renamed, so the call refers to the return
+/// value even if an argument is called `result`.
+struct ResultCallRewriter;
+
+impl VisitMut for ResultCallRewriter {
+    fn visit_expr_mut(&mut self, e: &mut Expr) {
+        syn::visit_mut::visit_expr_mut(self, e);
+        let Expr::Call(call) = e else { return };
+        let Expr::Path(func) = call.func.as_ref() else { return };
+        if call.args.is_empty() && matches_path(&func.path, &["kani", "result"]) {
+            let result = Ident::new("result", Span::call_site());
+            *e = syn::parse_quote!(#result);
+        }
+    }
 }
 
 /// A supporting function for creating shallow, unsafe copies of the arguments
@@ -1433,6 +2813,570 @@ fn rename_argument_occurrences(sig: &syn::Signature, attr: &mut Expr) -> HashMap
     arg_idents
 }
 
+/// Replaces every `old_with(expr, snapshot_fn)` (or `kani::old_with(..)`) call
+/// in a postcondition with a fresh variable and records `snapshot_fn(expr)` as
+/// the value that variable must be bound to on entry to the function. Likewise
+/// `old(expr)` (or `kani::old(..)`) is replaced and `expr` itself recorded, so
+/// it must evaluate to an owned value such as a `bool` computed from the
+/// arguments.
+///
+/// Calls with any other number of arguments are left untouched, which results
+/// in a name resolution error at the use site.
+///
+/// The snapshot is evaluated before the function body runs, so `expr` must not
+/// refer to variables bound inside the postcondition itself (e.g. by a `let` in
+/// a block). To detect this we track the variables that are in scope at each
+/// point of the postcondition in `locals` and record a violation in `error`.
+///
+/// A method called on `self` or one of its fields, as in `old(self.get(key))`
+/// or `old(self.items.len())`, is called on entry and only its result is kept,
+/// so `self` is not cloned. The result must therefore not borrow from `self`.
+#[derive(Default)]
+struct OldWithLifter {
+    snapshots: Vec<(Ident, Expr)>,
+    locals: Vec<Ident>,
+    error: Option<syn::Error>,
+}
+
+impl OldWithLifter {
+    fn bind(&mut self, pat: &syn::Pat) {
+        let mut collector = ArgumentIdentCollector::new();
+        collector.visit_pat(pat);
+        self.locals.extend(collector.0);
+    }
+
+    fn check_no_locals(&mut self, callee: &str, expr: &Expr) {
+        struct LocalFinder<'a>(&'a [Ident], Option<Ident>);
+        impl<'ast> Visit<'ast> for LocalFinder<'_> {
+            fn visit_expr_path(&mut self, i: &'ast syn::ExprPath) {
+                if let Some(ident) = i.path.get_ident() {
+                    if self.1.is_none() && self.0.contains(ident) {
+                        self.1 = Some(ident.clone());
+                    }
+                }
+            }
+        }
+        let mut finder = LocalFinder(&self.locals, None);
+        finder.visit_expr(expr);
+        if let Some(local) = finder.1 {
+            let error = syn::Error::new(
+                local.span(),
+                format!(
+                    "`{callee}` is evaluated on entry to the function and cannot refer to \
+                    `{local}`, which is bound inside the postcondition"
+                ),
+            );
+            match &mut self.error {
+                Some(errors) => errors.combine(error),
+                None => self.error = Some(error),
+            }
+        }
+    }
+}
+
+impl VisitMut for OldWithLifter {
+    fn visit_expr_mut(&mut self, e: &mut Expr) {
+        // Visit the children first so nested `old_with` calls are lifted too.
+        syn::visit_mut::visit_expr_mut(self, e);
+        let Expr::Call(call) = e else { return };
+        let Expr::Path(func) = call.func.as_ref() else { return };
+        let is_call_to = |callee| {
+            matches_path(&func.path, &[callee]) || matches_path(&func.path, &["kani", callee])
+        };
+        let snapshot = match call.args.len() {
+            1 if is_call_to("old") => {
+                let expr = &call.args[0];
+                self.check_no_locals("old", expr);
+                // The value behind a `&mut` argument cannot be moved out, and a
+                // bitwise copy would share e.g. the buffer of a `Vec` with the
+                // argument, so we snapshot a clone.
+                if let Expr::Unary(syn::ExprUnary { op: syn::UnOp::Deref(_), .. }) = expr {
+                    syn::parse_quote!(::core::clone::Clone::clone(&#expr))
+                } else {
+                    expr.clone()
+                }
+            }
+            2 if is_call_to("old_with") => {
+                let (expr, snapshot_fn) = (&call.args[0], &call.args[1]);
+                self.check_no_locals("old_with", expr);
+                syn::parse_quote!((#snapshot_fn)(#expr))
+            }
+            _ => return,
+        };
+        let name =
+            Ident::new(&format!("old_with_snapshot_{}", self.snapshots.len()), Span::mixed_site());
+        self.snapshots.push((name.clone(), snapshot));
+        *e = syn::parse_quote!(#name);
+    }
+
+    fn visit_block_mut(&mut self, i: &mut syn::Block) {
+        let scope = self.locals.len();
+        syn::visit_mut::visit_block_mut(self, i);
+        self.locals.truncate(scope);
+    }
+
+    /// The variables of a `let` are only in scope after its initializer.
+    fn visit_local_mut(&mut self, i: &mut syn::Local) {
+        if let Some(init) = &mut i.init {
+            self.visit_expr_mut(&mut init.expr);
+            if let Some((_, diverge)) = &mut init.diverge {
+                self.visit_expr_mut(diverge);
+            }
+        }
+        self.bind(&i.pat);
+    }
+
+    fn visit_expr_closure_mut(&mut self, i: &mut syn::ExprClosure) {
+        let scope = self.locals.len();
+        i.inputs.iter().for_each(|pat| self.bind(pat));
+        self.visit_expr_mut(&mut i.body);
+        self.locals.truncate(scope);
+    }
+
+    fn visit_arm_mut(&mut self, i: &mut syn::Arm) {
+        let scope = self.locals.len();
+        self.bind(&i.pat);
+        syn::visit_mut::visit_arm_mut(self, i);
+        self.locals.truncate(scope);
+    }
+
+    fn visit_expr_for_loop_mut(&mut self, i: &mut syn::ExprForLoop) {
+        self.visit_expr_mut(&mut i.expr);
+        let scope = self.locals.len();
+        self.bind(&i.pat);
+        self.visit_block_mut(&mut i.body);
+        self.locals.truncate(scope);
+    }
+
+    /// Conservatively keeps the variables of an `if let` or `while let` in
+    /// scope until the end of the enclosing block.
+    fn visit_expr_let_mut(&mut self, i: &mut syn::ExprLet) {
+        self.visit_expr_mut(&mut i.expr);
+        self.bind(&i.pat);
+    }
+}
+
+/// Replaces every `call_count(f)` (or `kani::call_count(f)`) call in a
+/// postcondition with a read of a fresh counter variable and records the
+/// counter together with the argument `f` whose invocations it counts.
+///
+/// Calls whose argument is not a plain identifier are left untouched, which
+/// results in a name resolution error at the use site.
+#[derive(Default)]
+struct CallCountLifter {
+    counters: Vec<(Ident, Ident)>,
+}
+
+impl VisitMut for CallCountLifter {
+    fn visit_expr_mut(&mut self, e: &mut Expr) {
+        syn::visit_mut::visit_expr_mut(self, e);
+        let Expr::Call(call) = e else { return };
+        let Expr::Path(func) = call.func.as_ref() else { return };
+        if call.args.len() != 1
+            || !(matches_path(&func.path, &["call_count"])
+                || matches_path(&func.path, &["kani", "call_count"]))
+        {
+            return;
+        }
+        let Expr::Path(arg) = &call.args[0] else { return };
+        let Some(argument) = arg.path.get_ident() else { return };
+        let counter = Ident::new(
+            &format!("call_count_{argument}_{}", self.counters.len()),
+            Span::mixed_site(),
+        );
+        self.counters.push((counter.clone(), argument.clone()));
+        *e = syn::parse_quote!(#counter.get());
+    }
+}
+
+/// A closure argument of the contracted function whose invocations are
+/// counted for a `call_count` in a postcondition.
+struct CallCounter {
+    /// The `Cell<usize>` that holds the number of invocations.
+    counter: Ident,
+    /// The argument whose invocations are counted.
+    argument: Ident,
+    /// The argument types from the `Fn` or `FnMut` bound of `argument`.
+    inputs: Vec<syn::Type>,
+}
+
+impl CallCounter {
+    /// Look up the `Fn` or `FnMut` bound of `argument` in `sig`. The argument
+    /// must either have an `impl Fn(..)` type or a type parameter that is
+    /// bounded (inline or in the `where` clause) by one of these traits.
+    fn new(sig: &Signature, counter: Ident, argument: Ident) -> Result<Self, syn::Error> {
+        let ty = sig.inputs.iter().find_map(|arg| match arg {
+            FnArg::Typed(syn::PatType { pat, ty, .. }) => match pat.as_ref() {
+                syn::Pat::Ident(pat) if pat.ident == argument => Some(ty.as_ref()),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        });
+        let inputs = match ty {
+            Some(syn::Type::ImplTrait(impl_trait)) => closure_inputs(&impl_trait.bounds),
+            Some(syn::Type::Path(syn::TypePath { qself: None, path })) => {
+                path.get_ident().and_then(|ty_param| {
+                    let inline = sig
+                        .generics
+                        .type_params()
+                        .filter(|param| param.ident == *ty_param)
+                        .find_map(|param| closure_inputs(&param.bounds));
+                    let in_where_clause = || {
+                        sig.generics.where_clause.iter().flat_map(|w| &w.predicates).find_map(
+                            |predicate| match predicate {
+                                syn::WherePredicate::Type(PredicateType {
+                                    bounded_ty: syn::Type::Path(bounded),
+                                    bounds,
+                                    ..
+                                }) if bounded.path.is_ident(ty_param) => closure_inputs(bounds),
+                                _ => None,
+                            },
+                        )
+                    };
+                    inline.or_else(in_where_clause)
+                })
+            }
+            _ => None,
+        };
+        let Some(inputs) = inputs else {
+            return Err(syn::Error::new(
+                argument.span(),
+                format!(
+                    "`call_count` requires `{argument}` to be an argument with an `Fn` or `FnMut` bound"
+                ),
+            ));
+        };
+        Ok(CallCounter { counter, argument, inputs })
+    }
+
+    /// Create the counter and shadow the argument with a closure that bumps
+    /// the counter before forwarding to the original closure.
+    fn make_counting_closure(&self) -> TokenStream2 {
+        let Self { counter, argument, inputs } = self;
+        let params = (0..inputs.len())
+            .map(|i| Ident::new(&format!("call_count_arg_{i}"), Span::mixed_site()))
+            .collect::<Vec<_>>();
+        let counter_ref = Ident::new("call_count_ref", Span::mixed_site());
+        quote!(
+            let #counter = std::cell::Cell::new(0usize);
+            let #argument = {
+                let #counter_ref = &#counter;
+                #[allow(unused_mut)]
+                let mut #argument = #argument;
+                move |#(#params: #inputs),*| {
+                    #counter_ref.set(#counter_ref.get() + 1);
+                    #argument(#(#params),*)
+                }
+            };
+        )
+    }
+
+    /// In a replacement the closure is never called, so the count is
+    /// nondeterministic and only constrained by the postcondition.
+    fn make_havocked_count(&self) -> TokenStream2 {
+        let counter = &self.counter;
+        quote!(let #counter = std::cell::Cell::new(kani::any::<usize>());)
+    }
+}
+
+/// Parse the condition of a `requires` or `ensures` clause. Implications
+/// `a ==> b` and quantifier ranges `|i in a..b|` are rewritten first (see
+/// [`rewrite_implications`]) and the `kani::forall` and `kani::exists`
+/// quantifiers are then lowered (see [`QuantifierLowerer`]).
+fn parse_condition(attr: TokenStream2) -> Result<Expr, syn::Error> {
+    let mut attr: Expr = syn::parse2(rewrite_implications(attr))?;
+    let mut lowerer = QuantifierLowerer::default();
+    lowerer.visit_expr_mut(&mut attr);
+    lowerer.error.map_or(Ok(attr), Err)
+}
+
+/// Rewrite every implication `a ==> b`, which is not Rust syntax, into
+/// `!(a) || (b)`.
+///
+/// An implication binds weaker than any Rust operator and associates to the
+/// right. It may appear in any delimited group, where it extends to the
+/// surrounding commas or semicolons, in the body of a closure with a single
+/// parameter, e.g. `kani::forall(|i| i < 8 ==> arr[i] == 0)`, and in the body
+/// of a match arm, e.g. `Some(x) => x > 0 ==> p`.
+///
+/// A closure over a range, `|i in a..b| body`, which is not Rust syntax either,
+/// is rewritten into the two arguments `a..b, |i| body` of a quantifier.
+fn rewrite_implications(stream: TokenStream2) -> TokenStream2 {
+    use proc_macro2::{Group, TokenTree};
+    let is_separator =
+        |t: &TokenTree| matches!(t, TokenTree::Punct(p) if matches!(p.as_char(), ',' | ';'));
+    let tokens: Vec<TokenTree> = stream
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Group(group) => {
+                let mut rewritten =
+                    Group::new(group.delimiter(), rewrite_implications(group.stream()));
+                rewritten.set_span(group.span());
+                TokenTree::Group(rewritten)
+            }
+            tt => tt,
+        })
+        .collect();
+    tokens
+        .split_inclusive(is_separator)
+        .flat_map(|chunk| match chunk.split_last() {
+            Some((separator, operands)) if is_separator(separator) => {
+                let mut rewritten = rewrite_implication_chain(operands);
+                rewritten.extend([separator.clone()]);
+                rewritten
+            }
+            _ => rewrite_implication_chain(chunk),
+        })
+        .collect()
+}
+
+/// Rewrite `a ==> b ==> c` into `!(a) || (!(b) || (c))`, keeping the pattern
+/// of a match arm and the parameters of a closure, if `tokens` has them, in
+/// front.
+fn rewrite_implication_chain(tokens: &[proc_macro2::TokenTree]) -> TokenStream2 {
+    use proc_macro2::{Spacing, TokenTree};
+    let is_bar = |t: &TokenTree| matches!(t, TokenTree::Punct(p) if p.as_char() == '|');
+    let is_implication = |t: &[TokenTree]| {
+        matches!(t, [TokenTree::Punct(a), TokenTree::Punct(b), TokenTree::Punct(c), ..]
+            if a.as_char() == '=' && a.spacing() == Spacing::Joint
+                && b.as_char() == '=' && b.spacing() == Spacing::Joint
+                && c.as_char() == '>')
+    };
+    let is_fat_arrow = |t: &[TokenTree]| {
+        matches!(t, [TokenTree::Punct(a), TokenTree::Punct(b), ..]
+            if a.as_char() == '=' && a.spacing() == Spacing::Joint && b.as_char() == '>')
+    };
+    // The `=>` of a match arm comes before any implication in its body, and the
+    // `=>` inside an implication `==>` is never reached.
+    let arm_len = (0..tokens.len())
+        .take_while(|idx| !is_implication(&tokens[*idx..]))
+        .find(|idx| is_fat_arrow(&tokens[*idx..]))
+        .map_or(0, |idx| idx + 2);
+    let (arm, tokens) = tokens.split_at(arm_len);
+    let params_len = match tokens {
+        [TokenTree::Ident(mv), rest @ ..] if mv == "move" => {
+            rest.iter().skip(1).position(is_bar).map_or(0, |end| end + 3)
+        }
+        [bar, rest @ ..] if is_bar(bar) => rest.iter().position(is_bar).map_or(0, |end| end + 2),
+        _ => 0,
+    };
+    let (params, body) = tokens.split_at(params_len);
+    let mut operands = vec![];
+    let (mut start, mut idx) = (0, 0);
+    while idx < body.len() {
+        if is_implication(&body[idx..]) {
+            operands.push(&body[start..idx]);
+            idx += 3;
+            start = idx;
+        } else {
+            idx += 1;
+        }
+    }
+    let conclusion: TokenStream2 = body[start..].iter().cloned().collect();
+    let implication = operands.into_iter().rev().fold(conclusion, |conclusion, premise| {
+        let premise: TokenStream2 = premise.iter().cloned().collect();
+        if premise.is_empty() { conclusion } else { quote!(!(#premise) || (#conclusion)) }
+    });
+    match params {
+        [open, TokenTree::Ident(var), TokenTree::Ident(kw_in), range @ .., close]
+            if is_bar(open) && kw_in == "in" && !range.is_empty() =>
+        {
+            quote!(#(#arm)* #(#range)*, #open #var #close #implication)
+        }
+        _ => quote!(#(#arm)* #(#params)* #implication),
+    }
+}
+
+/// Lowers the quantifiers `kani::forall` and `kani::exists` over a range of
+/// integers into plain Rust expressions, without relying on quantifier support
+/// in the solver. Functions of the same name that are called without the
+/// `kani::` prefix are left alone.
+///
+/// A quantifier either takes a closure whose range is written out,
+/// `|i in a..b| p` or `|i in a..=b| p`, or, only for `forall`, a closure of the
+/// form `|i| i < N ==> p` (or `i <= N`), which ranges from `0`. If both ends of
+/// the range are integer literals and the range has at most
+/// [`MAX_UNROLLED_TERMS`] elements, the quantifier is unrolled into the
+/// conjunction (`forall`) or disjunction (`exists`) of `p` for every `i` in the
+/// range. Otherwise it becomes a loop over the range, e.g.
+/// `(a..b).all(|i| p)`, whose unwinding bound must cover the length of the
+/// range.
+///
+/// A `forall` without a bound, `|i: T| p`, becomes `{ let i: T = kani::any(); p }`.
+/// Where the condition is asserted this checks `p` for every `i`. Where it is
+/// assumed, only `p` for one arbitrary `i` is assumed, which is sound but may
+/// lead to spurious failures. Any other use of a quantifier is reported as an
+/// error.
+#[derive(Default)]
+struct QuantifierLowerer {
+    error: Option<syn::Error>,
+}
+
+impl VisitMut for QuantifierLowerer {
+    fn visit_expr_mut(&mut self, e: &mut Expr) {
+        syn::visit_mut::visit_expr_mut(self, e);
+        let Expr::Call(call) = e else { return };
+        let Expr::Path(func) = call.func.as_ref() else { return };
+        let quantifier = [Quantifier::Forall, Quantifier::Exists]
+            .into_iter()
+            .find(|q| matches_path(&func.path, &["kani", q.name()]));
+        let Some(quantifier) = quantifier else { return };
+        match lower_quantifier(quantifier, call) {
+            Ok(lowered) => *e = lowered,
+            Err(error) => match &mut self.error {
+                Some(errors) => errors.combine(error),
+                None => self.error = Some(error),
+            },
+        }
+    }
+}
+
+/// The largest range over integer literals that a quantifier is unrolled for.
+/// A quantifier over a larger range becomes a loop, so that the size of the
+/// generated code stays bounded.
+const MAX_UNROLLED_TERMS: u64 = 64;
+
+#[derive(Clone, Copy)]
+enum Quantifier {
+    Forall,
+    Exists,
+}
+
+impl Quantifier {
+    fn name(self) -> &'static str {
+        match self {
+            Quantifier::Forall => "forall",
+            Quantifier::Exists => "exists",
+        }
+    }
+}
+
+/// Lower a single quantifier call, see [`QuantifierLowerer`]. A range written
+/// out in the closure has already been moved into a separate first argument,
+/// and an implication in the body has been rewritten to `!(i < N) || (p)`.
+fn lower_quantifier(quantifier: Quantifier, call: &syn::ExprCall) -> Result<Expr, syn::Error> {
+    let name = quantifier.name();
+    let unsupported = || {
+        let forms = match quantifier {
+            Quantifier::Forall => "`|i in a..b| ...`, `|i| i < N ==> ...` or `|i: T| ...`",
+            Quantifier::Exists => "`|i in a..b| ...`",
+        };
+        syn::Error::new(
+            call.span(),
+            format!("`kani::{name}` expects a closure of the form {forms}"),
+        )
+    };
+    let (range, closure) = match (call.args.first(), call.args.get(1), call.args.len()) {
+        (Some(Expr::Range(range)), Some(Expr::Closure(closure)), 2) => (Some(range), closure),
+        (Some(Expr::Closure(closure)), None, 1) => (None, closure),
+        _ => return Err(unsupported()),
+    };
+    let mut inputs = closure.inputs.iter();
+    let (Some(var), None) = (inputs.next(), inputs.next()) else {
+        return Err(unsupported());
+    };
+    let (start, end, inclusive, body) = match range {
+        Some(syn::ExprRange { start: Some(start), limits, end: Some(end), .. }) => {
+            let inclusive = matches!(limits, syn::RangeLimits::Closed(_));
+            (start.as_ref().clone(), end.as_ref().clone(), inclusive, closure.body.as_ref())
+        }
+        Some(_) => return Err(unsupported()),
+        None if matches!(quantifier, Quantifier::Forall) => {
+            match split_forall_bound(var, &closure.body) {
+                Some((bound, inclusive, body)) => {
+                    (syn::parse_quote!(0), bound.clone(), inclusive, body)
+                }
+                // Without a bound, the variable is havocked instead.
+                None if matches!(var, syn::Pat::Type(_)) => {
+                    let body = &closure.body;
+                    return Ok(syn::parse_quote!({ let #var = kani::any(); #body }));
+                }
+                None => return Err(unsupported()),
+            }
+        }
+        None => return Err(unsupported()),
+    };
+    let literal = |e: &Expr| match e {
+        Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) => Some(lit.base10_parse::<u64>()),
+        _ => None,
+    };
+    let unrolled = match (literal(&start), literal(&end)) {
+        (Some(lo), Some(hi)) => {
+            let (lo, hi) = (lo?, hi?);
+            // An inclusive range up to `u64::MAX` has no exclusive end.
+            let hi = if inclusive { hi.checked_add(1) } else { Some(hi) };
+            hi.filter(|hi| hi.saturating_sub(lo) <= MAX_UNROLLED_TERMS).map(|hi| lo..hi)
+        }
+        _ => None,
+    };
+    if let Some(values) = unrolled {
+        let terms = values.map(|value| {
+            let value = syn::LitInt::new(&value.to_string(), call.span());
+            quote!({ let #var = #value; #body })
+        });
+        return Ok(match quantifier {
+            Quantifier::Forall => syn::parse_quote!((true #(&& #terms)*)),
+            Quantifier::Exists => syn::parse_quote!((false #(|| #terms)*)),
+        });
+    }
+    let range = if inclusive { quote!(#start..=#end) } else { quote!(#start..#end) };
+    let method = match quantifier {
+        Quantifier::Forall => quote!(all),
+        Quantifier::Exists => quote!(any),
+    };
+    Ok(syn::parse_quote!(::core::iter::Iterator::#method(&mut (#range), |#var| #body)))
+}
+
+/// Split the body `!(i < N) || (p)` of a `forall` closure over the variable
+/// `var` into the bound `N`, whether it is inclusive (`i <= N`), and `p`.
+fn split_forall_bound<'a>(var: &syn::Pat, body: &'a Expr) -> Option<(&'a Expr, bool, &'a Expr)> {
+    let var_ident = match var {
+        syn::Pat::Ident(pat) => &pat.ident,
+        syn::Pat::Type(syn::PatType { pat, .. }) => match pat.as_ref() {
+            syn::Pat::Ident(pat) => &pat.ident,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let Expr::Binary(syn::ExprBinary { left, op: syn::BinOp::Or(_), right, .. }) = body else {
+        return None;
+    };
+    let Expr::Unary(syn::ExprUnary { op: syn::UnOp::Not(_), expr: range, .. }) = left.as_ref()
+    else {
+        return None;
+    };
+    let (Expr::Paren(range), Expr::Paren(body)) = (range.as_ref(), right.as_ref()) else {
+        return None;
+    };
+    let Expr::Binary(syn::ExprBinary { left: index, op, right: bound, .. }) = range.expr.as_ref()
+    else {
+        return None;
+    };
+    let Expr::Path(index) = index.as_ref() else { return None };
+    if !index.path.is_ident(var_ident) {
+        return None;
+    }
+    let inclusive = match op {
+        syn::BinOp::Lt(_) => false,
+        syn::BinOp::Le(_) => true,
+        _ => return None,
+    };
+    Some((bound.as_ref(), inclusive, &body.expr))
+}
+
+/// The argument types of the first `Fn(..)` or `FnMut(..)` bound in `bounds`.
+fn closure_inputs<'a>(
+    bounds: impl IntoIterator<Item = &'a TypeParamBound>,
+) -> Option<Vec<syn::Type>> {
+    bounds.into_iter().find_map(|bound| {
+        let TypeParamBound::Trait(TraitBound { path, .. }) = bound else { return None };
+        let segment = path.segments.last()?;
+        let syn::PathArguments::Parenthesized(args) = &segment.arguments else { return None };
+        (segment.ident == "Fn" || segment.ident == "FnMut")
+            .then(|| args.inputs.iter().cloned().collect())
+    })
+}
+
 /// Does the provided path have the same chain of identifiers as `mtch` (match)
 /// and no arguments anywhere?
 ///
@@ -1495,6 +3439,609 @@ fn self_by_mention(u: usize) {
         );
     }
 
+    #[test]
+    fn detect_impl_trait_return() {
+        let returns = |sig: &str| {
+            let sig: syn::Signature = syn::parse_str(sig).unwrap();
+            super::returns_impl_trait(&sig.output)
+        };
+        assert!(returns("fn f() -> impl Iterator<Item = u32>"));
+        assert!(returns("fn f() -> Option<impl Fn(u8) -> bool>"));
+        assert!(!returns("fn f(x: impl Copy) -> u32"));
+        assert!(!returns("fn f()"));
+    }
+
+    #[test]
+    fn detect_replace_return_havoc() {
+        let is_havoc = |stmt: &str| super::is_replace_return_havoc(&syn::parse_str(stmt).unwrap());
+        assert!(is_havoc("let result : u32 = kani::any();"));
+        assert!(is_havoc(
+            "#[kanitool::is_contract_generated(check)] let result : _ = f_check_1f(x);"
+        ));
+        assert!(!is_havoc("let result : _ = f_check_1f(x);"));
+        assert!(!is_havoc("let result : u32 = kani::any_where(|x| *x > 0);"));
+    }
+
+    /// Rename the arguments of `sig` in `expr` and check that the result is `expected`.
+    fn check_renamed(sig: &str, expr: &str, expected: &str) {
+        use quote::ToTokens;
+        let sig: syn::Signature = syn::parse_str(sig).unwrap();
+        let mut expr: syn::Expr = syn::parse_str(expr).unwrap();
+        super::rename_argument_occurrences(&sig, &mut expr);
+        let expected: syn::Expr = syn::parse_str(expected).unwrap();
+        assert_eq!(expr.to_token_stream().to_string(), expected.to_token_stream().to_string());
+    }
+
+    #[test]
+    fn rename_in_bitwise_and_shift_ops() {
+        check_renamed("fn f(x: u32)", "(result & 1) == 0", "(result & 1) == 0");
+        check_renamed("fn f(x: u32)", "x >> 2 < 10", "x_renamed >> 2 < 10");
+        check_renamed(
+            "fn f(x: u32, y: u32)",
+            "x << y | x ^ y",
+            "x_renamed << y_renamed | x_renamed ^ y_renamed",
+        );
+        check_renamed(
+            "fn f(x: u32)",
+            "Vec::<Vec<u8>>::new().len() >> x == 0",
+            "Vec::<Vec<u8>>::new().len() >> x_renamed == 0",
+        );
+    }
+
+    #[test]
+    fn rename_leaves_intrinsics_untouched() {
+        check_renamed(
+            "fn f(input: &[u8])",
+            "!kani::aliases(result, input)",
+            "!kani::aliases(result, input_renamed)",
+        );
+        check_renamed(
+            "fn f(aliases: &u8)",
+            "kani::aliases(aliases, result)",
+            "kani::aliases(aliases_renamed, result)",
+        );
+    }
+
+    #[test]
+    fn respan_only_result_binding() {
+        use syn::visit_mut::VisitMut;
+        let respanned = |expr: &str| {
+            let mut expr: syn::Expr = syn::parse_str(expr).unwrap();
+            let mut respanner = super::ResultRespanner::default();
+            respanner.visit_expr_mut(&mut expr);
+            respanner.uses.len()
+        };
+        assert_eq!(respanned("result > x && result < y"), 2);
+        assert_eq!(respanned("{ let result = result + 1; result > 0 }"), 1);
+        assert_eq!(respanned("v.iter().all(|result| *result > 0) && result"), 1);
+        assert_eq!(respanned("match x { Some(result) => result > 0, None => result }"), 1);
+        assert_eq!(respanned("{ if let Ok(result) = result { result > 0 } else { true } }"), 1);
+    }
+
+    #[test]
+    fn lift_old_with_calls() {
+        use quote::ToTokens;
+        use syn::visit_mut::VisitMut;
+        let mut expr: syn::Expr =
+            syn::parse_str("result == old_with(v, |v: &Vec<u8>| v.len()) + kani::old_with(x, f)")
+                .unwrap();
+        let mut lifter = super::OldWithLifter::default();
+        lifter.visit_expr_mut(&mut expr);
+        let to_string = |e: &syn::Expr| e.to_token_stream().to_string();
+        let parse = |s: &str| to_string(&syn::parse_str(s).unwrap());
+        let snapshots = lifter
+            .snapshots
+            .iter()
+            .map(|(name, value)| (name.to_string(), to_string(value)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            snapshots,
+            [
+                ("old_with_snapshot_0".to_string(), parse("(|v: &Vec<u8>| v.len())(v)")),
+                ("old_with_snapshot_1".to_string(), parse("(f)(x)")),
+            ]
+        );
+        assert_eq!(to_string(&expr), parse("result == old_with_snapshot_0 + old_with_snapshot_1"));
+    }
+
+    #[test]
+    fn lift_old_calls() {
+        use quote::ToTokens;
+        use syn::visit_mut::VisitMut;
+        let mut expr: syn::Expr = syn::parse_str(
+            "!old(kani::is_sorted(&v)) || kani::old(v.len()) == 0 || old(a, b) || old(*v) == *v",
+        )
+        .unwrap();
+        let mut lifter = super::OldWithLifter::default();
+        lifter.visit_expr_mut(&mut expr);
+        let to_string = |e: &syn::Expr| e.to_token_stream().to_string();
+        let parse = |s: &str| to_string(&syn::parse_str(s).unwrap());
+        let snapshots = lifter
+            .snapshots
+            .iter()
+            .map(|(name, value)| (name.to_string(), to_string(value)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            snapshots,
+            [
+                ("old_with_snapshot_0".to_string(), parse("kani::is_sorted(&v)")),
+                ("old_with_snapshot_1".to_string(), parse("v.len()")),
+                ("old_with_snapshot_2".to_string(), parse("::core::clone::Clone::clone(&*v)")),
+            ]
+        );
+        // A call with the wrong number of arguments is left alone.
+        assert_eq!(
+            to_string(&expr),
+            parse(
+                "!old_with_snapshot_0 || old_with_snapshot_1 == 0 || old(a, b) \
+                || old_with_snapshot_2 == *v"
+            )
+        );
+    }
+
+    #[test]
+    fn rewrite_result_calls_after_renaming() {
+        use quote::ToTokens;
+        use syn::visit_mut::VisitMut;
+        let sig: syn::Signature = syn::parse_str("fn f(result: u32, x: u32) -> u32").unwrap();
+        let mut expr: syn::Expr = syn::parse_str("kani::result() > result + x").unwrap();
+        super::rename_argument_occurrences(&sig, &mut expr);
+        super::ResultCallRewriter.visit_expr_mut(&mut expr);
+        let expected: syn::Expr = syn::parse_str("result > result_renamed + x_renamed").unwrap();
+        assert_eq!(expr.to_token_stream().to_string(), expected.to_token_stream().to_string());
+    }
+
+    #[test]
+    fn lift_old_method_calls_on_self() {
+        use quote::ToTokens;
+        use syn::visit_mut::VisitMut;
+        let mut expr: syn::Expr = syn::parse_str(
+            "old(self_renamed.get(id_renamed)) == old(self_renamed.contains(0)) \
+            && old(other.get(id_renamed)) && old(self_renamed.items.len()) > 0",
+        )
+        .unwrap();
+        let mut lifter = super::OldWithLifter::default();
+        lifter.visit_expr_mut(&mut expr);
+        let to_string = |e: &syn::Expr| e.to_token_stream().to_string();
+        let parse = |s: &str| to_string(&syn::parse_str(s).unwrap());
+        let snapshots = lifter
+            .snapshots
+            .iter()
+            .map(|(name, value)| (name.to_string(), to_string(value)))
+            .collect::<Vec<_>>();
+        // Only the results of the method calls are snapshotted, `self` is not cloned.
+        assert_eq!(
+            snapshots,
+            [
+                ("old_with_snapshot_0".to_string(), parse("self_renamed.get(id_renamed)")),
+                ("old_with_snapshot_1".to_string(), parse("self_renamed.contains(0)")),
+                ("old_with_snapshot_2".to_string(), parse("other.get(id_renamed)")),
+                ("old_with_snapshot_3".to_string(), parse("self_renamed.items.len()")),
+            ]
+        );
+        assert_eq!(
+            to_string(&expr),
+            parse(
+                "old_with_snapshot_0 == old_with_snapshot_1 && old_with_snapshot_2 \
+                && old_with_snapshot_3 > 0"
+            )
+        );
+    }
+
+    #[test]
+    fn lift_call_count_calls() {
+        use quote::ToTokens;
+        use syn::visit_mut::VisitMut;
+        let sig: syn::Signature = syn::parse_str(
+            "fn apply<F>(n: u8, f: impl Fn(u8) -> u8, g: F) where F: FnMut(&u8, u16)",
+        )
+        .unwrap();
+        let mut expr: syn::Expr =
+            syn::parse_str("call_count(f) == n as usize && kani::call_count(g) <= 1").unwrap();
+        let mut lifter = super::CallCountLifter::default();
+        lifter.visit_expr_mut(&mut expr);
+        let to_string = |e: &dyn ToTokens| e.to_token_stream().to_string();
+        let parse = |s: &str| to_string(&syn::parse_str::<syn::Expr>(s).unwrap());
+        assert_eq!(
+            to_string(&expr),
+            parse("call_count_f_0.get() == n as usize && call_count_g_1.get() <= 1")
+        );
+        let inputs = lifter
+            .counters
+            .into_iter()
+            .map(|(counter, argument)| {
+                let counter = super::CallCounter::new(&sig, counter, argument).unwrap();
+                counter.inputs.iter().map(|ty| to_string(ty)).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(inputs, [vec!["u8".to_string()], vec!["& u8".to_string(), "u16".to_string()]]);
+        let n = syn::Ident::new("n", proc_macro2::Span::call_site());
+        assert!(super::CallCounter::new(&sig, n.clone(), n).is_err());
+    }
+
+    #[test]
+    fn rename_leaves_consts_untouched() {
+        check_renamed(
+            "fn get<const N: usize>(arr: [u8; N], idx: usize) -> u8",
+            "idx < N && core::mem::size_of::<[u8; N]>() == N",
+            "idx_renamed < N && core::mem::size_of::<[u8; N]>() == N",
+        );
+        check_renamed(
+            "fn push(&mut self, cap: usize)",
+            "self.len < Self::CAP && cap <= <Self as Buffer>::CAP",
+            "self_renamed.len < Self::CAP && cap_renamed <= <Self as Buffer>::CAP",
+        );
+        // A binding inside the length of an array type is not an argument.
+        check_renamed(
+            "fn f(x: [u8; { let len = 4; len }])",
+            "x.len() == len",
+            "x_renamed.len() == len",
+        );
+    }
+
+    #[test]
+    fn rename_in_block_respects_local_bindings() {
+        check_renamed(
+            "fn f(v: &[u8])",
+            "{ let n = v.len(); let v = n + 1; v > n }",
+            "{ let n = v_renamed.len(); let v_renamed = n + 1; v_renamed > n }",
+        );
+    }
+
+    #[test]
+    fn bind_closure_results() {
+        let bind = |sig: &str, attr: &str| {
+            let sig: syn::Signature = syn::parse_str(sig).unwrap();
+            super::bind_closure_result(&sig, syn::parse_str(attr).unwrap())
+                .map_err(|err| err.to_string())
+        };
+        let block = |attr: &str| syn::parse_str::<syn::Expr>(attr).unwrap();
+        let sig = "fn f(x: u32) -> (u32, u32)";
+        assert_eq!(
+            bind(sig, "|(a, b)| a < b"),
+            Ok(block("{ let (a, b) = &kani::result(); a < b }"))
+        );
+        assert_eq!(
+            bind(sig, "|(a, ..)| *a == x"),
+            Ok(block("{ let (a, ..) = &kani::result(); *a == x }"))
+        );
+        assert_eq!(bind(sig, "result.0 < result.1"), Ok(block("result.0 < result.1")));
+        assert_eq!(
+            bind("fn f() -> u32", "|r: &u32| *r > 0"),
+            Ok(block("{ let r: &u32 = &kani::result(); *r > 0 }"))
+        );
+        assert_eq!(
+            bind(sig, "|(a, b, c)| a < b"),
+            Err("this pattern binds a tuple of 3 values, but the function returns a tuple of 2 \
+                values"
+                .to_string())
+        );
+        assert!(bind(sig, "|a, b| a < b").is_err());
+        assert!(bind(sig, "|| true").is_err());
+    }
+
+    #[test]
+    fn detect_strided_indices() {
+        let strided = |target: &str| {
+            super::strided_index(target.parse().unwrap()).map(|index| index.to_string())
+        };
+        assert_eq!(strided("&mut buf[0..8; 2]").as_deref(), Some("[0 .. 8 ; 2]"));
+        assert_eq!(strided("&mut (*self).data[1..n;step]").as_deref(), Some("[1 .. n ; step]"));
+        assert_eq!(strided("&mut bufs[0][0..4; 2]").as_deref(), Some("[0 .. 4 ; 2]"));
+        assert_eq!(strided("&mut buf[0..8]"), None);
+        assert_eq!(strided("&mut [0u8; 4]"), None);
+        assert_eq!(strided("{ let s = [0u8; 4]; s }"), None);
+    }
+
+    #[test]
+    fn expand_contract_assertions() {
+        let assertion = |tokens: &str| {
+            super::contract_assertion(tokens.parse().unwrap())
+                .map(|tokens| syn::parse2::<syn::Expr>(tokens).unwrap())
+                .map_err(|err| err.to_string())
+        };
+        let expected = |expr: &str| Ok(syn::parse_str::<syn::Expr>(expr).unwrap());
+        assert_eq!(
+            assertion("half(x)"),
+            expected(
+                r#"{
+                    let copy0 = kani::internal::untracked_deref(&(x));
+                    let copy1 = kani::internal::untracked_deref(&(result));
+                    #[allow(unused_unsafe)]
+                    let holds = unsafe { half_kani_postcondition(copy0, copy1) };
+                    kani::assert(holds, concat!("contract of `", stringify!(half), "` holds"))
+                }"#
+            )
+        );
+        assert_eq!(
+            assertion("m::Pair::sorted(&p, 1), sorted"),
+            expected(
+                r#"{
+                    let copy0 = kani::internal::untracked_deref(&(&p));
+                    let copy1 = kani::internal::untracked_deref(&(1));
+                    let copy2 = kani::internal::untracked_deref(&(sorted));
+                    #[allow(unused_unsafe)]
+                    let holds = unsafe {
+                        m::Pair::sorted_kani_postcondition(copy0, copy1, copy2)
+                    };
+                    kani::assert(
+                        holds,
+                        concat!("contract of `", stringify!(m::Pair::sorted), "` holds")
+                    )
+                }"#
+            )
+        );
+        assert!(assertion("half").is_err());
+        assert!(assertion("half(x), result, 1").is_err());
+    }
+
+    #[test]
+    fn replacement_client_arguments() {
+        let arguments = |function: &str| {
+            super::replacement_client_arguments(&syn::parse_str(function).unwrap()).map(
+                |(bindings, args)| {
+                    let bindings =
+                        bindings.into_iter().map(|binding| syn::parse2(binding).unwrap());
+                    (bindings.collect::<Vec<syn::Stmt>>(), args)
+                },
+            )
+        };
+        let (bindings, args) = arguments("fn f(mut x: u32, y: &mut S) {}").unwrap();
+        assert_eq!(args, [syn::parse_str::<syn::Expr>("x").unwrap(), syn::parse_str("y").unwrap()]);
+        assert_eq!(
+            bindings,
+            [
+                syn::parse_str::<syn::Stmt>(
+                    "let mut x: u32 = (&&kani::internal::ClientArgument::<u32>::new()).any_argument();"
+                )
+                .unwrap(),
+                syn::parse_str(
+                    "let y: &mut S = &mut (&&kani::internal::ClientArgument::<S>::new()).any_argument();"
+                )
+                .unwrap(),
+            ]
+        );
+        assert!(arguments("fn f<T>(x: T) {}").is_none());
+        assert!(arguments("fn f(x: impl Copy) {}").is_none());
+        assert!(arguments("fn f(s: &[u8]) {}").is_none());
+        assert!(arguments("fn f((a, b): (u8, u8)) {}").is_none());
+        assert!(arguments("fn f(&self) {}").is_none());
+        assert!(arguments("async fn f() {}").is_none());
+    }
+
+    #[test]
+    fn detect_deref_places() {
+        let is_place = |expr: &str| super::is_deref_place(&syn::parse_str(expr).unwrap());
+        assert!(is_place("*self"));
+        assert!(is_place("(*u).field_a"));
+        assert!(is_place("(*self.inner).data.len"));
+        assert!(!is_place("self.target"));
+        assert!(!is_place("&mut (*u).field_a"));
+        assert!(!is_place("(*ptr).as_mut()"));
+    }
+
+    #[test]
+    fn detect_shared_reference_targets() {
+        let sig: syn::Signature =
+            syn::parse_str("fn f(&self, r: &S, m: &mut S, p: *mut S, b: Box<S>)").unwrap();
+        let root = |expr: &str| {
+            super::shared_reference_root(&sig, &syn::parse_str(expr).unwrap())
+                .map(|(param, _)| param.to_string())
+        };
+        assert_eq!(root("r").as_deref(), Some("r"));
+        assert_eq!(root("*r").as_deref(), Some("r"));
+        assert_eq!(root("&r.field").as_deref(), Some("r"));
+        assert_eq!(root("(*self).field").as_deref(), Some("self"));
+        assert_eq!(root("&self.inner.field").as_deref(), Some("self"));
+        assert_eq!(root("m"), None);
+        assert_eq!(root("&m.field"), None);
+        assert_eq!(root("p"), None);
+        assert_eq!(root("b.as_ref()"), None);
+        // The field may be a raw pointer to memory that is not behind `r`.
+        assert_eq!(root("r.ptr"), None);
+        assert_eq!(root("&(*r.ptr).field"), None);
+        assert_eq!(root("&r"), None);
+    }
+
+    #[test]
+    fn detect_shared_smart_pointer_receivers() {
+        let root = |sig: &str, expr: &str| {
+            let sig: syn::Signature = syn::parse_str(sig).unwrap();
+            super::shared_reference_root(&sig, &syn::parse_str(expr).unwrap())
+                .map(|(param, _)| param.to_string())
+        };
+        assert_eq!(root("fn f(self: Rc<Self>)", "&self.field").as_deref(), Some("self"));
+        assert_eq!(root("fn f(self: std::sync::Arc<Self>)", "*self").as_deref(), Some("self"));
+        assert_eq!(root("fn f(self: Pin<&Self>)", "&(*self).field").as_deref(), Some("self"));
+        assert_eq!(root("fn f(self: Pin<Rc<Self>>)", "self").as_deref(), Some("self"));
+        assert_eq!(root("fn f(self: Pin<&mut Self>)", "&self.field"), None);
+        assert_eq!(root("fn f(self: Box<Self>)", "*self"), None);
+    }
+
+    #[test]
+    fn reborrow_smart_pointer_params() {
+        let sig: syn::Signature = syn::parse_str(
+            "fn f(self: Pin<&mut Self>, b: Box<u32>, p: Pin<Box<u32>>, r: &mut u32, s: Pin<&u32>, \
+            c: Rc<u32>)",
+        )
+        .unwrap();
+        let reborrow =
+            |expr: &str| super::is_smart_pointer_param(&sig, &syn::parse_str(expr).unwrap());
+        assert!(reborrow("self"));
+        assert!(reborrow("b"));
+        assert!(reborrow("p"));
+        assert!(reborrow("s"));
+        assert!(reborrow("c"));
+        assert!(!reborrow("r"));
+        assert!(!reborrow("*self"));
+        assert!(!reborrow("self.field"));
+    }
+
+    #[test]
+    fn detect_unused_snapshots() {
+        use syn::visit_mut::VisitMut;
+        let mut expr: syn::Expr = syn::parse_str("old(old(v.len())) == 0 || old(x)").unwrap();
+        let mut lifter = super::OldWithLifter::default();
+        lifter.visit_expr_mut(&mut expr);
+        assert!(super::unused_snapshots(&lifter.snapshots, &expr).is_empty());
+        // Drop the use of `old(x)`.
+        let syn::Expr::Binary(syn::ExprBinary { left, .. }) = &expr else { unreachable!() };
+        let unused = super::unused_snapshots(&lifter.snapshots, left);
+        assert_eq!(unused, [&syn::parse_str::<syn::Expr>("x").unwrap()]);
+        // Discarding a snapshot is not a use.
+        let mut expr: syn::Expr = syn::parse_str("{ let _ = old(x); old(y); result }").unwrap();
+        let mut lifter = super::OldWithLifter::default();
+        lifter.visit_expr_mut(&mut expr);
+        let unused = super::unused_snapshots(&lifter.snapshots, &expr);
+        assert_eq!(unused.len(), 2);
+    }
+
+    #[test]
+    fn old_with_rejects_block_locals() {
+        use syn::visit_mut::VisitMut;
+        let lift = |expr: &str| {
+            let mut expr: syn::Expr = syn::parse_str(expr).unwrap();
+            let mut lifter = super::OldWithLifter::default();
+            lifter.visit_expr_mut(&mut expr);
+            (lifter.snapshots.len(), lifter.error.is_some())
+        };
+        // Snapshots of arguments are fine, even inside a block or with a
+        // closure parameter that is named like a local.
+        assert_eq!(lift("{ let s = old_with(v, |s: &V| s.len()); s == result }"), (1, false));
+        assert_eq!(lift("{ let n = 1; n + old_with(v, f) }"), (1, false));
+        // A local is only in scope after its initializer.
+        assert_eq!(lift("{ let v = old_with(v, f); v }"), (1, false));
+        // Referring to a local is rejected.
+        assert_eq!(lift("{ let n = v.len(); old_with(n, f) == result }"), (1, true));
+        assert_eq!(lift("v.iter().all(|x| old_with(x, f) == 0)"), (1, true));
+        // The scope of the local ends with its block.
+        assert_eq!(lift("{ let m = { let n = 0; n }; m + old_with(n, f) }"), (1, false));
+    }
+
+    #[test]
+    fn hash_ignores_invisible_groups() {
+        use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+        let hash = |stream: TokenStream| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::default();
+            super::hash_of_token_stream(&mut hasher, stream);
+            std::hash::Hasher::finish(&hasher)
+        };
+        let plain: TokenStream = syn::parse_str("fn foo(x: u32) -> u32 { x + 1 }").unwrap();
+        // What `macro_rules!` produces for `fn foo(x: u32) -> u32 { $e }` with `$e:expr = x + 1`.
+        let mut grouped: TokenStream = syn::parse_str("fn foo(x: u32) -> u32").unwrap();
+        let body: TokenStream = syn::parse_str("x + 1").unwrap();
+        grouped.extend([TokenTree::Group(Group::new(
+            Delimiter::Brace,
+            TokenTree::Group(Group::new(Delimiter::None, body)).into(),
+        ))]);
+        assert_eq!(hash(plain.clone()), hash(grouped));
+        assert_eq!(hash(plain.clone()), hash(plain));
+    }
+
+    #[test]
+    fn rewrite_implications() {
+        let rewrite = |s: &str| {
+            let stream: proc_macro2::TokenStream = syn::parse_str(s).unwrap();
+            super::rewrite_implications(stream).to_string()
+        };
+        let parse = |s: &str| s.parse::<proc_macro2::TokenStream>().unwrap().to_string();
+        assert_eq!(rewrite("x > 0 ==> result > 0"), parse("!(x > 0) || (result > 0)"));
+        assert_eq!(rewrite("a ==> b ==> c"), parse("!(a) || (!(b) || (c))"));
+        assert_eq!(
+            rewrite("f(|i| i < 2 ==> a[i] == 0, move |j| j ==> k)"),
+            parse("f(|i| !(i < 2) || (a[i] == 0), move |j| !(j) || (k))")
+        );
+        assert_eq!(rewrite("a == b && c >= d"), parse("a == b && c >= d"));
+        assert_eq!(
+            rewrite("kani::forall(|i in 0..n| i > 0 ==> a[i] != 0)"),
+            parse("kani::forall(0..n, |i| !(i > 0) || (a[i] != 0))")
+        );
+        // The pattern of a match arm is not part of the premise.
+        assert_eq!(
+            rewrite("match x { Some(v) if v > 1 => v < 8 ==> p, None => q ==> r, _ => true }"),
+            parse(
+                "match x { Some(v) if v > 1 => !(v < 8) || (p), None => !(q) || (r), _ => true }"
+            )
+        );
+        assert_eq!(
+            rewrite("match x { Some(v) => |i| i < v ==> p }"),
+            parse("match x { Some(v) => |i| !(i < v) || (p) }")
+        );
+    }
+
+    #[test]
+    fn lower_quantifiers() {
+        use quote::ToTokens;
+        let unroll = |s: &str| {
+            super::parse_condition(s.parse().unwrap())
+                .map(|e| e.to_token_stream().to_string())
+                .map_err(|e| e.to_string())
+        };
+        let parse = |s: &str| syn::parse_str::<syn::Expr>(s).unwrap().to_token_stream().to_string();
+        assert_eq!(
+            unroll("kani::forall(|i| i < 2 ==> arr[i] == 0)"),
+            Ok(parse("(true && { let i = 0; arr[i] == 0 } && { let i = 1; arr[i] == 0 })"))
+        );
+        assert_eq!(
+            unroll("kani::forall(|i: usize| i <= 0 ==> arr[i] == 0) && result"),
+            Ok(parse("(true && { let i: usize = 0; arr[i] == 0 }) && result"))
+        );
+        assert_eq!(
+            unroll("kani::exists(|i in 1..=2| arr[i] == 0)"),
+            Ok(parse("(false || { let i = 1; arr[i] == 0 } || { let i = 2; arr[i] == 0 })"))
+        );
+        // A bound that is not a literal turns the quantifier into a loop.
+        assert_eq!(
+            unroll("kani::forall(|i: usize| i < n ==> arr[i] == 0)"),
+            Ok(parse("::core::iter::Iterator::all(&mut (0..n), |i: usize| arr[i] == 0)"))
+        );
+        assert_eq!(
+            unroll("kani::exists(|i in lo..=hi| arr[i] == 0)"),
+            Ok(parse("::core::iter::Iterator::any(&mut (lo..=hi), |i| arr[i] == 0)"))
+        );
+        // Ranges that are too long to unroll, or whose end overflows, become loops as well.
+        assert_eq!(
+            unroll("kani::forall(|i in 0..65| arr[i] == 0)"),
+            Ok(parse("::core::iter::Iterator::all(&mut (0..65), |i| arr[i] == 0)"))
+        );
+        assert_eq!(
+            unroll("kani::exists(|i in 1..=18446744073709551615| i == 0)"),
+            Ok(parse("::core::iter::Iterator::any(&mut (1..=18446744073709551615), |i| i == 0)"))
+        );
+        // A `forall` without a bound havocs its variable, which needs a type.
+        assert_eq!(
+            unroll("kani::forall(|i: u8| i > x ==> i != y)"),
+            Ok(parse("{ let i: u8 = kani::any(); !(i > x) || (i != y) }"))
+        );
+        assert!(unroll("kani::forall(|i| i != y)").is_err());
+        assert!(unroll("kani::exists(|i: u8| i == y)").is_err());
+        // Only the quantifiers of Kani are lowered.
+        assert_eq!(unroll("forall(|i| i < 2 ==> p)"), Ok(parse("forall(|i| !(i < 2) || (p))")));
+        assert!(unroll("kani::forall(|i| arr[i] == 0)").is_err());
+        assert!(unroll("kani::exists(|i| i < 2 ==> arr[i] == 0)").is_err());
+        assert!(unroll("kani::forall(|i in 0..| arr[i] == 0)").is_err());
+    }
+
+    #[test]
+    fn filter_inherited_attrs() {
+        let attr = |attr: &str| {
+            syn::parse_str::<syn::ItemFn>(&format!("{attr}\nfn f() {{}}")).unwrap().attrs.remove(0)
+        };
+        for inherited in ["#[cfg(feature = \"x\")]", "#[cfg_attr(test, inline)]", "/// Docs"] {
+            assert!(super::is_inherited_attr(&attr(inherited)), "{inherited}");
+        }
+        assert!(super::is_inherited_attr(&attr("#[kani::ensures(|result| *result)]")));
+        assert!(!super::is_inherited_attr(&attr("#[no_mangle]")));
+        assert!(!super::is_inherited_attr(&attr("#[export_name = \"f\"]")));
+
+        assert!(super::is_recursion_wrapper_attr(&attr("#[cfg(feature = \"x\")]")));
+        assert!(super::is_recursion_wrapper_attr(&attr("#[track_caller]")));
+        assert!(!super::is_recursion_wrapper_attr(&attr(
+            "#[cfg_attr(kani, kani::requires(true))]"
+        )));
+        assert!(!super::is_recursion_wrapper_attr(&attr("/// Docs")));
+    }
+
     #[test]
     fn detect_no_impl_fn() {
         detect_impl_fn!(
@@ -0,0 +1,190 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the loop contracts code generation.
+//!
+//! A loop contract is placed on a `while`, `loop` or `for` loop. The invariant
+//! and the assigns targets become closures that are created before the loop,
+//! and the loop guard registers them with a call to
+//! `kani::internal::loop_contract`. The compiler replaces that call by `true`
+//! and attaches the contract to the backedges of the loop, where CBMC's
+//! `--apply-loop-contracts` expects it.
+//!
+//! For instance
+//!
+//! ```ignore
+//! #[kani::loop_contract(invariant = x >= 1, assigns = [x])]
+//! while x > 1 {
+//!     x -= 1;
+//! }
+//! ```
+//!
+//! expands to
+//!
+//! ```ignore
+//! {
+//!     let kani_loop_invariant = || -> bool { x >= 1 };
+//!     let kani_loop_invariant = &kani_loop_invariant as *const _;
+//!     let kani_loop_assigns = || {
+//!         let _ = (&x,);
+//!     };
+//!     let kani_loop_assigns = &kani_loop_assigns as *const _;
+//!     while kani::internal::loop_contract(kani_loop_invariant, kani_loop_assigns) && (x > 1) {
+//!         x -= 1;
+//!     }
+//! }
+//! ```
+//!
+//! The closures are passed by raw pointer so that the borrows they hold of the
+//! variables they capture end before the loop, which may then modify them. The
+//! assigns closure borrows each target, so the places it captures are exactly
+//! the targets.
+//!
+//! A `for` loop is first desugared into a `while` loop over its iterator, which
+//! is added to the assigns targets since every iteration advances it.
+//!
+//! `#[kani::invariant(x >= 1)]` only gives the invariant. It expands in the same
+//! way, except that there is no assigns closure and the loop guard registers the
+//! invariant with `kani::internal::loop_invariant`, so that CBMC infers the
+//! places the loop modifies.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Expr, ExprForLoop, ExprLoop, ExprWhile, Token};
+
+pub fn loop_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let LoopContractArgs { invariant, assigns } = parse_macro_input!(attr as LoopContractArgs);
+    let lp = parse_macro_input!(item as Expr);
+    expand_loop_contract(invariant, Some(assigns), lp)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+pub fn invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let invariant = parse_macro_input!(attr as Expr);
+    let lp = parse_macro_input!(item as Expr);
+    expand_loop_contract(invariant, None, lp).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// The arguments of `#[kani::loop_contract(invariant = ..., assigns = [...])]`.
+struct LoopContractArgs {
+    invariant: Expr,
+    assigns: Vec<Expr>,
+}
+
+impl Parse for LoopContractArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut invariant = None;
+        let mut assigns = None;
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "invariant" if invariant.is_none() => invariant = Some(input.parse()?),
+                "assigns" if assigns.is_none() => {
+                    let targets;
+                    syn::bracketed!(targets in input);
+                    let targets = Punctuated::<Expr, Token![,]>::parse_terminated(&targets)?;
+                    assigns = Some(targets.into_iter().collect());
+                }
+                "invariant" | "assigns" => {
+                    return Err(syn::Error::new(key.span(), format!("duplicate `{key}` argument")));
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("unknown argument `{key}`, expected `invariant` or `assigns`"),
+                    ));
+                }
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        match (invariant, assigns) {
+            (Some(invariant), Some(assigns)) => Ok(LoopContractArgs { invariant, assigns }),
+            (None, _) => Err(input.error("missing `invariant = ...` argument")),
+            (_, None) => Err(input.error("missing `assigns = [...]` argument")),
+        }
+    }
+}
+
+/// Expand a loop contract. If `assigns` is `None`, only the invariant is registered and CBMC
+/// infers the places the loop modifies.
+fn expand_loop_contract(
+    invariant: Expr,
+    assigns: Option<Vec<Expr>>,
+    lp: Expr,
+) -> syn::Result<TokenStream2> {
+    let invariant_ident = Ident::new("kani_loop_invariant", Span::mixed_site());
+    let assigns_ident = Ident::new("kani_loop_assigns", Span::mixed_site());
+    let iter_ident = Ident::new("kani_loop_iter", Span::mixed_site());
+    let register = if assigns.is_some() {
+        quote!(kani::internal::loop_contract(#invariant_ident, #assigns_ident))
+    } else {
+        quote!(kani::internal::loop_invariant(#invariant_ident))
+    };
+    let declare_contract = |extra_target: Option<&Ident>| {
+        let declare_assigns = assigns.as_ref().map(|assigns| {
+            let extra_target = extra_target.map(|target| quote!(&#target,));
+            quote!(
+                let #assigns_ident = || {
+                    let _ = (#extra_target #(&#assigns,)*);
+                };
+                let #assigns_ident = &#assigns_ident as *const _;
+            )
+        });
+        quote!(
+            let #invariant_ident = || -> bool { #invariant };
+            let #invariant_ident = &#invariant_ident as *const _;
+            #declare_assigns
+        )
+    };
+
+    match lp {
+        Expr::While(ExprWhile { cond, .. }) if matches!(*cond, Expr::Let(_)) => Err(
+            syn::Error::new(cond.span(), "loop contracts are not supported on `while let` loops"),
+        ),
+        Expr::While(ExprWhile { attrs, label, cond, body, .. }) => {
+            let contract = declare_contract(None);
+            Ok(quote!({
+                #contract
+                #(#attrs)*
+                #label while #register && (#cond) #body
+            }))
+        }
+        Expr::Loop(ExprLoop { attrs, label, body, .. }) => {
+            let contract = declare_contract(None);
+            Ok(quote!({
+                #contract
+                #(#attrs)*
+                #label while #register #body
+            }))
+        }
+        Expr::ForLoop(ExprForLoop { attrs, label, pat, expr, body, .. }) => {
+            let contract = declare_contract(Some(&iter_ident));
+            let break_label = label.as_ref().map(|label| &label.name);
+            Ok(quote!({
+                let mut #iter_ident = ::core::iter::IntoIterator::into_iter(#expr);
+                #contract
+                #(#attrs)*
+                #label while #register {
+                    let ::core::option::Option::Some(#pat) =
+                        ::core::iter::Iterator::next(&mut #iter_ident)
+                    else {
+                        break #break_label;
+                    };
+                    #body
+                }
+            }))
+        }
+        _ => Err(syn::Error::new(
+            lp.span(),
+            "loop contracts can only be placed on a `while`, `loop` or `for` loop",
+        )),
+    }
+}
@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+pub mod arith {
+    #[kani::requires(divisor != 0)]
+    #[kani::ensures(result <= dividend)]
+    pub fn div(dividend: u32, divisor: u32) -> u32 {
+        dividend / divisor
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `stub_verified` can replace a function of another crate by its
+//! contract, both with an absolute path and with a path that starts with the
+//! name of the crate.
+
+#[kani::proof]
+#[kani::stub_verified(::base_crate::arith::div)]
+fn absolute_path() {
+    let v = vec![1, 2, 3];
+    let idx = base_crate::arith::div(v.len() as u32 - 1, kani::any_where(|d| *d != 0));
+    assert!(v[idx as usize] > 0);
+}
+
+#[kani::proof]
+#[kani::stub_verified(base_crate::arith::div)]
+fn crate_path() {
+    let dividend: u32 = kani::any();
+    let divisor: u32 = kani::any_where(|d| *d != 0);
+    assert!(base_crate::arith::div(dividend, divisor) <= dividend);
+}
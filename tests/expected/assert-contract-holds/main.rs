@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::assert_contract_holds!` asserts the `ensures` clauses of a
+//! function with the variables in scope.
+
+#[kani::ensures(|(low, high)| low <= high)]
+fn sort_pair(a: u32, b: u32) -> (u32, u32) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+#[kani::requires(x < 100)]
+#[kani::ensures(|result| *result <= x)]
+#[kani::ensures(x > 0 ==> kani::result() < x)]
+fn halve(x: u32) -> u32 {
+    // Rounds up, so the result is not smaller than `x` for `x == 1`.
+    (x + 1) / 2
+}
+
+#[kani::ensures(|result| *result == old(*x))]
+fn bump(x: &mut u32) -> u32 {
+    let previous = *x;
+    *x = previous.wrapping_add(1);
+    previous
+}
+
+#[kani::proof]
+fn check_sort_pair() {
+    let (a, b) = (kani::any(), kani::any());
+    let sorted = sort_pair(a, b);
+    kani::assert_contract_holds!(sort_pair(a, b), sorted);
+}
+
+#[kani::proof]
+fn check_halve() {
+    let x: u32 = kani::any_where(|x| *x < 100);
+    let result = halve(x);
+    kani::assert_contract_holds!(halve(x));
+}
+
+#[kani::proof]
+fn check_bump() {
+    let mut x: u32 = kani::any();
+    let result = bump(&mut x);
+    kani::assert_contract_holds!(bump(&mut x));
+}
@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::any()` only produces values that satisfy the invariant
+//! declared with `#[type_invariant]` on a type that derives `Arbitrary`.
+
+#[derive(kani::Arbitrary)]
+#[type_invariant(is_valid)]
+struct ValidatedType {
+    low: u8,
+    high: u8,
+}
+
+impl ValidatedType {
+    fn is_valid(&self) -> bool {
+        self.low <= self.high
+    }
+}
+
+#[kani::proof]
+fn check_invariant() {
+    let range: ValidatedType = kani::any();
+    assert!(range.low <= range.high);
+    kani::cover!(range.low == range.high);
+    kani::cover!(range.low > range.high); // <-- this condition should be `UNSATISFIABLE`
+}
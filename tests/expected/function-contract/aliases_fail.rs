@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `kani::aliases` in a postcondition catches a function that
+//! returns overlapping references.
+
+#[kani::requires(s.len() >= 2)]
+#[kani::ensures(!kani::aliases(result.0, result.1))]
+fn bad_split(s: &[u8]) -> (&[u8], &[u8]) {
+    let mid = s.len() / 2;
+    // Off by one: both halves contain the middle element.
+    (&s[..=mid], &s[mid..])
+}
+
+#[kani::proof_for_contract(bad_split)]
+fn bad_split_harness() {
+    let arr: [u8; 4] = kani::any();
+    bad_split(&arr);
+}
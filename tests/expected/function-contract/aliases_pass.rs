@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `kani::aliases` can be used to state that returned references
+//! are disjoint from each other and from other arguments.
+
+#[kani::requires(s.len() >= 2)]
+#[kani::ensures(!kani::aliases(result.0, result.1))]
+#[kani::ensures(!kani::aliases(result.0, other))]
+#[kani::ensures(kani::aliases(result.1, s))]
+fn split<'a>(s: &'a [u8], other: &u8) -> (&'a [u8], &'a [u8]) {
+    s.split_at(s.len() / 2)
+}
+
+#[kani::proof_for_contract(split)]
+fn split_harness() {
+    let arr: [u8; 4] = kani::any();
+    let other: u8 = kani::any();
+    split(&arr, &other);
+}
@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that contract clauses can refer to associated consts and use const
+//! generic parameters at the type level.
+
+struct Stack {
+    items: [u8; Stack::CAP],
+    len: usize,
+}
+
+impl Stack {
+    const CAP: usize = 4;
+
+    #[kani::requires(self.len < Self::CAP)]
+    #[kani::modifies(&mut self.items, &mut self.len)]
+    #[kani::ensures(self.len <= Self::CAP)]
+    fn push(&mut self, item: u8) {
+        self.items[self.len] = item;
+        self.len += 1;
+    }
+}
+
+#[kani::requires(idx < N && core::mem::size_of::<[u8; N]>() == N)]
+#[kani::ensures(result == arr[idx])]
+fn get<const N: usize>(arr: [u8; N], idx: usize) -> u8 {
+    arr[idx]
+}
+
+#[kani::proof_for_contract(Stack::push)]
+fn check_push() {
+    let mut stack = Stack { items: kani::any(), len: kani::any() };
+    stack.push(kani::any());
+}
+
+#[kani::proof_for_contract(get)]
+fn check_get() {
+    let arr: [u8; 3] = kani::any();
+    get(arr, kani::any());
+}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `#[kani::auto_harness]` generates one `proof_for_contract`
+//! harness per function, whether it is placed above or below the contract
+//! attributes, and that references are passed to symbolic values.
+
+#[kani::auto_harness]
+#[kani::requires(divisor != 0)]
+#[kani::ensures(result <= dividend)]
+fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[kani::requires(*counter < u8::MAX)]
+#[kani::modifies(counter)]
+#[kani::ensures(*counter == old(*counter) + (*step).min(1))]
+#[kani::auto_harness]
+fn bump(counter: &mut u8, step: &u8) {
+    *counter += (*step).min(1);
+}
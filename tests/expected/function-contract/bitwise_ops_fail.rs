@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a shift in a precondition is not misparsed and that the
+//! precondition is actually enforced when used as a replacement.
+
+#[kani::requires(x >> 2 < 10)]
+fn small(x: u32) -> u32 {
+    x
+}
+
+#[kani::proof]
+#[kani::stub_verified(small)]
+fn small_harness() {
+    let _ = Box::new(9_usize);
+    small(40);
+}
@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that bitwise and shift operators in contract clauses are expanded
+//! correctly, including shifts next to comparisons and generic arguments.
+
+#[kani::ensures((result & 1) == 0)]
+fn clear_low_bit(x: u32) -> u32 {
+    x & !1
+}
+
+#[kani::requires(x >> 2 < 10)]
+#[kani::ensures(result == (x << 1) | (x & 0))]
+fn double(x: u32) -> u32 {
+    x * 2
+}
+
+#[kani::requires(Vec::<Vec<u8>>::new().len() >> 1 == 0)]
+#[kani::ensures(result >> 1 == x as u64)]
+fn widen_double(x: u32) -> u64 {
+    (x as u64) << 1
+}
+
+#[kani::proof_for_contract(clear_low_bit)]
+fn clear_low_bit_harness() {
+    let _ = Box::new(9_usize);
+    clear_low_bit(kani::any());
+}
+
+#[kani::proof_for_contract(double)]
+fn double_harness() {
+    let _ = Box::new(9_usize);
+    double(kani::any());
+}
+
+#[kani::proof_for_contract(widen_double)]
+fn widen_double_harness() {
+    let _ = Box::new(9_usize);
+    widen_double(kani::any());
+}
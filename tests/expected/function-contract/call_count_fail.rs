@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a postcondition with a wrong `call_count` is refuted.
+
+#[kani::ensures(kani::call_count(f) == 1)]
+fn apply_unless_zero(x: u8, f: impl Fn(u8) -> u8) -> u8 {
+    if x == 0 { x } else { f(x) }
+}
+
+#[kani::proof_for_contract(apply_unless_zero)]
+fn apply_unless_zero_harness() {
+    apply_unless_zero(kani::any(), |x| x - 1);
+}
@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `call_count` counts the invocations of a closure argument, both
+//! for `Fn` and `FnMut` closures, and that the contract can be used as a stub.
+
+#[kani::requires(n <= 3)]
+#[kani::ensures(kani::call_count(f) == n as usize)]
+fn repeat<F: FnMut(u32) -> u32>(n: u8, init: u32, mut f: F) -> u32 {
+    let mut acc = init;
+    for _ in 0..n {
+        acc = f(acc);
+    }
+    acc
+}
+
+#[kani::ensures(call_count(f) == 2)]
+fn apply_twice(x: u8, f: impl Fn(u8) -> u8) -> u8 {
+    f(f(x))
+}
+
+#[kani::proof_for_contract(repeat)]
+#[kani::unwind(4)]
+fn repeat_harness() {
+    let mut calls = 0u32;
+    repeat(kani::any(), kani::any(), |x| {
+        calls += 1;
+        x.wrapping_mul(2)
+    });
+    assert!(calls <= 3);
+}
+
+#[kani::proof_for_contract(apply_twice)]
+fn apply_twice_harness() {
+    apply_twice(kani::any(), |x| x.wrapping_add(1));
+}
+
+#[kani::proof]
+#[kani::stub_verified(apply_twice)]
+fn apply_twice_stub_harness() {
+    apply_twice(kani::any(), |x| x);
+}
@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Checks that the functions generated from a contract are compiled out
+//! together with the contracted function, and that attributes which fix the
+//! symbol of the function stay on the original function.
+
+extern crate kani;
+
+/// Only available with the feature, which is not enabled here.
+#[cfg(feature = "x")]
+#[kani::requires(x < 100)]
+#[kani::ensures(result == x + 1)]
+fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+/// Used in place of the version above without the feature.
+#[cfg(not(feature = "x"))]
+#[kani::requires(x < 100)]
+#[kani::ensures(result == x + 2)]
+fn increment(x: u32) -> u32 {
+    x + 2
+}
+
+#[no_mangle]
+#[kani::ensures(result <= x)]
+pub extern "C" fn halve(x: u32) -> u32 {
+    x / 2
+}
+
+#[kani::proof_for_contract(increment)]
+fn increment_harness() {
+    increment(kani::any());
+}
+
+#[kani::proof_for_contract(halve)]
+fn halve_harness() {
+    halve(kani::any());
+}
@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a closure-style postcondition that destructures a tuple is
+//! checked against the returned values.
+
+#[kani::ensures(|(quotient, remainder)| *remainder < divisor && *quotient <= dividend)]
+fn div_rem(dividend: u32, divisor: u32) -> (u32, u32) {
+    // Wrong: the remainder may be as large as the divisor.
+    (dividend / divisor, dividend % divisor + 1)
+}
+
+#[kani::proof_for_contract(div_rem)]
+fn check_div_rem() {
+    div_rem(kani::any(), kani::any_where(|divisor| *divisor != 0));
+}
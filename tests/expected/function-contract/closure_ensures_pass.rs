@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a closure-style postcondition can destructure a tuple returned
+//! by the function, both when the contract is checked and when it replaces
+//! the function.
+
+#[kani::ensures(|(low, high)| low <= high)]
+#[kani::ensures(|(low, high)| (*low == a && *high == b) || (*low == b && *high == a))]
+fn sort_pair(a: u32, b: u32) -> (u32, u32) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+#[kani::proof_for_contract(sort_pair)]
+fn check_sort_pair() {
+    sort_pair(kani::any(), kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(sort_pair)]
+fn use_sort_pair() {
+    let (low, high) = sort_pair(7, 3);
+    assert!(low == 3 && high == 7);
+}
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a `const fn` with a contract can still be evaluated in a const
+//! context, while its contract is checked and used like any other.
+
+#[kani::requires(n <= 16)]
+#[kani::ensures(result >= n && result <= 2 * n)]
+const fn padded_len(n: usize) -> usize {
+    (n + 1) / 2 * 2
+}
+
+const BUFFER_LEN: usize = padded_len(7);
+
+#[kani::proof_for_contract(padded_len)]
+fn check_padded_len() {
+    padded_len(kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(padded_len)]
+fn use_padded_len() {
+    let buffer = [0u8; padded_len(3)];
+    assert_eq!(buffer.len(), 4);
+    assert_eq!(BUFFER_LEN, 8);
+    assert!(padded_len(5) >= 5);
+}
@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that contract clauses can refer to the const generic parameters of a
+//! function, even if they cannot be inferred from its arguments.
+
+#[kani::requires(N > 0)]
+#[kani::ensures(result < N)]
+fn argmax<const N: usize>(arr: [u32; N]) -> usize {
+    let mut max = 0;
+    for i in 1..N {
+        if arr[i] > arr[max] {
+            max = i;
+        }
+    }
+    max
+}
+
+#[kani::ensures(result == N * 2)]
+fn capacity_in_bytes<const N: usize>() -> usize {
+    N * 2
+}
+
+#[kani::proof_for_contract(argmax)]
+#[kani::unwind(5)]
+fn argmax_harness() {
+    let arr: [u32; 4] = kani::any();
+    argmax(arr);
+}
+
+#[kani::proof_for_contract(capacity_in_bytes)]
+fn capacity_in_bytes_harness() {
+    capacity_in_bytes::<16>();
+}
+
+#[kani::proof]
+#[kani::stub_verified(argmax)]
+fn argmax_replaced() {
+    let arr: [u32; 8] = kani::any();
+    let max = argmax(arr);
+    assert!(max < 8);
+}
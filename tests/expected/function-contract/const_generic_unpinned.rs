@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that we report an error if a harness calls a const generic function
+//! with more than one value for `N`.
+
+#[kani::ensures(result == N)]
+fn len<const N: usize>(_arr: [u8; N]) -> usize {
+    N
+}
+
+#[kani::proof_for_contract(len)]
+fn len_harness() {
+    if kani::any() {
+        len([0u8; 2]);
+    } else {
+        len([0u8; 3]);
+    }
+}
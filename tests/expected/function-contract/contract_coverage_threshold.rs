@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --contract-coverage-threshold 75
+
+//! Check that `--contract-coverage-threshold` reports the functions whose
+//! contract is not checked by any harness and fails when the share of checked
+//! contracts is below the threshold.
+
+#[kani::requires(x < 100)]
+#[kani::ensures(result > x)]
+fn checked(x: u32) -> u32 {
+    x + 1
+}
+
+#[kani::ensures(result <= x)]
+fn unchecked(x: u32) -> u32 {
+    x / 2
+}
+
+#[kani::proof_for_contract(checked)]
+fn checked_harness() {
+    checked(kani::any());
+}
@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --contract-report
+
+//! Check that `--contract-report` tells which clauses were checked, and flags
+//! a constant postcondition and a precondition that no input satisfies.
+
+#[kani::requires(x < 100)]
+#[kani::ensures(result > x)]
+#[kani::ensures(true)]
+fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+#[kani::requires(x > 10 && x < 5)]
+#[kani::ensures(result == 0)]
+fn impossible(x: u32) -> u32 {
+    x
+}
+
+#[kani::proof_for_contract(increment)]
+fn check_increment() {
+    increment(kani::any());
+}
+
+#[kani::proof_for_contract(impossible)]
+fn check_impossible() {
+    impossible(kani::any());
+}
@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a function with a contract that is called from a postcondition
+//! can be replaced by its contract while another contract is checked.
+
+#[kani::pure]
+#[kani::ensures(result == (x % 2 == 0))]
+fn is_even(x: u32) -> bool {
+    x % 2 == 0
+}
+
+#[kani::requires(x < u32::MAX)]
+#[kani::ensures(is_even(result) && result >= x)]
+fn round_up_even(x: u32) -> u32 {
+    x + x % 2
+}
+
+#[kani::proof_for_contract(is_even)]
+fn check_is_even() {
+    let _ = is_even(kani::any());
+}
+
+#[kani::proof_for_contract(round_up_even)]
+#[kani::stub_verified(is_even)]
+fn check_round_up_even() {
+    let _ = round_up_even(kani::any());
+}
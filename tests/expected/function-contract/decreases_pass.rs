@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a measure which shrinks with every recursive call proves
+//! termination.
+
+#[kani::requires(x != 0 && y != 0)]
+#[kani::ensures(result != 0 && x % result == 0 && y % result == 0)]
+#[kani::decreases(y)]
+fn gcd(x: u8, y: u8) -> u8 {
+    let r = x % y;
+    if r == 0 { y } else { gcd(y, r) }
+}
+
+#[kani::proof_for_contract(gcd)]
+fn gcd_harness() {
+    let _ = gcd(kani::any(), kani::any());
+}
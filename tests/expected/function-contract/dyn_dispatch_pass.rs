@@ -0,0 +1,39 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the contract of a method is checked when the harness only
+//! reaches it through a trait object.
+
+trait Counter {
+    fn step(&mut self);
+}
+
+struct Bounded {
+    count: u8,
+}
+
+impl Bounded {
+    #[kani::requires(self.count < 10)]
+    #[kani::modifies(&self.count)]
+    #[kani::ensures(self.count <= 10)]
+    fn bounded_step(&mut self) {
+        self.count += 1;
+    }
+}
+
+impl Counter for Bounded {
+    fn step(&mut self) {
+        self.bounded_step()
+    }
+}
+
+fn run(counter: &mut dyn Counter) {
+    counter.step();
+}
+
+#[kani::proof_for_contract(Bounded::bounded_step)]
+fn check_through_dyn() {
+    let mut bounded = Bounded { count: kani::any() };
+    run(&mut bounded);
+}
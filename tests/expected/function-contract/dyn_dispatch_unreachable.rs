@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that Kani reports an error if the trait object the harness calls
+//! through never dispatches to the function under contract.
+
+trait Counter {
+    fn step(&mut self);
+}
+
+struct Bounded {
+    count: u8,
+}
+
+impl Bounded {
+    #[kani::requires(self.count < 10)]
+    #[kani::modifies(&self.count)]
+    #[kani::ensures(self.count <= 10)]
+    fn bounded_step(&mut self) {
+        self.count += 1;
+    }
+}
+
+impl Counter for Bounded {
+    fn step(&mut self) {
+        self.bounded_step()
+    }
+}
+
+struct Wrapping {
+    count: u8,
+}
+
+impl Counter for Wrapping {
+    fn step(&mut self) {
+        self.count = self.count.wrapping_add(1);
+    }
+}
+
+fn run(counter: &mut dyn Counter) {
+    counter.step();
+}
+
+#[kani::proof_for_contract(Bounded::bounded_step)]
+fn check_through_dyn() {
+    let mut wrapping = Wrapping { count: kani::any() };
+    run(&mut wrapping);
+}
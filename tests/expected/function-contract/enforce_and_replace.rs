@@ -0,0 +1,61 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --enforce-and-replace
+
+//! Check that `--enforce-and-replace` reports the enforcement and the
+//! replacement phase of every checked contract, and that the replacement phase
+//! is also checked for a contract that no `stub_verified` harness uses, unless
+//! the function is generic.
+
+#[kani::requires(divisor != 0)]
+#[kani::ensures(result <= dividend)]
+fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[kani::ensures(result < x)]
+fn halve(x: u8) -> u8 {
+    x / 2
+}
+
+#[kani::ensures(result == 0)]
+fn zero() -> u8 {
+    0
+}
+
+#[kani::ensures(result.is_some())]
+fn or_default<T: Default>(opt: Option<T>) -> Option<T> {
+    opt.or(Some(T::default()))
+}
+
+#[kani::proof_for_contract(div)]
+fn check_div() {
+    let _ = div(kani::any(), kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(div)]
+fn use_div() {
+    assert!(div(9, 1) != 10, "contract guarantees smallness");
+}
+
+#[kani::proof_for_contract(halve)]
+fn check_halve() {
+    let _ = halve(kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(halve)]
+fn use_halve() {
+    assert!(halve(kani::any()) < u8::MAX);
+}
+
+#[kani::proof_for_contract(zero)]
+fn check_zero() {
+    let _ = zero();
+}
+
+#[kani::proof_for_contract(or_default)]
+fn check_or_default() {
+    let _ = or_default::<u8>(kani::any());
+}
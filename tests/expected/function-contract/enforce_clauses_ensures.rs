@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --enforce-clauses=ensures
+
+//! Check that `--enforce-clauses=ensures` only checks the postcondition of a
+//! function, and not its (here incomplete) write set.
+
+#[kani::modifies(first)]
+#[kani::ensures(*first == 1 && *second == 1)]
+fn reset(first: &mut u8, second: &mut u8) {
+    *first = 1;
+    *second = 1;
+}
+
+#[kani::proof_for_contract(reset)]
+fn reset_harness() {
+    let mut first: u8 = kani::any();
+    let mut second: u8 = kani::any();
+    reset(&mut first, &mut second);
+}
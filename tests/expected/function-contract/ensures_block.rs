@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that conditions can be blocks with intermediate `let` bindings, that
+//! the bindings may shadow arguments and that `old_with` can be used inside.
+
+#[derive(kani::Arbitrary)]
+struct Stats {
+    count: u32,
+    total: u64,
+}
+
+fn mean(s: &Stats) -> u64 {
+    if s.count == 0 { 0 } else { s.total / s.count as u64 }
+}
+
+#[kani::requires({
+    let headroom = u64::MAX - s.total;
+    s.count < u32::MAX && headroom > 255
+})]
+#[kani::modifies(s)]
+#[kani::ensures({
+    let before = old_with(&*s, |s: &Stats| s.count);
+    let s = &*s;
+    s.count == before + 1 && mean(s) <= s.total
+})]
+fn record(s: &mut Stats, v: u8) {
+    s.count += 1;
+    s.total += v as u64;
+}
+
+#[kani::proof_for_contract(record)]
+fn record_harness() {
+    let mut s: Stats = kani::any();
+    record(&mut s, kani::any());
+}
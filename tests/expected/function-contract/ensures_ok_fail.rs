@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `ensures_ok` fails for a function that returns an error for an
+//! input allowed by its precondition.
+
+#[derive(kani::Arbitrary)]
+enum DivError {
+    ByZero,
+    Overflow,
+}
+
+#[kani::requires(divisor != 0)]
+#[kani::ensures_ok]
+fn checked_div(dividend: i32, divisor: i32) -> Result<i32, DivError> {
+    if divisor == 0 {
+        Err(DivError::ByZero)
+    } else {
+        dividend.checked_div(divisor).ok_or(DivError::Overflow)
+    }
+}
+
+#[kani::proof_for_contract(checked_div)]
+fn checked_div_harness() {
+    let _ = checked_div(kani::any(), kani::any());
+}
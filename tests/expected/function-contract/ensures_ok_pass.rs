@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `ensures_ok` holds for a function that succeeds on all inputs
+//! allowed by its precondition, even though it fails on other inputs.
+
+#[derive(kani::Arbitrary)]
+enum DivError {
+    ByZero,
+}
+
+#[kani::requires(divisor != 0)]
+#[kani::ensures_ok]
+fn checked_div(dividend: u32, divisor: u32) -> Result<u32, DivError> {
+    if divisor == 0 { Err(DivError::ByZero) } else { Ok(dividend / divisor) }
+}
+
+#[kani::proof_for_contract(checked_div)]
+fn checked_div_harness() {
+    let _ = checked_div(kani::any(), kani::any());
+}
@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that Kani rejects a `proof_for_contract` harness for a function that
+//! is declared without a body, since there is nothing to check the contract
+//! against.
+
+#[kani::requires(x != i32::MIN)]
+#[kani::ensures(result >= 0)]
+extern "C" fn ffi_abs(x: i32) -> i32;
+
+#[kani::proof_for_contract(ffi_abs)]
+fn abs_harness() {
+    let _ = unsafe { ffi_abs(kani::any()) };
+}
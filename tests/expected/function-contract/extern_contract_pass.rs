@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a contract can be attached to a function that is declared
+//! without a body, such as an FFI import, and that the declaration is replaced
+//! by its contract via `stub_verified`.
+
+#[kani::requires(x != i32::MIN)]
+#[kani::ensures(result >= 0 && (result == x || result == -x))]
+extern "C" fn ffi_abs(x: i32) -> i32;
+
+#[kani::proof]
+#[kani::stub_verified(ffi_abs)]
+fn abs_harness() {
+    let x: i32 = kani::any();
+    kani::assume(x != i32::MIN);
+    let abs = unsafe { ffi_abs(x) };
+    assert!(abs >= 0);
+    assert!(abs == x || abs == -x);
+}
@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that an unrolled quantifier catches the one element of the array
+//! that the function forgets to zero.
+
+#[kani::modifies(arr)]
+#[kani::ensures(kani::forall(|i| i < 8 ==> arr[i] == 0))]
+fn zero(arr: &mut [u32; 8]) {
+    let last = arr[7];
+    *arr = [0; 8];
+    arr[7] = last;
+}
+
+#[kani::proof_for_contract(zero)]
+fn zero_harness() {
+    let mut arr: [u32; 8] = kani::any();
+    zero(&mut arr);
+}
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a postcondition quantifying over the elements of a fixed-size
+//! array is unrolled and proven.
+
+#[kani::modifies(arr)]
+#[kani::ensures(kani::forall(|i| i < 8 ==> arr[i] == 0))]
+fn zero(arr: &mut [u32; 8]) {
+    *arr = [0; 8];
+}
+
+#[kani::proof_for_contract(zero)]
+fn zero_harness() {
+    let mut arr: [u32; 8] = kani::any();
+    zero(&mut arr);
+}
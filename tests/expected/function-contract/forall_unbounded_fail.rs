@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a quantifier without a bound fails if a single value of its
+//! variable violates the condition.
+
+#[kani::ensures(kani::forall(|i: u8| i > x ==> i > result))]
+fn increment(x: u8) -> u8 {
+    x.wrapping_add(1)
+}
+
+#[kani::proof_for_contract(increment)]
+fn increment_harness() {
+    increment(kani::any());
+}
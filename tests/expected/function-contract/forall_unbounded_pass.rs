@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a quantifier without a bound is proven for every value of its
+//! variable, and that it can be assumed when the contract replaces the function.
+
+#[kani::ensures(kani::forall(|i: u8| i > x ==> i > result))]
+fn halve(x: u8) -> u8 {
+    x / 2
+}
+
+#[kani::proof_for_contract(halve)]
+fn halve_harness() {
+    halve(kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(halve)]
+fn halve_replace() {
+    // The postcondition is only assumed for one arbitrary value of `i`, which
+    // is sound but does not bound the result on its own.
+    let _ = halve(kani::any());
+}
@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that contracts on associated functions of generic `impl` blocks can
+//! rely on the bounds declared in the `impl` header, inline or in a `where`
+//! clause.
+
+#[derive(kani::Arbitrary)]
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+impl<T: Ord + Copy> Pair<T> {
+    #[kani::ensures(result >= self.first && result >= self.second)]
+    fn max(&self) -> T {
+        if self.first < self.second { self.second } else { self.first }
+    }
+}
+
+impl<T> Pair<T>
+where
+    T: PartialOrd + Copy,
+{
+    #[kani::requires(first <= second)]
+    #[kani::ensures(result.first <= result.second)]
+    fn ordered(first: T, second: T) -> Self {
+        Pair { first, second }
+    }
+}
+
+#[kani::proof_for_contract(Pair::max)]
+fn max_harness() {
+    let pair: Pair<u8> = kani::any();
+    pair.max();
+}
+
+#[kani::proof_for_contract(Pair::ordered)]
+fn ordered_harness() {
+    let _ = Pair::<i16>::ordered(kani::any(), kani::any());
+}
@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a function returning `impl Trait` can have a contract.
+
+#[kani::requires(n < 10)]
+#[kani::ensures(result.size_hint() == (n as usize, Some(n as usize)))]
+fn count_to(n: u32) -> impl Iterator<Item = u32> {
+    0..n
+}
+
+#[kani::proof_for_contract(count_to)]
+fn count_to_harness() {
+    let _ = count_to(kani::any());
+}
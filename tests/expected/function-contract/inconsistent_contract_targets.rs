@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that Kani reports an internal error if the functions that the
+//! contract attributes of a function refer to were not generated for the same
+//! contract. The attributes are crafted by hand to simulate contract macros
+//! that are out of sync with the compiler.
+
+#[kanitool::checked_with = "div_recursion_wrapper_1a2b3c"]
+#[kanitool::replaced_with = "div_replace_4d5e6f"]
+#[kanitool::inner_check = "div_wrapper_1a2b3c"]
+fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+fn div_recursion_wrapper_1a2b3c(dividend: u32, divisor: u32) -> u32 {
+    div_wrapper_1a2b3c(dividend, divisor)
+}
+
+fn div_replace_4d5e6f(_dividend: u32, _divisor: u32) -> u32 {
+    kani::any()
+}
+
+fn div_wrapper_1a2b3c(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[kani::proof_for_contract(div)]
+fn div_harness() {
+    div(kani::any(), kani::any());
+}
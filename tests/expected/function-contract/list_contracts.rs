@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --list-contracts
+
+//! Check that `--list-contracts` lists the functions with contracts and their
+//! harnesses without running verification.
+
+#[kani::requires(x < 100)]
+#[kani::ensures(result > x)]
+fn checked(x: u32) -> u32 {
+    x + 1
+}
+
+#[kani::ensures(result <= x)]
+fn unchecked(x: u32) -> u32 {
+    x / 2
+}
+
+#[kani::proof_for_contract(checked)]
+fn checked_harness() {
+    checked(kani::any());
+}
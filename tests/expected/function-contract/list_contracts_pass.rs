@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --list-contracts
+
+//! Check that `--list-contracts` succeeds when every function with a contract
+//! is checked by a harness.
+
+#[kani::requires(x < 100)]
+#[kani::ensures(result > x)]
+fn checked(x: u32) -> u32 {
+    x + 1
+}
+
+#[kani::proof_for_contract(checked)]
+fn checked_harness() {
+    checked(kani::any());
+}
@@ -0,0 +1,61 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that contracts can be attached to functions generated by
+//! `macro_rules!`, including when the body and the conditions are passed in as
+//! macro fragments.
+
+macro_rules! saturating_op {
+    ($name:ident, $op:ident, $post:expr) => {
+        #[kani::requires(y <= 10)]
+        #[kani::ensures($post)]
+        fn $name(x: u8, y: u8) -> u8 {
+            x.$op(y)
+        }
+    };
+}
+
+saturating_op!(sat_add, saturating_add, result >= x);
+saturating_op!(sat_sub, saturating_sub, result <= x);
+// A `result` bound inside the postcondition shadows the return value.
+saturating_op!(wide_add, saturating_add, {
+    let result = result as u16;
+    result >= x as u16
+});
+
+macro_rules! contracted_body {
+    ($name:ident, $body:block) => {
+        #[kani::ensures(result % 2 == 0)]
+        fn $name(x: u8) -> u16 $body
+    };
+}
+
+contracted_body!(double, { x as u16 * 2 });
+
+#[kani::proof_for_contract(sat_add)]
+fn sat_add_harness() {
+    sat_add(kani::any(), kani::any());
+}
+
+#[kani::proof_for_contract(sat_sub)]
+fn sat_sub_harness() {
+    sat_sub(kani::any(), kani::any());
+}
+
+#[kani::proof_for_contract(wide_add)]
+fn wide_add_harness() {
+    wide_add(kani::any(), kani::any());
+}
+
+#[kani::proof_for_contract(double)]
+fn double_harness() {
+    double(kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(sat_add)]
+fn sat_add_stub_harness() {
+    let x: u8 = kani::any();
+    assert!(sat_add(x, 1) >= x);
+}
@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `modifies(*self)` allows a method to write any field of its
+//! receiver.
+
+#[derive(kani::Arbitrary)]
+struct Counter {
+    hits: u32,
+    misses: u32,
+    last: Option<bool>,
+}
+
+impl Counter {
+    #[kani::modifies(*self)]
+    #[kani::ensures(self.hits == 0 && self.misses == 0 && self.last.is_none())]
+    fn reset(&mut self) {
+        self.hits = 0;
+        self.misses = 0;
+        self.last = None;
+    }
+}
+
+#[kani::proof_for_contract(Counter::reset)]
+fn check_reset() {
+    let mut counter: Counter = kani::any();
+    counter.reset();
+}
+
+#[kani::proof]
+#[kani::stub_verified(Counter::reset)]
+fn use_reset() {
+    let mut counter: Counter = kani::any();
+    counter.reset();
+    assert_eq!(counter.hits, 0);
+}
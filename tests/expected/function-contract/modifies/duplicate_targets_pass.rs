@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that targets repeated within and across `modifies` clauses are
+//! accepted, both when the contract is checked and when it is used as a stub.
+
+#[kani::requires(*a < 100 && *b < 100)]
+#[kani::modifies(a, a)]
+#[kani::modifies(b)]
+#[kani::modifies(a)]
+#[kani::ensures(*a == old(*a) + 1 && *b == old(*b) + 1)]
+fn bump_both(a: &mut u32, b: &mut u32) {
+    *a += 1;
+    *b += 1;
+}
+
+#[kani::proof_for_contract(bump_both)]
+fn check_bump_both() {
+    let mut a = kani::any();
+    let mut b = kani::any();
+    bump_both(&mut a, &mut b);
+}
+
+#[kani::proof]
+#[kani::stub_verified(bump_both)]
+fn use_bump_both() {
+    let mut a = 1;
+    let mut b = 2;
+    let c = 3;
+    bump_both(&mut a, &mut b);
+    assert_eq!((a, b, c), (2, 3, 3));
+}
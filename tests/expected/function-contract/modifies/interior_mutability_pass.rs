@@ -0,0 +1,47 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a `modifies` target behind a shared reference is accepted if it
+//! has interior mutability.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+struct Counter {
+    count: Cell<u32>,
+}
+
+impl Counter {
+    #[kani::requires(self.count.get() < 100)]
+    #[kani::modifies(&self.count)]
+    fn increment(&self) {
+        self.count.set(self.count.get() + 1)
+    }
+}
+
+#[kani::modifies(hits)]
+fn record(hits: &AtomicU32) {
+    hits.fetch_add(1, Ordering::Relaxed);
+}
+
+#[kani::proof_for_contract(Counter::increment)]
+fn check_increment() {
+    let counter = Counter { count: Cell::new(kani::any()) };
+    counter.increment();
+}
+
+#[kani::proof_for_contract(record)]
+fn check_record() {
+    let hits = AtomicU32::new(kani::any());
+    record(&hits);
+}
+
+#[kani::proof]
+#[kani::stub_verified(Counter::increment)]
+fn use_increment() {
+    let counter = Counter { count: Cell::new(1) };
+    let other = Cell::new(2);
+    counter.increment();
+    assert_eq!(other.get(), 2);
+}
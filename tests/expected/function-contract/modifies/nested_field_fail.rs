@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a write next to a modifies target that projects through several
+//! levels of fields is rejected.
+
+#[derive(kani::Arbitrary)]
+struct Inner {
+    target: u32,
+    sibling: u32,
+}
+
+#[derive(kani::Arbitrary)]
+struct Middle {
+    inner: Inner,
+}
+
+#[derive(kani::Arbitrary)]
+struct Outer {
+    middle: Middle,
+}
+
+impl Outer {
+    #[kani::modifies(&self.middle.inner.target)]
+    fn reset(&mut self) {
+        self.middle.inner.target = 0;
+        self.middle.inner.sibling = 0;
+    }
+}
+
+#[kani::proof_for_contract(Outer::reset)]
+fn check_reset() {
+    let mut outer: Outer = kani::any();
+    outer.reset();
+}
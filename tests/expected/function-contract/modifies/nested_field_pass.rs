@@ -0,0 +1,50 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a modifies target can project through several levels of fields
+//! and that only that field is havocked when the contract is used as a stub.
+
+#[derive(kani::Arbitrary)]
+struct Inner {
+    target: u32,
+    sibling: u32,
+}
+
+#[derive(kani::Arbitrary)]
+struct Middle {
+    inner: Inner,
+    sibling: u32,
+}
+
+#[derive(kani::Arbitrary)]
+struct Outer {
+    middle: Middle,
+    sibling: u32,
+}
+
+impl Outer {
+    #[kani::requires(self.middle.inner.target < 100)]
+    #[kani::modifies(&self.middle.inner.target)]
+    #[kani::ensures(self.middle.inner.target < 101)]
+    fn increment(&mut self) {
+        self.middle.inner.target += 1;
+    }
+}
+
+#[kani::proof_for_contract(Outer::increment)]
+fn check_increment() {
+    let mut outer: Outer = kani::any();
+    outer.increment();
+}
+
+#[kani::proof]
+#[kani::stub_verified(Outer::increment)]
+fn use_increment() {
+    let mut outer: Outer = kani::any();
+    kani::assume(outer.middle.inner.target < 100);
+    let (a, b, c) = (outer.middle.inner.sibling, outer.middle.sibling, outer.sibling);
+    outer.increment();
+    assert!(outer.middle.inner.target < 101);
+    assert_eq!((outer.middle.inner.sibling, outer.middle.sibling, outer.sibling), (a, b, c));
+}
@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a function whose contract consists only of a `modifies` clause
+//! is accepted as the target of `proof_for_contract`.
+
+#[kani::modifies(ptr)]
+fn modify(ptr: &mut u32) {
+    *ptr = 1;
+}
+
+#[kani::proof_for_contract(modify)]
+fn main() {
+    let mut i = kani::any();
+    modify(&mut i);
+}
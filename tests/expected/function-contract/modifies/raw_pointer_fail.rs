@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old(..)` of the pointee of a raw out-pointer is a copy of the
+//! value on entry, so a write through the pointer is observed.
+
+#[kani::requires(unsafe { *out } < 100)]
+#[kani::modifies(*out)]
+#[kani::ensures(old(unsafe { *out }) == unsafe { *out })]
+unsafe fn bump(out: *mut u32) {
+    *out += 1;
+}
+
+#[kani::proof_for_contract(bump)]
+fn bump_harness() {
+    let mut value: u32 = kani::any();
+    unsafe { bump(&mut value) };
+}
@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a function returning its result through a raw out-pointer can
+//! name the pointee in its `modifies` clause, both when the contract is checked
+//! and when it replaces the function.
+
+#[kani::modifies(*out)]
+#[kani::ensures(unsafe { *out } == value as u32 * 2)]
+unsafe fn fill(out: *mut u32, value: u16) {
+    *out = value as u32 * 2;
+}
+
+#[kani::proof_for_contract(fill)]
+fn fill_harness() {
+    let mut result: u32 = kani::any();
+    unsafe { fill(&mut result, kani::any()) };
+}
+
+#[kani::proof]
+#[kani::stub_verified(fill)]
+fn fill_replace_harness() {
+    let mut result: u32 = 0;
+    unsafe { fill(&mut result, 21) };
+    assert_eq!(result, 42);
+}
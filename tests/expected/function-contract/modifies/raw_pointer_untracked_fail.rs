@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a raw pointer argument is only allowed if its pointee is a
+//! `modifies` target, even if another raw pointer argument is one.
+
+#[kani::modifies(&mut (*out).0)]
+unsafe fn copy_first(out: *mut (u32, u32), src: *mut u32) {
+    (*out).0 = *src;
+}
+
+#[kani::proof_for_contract(copy_first)]
+fn copy_first_harness() {
+    let mut out: (u32, u32) = kani::any();
+    let mut src: u32 = kani::any();
+    unsafe { copy_first(&mut out, &mut src) };
+}
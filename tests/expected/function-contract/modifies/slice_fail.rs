@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a slice `modifies` target only allows writes to the elements of
+//! the slice, and that replacing the function havocs all of them.
+
+struct Buffer {
+    ptr: *mut u8,
+    len: usize,
+    cap: usize,
+}
+
+impl Buffer {
+    /// Only declares the initialized part of the buffer, but writes anywhere
+    /// below its capacity.
+    #[kani::requires(idx < self.cap)]
+    #[kani::modifies(core::ptr::slice_from_raw_parts_mut(self.ptr, self.len))]
+    fn write(&mut self, idx: usize, val: u8) {
+        unsafe { *self.ptr.add(idx) = val }
+    }
+}
+
+#[kani::proof_for_contract(Buffer::write)]
+fn check_write() {
+    let mut data: [u8; 4] = kani::any();
+    let mut buffer = Buffer { ptr: data.as_mut_ptr(), len: 2, cap: data.len() };
+    buffer.write(kani::any(), kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(Buffer::write)]
+fn use_write() {
+    let mut data = [1u8; 4];
+    let mut buffer = Buffer { ptr: data.as_mut_ptr(), len: 4, cap: data.len() };
+    buffer.write(2, 5);
+    assert!(data[0] == 1, "not havocked");
+}
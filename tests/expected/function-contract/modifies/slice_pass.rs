@@ -0,0 +1,52 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a slice can be used as a `modifies` target, both as a `&mut [T]`
+//! argument and as a pointer-with-length pair built with
+//! `core::ptr::slice_from_raw_parts_mut`.
+
+struct Buffer {
+    ptr: *mut u8,
+    cap: usize,
+}
+
+impl Buffer {
+    #[kani::requires(idx < self.cap)]
+    #[kani::modifies(core::ptr::slice_from_raw_parts_mut(self.ptr, self.cap))]
+    fn write(&mut self, idx: usize, val: u8) {
+        unsafe { *self.ptr.add(idx) = val }
+    }
+}
+
+#[kani::modifies(s)]
+fn zero_all(s: &mut [u8]) {
+    for x in s.iter_mut() {
+        *x = 0;
+    }
+}
+
+#[kani::proof_for_contract(Buffer::write)]
+fn check_write() {
+    let mut data: [u8; 4] = kani::any();
+    let mut buffer = Buffer { ptr: data.as_mut_ptr(), cap: data.len() };
+    buffer.write(kani::any(), kani::any());
+}
+
+#[kani::proof_for_contract(zero_all)]
+#[kani::unwind(3)]
+fn check_zero_all() {
+    let mut data: [u8; 2] = kani::any();
+    zero_all(&mut data);
+}
+
+#[kani::proof]
+#[kani::stub_verified(Buffer::write)]
+fn use_write() {
+    let mut data = [1u8; 4];
+    let untouched = 7u8;
+    let mut buffer = Buffer { ptr: data.as_mut_ptr(), cap: data.len() };
+    buffer.write(2, 5);
+    assert_eq!(buffer.cap, 4);
+    assert_eq!(untouched, 7);
+}
@@ -0,0 +1,54 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `modifies` clauses can name the receiver of methods that take
+//! `self` through a `Pin<&mut Self>` or a `Box<Self>`.
+
+use std::pin::Pin;
+
+#[derive(kani::Arbitrary)]
+struct Counter {
+    count: u32,
+    limit: u32,
+}
+
+impl Counter {
+    #[kani::modifies(&self.count)]
+    #[kani::ensures(self.count == 0)]
+    fn clear(mut self: Pin<&mut Self>) {
+        self.count = 0;
+    }
+
+    #[kani::modifies(self)]
+    #[kani::ensures(self.count == self.limit)]
+    fn fill(mut self: Pin<&mut Self>) {
+        self.count = self.limit;
+    }
+
+    #[kani::modifies(self)]
+    #[kani::ensures(result.count == 0 && result.limit == 0)]
+    fn reset(mut self: Box<Self>) -> Box<Self> {
+        self.count = 0;
+        self.limit = 0;
+        self
+    }
+}
+
+#[kani::proof_for_contract(Counter::clear)]
+fn check_clear() {
+    let mut counter: Counter = kani::any();
+    Pin::new(&mut counter).clear();
+}
+
+#[kani::proof_for_contract(Counter::fill)]
+fn check_fill() {
+    let mut counter: Counter = kani::any();
+    Pin::new(&mut counter).fill();
+}
+
+#[kani::proof_for_contract(Counter::reset)]
+fn check_reset() {
+    let counter: Box<Counter> = Box::new(kani::any());
+    counter.reset();
+}
@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a write to a tuple struct field next to the modifies target is
+//! rejected.
+
+#[derive(kani::Arbitrary)]
+struct Wrapper(u32, u32);
+
+impl Wrapper {
+    #[kani::modifies(&self.0)]
+    fn reset(&mut self) {
+        self.0 = 0;
+        self.1 = 0;
+    }
+}
+
+#[kani::proof_for_contract(Wrapper::reset)]
+fn check_reset() {
+    let mut wrapper: Wrapper = kani::any();
+    wrapper.reset();
+}
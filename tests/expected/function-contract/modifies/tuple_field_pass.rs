@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a numeric field of a tuple struct can be a modifies target.
+
+#[derive(kani::Arbitrary)]
+struct Wrapper(u32, u32);
+
+impl Wrapper {
+    #[kani::requires(self.0 < 100)]
+    #[kani::modifies(&self.0)]
+    #[kani::ensures(self.0 == old(self.0) + 1)]
+    fn increment_first(&mut self) {
+        self.0 += 1;
+    }
+}
+
+#[kani::proof_for_contract(Wrapper::increment_first)]
+fn check_increment_first() {
+    let mut wrapper: Wrapper = kani::any();
+    wrapper.increment_first();
+}
+
+#[kani::proof]
+#[kani::stub_verified(Wrapper::increment_first)]
+fn check_second_unchanged() {
+    let mut wrapper = Wrapper(1, kani::any());
+    let second = wrapper.1;
+    wrapper.increment_first();
+    assert_eq!(wrapper.1, second);
+}
@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a write to a location that is missing from the `modifies` clause
+//! is reported as an undeclared write of the function.
+
+#[kani::modifies(x)]
+fn reset_both(x: &mut u32, y: &mut u32) {
+    *x = 0;
+    *y = 0;
+}
+
+#[kani::proof_for_contract(reset_both)]
+fn check_reset_both() {
+    let mut x: u32 = kani::any();
+    let mut y: u32 = kani::any();
+    reset_both(&mut x, &mut y);
+}
@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that writing a wider field of a `union` is rejected when only a
+//! narrower field is a `modifies` target, since the write also covers bytes
+//! outside of the target.
+
+union Bits {
+    low: u32,
+    word: u64,
+}
+
+#[kani::modifies((*bits).low)]
+unsafe fn clear(bits: *mut Bits) {
+    (*bits).word = 0;
+}
+
+#[kani::proof_for_contract(clear)]
+fn check_clear() {
+    let mut bits = Bits { word: kani::any() };
+    unsafe { clear(&mut bits) };
+}
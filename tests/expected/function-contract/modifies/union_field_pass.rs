@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a single field of a `union` behind a pointer can be used as a
+//! `modifies` target, both when checking and when replacing the contract.
+
+union Bits {
+    low: u32,
+    word: u64,
+}
+
+#[kani::modifies((*bits).low)]
+#[kani::ensures(unsafe { (*bits).low } == 0)]
+unsafe fn clear_low(bits: *mut Bits) {
+    (*bits).low = 0;
+}
+
+#[kani::proof_for_contract(clear_low)]
+fn check_clear_low() {
+    let mut bits = Bits { word: kani::any() };
+    unsafe { clear_low(&mut bits) };
+}
+
+#[kani::proof]
+#[kani::stub_verified(clear_low)]
+fn use_clear_low() {
+    let mut bits = Bits { word: kani::any() };
+    unsafe {
+        clear_low(&mut bits);
+        assert_eq!(bits.low, 0);
+    }
+}
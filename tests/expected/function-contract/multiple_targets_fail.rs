@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the contract of every target of `proof_for_contract` is checked,
+//! not only the first one.
+
+#[kani::ensures(result >= x)]
+fn double(x: u8) -> u8 {
+    x.saturating_mul(2)
+}
+
+#[kani::ensures(result > x)]
+fn halve(x: u8) -> u8 {
+    x / 2
+}
+
+#[kani::proof_for_contract(double, halve)]
+fn check_double_and_halve() {
+    let x = kani::any();
+    halve(double(x));
+}
@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that one harness can check the contracts of several functions, once
+//! per function so the `modifies` clause of each of them is enforced.
+
+struct Library {
+    available: u32,
+    lent: u32,
+}
+
+impl Library {
+    #[kani::requires(self.available > 0)]
+    #[kani::modifies(&mut self.available, &mut self.lent)]
+    #[kani::ensures(self.available == old(self.available) - 1)]
+    fn lend(&mut self) {
+        self.available -= 1;
+        self.lent += 1;
+    }
+
+    #[kani::requires(self.lent > 0)]
+    #[kani::modifies(&mut self.available, &mut self.lent)]
+    #[kani::ensures(self.lent == old(self.lent) - 1)]
+    fn return_book(&mut self) {
+        self.available += 1;
+        self.lent -= 1;
+    }
+}
+
+#[kani::proof_for_contract(Library::lend, Library::return_book)]
+fn check_lend_and_return() {
+    let mut library = Library { available: kani::any_where(|n| *n < 10), lent: 0 };
+    kani::assume(library.available > 0);
+    library.lend();
+    library.return_book();
+}
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the contract of a mutually recursive function is used as the
+//! induction hypothesis when the function is called again through another
+//! function, and that a harness may check both contracts.
+
+#[kani::ensures(result == (n % 2 == 0))]
+fn is_even(n: u32) -> bool {
+    if n == 0 { true } else { is_odd(n - 1) }
+}
+
+#[kani::ensures(result == (n % 2 == 1))]
+fn is_odd(n: u32) -> bool {
+    if n == 0 { false } else { is_even(n - 1) }
+}
+
+#[kani::proof_for_contract(is_even)]
+fn is_even_harness() {
+    is_even(kani::any());
+}
+
+#[kani::proof_for_contract(is_even, is_odd)]
+fn both_harness() {
+    is_odd(kani::any());
+    is_even(kani::any());
+}
@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old(*ptr)` is not affected by a write through the `&mut u32`
+//! argument, i.e., the snapshot is a copy of the value and not of the
+//! reference.
+
+#[kani::requires(*ptr < 100)]
+#[kani::modifies(ptr)]
+#[kani::ensures(old(*ptr) == *ptr)]
+fn modify(ptr: &mut u32) {
+    *ptr += 1;
+}
+
+#[kani::proof_for_contract(modify)]
+fn modify_harness() {
+    let mut value: u32 = kani::any();
+    modify(&mut value);
+}
@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old(*ptr)` captures the value behind a `&mut u32` on entry, so
+//! that it can be compared to the value the function leaves behind.
+
+#[kani::requires(*ptr < 100)]
+#[kani::modifies(ptr)]
+#[kani::ensures(*ptr == old(*ptr) + 1)]
+fn increment(ptr: &mut u32) {
+    *ptr += 1;
+}
+
+#[kani::modifies(ptr)]
+#[kani::ensures(old(*ptr) == *ptr)]
+fn modify(ptr: &mut u32) {
+    let value = *ptr;
+    *ptr = 0;
+    *ptr = value;
+}
+
+#[kani::proof_for_contract(increment)]
+fn increment_harness() {
+    let mut value: u32 = kani::any();
+    increment(&mut value);
+}
+
+#[kani::proof_for_contract(modify)]
+fn modify_harness() {
+    let mut value: u32 = kani::any();
+    modify(&mut value);
+}
@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a postcondition comparing an `old` expression over a `&mut`
+//! argument with its final value is refuted if the function writes a
+//! different value through the reference.
+
+#[kani::requires(*ptr < 100)]
+#[kani::modifies(ptr)]
+#[kani::ensures(old(*ptr + 1) == *ptr)]
+fn add1(ptr: &mut u32) {
+    *ptr += 2;
+}
+
+#[kani::proof_for_contract(add1)]
+fn add1_harness() {
+    let mut i = kani::any();
+    add1(&mut i);
+}
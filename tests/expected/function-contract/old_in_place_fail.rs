@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old(*v)` captures the contents of a `Vec` on entry, and does not
+//! share its buffer with the argument.
+
+#[kani::requires(v.len() == 2)]
+#[kani::modifies(&v[0], &v[1])]
+#[kani::ensures(v.len() == old(v.len()))]
+#[kani::ensures(*v == old(*v))]
+fn normalize(v: &mut Vec<u32>) {
+    if v[0] > v[1] {
+        v.swap(0, 1);
+    }
+}
+
+#[kani::proof_for_contract(normalize)]
+#[kani::unwind(3)]
+fn normalize_harness() {
+    let mut v = vec![kani::any(), kani::any()];
+    normalize(&mut v);
+}
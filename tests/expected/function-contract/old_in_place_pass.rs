@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check a contract of an in-place transformation of a `Vec` that relates the
+//! result to the value on entry via `old(*v)` and `old(v.len())`.
+
+#[kani::requires(v.len() == 2)]
+#[kani::modifies(&v[0], &v[1])]
+#[kani::ensures(v.len() == old(v.len()))]
+#[kani::ensures(v[0] <= v[1] && old(*v).contains(&v[0]) && old(*v).contains(&v[1]))]
+fn normalize(v: &mut Vec<u32>) {
+    if v[0] > v[1] {
+        v.swap(0, 1);
+    }
+}
+
+#[kani::proof_for_contract(normalize)]
+#[kani::unwind(3)]
+fn normalize_harness() {
+    let mut v = vec![kani::any(), kani::any()];
+    normalize(&mut v);
+}
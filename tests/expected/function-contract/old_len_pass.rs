@@ -0,0 +1,54 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old(self.field.len())` snapshots the length on entry without
+//! cloning `self`, so length deltas can be stated for types that are not
+//! `Clone`.
+
+struct Library {
+    available: Vec<u8>,
+    lent: Vec<u8>,
+}
+
+impl Library {
+    #[kani::modifies(self)]
+    #[kani::ensures(self.available.len() == old(self.available.len()) + 1)]
+    #[kani::ensures(self.lent.len() == old(self.lent.len()))]
+    fn donate(&mut self, book: u8) {
+        self.available.push(book);
+    }
+
+    #[kani::requires(!self.available.is_empty())]
+    #[kani::modifies(self)]
+    #[kani::ensures(self.available.len() == old(self.available.len()) - 1)]
+    #[kani::ensures(self.lent.len() == old(self.lent.len()) + 1)]
+    fn lend(&mut self) {
+        let book = self.available.pop().unwrap();
+        self.lent.push(book);
+    }
+}
+
+fn any_library() -> Library {
+    let mut available = Vec::with_capacity(4);
+    let mut lent = Vec::with_capacity(4);
+    if kani::any() {
+        available.push(kani::any());
+    }
+    if kani::any() {
+        lent.push(kani::any());
+    }
+    Library { available, lent }
+}
+
+#[kani::proof_for_contract(Library::donate)]
+fn donate_harness() {
+    let mut library = any_library();
+    library.donate(kani::any());
+}
+
+#[kani::proof_for_contract(Library::lend)]
+fn lend_harness() {
+    let mut library = any_library();
+    library.lend();
+}
@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that an `old` expression over a `&mut` argument is evaluated on
+//! entry, before the function writes through the reference, both when the
+//! contract is checked and when it replaces the function.
+
+#[kani::requires(*ptr < 100)]
+#[kani::modifies(ptr)]
+#[kani::ensures(old(*ptr + 1) == *ptr)]
+fn add1(ptr: &mut u32) {
+    *ptr += 1;
+}
+
+#[kani::proof_for_contract(add1)]
+fn add1_harness() {
+    let mut i = kani::any();
+    add1(&mut i);
+}
+
+#[kani::proof]
+#[kani::stub_verified(add1)]
+fn add1_stub_harness() {
+    let mut i: u32 = kani::any_where(|i| *i < 100);
+    let before = i;
+    add1(&mut i);
+    assert_eq!(i, before + 1);
+}
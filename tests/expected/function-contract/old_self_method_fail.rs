@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old(self.method(..))` does not observe the modifications of the
+//! function body. Only the result of the method is snapshotted, `self` is not
+//! cloned.
+
+#[derive(kani::Arbitrary)]
+struct Library {
+    books: [Option<u8>; 2],
+}
+
+impl Library {
+    fn book(&self, book_id: usize) -> Option<&u8> {
+        self.books[book_id].as_ref()
+    }
+
+    #[kani::requires(book_id < 2)]
+    #[kani::modifies(self)]
+    #[kani::ensures(self.book(book_id).copied() == old(self.book(book_id).copied()))]
+    fn rename(&mut self, book_id: usize, title: u8) {
+        self.books[book_id] = Some(title);
+    }
+}
+
+#[kani::proof_for_contract(Library::rename)]
+fn rename_harness() {
+    let mut library: Library = kani::any();
+    library.rename(kani::any(), kani::any());
+}
@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old(self.method(..))` evaluates the method against the state of
+//! `self` on entry without `Self: Clone`. A result that borrows from `self` is
+//! copied so that the snapshot does not borrow from it.
+
+#[derive(kani::Arbitrary)]
+struct Library {
+    books: [Option<u8>; 2],
+}
+
+impl Library {
+    fn book_exists(&self, book_id: usize) -> bool {
+        self.books[book_id].is_some()
+    }
+
+    fn book(&self, book_id: usize) -> Option<&u8> {
+        self.books[book_id].as_ref()
+    }
+
+    #[kani::requires(book_id < 2)]
+    #[kani::modifies(self)]
+    #[kani::ensures(old(self.book_exists(book_id)) == result.is_some())]
+    #[kani::ensures(result == old(self.book(book_id).copied()))]
+    #[kani::ensures(!self.book_exists(book_id))]
+    fn remove(&mut self, book_id: usize) -> Option<u8> {
+        self.books[book_id].take()
+    }
+}
+
+#[kani::proof_for_contract(Library::remove)]
+fn remove_harness() {
+    let mut library: Library = kani::any();
+    library.remove(kani::any());
+}
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that an `old` snapshot of a computed predicate detects an insertion
+//! that breaks the order of a buffer.
+
+#[derive(kani::Arbitrary)]
+struct Buffer {
+    v: [u8; 3],
+}
+
+impl Buffer {
+    /// Drops the smallest element and inserts `x` in its place.
+    #[kani::modifies(self)]
+    #[kani::ensures(!old(kani::is_sorted(&self.v)) || kani::is_sorted(&self.v))]
+    fn insert(&mut self, x: u8) {
+        // Bug: does not move `x` to its position.
+        self.v[0] = x;
+    }
+}
+
+#[kani::proof_for_contract(Buffer::insert)]
+#[kani::unwind(4)]
+fn insert_harness() {
+    let mut buffer: Buffer = kani::any();
+    buffer.insert(kani::any());
+}
@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that an `old` snapshot of a computed predicate can state that an
+//! insertion preserves the order of a buffer.
+
+#[derive(kani::Arbitrary)]
+struct Buffer {
+    v: [u8; 3],
+}
+
+impl Buffer {
+    /// Drops the smallest element and inserts `x` in its place.
+    #[kani::modifies(self)]
+    #[kani::ensures(!old(kani::is_sorted(&self.v)) || kani::is_sorted(&self.v))]
+    fn insert(&mut self, x: u8) {
+        let mut i = 0;
+        while i + 1 < self.v.len() && self.v[i + 1] < x {
+            self.v[i] = self.v[i + 1];
+            i += 1;
+        }
+        self.v[i] = x;
+    }
+}
+
+#[kani::proof_for_contract(Buffer::insert)]
+#[kani::unwind(4)]
+fn insert_harness() {
+    let mut buffer: Buffer = kani::any();
+    buffer.insert(kani::any());
+}
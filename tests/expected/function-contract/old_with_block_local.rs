@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old_with` inside a block cannot refer to a variable bound in
+//! that block, since the snapshot is taken before the function runs.
+
+#[kani::ensures({
+    let doubled = x * 2;
+    result == old_with(doubled, |d: u8| d)
+})]
+fn double(x: u8) -> u8 {
+    x * 2
+}
+
+#[kani::proof_for_contract(double)]
+fn double_harness() {
+    double(kani::any());
+}
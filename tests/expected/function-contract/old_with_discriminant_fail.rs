@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a postcondition on the discriminant of the receiver fails if the
+//! method changes the variant.
+
+#[derive(kani::Arbitrary)]
+enum Connection {
+    Idle,
+    Open { sent: u32 },
+    Closed(u8),
+}
+
+impl Connection {
+    #[kani::modifies(self)]
+    #[kani::ensures(std::mem::discriminant(self) == old_with(&*self, std::mem::discriminant))]
+    fn send(&mut self) {
+        match self {
+            Connection::Open { sent } if *sent == u32::MAX => *self = Connection::Closed(0),
+            Connection::Open { sent } => *sent += 1,
+            _ => {}
+        }
+    }
+}
+
+#[kani::proof_for_contract(Connection::send)]
+fn send_harness() {
+    let mut connection: Connection = kani::any();
+    connection.send();
+}
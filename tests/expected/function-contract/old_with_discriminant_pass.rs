@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a postcondition can compare the discriminant of the receiver
+//! against an entry snapshot taken with `old_with`.
+
+#[derive(kani::Arbitrary)]
+enum Connection {
+    Idle,
+    Open { sent: u32 },
+    Closed(u8),
+}
+
+impl Connection {
+    #[kani::modifies(self)]
+    #[kani::ensures(std::mem::discriminant(self) == old_with(&*self, std::mem::discriminant))]
+    fn send(&mut self) {
+        if let Connection::Open { sent } = self {
+            *sent = sent.wrapping_add(1);
+        }
+    }
+}
+
+#[kani::proof_for_contract(Connection::send)]
+fn send_harness() {
+    let mut connection: Connection = kani::any();
+    connection.send();
+}
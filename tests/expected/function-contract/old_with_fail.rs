@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that an `old_with` snapshot is taken before the function body runs,
+//! so a postcondition that claims nothing changed is refuted.
+
+#[derive(kani::Arbitrary)]
+struct Stats {
+    count: u32,
+}
+
+#[kani::requires(s.count < u32::MAX)]
+#[kani::modifies(s)]
+#[kani::ensures(s.count == old_with(&*s, |s: &Stats| s.count))]
+fn bump(s: &mut Stats) {
+    s.count += 1;
+}
+
+#[kani::proof_for_contract(bump)]
+fn bump_harness() {
+    let mut s: Stats = kani::any();
+    bump(&mut s);
+}
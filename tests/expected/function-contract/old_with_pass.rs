@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old_with` snapshots a computed summary of a non-`Clone`
+//! argument on entry to the function.
+
+#[derive(kani::Arbitrary)]
+struct Stats {
+    count: u32,
+    total: u64,
+}
+
+#[kani::requires(s.count < u32::MAX && s.total < u64::MAX - 255)]
+#[kani::modifies(s)]
+#[kani::ensures(s.count == old_with(&*s, |s: &Stats| s.count) + 1)]
+#[kani::ensures(s.total == kani::old_with(&*s, |s: &Stats| s.total) + v as u64)]
+fn record(s: &mut Stats, v: u8) {
+    s.count += 1;
+    s.total += v as u64;
+}
+
+#[kani::proof_for_contract(record)]
+fn record_harness() {
+    let mut s: Stats = kani::any();
+    record(&mut s, kani::any());
+}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --precondition-call-site
+
+//! Check that a client passing an out-of-range argument to a function replaced
+//! by its contract is reported as a precondition violation at the call site.
+
+#[kani::requires(percent <= 100)]
+#[kani::ensures(result <= total)]
+fn share(total: u32, percent: u32) -> u32 {
+    (total as u64 * percent as u64 / 100) as u32
+}
+
+fn client(total: u32) -> u32 {
+    share(total, 120)
+}
+
+#[kani::proof]
+#[kani::stub_verified(share)]
+fn client_harness() {
+    let _ = client(kani::any());
+}
@@ -0,0 +1,39 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that one predicate can be reused in the contracts of several
+//! functions.
+
+#[derive(Clone, Copy, kani::Arbitrary)]
+struct Range {
+    lo: u8,
+    hi: u8,
+}
+
+#[kani::predicate]
+fn valid(r: &Range) -> bool {
+    r.lo <= r.hi
+}
+
+#[kani::requires(valid(&r))]
+#[kani::ensures(valid(&result))]
+fn widen(r: Range) -> Range {
+    Range { lo: r.lo.saturating_sub(1), hi: r.hi.saturating_add(1) }
+}
+
+#[kani::requires(valid(&r) && valid(&s))]
+#[kani::ensures(valid(&result))]
+fn hull(r: Range, s: Range) -> Range {
+    Range { lo: r.lo.min(s.lo), hi: r.hi.max(s.hi) }
+}
+
+#[kani::proof_for_contract(widen)]
+fn widen_harness() {
+    let _ = widen(kani::any());
+}
+
+#[kani::proof_for_contract(hull)]
+fn hull_harness() {
+    let _ = hull(kani::any(), kani::any());
+}
@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that Kani rejects a predicate that writes to memory.
+
+static mut EVALUATIONS: u32 = 0;
+
+#[kani::predicate]
+fn is_positive(x: i32) -> bool {
+    unsafe { EVALUATIONS += 1 };
+    x > 0
+}
+
+#[kani::requires(is_positive(x))]
+#[kani::ensures(is_positive(result))]
+fn decrement_abs(x: i32) -> i32 {
+    if x > 1 { x - 1 } else { x }
+}
+
+#[kani::proof_for_contract(decrement_abs)]
+fn decrement_abs_harness() {
+    let _ = decrement_abs(kani::any());
+}
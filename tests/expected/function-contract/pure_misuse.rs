@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that Kani rejects `#[kani::pure]` on a function without a contract
+//! and on a function whose contract has a `modifies` clause.
+
+#[kani::pure]
+fn no_contract(x: u32) -> u32 {
+    x
+}
+
+#[kani::pure]
+#[kani::modifies(x)]
+fn with_modifies(x: &mut u32) {
+    *x = 0;
+}
+
+#[kani::proof_for_contract(with_modifies)]
+fn check_with_modifies() {
+    let mut x = kani::any();
+    with_modifies(&mut x);
+    let _ = no_contract(x);
+}
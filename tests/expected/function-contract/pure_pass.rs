@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a `#[kani::pure]` function can be checked and used as a
+//! verified stub, and that the stub leaves the caller's memory untouched.
+
+#[kani::pure]
+#[kani::requires(!v.is_empty())]
+#[kani::ensures(v.contains(&result))]
+fn first(v: &[u32]) -> u32 {
+    v[0]
+}
+
+#[kani::proof_for_contract(first)]
+fn check_first() {
+    let v: [u32; 3] = kani::any();
+    let _ = first(&v);
+}
+
+#[kani::proof]
+#[kani::stub_verified(first)]
+fn use_first() {
+    let v = [1, 2, 3];
+    let x = first(&v);
+    assert!(x >= 1 && x <= 3);
+    assert_eq!(v, [1, 2, 3]);
+}
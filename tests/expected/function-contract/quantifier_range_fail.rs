@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a `kani::exists` over an explicit range fails when no element of
+//! the range satisfies it.
+
+#[kani::requires(v.len() <= 4)]
+#[kani::ensures(kani::exists(|i in 0..=v.len()| i < v.len() && v[i] > result))]
+fn max(v: &[u8]) -> u8 {
+    v.iter().copied().max().unwrap_or(0)
+}
+
+#[kani::proof_for_contract(max)]
+#[kani::unwind(6)]
+fn max_harness() {
+    let arr: [u8; 4] = kani::any();
+    let len: usize = kani::any_where(|len| *len <= 4);
+    max(&arr[..len]);
+}
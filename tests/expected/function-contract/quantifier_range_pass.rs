@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `kani::forall` and `kani::exists` over a range whose bound is
+//! not a literal are lowered to loops over the range, both when the contract
+//! is checked and when it is used as a stub.
+
+#[kani::requires(v.len() <= 4)]
+#[kani::ensures(kani::forall(|i| i < v.len() ==> v[i] <= result))]
+#[kani::ensures(v.is_empty() || kani::exists(|i in 0..v.len()| v[i] == result))]
+fn max(v: &[u8]) -> u8 {
+    let mut max = 0;
+    for &x in v {
+        if x > max {
+            max = x;
+        }
+    }
+    max
+}
+
+#[kani::proof_for_contract(max)]
+#[kani::unwind(5)]
+fn max_harness() {
+    let arr: [u8; 4] = kani::any();
+    let len: usize = kani::any_where(|len| *len <= 4);
+    max(&arr[..len]);
+}
+
+#[kani::proof]
+#[kani::stub_verified(max)]
+#[kani::unwind(5)]
+fn max_replaced() {
+    let arr: [u8; 3] = kani::any();
+    let m = max(&arr);
+    assert!(m >= arr[1]);
+    assert!(m == arr[0] || m == arr[1] || m == arr[2]);
+}
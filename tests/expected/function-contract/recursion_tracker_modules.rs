@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Two recursive functions with the same name in different modules must not
+//! share a recursion tracker. The functions are token for token identical, so
+//! the macros generate the same hash, recursion wrapper and tracker names for
+//! both. Only the `LIMIT` they refer to differs.
+
+mod first {
+    const LIMIT: u8 = 5;
+
+    #[kani::requires(n <= LIMIT)]
+    #[kani::ensures(result == 0)]
+    pub fn countdown(n: u8) -> u8 {
+        if n == 0 { 0 } else { countdown(n - 1) }
+    }
+}
+
+mod second {
+    const LIMIT: u8 = 10;
+
+    #[kani::requires(n <= LIMIT)]
+    #[kani::ensures(result == 0)]
+    pub fn countdown(n: u8) -> u8 {
+        if n == 0 { 0 } else { countdown(n - 1) }
+    }
+}
+
+#[kani::proof_for_contract(first::countdown)]
+fn first_harness() {
+    let _ = first::countdown(kani::any());
+}
+
+#[kani::proof_for_contract(second::countdown)]
+fn second_harness() {
+    let _ = second::countdown(kani::any());
+}
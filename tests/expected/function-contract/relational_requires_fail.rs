@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a precondition relating several arguments excludes calls that
+//! violate the relation when the function is replaced by its contract.
+
+#[kani::requires(start <= end && end <= buf.len())]
+#[kani::ensures(result == end - start)]
+fn range_len(buf: &[u8], start: usize, end: usize) -> usize {
+    buf[start..end].len()
+}
+
+#[kani::proof]
+#[kani::stub_verified(range_len)]
+fn range_len_replaced() {
+    let buf: [u8; 4] = kani::any();
+    range_len(&buf, 2, 5);
+}
@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a precondition relating several arguments, one of them through a
+//! method call, is assumed when checking the contract. Without it the slice
+//! operation in `range_len` could panic.
+
+#[kani::requires(start <= end && end <= buf.len())]
+#[kani::ensures(result == end - start)]
+fn range_len(buf: &[u8], start: usize, end: usize) -> usize {
+    buf[start..end].len()
+}
+
+#[kani::proof_for_contract(range_len)]
+fn range_len_harness() {
+    let buf: [u8; 4] = kani::any();
+    range_len(&buf, kani::any(), kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(range_len)]
+fn range_len_replaced() {
+    let buf: [u8; 4] = kani::any();
+    assert_eq!(range_len(&buf, 1, 4), 3);
+}
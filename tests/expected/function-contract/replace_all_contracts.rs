@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --replace-all-contracts
+
+//! Check that `--replace-all-contracts` replaces the callees of the function
+//! under contract by their contracts. The body of `half` would violate the
+//! contract of `quarter`, but only the contract of `half` is used.
+
+#[kani::ensures(result <= x / 2)]
+fn half(x: u32) -> u32 {
+    x
+}
+
+#[kani::ensures(result <= x / 4)]
+fn quarter(x: u32) -> u32 {
+    half(half(x))
+}
+
+#[kani::proof_for_contract(quarter)]
+fn quarter_harness() {
+    quarter(kani::any());
+}
@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `kani::result()` refers to the return value even if an argument
+//! is called `result`.
+
+#[kani::requires(result < 100)]
+#[kani::ensures(kani::result() == result + 1)]
+fn successor(result: u32) -> u32 {
+    result + 1
+}
+
+#[kani::proof_for_contract(successor)]
+fn check_successor() {
+    successor(kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(successor)]
+fn use_successor() {
+    assert_eq!(successor(41), 42);
+}
@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::result()` fails verification outside of a postcondition.
+
+#[kani::proof]
+fn result_in_harness() {
+    let value: u32 = kani::result();
+    assert!(value > 0);
+}
@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `stub_verified` requires the `function-contracts` feature, like
+//! `proof_for_contract`.
+
+#[kani::ensures(result < 10)]
+fn small() -> u32 {
+    3
+}
+
+#[kani::proof]
+#[kani::stub_verified(small)]
+fn small_harness() {
+    assert!(small() < 10);
+}
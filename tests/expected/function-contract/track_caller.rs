@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that contracts work on `#[track_caller]` functions, both when checking
+//! the contract and when using it as a verified stub.
+
+#[kani::requires(divisor != 0)]
+#[kani::ensures(result <= dividend)]
+#[track_caller]
+fn checked_div(dividend: u32, divisor: u32) -> u32 {
+    if divisor == 0 {
+        panic!("division by zero");
+    }
+    dividend / divisor
+}
+
+#[kani::proof_for_contract(checked_div)]
+fn checked_div_harness() {
+    checked_div(kani::any(), kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(checked_div)]
+fn checked_div_stub_harness() {
+    let dividend: u32 = kani::any();
+    assert!(checked_div(dividend, 3) <= dividend);
+}
@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a contract on a method provided by a trait can be checked for
+//! every implementor that inherits it, and used as a stub.
+
+trait Counter {
+    fn count(&self) -> u32;
+
+    #[kani::requires(self.count() < u32::MAX)]
+    #[kani::ensures(result == self.count() + 1)]
+    fn next(&self) -> u32 {
+        self.count() + 1
+    }
+}
+
+struct Zero;
+
+impl Counter for Zero {
+    fn count(&self) -> u32 {
+        0
+    }
+}
+
+struct Fixed(u32);
+
+impl Counter for Fixed {
+    fn count(&self) -> u32 {
+        self.0
+    }
+}
+
+#[kani::proof_for_contract(Counter::next)]
+fn check_next_zero() {
+    Zero.next();
+}
+
+#[kani::proof_for_contract(Counter::next)]
+fn check_next_fixed() {
+    Fixed(kani::any()).next();
+}
+
+#[kani::proof]
+#[kani::stub_verified(Counter::next)]
+fn use_next() {
+    let counter = Fixed(kani::any_where(|n| *n < 10));
+    assert!(counter.next() <= 10);
+}
@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Checks that a postcondition which only fails when a function returns early
+//! through the `?` operator is reported.
+
+enum ParseError {
+    NotDigit(u8),
+}
+
+fn digit(c: u8) -> Result<u8, ParseError> {
+    if c.is_ascii_digit() { Ok(c - b'0') } else { Err(ParseError::NotDigit(c)) }
+}
+
+/// Wrong for an invalid first character, which returns at the first `?`.
+fn rejects_last(input: [u8; 2], result: &Result<u8, ParseError>) -> bool {
+    match result {
+        Ok(n) => *n < 100,
+        Err(ParseError::NotDigit(c)) => *c == input[1],
+    }
+}
+
+#[kani::ensures(rejects_last(input, &result))]
+fn parse(input: [u8; 2]) -> Result<u8, ParseError> {
+    let tens = digit(input[0])?;
+    let ones = digit(input[1])?;
+    Ok(tens * 10 + ones)
+}
+
+#[kani::proof_for_contract(parse)]
+fn parse_harness() {
+    let _ = parse(kani::any());
+}
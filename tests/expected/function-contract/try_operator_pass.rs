@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Checks that postconditions are checked on every exit of a function that
+//! uses the `?` operator, i.e. also when it returns early with an error.
+
+enum ParseError {
+    NotDigit(u8),
+}
+
+fn digit(c: u8) -> Result<u8, ParseError> {
+    if c.is_ascii_digit() { Ok(c - b'0') } else { Err(ParseError::NotDigit(c)) }
+}
+
+fn well_formed(input: [u8; 2], result: &Result<u8, ParseError>) -> bool {
+    match result {
+        Ok(n) => *n < 100,
+        Err(ParseError::NotDigit(c)) => !c.is_ascii_digit() && input.contains(c),
+    }
+}
+
+#[kani::ensures(well_formed(input, &result))]
+fn parse(input: [u8; 2]) -> Result<u8, ParseError> {
+    let tens = digit(input[0])?;
+    let ones = digit(input[1])?;
+    Ok(tens * 10 + ones)
+}
+
+#[kani::proof_for_contract(parse)]
+fn parse_harness() {
+    let _ = parse(kani::any());
+}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zloop-contracts
+
+//! Check that a loop with a contract is verified without unwinding it, even
+//! though its number of iterations is unbounded.
+
+#![feature(stmt_expr_attributes)]
+#![feature(proc_macro_hygiene)]
+
+#[kani::proof]
+fn count_down() {
+    let mut x: u64 = kani::any_where(|i| *i >= 1);
+
+    #[kani::loop_contract(invariant = x >= 1, assigns = [x])]
+    while x > 1 {
+        x = x - 1;
+    }
+
+    assert!(x == 1);
+}
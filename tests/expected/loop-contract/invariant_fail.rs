@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zloop-contracts
+
+//! Check that Kani reports a loop invariant that is not preserved by the loop
+//! body.
+
+#![feature(stmt_expr_attributes)]
+#![feature(proc_macro_hygiene)]
+
+#[kani::proof]
+fn count_down_by_two() {
+    let mut x: u64 = kani::any_where(|i| *i % 2 == 1);
+
+    #[kani::loop_contract(invariant = x % 2 == 1, assigns = [x])]
+    loop {
+        if x < 2 {
+            break;
+        }
+        x = x - 1;
+    }
+
+    assert!(x == 1);
+}
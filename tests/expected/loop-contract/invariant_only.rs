@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zloop-contracts
+
+//! Check that a loop with only an invariant is verified without unwinding it,
+//! with the places the loop modifies inferred by CBMC.
+
+#![feature(stmt_expr_attributes)]
+#![feature(proc_macro_hygiene)]
+
+#[kani::proof]
+fn count_up() {
+    let bound: u8 = kani::any();
+    let mut x: u8 = 0;
+
+    #[kani::invariant(x <= bound)]
+    while x < bound {
+        x = x + 1;
+    }
+
+    assert!(x == bound);
+}
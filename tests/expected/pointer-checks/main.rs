@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `#[kani::pointer_checks]` enables pointer overflow checks for a
+//! single harness without affecting the other harnesses. Both harnesses run the
+//! same pointer arithmetic and only differ in the attribute.
+
+fn offset_past_end() {
+    let a = [0; 5];
+    let ptr: *const i32 = &a[1];
+    let _ = ptr.wrapping_offset(10);
+}
+
+#[kani::proof]
+#[kani::pointer_checks(overflow = true)]
+fn check_with_overflow() {
+    offset_past_end();
+}
+
+#[kani::proof]
+#[kani::pointer_checks(overflow = false)]
+fn check_without_overflow() {
+    offset_past_end();
+}
@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `proof_idempotent` fails for a function that is not idempotent.
+
+fn increment(x: u8) -> u8 {
+    x.saturating_add(1)
+}
+
+#[kani::proof_idempotent(increment)]
+fn increment_is_idempotent() {}
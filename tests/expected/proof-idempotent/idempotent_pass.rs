@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `proof_idempotent` succeeds for an idempotent function.
+
+fn clamp(x: u8) -> u8 {
+    x.min(100)
+}
+
+#[kani::proof_idempotent(clamp)]
+fn clamp_is_idempotent() {}
@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the preconditions of a function with a contract are assumed for
+//! both applications. Without them `x / x` would divide by zero.
+
+#[kani::requires(x != 0)]
+#[kani::ensures(result != 0)]
+fn identity(x: u8) -> u8 {
+    x / x * x
+}
+
+#[kani::proof_idempotent(identity)]
+fn identity_is_idempotent() {}
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that a harness marked with `#[kani::slow]` is skipped when no harness
+//! is selected explicitly.
+
+#[kani::proof]
+fn quick() {
+    let x: u8 = kani::any();
+    assert!(x.checked_add(0).is_some());
+}
+
+#[kani::proof]
+#[kani::slow]
+fn expensive() {
+    let x: u8 = kani::any();
+    assert!(x == 0);
+}
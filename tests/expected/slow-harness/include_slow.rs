@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --include-slow
+
+//! Check that `--include-slow` also verifies harnesses marked with
+//! `#[kani::slow]`.
+
+#[kani::proof]
+fn quick() {
+    let x: u8 = kani::any();
+    assert!(x.checked_add(0).is_some());
+}
+
+#[kani::proof]
+#[kani::slow]
+fn expensive() {
+    let x: u8 = kani::any();
+    assert!(x.wrapping_mul(2) % 2 == 0);
+}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --default-unwind 2
+
+//! Check that `#[kani::unwind(0)]` does not bound the loops of a harness and
+//! turns off its unwinding assertions, so the default unwind value of 2 does
+//! not apply to the loop below.
+
+fn my_sum(vec: &Vec<u32>) -> u32 {
+    let mut sum = 0;
+    for elem in vec {
+        sum += elem;
+    }
+    sum
+}
+
+#[kani::proof]
+#[kani::unwind(0)]
+fn check_sum() {
+    let vec = vec![1, 2, 3, 4, 5];
+    assert!(my_sum(&vec) == 15);
+}
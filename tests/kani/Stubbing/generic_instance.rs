@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness main -Z stubbing
+//
+//! This tests stubbing a single instance of a generic function, which leaves the other
+//! instances untouched.
+
+fn decode<T: Default>() -> T {
+    T::default()
+}
+
+fn decode_u32() -> u32 {
+    42
+}
+
+#[kani::proof]
+#[kani::stub(decode::<u32>, decode_u32)]
+fn main() {
+    assert_eq!(decode::<u32>(), 42);
+    assert_eq!(decode::<u64>(), 0);
+    assert_eq!(decode::<(u32, bool)>(), (0, false));
+}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! A contract harness whose function under contract calls a helper without a contract, which
+//! `changed_only_helper.sh` changes between runs.
+
+fn step() -> u32 {
+    1
+}
+
+#[kani::requires(*x < 100)]
+#[kani::ensures(|_| *x > old(*x))]
+#[kani::modifies(x)]
+fn increment(x: &mut u32) {
+    *x += step();
+}
+
+#[kani::proof_for_contract(increment)]
+fn check_increment() {
+    let mut x: u32 = kani::any();
+    increment(&mut x);
+}
@@ -0,0 +1,32 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `--changed-only` verifies a contract harness again when only a helper without a
+# contract that the function under contract calls changes.
+
+set +e
+
+OUT_DIR=tmp_changed_only_helper
+rm -rf ${OUT_DIR}
+mkdir ${OUT_DIR}
+cp changed_only_helper.rs ${OUT_DIR}/lib.rs
+
+function run_kani() {
+    kani ${OUT_DIR}/lib.rs -Z function-contracts --target-dir ${OUT_DIR} --changed-only \
+        | grep -E "^(Checking harness|Skipped harness|Complete|VERIFICATION)"
+}
+
+echo "[TEST] First run..."
+run_kani
+
+echo "[TEST] Unchanged..."
+run_kani
+
+echo "[TEST] Changed helper..."
+sed -i 's/^    1$/    0/' ${OUT_DIR}/lib.rs
+run_kani
+
+echo "[TEST] Changed helper again..."
+run_kani
+
+rm -rf ${OUT_DIR}
@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! A contract harness that `changed_only_options.sh` verifies with different options.
+
+#[kani::requires(*x < 100)]
+#[kani::ensures(|_| *x > old(*x))]
+#[kani::modifies(x)]
+fn increment(x: &mut u32) {
+    *x += 1;
+}
+
+#[kani::proof_for_contract(increment)]
+fn check_increment() {
+    let mut x: u32 = kani::any();
+    increment(&mut x);
+}
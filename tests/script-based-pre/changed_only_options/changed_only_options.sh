@@ -0,0 +1,36 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `--changed-only` verifies an unchanged harness again when the options change, and
+# that a run which only enforces some contract clauses does not mark the harness as verified.
+
+set +e
+
+OUT_DIR=tmp_changed_only_options
+rm -rf ${OUT_DIR}
+mkdir ${OUT_DIR}
+
+function run_kani() {
+    kani changed_only.rs -Z function-contracts --target-dir ${OUT_DIR} --changed-only "$@" \
+        | grep -E "^(Checking harness|Skipped harness|Complete)"
+}
+
+echo "[TEST] First run..."
+run_kani
+
+echo "[TEST] Same options..."
+run_kani
+
+echo "[TEST] Only enforce the modifies clause..."
+run_kani --enforce-clauses=assigns
+
+echo "[TEST] Only enforce the modifies clause again..."
+run_kani --enforce-clauses=assigns
+
+echo "[TEST] Other options..."
+run_kani --default-unwind 2
+
+echo "[TEST] Other options again..."
+run_kani --default-unwind 2
+
+rm -rf ${OUT_DIR}
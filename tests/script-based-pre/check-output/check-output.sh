@@ -67,6 +67,66 @@ done
 echo "Finished single-file check successfully..."
 echo
 
+echo "Running single-file check with demangled locals..."
+rm -rf *.c
+kani --gen-c --demangle-locals --enable-unstable singlefile.rs >& kani.log || \
+    { ret=$?; echo "== Failed to run Kani"; cat kani.log; rm kani.log; exit 1; }
+rm -f kani.log
+if ! grep -Fq 'struct PrettyStruct pretty_function(struct PrettyStruct argument)' singlefile_main.demangled.c;
+then
+    echo "Error: demangled file singlefile_main.demangled.c did not contain the source name of the argument."
+    exit 1
+fi
+echo "Finished single-file check with demangled locals successfully..."
+echo
+
+echo "Running single-file check with one C file for all harnesses..."
+rm -rf *.c
+kani --gen-c --gen-c-crate --enable-unstable singlefile.rs >& kani.log || \
+    { ret=$?; echo "== Failed to run Kani"; cat kani.log; rm kani.log; exit 1; }
+rm -f kani.log
+if [ -e singlefile_main.c ]
+then
+    echo "Error: unexpected GotoC file for a single harness: singlefile_main.c"
+    exit 1
+fi
+if ! grep -Fq 'struct PrettyStruct pretty_function(struct PrettyStruct' linked-harnesses.demangled.c;
+then
+    echo "Error: demangled file linked-harnesses.demangled.c did not contain expected demangled struct and function name."
+    exit 1
+fi
+rm -f linked-harnesses.c linked-harnesses.demangled.c
+echo "Finished single-file check with one C file for all harnesses successfully..."
+echo
+
+echo "Running multi-harness check with one C file for all harnesses..."
+kani --gen-c --gen-c-crate --enable-unstable multiharness.rs --harness first_harness \
+    --harness second_harness >& kani.log || \
+    { ret=$?; echo "== Failed to run Kani"; cat kani.log; rm kani.log; exit 1; }
+rm -f kani.log
+for function in only_in_first_harness only_in_second_harness
+do
+  if ! grep -Fq "$function(" linked-harnesses.demangled.c;
+  then
+    echo "Error: demangled file linked-harnesses.demangled.c did not contain $function."
+    exit 1
+  fi
+done
+rm -f linked-harnesses.c linked-harnesses.demangled.c
+if kani --gen-c --gen-c-crate --enable-unstable -Z stubbing multiharness.rs >& kani.log
+then
+  echo "Error: Kani accepted \`--gen-c-crate\` for a harness with stubs"
+  cat kani.log; rm kani.log; exit 1
+fi
+if ! grep -Fq 'does not support harnesses that use stubs or contracts: `stubbed_harness`' kani.log;
+then
+  echo "Error: Kani did not reject the harness with stubs"
+  cat kani.log; rm kani.log; exit 1
+fi
+rm -f kani.log linked-harnesses.c linked-harnesses.demangled.c
+echo "Finished multi-harness check with one C file for all harnesses successfully..."
+echo
+
 (cd multifile
 echo "Running multi-file check..."
 rm -rf build
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Each harness reaches a function that the other one does not.
+
+#[kani::proof]
+pub fn first_harness() {
+    assert_eq!(only_in_first_harness(1), 2);
+}
+
+#[kani::proof]
+pub fn second_harness() {
+    assert_eq!(only_in_second_harness(1), 0);
+}
+
+#[kani::proof]
+#[kani::stub(only_in_second_harness, only_in_first_harness)]
+pub fn stubbed_harness() {
+    assert_eq!(only_in_second_harness(1), 2);
+}
+
+pub fn only_in_first_harness(x: u32) -> u32 {
+    x + 1
+}
+
+pub fn only_in_second_harness(x: u32) -> u32 {
+    x - 1
+}
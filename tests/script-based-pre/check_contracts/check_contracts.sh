@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+set +e
+
+for crate in valid_crate invalid_crate; do
+    pushd ${crate} > /dev/null
+    cargo clean
+    echo "[TEST] Check contracts of ${crate}..."
+    cargo kani -Z function-contracts check-contracts
+    echo "[TEST] Exit code: $?"
+    echo "[TEST] Check contracts of ${crate} with standalone Kani..."
+    kani check-contracts -Z function-contracts --target-dir target/standalone src/lib.rs
+    echo "[TEST] Exit code: $?"
+    cargo clean
+    popd > /dev/null
+done
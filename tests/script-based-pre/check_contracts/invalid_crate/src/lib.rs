@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This crate has a harness that targets a function without a contract.
+
+pub fn no_contract(x: u32) -> u32 {
+    x
+}
+
+#[cfg(kani)]
+mod verify {
+    use super::*;
+
+    #[kani::proof_for_contract(no_contract)]
+    fn check_no_contract() {
+        no_contract(kani::any());
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This crate has a well-formed contract and a harness for it.
+
+#[cfg_attr(kani, kani::requires(divisor != 0))]
+#[cfg_attr(kani, kani::ensures(result <= dividend))]
+pub fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[cfg(kani)]
+mod verify {
+    use super::*;
+
+    #[kani::proof_for_contract(div)]
+    fn check_div() {
+        div(kani::any(), kani::any());
+    }
+}
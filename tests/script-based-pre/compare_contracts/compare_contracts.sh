@@ -0,0 +1,23 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+set +e
+
+OUT_DIR=tmp_compare_contracts
+rm -rf ${OUT_DIR}
+mkdir ${OUT_DIR}
+
+echo "[TEST] Export the contracts of the old version..."
+kani old.rs -Z function-contracts --target-dir ${OUT_DIR} --export-contracts ${OUT_DIR}/old.json --list-contracts > /dev/null
+echo "[TEST] Exit code: $?"
+
+echo "[TEST] Compare the new version against the old one..."
+kani new.rs -Z function-contracts --target-dir ${OUT_DIR} --compare-contracts ${OUT_DIR}/old.json
+echo "[TEST] Exit code: $?"
+
+echo "[TEST] Compare the old version against itself..."
+kani old.rs -Z function-contracts --target-dir ${OUT_DIR} --compare-contracts ${OUT_DIR}/old.json
+echo "[TEST] Exit code: $?"
+
+rm -rf ${OUT_DIR}
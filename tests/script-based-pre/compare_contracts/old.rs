@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The contracts before the change, see `new.rs`.
+
+#[kani::requires(x < 100)]
+#[kani::ensures(result > x)]
+fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+#[kani::modifies(out)]
+#[kani::ensures(*out == 0)]
+fn clear(out: &mut u32) {
+    *out = 0;
+}
+
+#[kani::ensures(result <= x)]
+fn halve(x: u32) -> u32 {
+    x / 2
+}
+
+/// No harness checks this contract, but it is compared all the same.
+#[kani::requires(x < 10)]
+fn triple(x: u32) -> u32 {
+    3 * x
+}
+
+#[kani::proof_for_contract(increment)]
+fn increment_harness() {
+    increment(kani::any());
+}
+
+#[kani::proof_for_contract(clear)]
+fn clear_harness() {
+    clear(&mut kani::any());
+}
@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A contract harness whose instrumented goto model is dumped.
+
+#[kani::requires(x < 100)]
+#[kani::ensures(result > x)]
+fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+#[kani::proof_for_contract(increment)]
+fn check_increment() {
+    increment(kani::any());
+}
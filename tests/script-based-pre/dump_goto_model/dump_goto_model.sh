@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `--dump-goto-model` writes the goto functions of each harness as JSON next to its goto
+# binary, and that the file is kept after verification.
+
+set +e
+
+OUT_DIR=tmp_dump_goto_model
+rm -rf ${OUT_DIR}
+mkdir ${OUT_DIR}
+
+echo "[TEST] Dump the goto model..."
+kani dump_goto_model.rs --target-dir ${OUT_DIR} -Z function-contracts --enable-unstable \
+    --dump-goto-model | grep -E "^(Instrumented goto model written to|VERIFICATION)" \
+    | sed -E 's/written to .*\.goto-model\.json$/written to FILE.goto-model.json/'
+
+echo "[TEST] Inspect the goto model..."
+MODELS=$(find ${OUT_DIR} -name "*.goto-model.json")
+echo "$(echo "${MODELS}" | wc -l) goto model(s)"
+grep -q '"functions"' ${MODELS} && echo "The goto model lists the goto functions"
+grep -q 'check_increment' ${MODELS} && echo "The goto model contains the harness"
+
+rm -rf ${OUT_DIR}
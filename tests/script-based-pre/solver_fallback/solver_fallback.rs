@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A harness whose first solver never answers, see `solver_fallback.sh`.
+
+#[kani::proof]
+#[kani::solver(bin = "unresponsive_solver", minisat)]
+fn check() {
+    let x: u8 = kani::any();
+    kani::assume(x < 10);
+    assert!(x + 1 <= 10);
+}
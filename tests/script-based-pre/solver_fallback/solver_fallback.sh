@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that Kani falls back to the next solver when one times out, and that the solver that
+# finished is recorded in the harness metadata. The first solver never answers, so it always
+# times out regardless of how fast the machine is.
+
+set +e
+
+OUT_DIR=tmp_solver_fallback
+rm -rf ${OUT_DIR}
+mkdir -p ${OUT_DIR}/bin
+
+cat > ${OUT_DIR}/bin/unresponsive_solver <<SOLVER
+#!/usr/bin/env bash
+sleep 30
+SOLVER
+chmod +x ${OUT_DIR}/bin/unresponsive_solver
+export PATH="$(pwd)/${OUT_DIR}/bin:${PATH}"
+
+echo "[TEST] Fall back to the next solver..."
+kani solver_fallback.rs --target-dir ${OUT_DIR} --solver-timeout 5 --keep-temps \
+    | grep -E "^(CBMC timed out|VERIFICATION|Solver:)"
+
+echo "[TEST] Inspect the metadata..."
+grep -h '"verified_with"' $(find ${OUT_DIR} -name "*.kani-metadata.json")
+
+rm -rf ${OUT_DIR}
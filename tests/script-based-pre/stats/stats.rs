@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Harnesses whose resource usage `stats.sh` reports.
+
+#[kani::proof]
+fn check_add() {
+    let x: u8 = kani::any();
+    kani::assume(x < 100);
+    assert!(x + 1 > x);
+}
+
+#[kani::proof]
+fn check_overflow() {
+    let x: u8 = kani::any();
+    let _ = x + 1;
+}
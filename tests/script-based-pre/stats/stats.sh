@@ -0,0 +1,23 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `--stats` reports the resources used by each harness, as text and as JSON. The
+# numbers depend on the machine, so we only check which entries are printed.
+
+set +e
+
+OUT_DIR=tmp_stats
+rm -rf ${OUT_DIR}
+mkdir ${OUT_DIR}
+
+echo "[TEST] Text statistics..."
+kani stats.rs --target-dir ${OUT_DIR} --stats \
+    | grep -A2 "^Statistics:" \
+    | sed -E 's/[0-9]+(\.[0-9]+)?/N/g'
+
+echo "[TEST] JSON statistics..."
+kani stats.rs --target-dir ${OUT_DIR} --stats=json \
+    | grep -oE '"(harness|status|runtime|peak_memory)": [^,]*' \
+    | sed -E 's/: [0-9]+(\.[0-9]+)?$/: N/'
+
+rm -rf ${OUT_DIR}
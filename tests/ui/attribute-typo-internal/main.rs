@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that an unknown `kanitool` attribute that is only close to an internal
+//! attribute (here `checked_with`) gets no suggestion. The expected output
+//! requires the next diagnostic to follow directly, which leaves no room for a
+//! `help` line.
+
+#[kanitool::proof]
+#[kanitool::checked_wit = "check"]
+#[kanitool::unwnd(2)]
+fn check() {}
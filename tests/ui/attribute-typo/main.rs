@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that an unknown `kanitool` attribute suggests the closest known one.
+
+#[kanitool::proof]
+#[kanitool::unwnd(2)]
+fn check() {}
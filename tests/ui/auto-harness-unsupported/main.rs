@@ -0,0 +1,35 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `#[kani::auto_harness]` rejects functions for which it cannot
+//! pick the arguments, and associated functions, including those that do not
+//! mention `Self`.
+
+#[kani::requires(N > 0)]
+#[kani::auto_harness]
+fn first<const N: usize>(arr: [u8; N]) -> u8 {
+    arr[0]
+}
+
+#[kani::requires(x < 100)]
+#[kani::auto_harness]
+fn apply(x: u8, f: impl Fn(u8) -> u8) -> u8 {
+    f(x)
+}
+
+struct Counter(u8);
+
+impl Counter {
+    #[kani::requires(self.0 < u8::MAX)]
+    #[kani::auto_harness]
+    fn bump(&mut self) {
+        self.0 += 1;
+    }
+
+    #[kani::requires(start < u8::MAX)]
+    #[kani::auto_harness]
+    fn starting_at(start: u8) -> Counter {
+        Counter(start)
+    }
+}
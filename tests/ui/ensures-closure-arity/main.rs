@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a closure-style postcondition is rejected if its tuple pattern
+//! does not match the returned tuple, or if it takes more than one argument.
+
+#[kani::ensures(|(low, mid, high)| low <= mid && mid <= high)]
+fn sort_pair(a: u32, b: u32) -> (u32, u32) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+#[kani::ensures(|low, high| low <= high)]
+fn sort_other_pair(a: u32, b: u32) -> (u32, u32) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+#[kani::proof_for_contract(sort_pair)]
+fn check_sort_pair() {
+    sort_pair(kani::any(), kani::any());
+}
+
+#[kani::proof_for_contract(sort_other_pair)]
+fn check_sort_other_pair() {
+    sort_other_pair(kani::any(), kani::any());
+}
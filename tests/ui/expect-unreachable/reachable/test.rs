@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that verification succeeds with `#[kani::expect_unreachable]` when
+//! some path of the harness satisfies all of its assumptions.
+
+#[kani::proof]
+#[kani::expect_unreachable]
+fn check() {
+    let x: u8 = kani::any();
+    kani::assume(x > 10);
+    kani::assume(x.checked_mul(x).is_some());
+}
@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that verification fails with `#[kani::expect_unreachable]` when
+//! the assumptions of the harness prune every path.
+
+#[kani::proof]
+#[kani::expect_unreachable]
+fn check() {
+    let x: u8 = kani::any();
+    kani::assume(x > 15);
+    kani::assume(x.checked_mul(x).is_some());
+}
@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `#[kani::expect_unreachable]` cannot be combined with
+//! `#[kani::should_panic]`.
+
+#[kani::proof]
+#[kani::expect_unreachable]
+#[kani::should_panic]
+fn check() {}
@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the error for a generic harness names its type and const
+//! parameters, but not its lifetimes.
+
+#[kani::proof]
+fn check_array<'a, T: kani::Arbitrary, const N: usize>() {
+    let array: [T; N] = kani::any();
+    let _: &[T] = &array;
+}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --output-format old --harness-timeout 1
+
+//! Checks that the harness timeout also stops CBMC when its output is printed directly with
+//! `--output-format old`, and that the remaining harnesses are still verified.
+
+#[kani::proof]
+fn check_too_hard() {
+    let a: u64 = kani::any();
+    let b: u64 = kani::any();
+    kani::assume(a > 1 && a < (1 << 32));
+    kani::assume(b > 1 && b < (1 << 32));
+    // The square of the largest 32-bit prime.
+    assert_ne!(a * b, 18446744030759878681);
+}
+
+#[kani::proof]
+fn check_easy() {
+    let x: u8 = kani::any();
+    assert_eq!(x.wrapping_add(0), x);
+}
@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the replace function of a function returning `impl Trait` with
+//! several contract clauses is generated for a function whose name contains
+//! `_check_`, like the name of its generated check function.
+
+fn bound_check_len(n: u32) -> u32 {
+    n.min(10)
+}
+
+#[kani::requires(n < 10)]
+#[kani::ensures(result.size_hint().0 == n as usize)]
+#[kani::ensures(result.size_hint().1 == Some(n as usize))]
+fn range_check_iter(n: u32) -> impl Iterator<Item = u32> {
+    0..bound_check_len(n)
+}
+
+#[kani::proof_for_contract(range_check_iter)]
+fn range_check_iter_harness() {
+    let _ = range_check_iter(kani::any());
+}
@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a `modifies` target behind a receiver that only gives shared
+//! access, such as `Rc<Self>` or `Pin<&Self>`, is rejected if it has no
+//! interior mutability.
+
+use std::pin::Pin;
+use std::rc::Rc;
+
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    #[kani::modifies(&self.count)]
+    fn peek_rc(self: Rc<Self>) -> u32 {
+        self.count
+    }
+
+    #[kani::modifies(*self)]
+    fn peek_pinned(self: Pin<&Self>) -> u32 {
+        self.count
+    }
+}
+
+#[kani::proof_for_contract(Counter::peek_rc)]
+fn peek_rc_harness() {
+    Rc::new(Counter { count: kani::any() }).peek_rc();
+}
+
+#[kani::proof_for_contract(Counter::peek_pinned)]
+fn peek_pinned_harness() {
+    let counter = Counter { count: kani::any() };
+    Pin::new(&counter).peek_pinned();
+}
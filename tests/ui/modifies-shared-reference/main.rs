@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a `modifies` target behind a shared reference is rejected if it
+//! has no interior mutability.
+
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    #[kani::modifies(&self.count)]
+    fn peek(&self) -> u32 {
+        self.count
+    }
+}
+
+#[kani::modifies(*value)]
+fn read(value: &u32) -> u32 {
+    *value
+}
+
+#[kani::proof_for_contract(Counter::peek)]
+fn peek_harness() {
+    let counter = Counter { count: kani::any() };
+    counter.peek();
+}
+
+#[kani::proof_for_contract(read)]
+fn read_harness() {
+    read(&kani::any());
+}
@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a `modifies` target with a stepped range, which would select
+//! every other element, is rejected with a specific error.
+
+#[kani::modifies(&mut buf[0..8; 2])]
+fn clear_even(buf: &mut [u8; 8]) {
+    for i in (0..8).step_by(2) {
+        buf[i] = 0;
+    }
+}
+
+#[kani::proof_for_contract(clear_even)]
+fn check_clear_even() {
+    let mut buf: [u8; 8] = kani::any();
+    clear_even(&mut buf);
+}
@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `modifies` targets whose type is neither `Sized` nor a slice are
+//! rejected.
+
+trait Reset {
+    fn reset(&mut self);
+}
+
+impl Reset for u8 {
+    fn reset(&mut self) {
+        *self = 0;
+    }
+}
+
+#[kani::modifies(r)]
+fn reset(r: &mut dyn Reset) {
+    r.reset()
+}
+
+#[kani::proof_for_contract(reset)]
+fn check_reset() {
+    let mut x = 1u8;
+    reset(&mut x);
+}
@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that every duplicate `#[kani::unwind]` attribute is pointed out.
+
+#[kani::proof]
+#[kani::unwind(2)]
+#[kani::unwind(3)]
+#[kani::unwind(4)]
+fn check() {}
@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Checks that a harness cannot check the same contract twice.
+
+#[kani::requires(x > 0)]
+fn foo(x: u32) -> u32 {
+    x - 1
+}
+
+#[kani::proof_for_contract(foo, foo)]
+fn check_foo() {
+    foo(kani::any());
+}
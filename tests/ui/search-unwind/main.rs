@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: -Z search-unwind --harness count_to_five --search-unwind 10
+
+//! Check that `--search-unwind` reports the smallest bound for which no unwinding assertion
+//! fails. The loop body runs five times, so the loop condition is evaluated six times.
+
+#[kani::proof]
+fn count_to_five() {
+    let mut counter = 0;
+    while counter < 5 {
+        counter += 1;
+    }
+    assert!(counter == 5);
+}
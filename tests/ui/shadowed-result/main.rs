@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that using `result` in a postcondition of a function with an argument
+//! of that name is reported, since it does not refer to the return value.
+
+#[kani::requires(result > 0)]
+#[kani::ensures(result > 0)]
+fn double(result: u32) -> u32 {
+    result.wrapping_mul(2)
+}
+
+#[kani::proof_for_contract(double)]
+fn check_double() {
+    double(kani::any());
+}
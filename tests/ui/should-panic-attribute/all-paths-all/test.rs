@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that verification passes with `#[kani::should_panic(mode = "all")]`
+//! when every path of the harness panics.
+
+#[kani::proof]
+#[kani::should_panic(mode = "all")]
+fn check() {
+    let x: u8 = kani::any();
+    if x > 100 {
+        panic!("panicked on a large value!");
+    } else {
+        panic!("panicked on a small value!");
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that verification passes with `#[kani::should_panic(expected = "...")]`
+//! when the harness panics with a message that contains the expected one.
+
+#[kani::proof]
+#[kani::should_panic(expected = "index out of bounds")]
+fn check() {
+    let v = [1, 2, 3];
+    let i: usize = kani::any();
+    kani::assume(i <= 3);
+    assert!(v[i] > 0);
+}
@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that verification fails with `#[kani::should_panic(expected = "...")]`
+//! when the harness may panic with the expected message, but also with another one.
+
+#[kani::proof]
+#[kani::should_panic(expected = "too large")]
+fn check() {
+    let x: u8 = kani::any();
+    if x > 200 {
+        panic!("value is too large");
+    }
+    if x == 0 {
+        panic!("value is zero");
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that verification passes with `#[kani::should_panic(mode = "any")]`
+//! when only some paths of the harness panic.
+
+#[kani::proof]
+#[kani::should_panic(mode = "any")]
+fn check() {
+    let x: u8 = kani::any();
+    if x > 100 {
+        panic!("panicked on a large value!");
+    }
+}
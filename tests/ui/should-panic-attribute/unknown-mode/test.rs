@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `#[kani::should_panic]` rejects an unknown mode.
+
+#[kani::proof]
+#[kani::should_panic(mode = "some")]
+fn check() {}
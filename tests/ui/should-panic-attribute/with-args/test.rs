@@ -1,7 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-//! Checks that `#[kani::should_panic]` doesn't accept arguments.
+//! Checks that `#[kani::should_panic]` only accepts `mode` and `expected` arguments.
 
 #[kani::proof]
 #[kani::should_panic(arg)]
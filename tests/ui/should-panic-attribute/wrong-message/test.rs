@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that verification fails with `#[kani::should_panic(expected = "...")]`
+//! when the harness panics with a different message.
+
+#[kani::proof]
+#[kani::should_panic(expected = "index out of bounds")]
+fn check() {
+    let x: Option<u8> = None;
+    x.unwrap();
+}
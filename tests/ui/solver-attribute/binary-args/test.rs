@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that a solver binary is invoked with the arguments given in the
+//! attribute.
+
+#[kani::proof]
+#[kani::solver(bin = "kissat", args = ["--quiet"])]
+fn check() {
+    let x: u8 = kani::any();
+    kani::assume(x < 10);
+    assert!(x.wrapping_add(1) <= 10);
+}
@@ -0,0 +1,9 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that a solver cannot be listed twice for the same harness
+
+#[kani::proof]
+#[kani::solver(kissat)]
+#[kani::solver(minisat, kissat)]
+fn check() {}
@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that the arguments of a solver binary must be string literals.
+
+#[kani::proof]
+#[kani::solver(bin = "kissat", args = ["--quiet", 1])]
+fn check() {}
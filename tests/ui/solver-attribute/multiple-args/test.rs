@@ -1,6 +1,14 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+//! Checks that the first of several solvers is used if it does not time out
+
 #[kani::proof]
 #[kani::solver(kissat, minisat)]
-fn check() {}
+fn check() {
+    let mut a = [2, 3, 1];
+    a.sort();
+    assert_eq!(a[0], 1);
+    assert_eq!(a[1], 2);
+    assert_eq!(a[2], 3);
+}
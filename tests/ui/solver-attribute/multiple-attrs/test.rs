@@ -1,7 +1,15 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+//! Checks that the solvers of repeated `kani::solver` attributes are tried in order
+
 #[kani::proof]
+#[kani::solver(cadical)]
 #[kani::solver(kissat)]
-#[kani::solver(kissat)]
-fn check() {}
+fn check() {
+    let mut a = [2, 3, 1];
+    a.sort();
+    assert_eq!(a[0], 1);
+    assert_eq!(a[1], 2);
+    assert_eq!(a[2], 3);
+}
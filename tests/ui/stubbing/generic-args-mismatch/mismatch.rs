@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness mismatch -Z stubbing
+//
+//! This tests that we report a stub whose signature does not match the stubbed instance.
+
+fn decode<T: Default>() -> T {
+    T::default()
+}
+
+fn decode_u32() -> u32 {
+    42
+}
+
+#[kani::proof]
+#[kani::stub(decode::<u64>, decode_u32)]
+fn mismatch() {
+    decode::<u64>();
+}
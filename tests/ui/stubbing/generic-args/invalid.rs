@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness invalid_stub -Z stubbing
+//
+//! This tests that we report generic arguments of a stubbed instance that cannot be resolved.
+
+fn decode<T: Default>() -> T {
+    T::default()
+}
+
+fn decode_u32() -> u32 {
+    42
+}
+
+#[kani::proof]
+#[kani::stub(decode::<Missing>, decode_u32)]
+#[kani::stub(decode::<u32, u64>, decode_u32)]
+fn invalid_stub() {
+    decode::<u32>();
+}
@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness main -Z stubbing
+//
+//! This tests that we warn about calls from generic code to a generic function
+//! with a stubbed instance, since they are not replaced.
+
+fn decode<T: Default>() -> T {
+    T::default()
+}
+
+fn decode_u32() -> u32 {
+    42
+}
+
+fn decode_twice<T: Default>() -> (T, T) {
+    (decode::<T>(), decode::<T>())
+}
+
+#[kani::proof]
+#[kani::stub(decode::<u32>, decode_u32)]
+fn main() {
+    assert_eq!(decode::<u32>(), 42);
+    assert_eq!(decode_twice::<u32>(), (0, 0));
+}
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that an `old` snapshot whose value is discarded by the postcondition
+//! is reported, since it does not constrain anything.
+
+#[kani::modifies(counter)]
+#[kani::ensures({ let _ = old(*counter); *counter > 0 })]
+fn bump(counter: &mut u32) {
+    *counter = counter.saturating_add(1);
+}
+
+#[kani::proof_for_contract(bump)]
+fn check_bump() {
+    let mut counter: u32 = kani::any();
+    bump(&mut counter);
+}
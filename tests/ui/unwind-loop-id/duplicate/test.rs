@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that the bound of a loop cannot be given twice.
+
+#[kani::proof]
+#[kani::unwind(loop_id = 0, value = 11)]
+#[kani::unwind(loop_id = 0, value = 5)]
+fn check() {
+    for _ in 0..10 {}
+}
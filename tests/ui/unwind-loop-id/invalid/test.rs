@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that a loop bound needs both a `loop_id` and a `value`.
+
+#[kani::proof]
+#[kani::unwind(loop_id = 0)]
+fn check() {
+    for _ in 0..10 {}
+}
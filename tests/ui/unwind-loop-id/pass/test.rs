@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `#[kani::unwind(loop_id = ..., value = ...)]` overrides the bound of a single loop,
+//! while the other loops keep the bound of `#[kani::unwind(...)]`.
+
+#[kani::proof]
+#[kani::unwind(3)]
+#[kani::unwind(loop_id = 0, value = 11)]
+fn check() {
+    let mut sum = 0;
+    for i in 0..10 {
+        sum += i;
+    }
+    for i in 0..2 {
+        sum += i;
+    }
+    assert!(sum == 46);
+}